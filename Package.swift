@@ -41,7 +41,9 @@ let package = Package(
         ),
         .target(
             name: "QueueFileFFI",
-            dependencies: ["QueueFileRS"]
+            dependencies: ["QueueFileRS"],
+            // The SQLite import and export use the system library.
+            linkerSettings: [.linkedLibrary("sqlite3")]
         ),
         .testTarget(
             name: "QueueFileSwiftTests",
@@ -414,7 +414,13 @@ fileprivate final class UniffiHandleMap<T>: @unchecked Sendable {
 
 
 // Public interface members begin here.
-
+// Magic number for the Rust proxy to call using the same mechanism as every other method,
+// to free the callback once it's dropped by Rust.
+private let IDX_CALLBACK_FREE: Int32 = 0
+// Callback return codes
+private let UNIFFI_CALLBACK_SUCCESS: Int32 = 0
+private let UNIFFI_CALLBACK_ERROR: Int32 = 1
+private let UNIFFI_CALLBACK_UNEXPECTED_ERROR: Int32 = 2
 
 #if swift(>=5.8)
 @_documentation(visibility: private)
@@ -432,6 +438,22 @@ fileprivate struct FfiConverterUInt32: FfiConverterPrimitive {
     }
 }
 
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterInt32: FfiConverterPrimitive {
+    typealias FfiType = Int32
+    typealias SwiftType = Int32
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Int32 {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: Int32, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
 #if swift(>=5.8)
 @_documentation(visibility: private)
 #endif
@@ -448,6 +470,38 @@ fileprivate struct FfiConverterUInt64: FfiConverterPrimitive {
     }
 }
 
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterInt64: FfiConverterPrimitive {
+    typealias FfiType = Int64
+    typealias SwiftType = Int64
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Int64 {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: Int64, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterDouble: FfiConverterPrimitive {
+    typealias FfiType = Double
+    typealias SwiftType = Double
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Double {
+        return try lift(readDouble(&buf))
+    }
+
+    public static func write(_ value: Double, into buf: inout [UInt8]) {
+        writeDouble(&buf, lower(value))
+    }
+}
+
 #if swift(>=5.8)
 @_documentation(visibility: private)
 #endif
@@ -531,47 +585,118 @@ fileprivate struct FfiConverterData: FfiConverterRustBuffer {
     }
 }
 
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterTimestamp: FfiConverterRustBuffer {
+    typealias SwiftType = Date
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Date {
+        let seconds: Int64 = try readInt(&buf)
+        let nanoseconds: UInt32 = try readInt(&buf)
+        if seconds >= 0 {
+            let delta = Double(seconds) + (Double(nanoseconds) / 1.0e9)
+            return Date.init(timeIntervalSince1970: delta)
+        } else {
+            let delta = Double(seconds) - (Double(nanoseconds) / 1.0e9)
+            return Date.init(timeIntervalSince1970: delta)
+        }
+    }
 
+    public static func write(_ value: Date, into buf: inout [UInt8]) {
+        var delta = value.timeIntervalSince1970
+        var sign: Int64 = 1
+        if delta < 0 {
+            // The nanoseconds portion of the epoch offset must always be
+            // positive, to simplify the calculation we will use the absolute
+            // value of the offset.
+            sign = -1
+            delta = -delta
+        }
+        if delta.rounded(.down) > Double(Int64.max) {
+            fatalError("Timestamp overflow, exceeds max bounds supported by Uniffi")
+        }
+        let seconds = Int64(delta)
+        let nanoseconds = UInt32((delta - Double(seconds)) * 1.0e9)
+        writeInt(&buf, sign * seconds)
+        writeInt(&buf, nanoseconds)
+    }
+}
 
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterDuration: FfiConverterRustBuffer {
+    typealias SwiftType = TimeInterval
 
-public protocol QueueFileProtocol: AnyObject, Sendable {
-    
-    func add(data: Data) throws 
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> TimeInterval {
+        let seconds: UInt64 = try readInt(&buf)
+        let nanoseconds: UInt32 = try readInt(&buf)
+        return Double(seconds) + (Double(nanoseconds) / 1.0e9)
+    }
+
+    public static func write(_ value: TimeInterval, into buf: inout [UInt8]) {
+        if value.rounded(.down) > Double(Int64.max) {
+            fatalError("Duration overflow, exceeds max bounds supported by Uniffi")
+        }
+
+        if value < 0 {
+            fatalError("Invalid duration, must be non-negative")
+        }
+
+        let seconds = UInt64(value)
+        let nanoseconds = UInt32((value - Double(seconds)) * 1.0e9)
+        writeInt(&buf, seconds)
+        writeInt(&buf, nanoseconds)
+    }
+}
+
+
+
+
+/**
+ * A queue file compressed with gzip, read by decompressing it as it goes. Elements can only be
+ * read in order; `rewind` starts over from the eldest.
+ */
+public protocol ArchivedQueueProtocol: AnyObject, Sendable {
     
-    func addMultiple(items: [Data]) throws 
+    func exportCsv(path: String) throws  -> UInt64
     
-    func clear() throws 
+    func exportNdjson(path: String, encoding: ExportEncoding?) throws  -> UInt64
     
-    func fileLen() throws  -> UInt64
+    /**
+     * Streams every element to `exporter`, framed as `QueueFile::export_with` does.
+     */
+    func exportWith(exporter: Exporter) throws  -> UInt64
     
     func getAll() throws  -> [Data]
     
-    func isEmpty() throws  -> Bool
-    
-    func overwriteOnRemove() throws  -> Bool
-    
-    func peek() throws  -> Data?
-    
-    func remove() throws 
-    
-    func removeN(n: UInt32) throws 
-    
-    func setCacheOffsetPolicy(policy: OffsetCachePolicy) throws 
-    
-    func setOverwriteOnRemove(value: Bool) throws 
-    
-    func setSyncWrites(value: Bool) throws 
+    func isEmpty()  -> Bool
     
-    func size() throws  -> UInt32
+    /**
+     * The next element in order, or `None` after the last one.
+     */
+    func next() throws  -> Data?
     
-    func syncAll() throws 
+    /**
+     * Starts `next` over from the eldest element.
+     */
+    func rewind() throws 
     
-    func syncWrites() throws  -> Bool
+    func size()  -> UInt32
     
-    func usedBytes() throws  -> UInt64
+    /**
+     * Decompresses the whole archive, checking every element frame and the gzip checksums and
+     * decoding every element compressed against a dictionary, and fails with `CorruptedFile` or an I/O error at the first problem.
+     */
+    func verify() throws 
     
 }
-open class QueueFile: QueueFileProtocol, @unchecked Sendable {
+/**
+ * A queue file compressed with gzip, read by decompressing it as it goes. Elements can only be
+ * read in order; `rewind` starts over from the eldest.
+ */
+open class ArchivedQueue: ArchivedQueueProtocol, @unchecked Sendable {
     fileprivate let handle: UInt64
 
     /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
@@ -608,165 +733,116 @@ open class QueueFile: QueueFileProtocol, @unchecked Sendable {
     @_documentation(visibility: private)
 #endif
     public func uniffiCloneHandle() -> UInt64 {
-        return try! rustCall { uniffi_queuefile_fn_clone_queuefile(self.handle, $0) }
+        return try! rustCall { uniffi_queuefile_fn_clone_archivedqueue(self.handle, $0) }
     }
     // No primary constructor declared for this class.
 
     deinit {
-        try! rustCall { uniffi_queuefile_fn_free_queuefile(handle, $0) }
+        try! rustCall { uniffi_queuefile_fn_free_archivedqueue(handle, $0) }
     }
 
     
-public static func `open`(path: String)throws  -> QueueFile  {
-    return try  FfiConverterTypeQueueFile_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_constructor_queuefile_open(
+    /**
+     * Opens a gzip-compressed queue file (such as a `.qf.gz` archive) for reading. Only the
+     * header is read here, along with the dictionaries of a queue that compresses its
+     * elements, which stay uncompressed next to the archive under the queue's name
+     * (`queue.qf.dict` for `queue.qf.gz`).
+     */
+public static func openCompressedReadOnly(path: String)throws  -> ArchivedQueue  {
+    return try  FfiConverterTypeArchivedQueue_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_constructor_archivedqueue_open_compressed_read_only(
         FfiConverterString.lower(path),$0
     )
 })
 }
     
-public static func withCapacity(path: String, capacity: UInt64)throws  -> QueueFile  {
-    return try  FfiConverterTypeQueueFile_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_constructor_queuefile_with_capacity(
-        FfiConverterString.lower(path),
-        FfiConverterUInt64.lower(capacity),$0
-    )
-})
-}
-    
 
     
-open func add(data: Data)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_add(
+open func exportCsv(path: String)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_archivedqueue_export_csv(
             self.uniffiCloneHandle(),
-        FfiConverterData.lower(data),$0
+        FfiConverterString.lower(path),$0
     )
-}
+})
 }
     
-open func addMultiple(items: [Data])throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_add_multiple(
+open func exportNdjson(path: String, encoding: ExportEncoding?)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_archivedqueue_export_ndjson(
             self.uniffiCloneHandle(),
-        FfiConverterSequenceData.lower(items),$0
-    )
-}
-}
-    
-open func clear()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_clear(
-            self.uniffiCloneHandle(),$0
+        FfiConverterString.lower(path),
+        FfiConverterOptionTypeExportEncoding.lower(encoding),$0
     )
-}
+})
 }
     
-open func fileLen()throws  -> UInt64  {
+    /**
+     * Streams every element to `exporter`, framed as `QueueFile::export_with` does.
+     */
+open func exportWith(exporter: Exporter)throws  -> UInt64  {
     return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_file_len(
-            self.uniffiCloneHandle(),$0
+    uniffi_queuefile_fn_method_archivedqueue_export_with(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeExporter_lower(exporter),$0
     )
 })
 }
     
 open func getAll()throws  -> [Data]  {
     return try  FfiConverterSequenceData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_get_all(
-            self.uniffiCloneHandle(),$0
-    )
-})
-}
-    
-open func isEmpty()throws  -> Bool  {
-    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_is_empty(
+    uniffi_queuefile_fn_method_archivedqueue_get_all(
             self.uniffiCloneHandle(),$0
     )
 })
 }
     
-open func overwriteOnRemove()throws  -> Bool  {
-    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_overwrite_on_remove(
+open func isEmpty() -> Bool  {
+    return try!  FfiConverterBool.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_archivedqueue_is_empty(
             self.uniffiCloneHandle(),$0
     )
 })
 }
     
-open func peek()throws  -> Data?  {
+    /**
+     * The next element in order, or `None` after the last one.
+     */
+open func next()throws  -> Data?  {
     return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_peek(
+    uniffi_queuefile_fn_method_archivedqueue_next(
             self.uniffiCloneHandle(),$0
     )
 })
 }
     
-open func remove()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_remove(
+    /**
+     * Starts `next` over from the eldest element.
+     */
+open func rewind()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_archivedqueue_rewind(
             self.uniffiCloneHandle(),$0
     )
 }
 }
     
-open func removeN(n: UInt32)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_remove_n(
-            self.uniffiCloneHandle(),
-        FfiConverterUInt32.lower(n),$0
-    )
-}
-}
-    
-open func setCacheOffsetPolicy(policy: OffsetCachePolicy)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_set_cache_offset_policy(
-            self.uniffiCloneHandle(),
-        FfiConverterTypeOffsetCachePolicy_lower(policy),$0
-    )
-}
-}
-    
-open func setOverwriteOnRemove(value: Bool)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_set_overwrite_on_remove(
-            self.uniffiCloneHandle(),
-        FfiConverterBool.lower(value),$0
-    )
-}
-}
-    
-open func setSyncWrites(value: Bool)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_set_sync_writes(
-            self.uniffiCloneHandle(),
-        FfiConverterBool.lower(value),$0
-    )
-}
-}
-    
-open func size()throws  -> UInt32  {
-    return try  FfiConverterUInt32.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_size(
+open func size() -> UInt32  {
+    return try!  FfiConverterUInt32.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_archivedqueue_size(
             self.uniffiCloneHandle(),$0
     )
 })
 }
     
-open func syncAll()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_sync_all(
-            self.uniffiCloneHandle(),$0
-    )
-}
-}
-    
-open func syncWrites()throws  -> Bool  {
-    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_sync_writes(
+    /**
+     * Decompresses the whole archive, checking every element frame and the gzip checksums and
+     * decoding every element compressed against a dictionary, and fails with `CorruptedFile` or an I/O error at the first problem.
+     */
+open func verify()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_archivedqueue_verify(
             self.uniffiCloneHandle(),$0
     )
-})
 }
-    
-open func usedBytes()throws  -> UInt64  {
-    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
-    uniffi_queuefile_fn_method_queuefile_used_bytes(
-            self.uniffiCloneHandle(),$0
-    )
-})
 }
     
 
@@ -777,24 +853,24 @@ open func usedBytes()throws  -> UInt64  {
 #if swift(>=5.8)
 @_documentation(visibility: private)
 #endif
-public struct FfiConverterTypeQueueFile: FfiConverter {
+public struct FfiConverterTypeArchivedQueue: FfiConverter {
     typealias FfiType = UInt64
-    typealias SwiftType = QueueFile
+    typealias SwiftType = ArchivedQueue
 
-    public static func lift(_ handle: UInt64) throws -> QueueFile {
-        return QueueFile(unsafeFromHandle: handle)
+    public static func lift(_ handle: UInt64) throws -> ArchivedQueue {
+        return ArchivedQueue(unsafeFromHandle: handle)
     }
 
-    public static func lower(_ value: QueueFile) -> UInt64 {
+    public static func lower(_ value: ArchivedQueue) -> UInt64 {
         return value.uniffiCloneHandle()
     }
 
-    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueFile {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ArchivedQueue {
         let handle: UInt64 = try readInt(&buf)
         return try lift(handle)
     }
 
-    public static func write(_ value: QueueFile, into buf: inout [UInt8]) {
+    public static func write(_ value: ArchivedQueue, into buf: inout [UInt8]) {
         writeInt(&buf, lower(value))
     }
 }
@@ -803,196 +879,206 @@ public struct FfiConverterTypeQueueFile: FfiConverter {
 #if swift(>=5.8)
 @_documentation(visibility: private)
 #endif
-public func FfiConverterTypeQueueFile_lift(_ handle: UInt64) throws -> QueueFile {
-    return try FfiConverterTypeQueueFile.lift(handle)
+public func FfiConverterTypeArchivedQueue_lift(_ handle: UInt64) throws -> ArchivedQueue {
+    return try FfiConverterTypeArchivedQueue.lift(handle)
 }
 
 #if swift(>=5.8)
 @_documentation(visibility: private)
 #endif
-public func FfiConverterTypeQueueFile_lower(_ value: QueueFile) -> UInt64 {
-    return FfiConverterTypeQueueFile.lower(value)
+public func FfiConverterTypeArchivedQueue_lower(_ value: ArchivedQueue) -> UInt64 {
+    return FfiConverterTypeArchivedQueue.lower(value)
 }
 
 
 
-// Note that we don't yet support `indirect` for enums.
-// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
-
-public enum OffsetCachePolicy: Equatable, Hashable {
-    
-    case none
-    case linear(offset: UInt32
-    )
-    case quadratic
 
 
 
+/**
+ * Test support for power-loss testing. Records the writes a queue makes to its file between
+ * checkpoints, then rebuilds the file as it would be after losing power at any byte of that
+ * write stream and checks that it reopens to the state before or after the interrupted
+ * operation.
+ *
+ * Writes are reconstructed by diffing the file at consecutive checkpoints and ordered around
+ * the header update the way queue-file issues them. This models a queue with
+ * `set_sync_writes(true)`, where each operation reaches the disk in order before the next one
+ * starts, and assumes the versioned (non-legacy) file format.
+ */
+public protocol CrashSimulatorProtocol: AnyObject, Sendable {
+    
+    /**
+     * Records the writes made since the previous checkpoint as one operation. Call it after
+     * each operation under test, once that operation has returned. Returns the checkpoint's
+     * number.
+     */
+    func checkpoint() throws  -> UInt32
+    
+    /**
+     * Loses power after `offset` bytes of the write stream and reopens the result.
+     */
+    func replay(offset: UInt64, mode: CrashMode) throws  -> CrashOutcome
+    
+    /**
+     * Replays a crash at every offset of the write stream and returns the ones that did not
+     * recover to a consistent prefix, so a test can assert that the result is empty.
+     */
+    func verify(mode: CrashMode) throws  -> [CrashOutcome]
+    
+    /**
+     * Total bytes in the recorded write stream; crash offsets run from 0 to this value.
+     */
+    func writeLen() throws  -> UInt64
+    
 }
+/**
+ * Test support for power-loss testing. Records the writes a queue makes to its file between
+ * checkpoints, then rebuilds the file as it would be after losing power at any byte of that
+ * write stream and checks that it reopens to the state before or after the interrupted
+ * operation.
+ *
+ * Writes are reconstructed by diffing the file at consecutive checkpoints and ordered around
+ * the header update the way queue-file issues them. This models a queue with
+ * `set_sync_writes(true)`, where each operation reaches the disk in order before the next one
+ * starts, and assumes the versioned (non-legacy) file format.
+ */
+open class CrashSimulator: CrashSimulatorProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
 
-#if compiler(>=6)
-extension OffsetCachePolicy: Sendable {}
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
 #endif
+    public struct NoHandle {
+        public init() {}
+    }
 
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
 #if swift(>=5.8)
-@_documentation(visibility: private)
+    @_documentation(visibility: private)
 #endif
-public struct FfiConverterTypeOffsetCachePolicy: FfiConverterRustBuffer {
-    typealias SwiftType = OffsetCachePolicy
-
-    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> OffsetCachePolicy {
-        let variant: Int32 = try readInt(&buf)
-        switch variant {
-        
-        case 1: return .none
-        
-        case 2: return .linear(offset: try FfiConverterUInt32.read(from: &buf)
-        )
-        
-        case 3: return .quadratic
-        
-        default: throw UniffiInternalError.unexpectedEnumCase
-        }
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
     }
 
-    public static func write(_ value: OffsetCachePolicy, into buf: inout [UInt8]) {
-        switch value {
-        
-        
-        case .none:
-            writeInt(&buf, Int32(1))
-        
-        
-        case let .linear(offset):
-            writeInt(&buf, Int32(2))
-            FfiConverterUInt32.write(offset, into: &buf)
-            
-        
-        case .quadratic:
-            writeInt(&buf, Int32(3))
-        
-        }
-    }
-}
-
-
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
 #if swift(>=5.8)
-@_documentation(visibility: private)
+    @_documentation(visibility: private)
 #endif
-public func FfiConverterTypeOffsetCachePolicy_lift(_ buf: RustBuffer) throws -> OffsetCachePolicy {
-    return try FfiConverterTypeOffsetCachePolicy.lift(buf)
-}
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
 
 #if swift(>=5.8)
-@_documentation(visibility: private)
+    @_documentation(visibility: private)
 #endif
-public func FfiConverterTypeOffsetCachePolicy_lower(_ value: OffsetCachePolicy) -> RustBuffer {
-    return FfiConverterTypeOffsetCachePolicy.lower(value)
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_crashsimulator(self.handle, $0) }
+    }
+    /**
+     * Starts recording from the current contents of the queue file at `path`.
+     */
+public convenience init(path: String)throws  {
+    let handle =
+        try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_constructor_crashsimulator_new(
+        FfiConverterString.lower(path),$0
+    )
+}
+    self.init(unsafeFromHandle: handle)
 }
 
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_crashsimulator(handle, $0) }
+    }
 
-
-public enum QueueFileError: Swift.Error, Equatable, Hashable, Foundation.LocalizedError {
-
-    
     
-    case IoError(message: String)
-    
-    case EmptyQueue(message: String)
-    
-    case ElementTooBig(message: String)
-    
-    case TooManyElements(message: String)
+
     
-    case CorruptedFile(message: String)
+    /**
+     * Records the writes made since the previous checkpoint as one operation. Call it after
+     * each operation under test, once that operation has returned. Returns the checkpoint's
+     * number.
+     */
+open func checkpoint()throws  -> UInt32  {
+    return try  FfiConverterUInt32.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_crashsimulator_checkpoint(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
     
-    case UnsupportedVersion(message: String)
+    /**
+     * Loses power after `offset` bytes of the write stream and reopens the result.
+     */
+open func replay(offset: UInt64, mode: CrashMode)throws  -> CrashOutcome  {
+    return try  FfiConverterTypeCrashOutcome_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_crashsimulator_replay(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt64.lower(offset),
+        FfiConverterTypeCrashMode_lower(mode),$0
+    )
+})
+}
     
-    case LockError(message: String)
+    /**
+     * Replays a crash at every offset of the write stream and returns the ones that did not
+     * recover to a consistent prefix, so a test can assert that the result is empty.
+     */
+open func verify(mode: CrashMode)throws  -> [CrashOutcome]  {
+    return try  FfiConverterSequenceTypeCrashOutcome.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_crashsimulator_verify(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeCrashMode_lower(mode),$0
+    )
+})
+}
     
-
+    /**
+     * Total bytes in the recorded write stream; crash offsets run from 0 to this value.
+     */
+open func writeLen()throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_crashsimulator_write_len(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
     
 
     
-    public var errorDescription: String? {
-        String(reflecting: self)
-    }
-    
 }
 
-#if compiler(>=6)
-extension QueueFileError: Sendable {}
-#endif
 
 #if swift(>=5.8)
 @_documentation(visibility: private)
 #endif
-public struct FfiConverterTypeQueueFileError: FfiConverterRustBuffer {
-    typealias SwiftType = QueueFileError
-
-    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueFileError {
-        let variant: Int32 = try readInt(&buf)
-        switch variant {
-
-        
-
-        
-        case 1: return .IoError(
-            message: try FfiConverterString.read(from: &buf)
-        )
-        
-        case 2: return .EmptyQueue(
-            message: try FfiConverterString.read(from: &buf)
-        )
-        
-        case 3: return .ElementTooBig(
-            message: try FfiConverterString.read(from: &buf)
-        )
-        
-        case 4: return .TooManyElements(
-            message: try FfiConverterString.read(from: &buf)
-        )
-        
-        case 5: return .CorruptedFile(
-            message: try FfiConverterString.read(from: &buf)
-        )
-        
-        case 6: return .UnsupportedVersion(
-            message: try FfiConverterString.read(from: &buf)
-        )
-        
-        case 7: return .LockError(
-            message: try FfiConverterString.read(from: &buf)
-        )
-        
+public struct FfiConverterTypeCrashSimulator: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = CrashSimulator
 
-        default: throw UniffiInternalError.unexpectedEnumCase
-        }
+    public static func lift(_ handle: UInt64) throws -> CrashSimulator {
+        return CrashSimulator(unsafeFromHandle: handle)
     }
 
-    public static func write(_ value: QueueFileError, into buf: inout [UInt8]) {
-        switch value {
-
-        
+    public static func lower(_ value: CrashSimulator) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
 
-        
-        case .IoError(_ /* message is ignored*/):
-            writeInt(&buf, Int32(1))
-        case .EmptyQueue(_ /* message is ignored*/):
-            writeInt(&buf, Int32(2))
-        case .ElementTooBig(_ /* message is ignored*/):
-            writeInt(&buf, Int32(3))
-        case .TooManyElements(_ /* message is ignored*/):
-            writeInt(&buf, Int32(4))
-        case .CorruptedFile(_ /* message is ignored*/):
-            writeInt(&buf, Int32(5))
-        case .UnsupportedVersion(_ /* message is ignored*/):
-            writeInt(&buf, Int32(6))
-        case .LockError(_ /* message is ignored*/):
-            writeInt(&buf, Int32(7))
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> CrashSimulator {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
 
-        
-        }
+    public static func write(_ value: CrashSimulator, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
     }
 }
 
@@ -1000,139 +1086,12635 @@ public struct FfiConverterTypeQueueFileError: FfiConverterRustBuffer {
 #if swift(>=5.8)
 @_documentation(visibility: private)
 #endif
-public func FfiConverterTypeQueueFileError_lift(_ buf: RustBuffer) throws -> QueueFileError {
-    return try FfiConverterTypeQueueFileError.lift(buf)
+public func FfiConverterTypeCrashSimulator_lift(_ handle: UInt64) throws -> CrashSimulator {
+    return try FfiConverterTypeCrashSimulator.lift(handle)
 }
 
 #if swift(>=5.8)
 @_documentation(visibility: private)
 #endif
-public func FfiConverterTypeQueueFileError_lower(_ value: QueueFileError) -> RustBuffer {
-    return FfiConverterTypeQueueFileError.lower(value)
+public func FfiConverterTypeCrashSimulator_lower(_ value: CrashSimulator) -> UInt64 {
+    return FfiConverterTypeCrashSimulator.lower(value)
+}
+
+
+
+
+
+
+/**
+ * Consumes the elements `drain_with` passes to it.
+ */
+public protocol DrainHandler: AnyObject, Sendable {
+    
+    /**
+     * Processes the eldest element. Returning `true` removes it; `false` leaves it at the
+     * front of the queue and ends the drain.
+     */
+    func handle(element: Data)  -> Bool
+    
 }
+/**
+ * Consumes the elements `drain_with` passes to it.
+ */
+open class DrainHandlerImpl: DrainHandler, @unchecked Sendable {
+    fileprivate let handle: UInt64
 
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
 #if swift(>=5.8)
-@_documentation(visibility: private)
+    @_documentation(visibility: private)
 #endif
-fileprivate struct FfiConverterOptionData: FfiConverterRustBuffer {
-    typealias SwiftType = Data?
+    public struct NoHandle {
+        public init() {}
+    }
 
-    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
-        guard let value = value else {
-            writeInt(&buf, Int8(0))
-            return
-        }
-        writeInt(&buf, Int8(1))
-        FfiConverterData.write(value, into: &buf)
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
     }
 
-    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
-        switch try readInt(&buf) as Int8 {
-        case 0: return nil
-        case 1: return try FfiConverterData.read(from: &buf)
-        default: throw UniffiInternalError.unexpectedOptionalTag
-        }
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
     }
-}
 
 #if swift(>=5.8)
-@_documentation(visibility: private)
+    @_documentation(visibility: private)
 #endif
-fileprivate struct FfiConverterSequenceData: FfiConverterRustBuffer {
-    typealias SwiftType = [Data]
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_drainhandler(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
 
-    public static func write(_ value: [Data], into buf: inout [UInt8]) {
-        let len = Int32(value.count)
-        writeInt(&buf, len)
-        for item in value {
-            FfiConverterData.write(item, into: &buf)
-        }
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_drainhandler(handle, $0) }
     }
 
-    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [Data] {
-        let len: Int32 = try readInt(&buf)
-        var seq = [Data]()
-        seq.reserveCapacity(Int(len))
-        for _ in 0 ..< len {
-            seq.append(try FfiConverterData.read(from: &buf))
+    
+
+    
+    /**
+     * Processes the eldest element. Returning `true` removes it; `false` leaves it at the
+     * front of the queue and ends the drain.
+     */
+open func handle(element: Data) -> Bool  {
+    return try!  FfiConverterBool.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_drainhandler_handle(
+            self.uniffiCloneHandle(),
+        FfiConverterData.lower(element),$0
+    )
+})
+}
+    
+
+    
+}
+
+
+
+// Put the implementation in a struct so we don't pollute the top-level namespace
+fileprivate struct UniffiCallbackInterfaceDrainHandler {
+
+    // Create the VTable using a series of closures.
+    // Swift automatically converts these into C callback functions.
+    //
+    // This creates 1-element array, since this seems to be the only way to construct a const
+    // pointer that we can pass to the Rust code.
+    static let vtable: [UniffiVTableCallbackInterfaceDrainHandler] = [UniffiVTableCallbackInterfaceDrainHandler(
+        uniffiFree: { (uniffiHandle: UInt64) -> () in
+            do {
+                try FfiConverterTypeDrainHandler.handleMap.remove(handle: uniffiHandle)
+            } catch {
+                print("Uniffi callback interface DrainHandler: handle missing in uniffiFree")
+            }
+        },
+        uniffiClone: { (uniffiHandle: UInt64) -> UInt64 in
+            do {
+                return try FfiConverterTypeDrainHandler.handleMap.clone(handle: uniffiHandle)
+            } catch {
+                fatalError("Uniffi callback interface DrainHandler: handle missing in uniffiClone")
+            }
+        },
+        handle: { (
+            uniffiHandle: UInt64,
+            element: RustBuffer,
+            uniffiOutReturn: UnsafeMutablePointer<Int8>,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            let makeCall = {
+                () throws -> Bool in
+                guard let uniffiObj = try? FfiConverterTypeDrainHandler.handleMap.get(handle: uniffiHandle) else {
+                    throw UniffiInternalError.unexpectedStaleHandle
+                }
+                return uniffiObj.handle(
+                     element: try FfiConverterData.lift(element)
+                )
+            }
+
+            
+            let writeReturn = { uniffiOutReturn.pointee = FfiConverterBool.lower($0) }
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: makeCall,
+                writeReturn: writeReturn
+            )
         }
-        return seq
-    }
+    )]
 }
 
-private enum InitializationResult {
-    case ok
-    case contractVersionMismatch
-    case apiChecksumMismatch
+private func uniffiCallbackInitDrainHandler() {
+    uniffi_queuefile_fn_init_callback_vtable_drainhandler(UniffiCallbackInterfaceDrainHandler.vtable)
 }
-// Use a global variable to perform the versioning checks. Swift ensures that
-// the code inside is only computed once.
-private let initializationResult: InitializationResult = {
-    // Get the bindings contract version from our ComponentInterface
-    let bindings_contract_version = 30
-    // Get the scaffolding contract version by calling the into the dylib
-    let scaffolding_contract_version = ffi_queuefile_uniffi_contract_version()
-    if bindings_contract_version != scaffolding_contract_version {
-        return InitializationResult.contractVersionMismatch
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeDrainHandler: FfiConverter {
+    fileprivate static let handleMap = UniffiHandleMap<DrainHandler>()
+
+    typealias FfiType = UInt64
+    typealias SwiftType = DrainHandler
+
+    public static func lift(_ handle: UInt64) throws -> DrainHandler {
+        if ((handle & 1) == 0) {
+            // Rust-generated handle, construct a new class that uses the handle to implement the
+            // interface
+            return DrainHandlerImpl(unsafeFromHandle: handle)
+        } else {
+            // Swift-generated handle, get the object from the handle map
+            return try handleMap.remove(handle: handle)
+        }
     }
-    if (uniffi_queuefile_checksum_method_queuefile_add() != 24339) {
-        return InitializationResult.apiChecksumMismatch
+
+    public static func lower(_ value: DrainHandler) -> UInt64 {
+         if let rustImpl = value as? DrainHandlerImpl {
+             // Rust-implemented object.  Clone the handle and return it
+            return rustImpl.uniffiCloneHandle()
+         } else {
+            // Swift object, generate a new vtable handle and return that.
+            return handleMap.insert(obj: value)
+         }
     }
-    if (uniffi_queuefile_checksum_method_queuefile_add_multiple() != 60118) {
-        return InitializationResult.apiChecksumMismatch
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> DrainHandler {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
     }
-    if (uniffi_queuefile_checksum_method_queuefile_clear() != 34945) {
-        return InitializationResult.apiChecksumMismatch
+
+    public static func write(_ value: DrainHandler, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
     }
-    if (uniffi_queuefile_checksum_method_queuefile_file_len() != 15704) {
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeDrainHandler_lift(_ handle: UInt64) throws -> DrainHandler {
+    return try FfiConverterTypeDrainHandler.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeDrainHandler_lower(_ value: DrainHandler) -> UInt64 {
+    return FfiConverterTypeDrainHandler.lower(value)
+}
+
+
+
+
+
+
+/**
+ * Receives the elements of a queue from `subscribe_from`, one at a time and in order, on the
+ * subscription's own thread.
+ */
+public protocol ElementListener: AnyObject, Sendable {
+    
+    func onElement(seq: UInt64, element: Data) 
+    
+}
+/**
+ * Receives the elements of a queue from `subscribe_from`, one at a time and in order, on the
+ * subscription's own thread.
+ */
+open class ElementListenerImpl: ElementListener, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_elementlistener(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_elementlistener(handle, $0) }
+    }
+
+    
+
+    
+open func onElement(seq: UInt64, element: Data)  {try! rustCall() {
+    uniffi_queuefile_fn_method_elementlistener_on_element(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt64.lower(seq),
+        FfiConverterData.lower(element),$0
+    )
+}
+}
+    
+
+    
+}
+
+
+
+// Put the implementation in a struct so we don't pollute the top-level namespace
+fileprivate struct UniffiCallbackInterfaceElementListener {
+
+    // Create the VTable using a series of closures.
+    // Swift automatically converts these into C callback functions.
+    //
+    // This creates 1-element array, since this seems to be the only way to construct a const
+    // pointer that we can pass to the Rust code.
+    static let vtable: [UniffiVTableCallbackInterfaceElementListener] = [UniffiVTableCallbackInterfaceElementListener(
+        uniffiFree: { (uniffiHandle: UInt64) -> () in
+            do {
+                try FfiConverterTypeElementListener.handleMap.remove(handle: uniffiHandle)
+            } catch {
+                print("Uniffi callback interface ElementListener: handle missing in uniffiFree")
+            }
+        },
+        uniffiClone: { (uniffiHandle: UInt64) -> UInt64 in
+            do {
+                return try FfiConverterTypeElementListener.handleMap.clone(handle: uniffiHandle)
+            } catch {
+                fatalError("Uniffi callback interface ElementListener: handle missing in uniffiClone")
+            }
+        },
+        onElement: { (
+            uniffiHandle: UInt64,
+            seq: UInt64,
+            element: RustBuffer,
+            uniffiOutReturn: UnsafeMutableRawPointer,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            let makeCall = {
+                () throws -> () in
+                guard let uniffiObj = try? FfiConverterTypeElementListener.handleMap.get(handle: uniffiHandle) else {
+                    throw UniffiInternalError.unexpectedStaleHandle
+                }
+                return uniffiObj.onElement(
+                     seq: try FfiConverterUInt64.lift(seq),
+                     element: try FfiConverterData.lift(element)
+                )
+            }
+
+            
+            let writeReturn = { () }
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: makeCall,
+                writeReturn: writeReturn
+            )
+        }
+    )]
+}
+
+private func uniffiCallbackInitElementListener() {
+    uniffi_queuefile_fn_init_callback_vtable_elementlistener(UniffiCallbackInterfaceElementListener.vtable)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeElementListener: FfiConverter {
+    fileprivate static let handleMap = UniffiHandleMap<ElementListener>()
+
+    typealias FfiType = UInt64
+    typealias SwiftType = ElementListener
+
+    public static func lift(_ handle: UInt64) throws -> ElementListener {
+        if ((handle & 1) == 0) {
+            // Rust-generated handle, construct a new class that uses the handle to implement the
+            // interface
+            return ElementListenerImpl(unsafeFromHandle: handle)
+        } else {
+            // Swift-generated handle, get the object from the handle map
+            return try handleMap.remove(handle: handle)
+        }
+    }
+
+    public static func lower(_ value: ElementListener) -> UInt64 {
+         if let rustImpl = value as? ElementListenerImpl {
+             // Rust-implemented object.  Clone the handle and return it
+            return rustImpl.uniffiCloneHandle()
+         } else {
+            // Swift object, generate a new vtable handle and return that.
+            return handleMap.insert(obj: value)
+         }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ElementListener {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: ElementListener, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeElementListener_lift(_ handle: UInt64) throws -> ElementListener {
+    return try FfiConverterTypeElementListener.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeElementListener_lower(_ value: ElementListener) -> UInt64 {
+    return FfiConverterTypeElementListener.lower(value)
+}
+
+
+
+
+
+
+/**
+ * Receives a queue's elements as a byte stream. The stream is each element in order, eldest
+ * first, framed as a 4-byte big-endian length followed by the payload. Chunk boundaries carry
+ * no meaning: a frame may be split across chunks.
+ */
+public protocol Exporter: AnyObject, Sendable {
+    
+    /**
+     * Takes the next chunk of the stream, about 64 KiB. Returning `false` stops the export.
+     */
+    func write(chunk: Data)  -> Bool
+    
+    /**
+     * Called once after the last chunk of an export that ran to the end.
+     */
+    func finish() 
+    
+}
+/**
+ * Receives a queue's elements as a byte stream. The stream is each element in order, eldest
+ * first, framed as a 4-byte big-endian length followed by the payload. Chunk boundaries carry
+ * no meaning: a frame may be split across chunks.
+ */
+open class ExporterImpl: Exporter, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_exporter(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_exporter(handle, $0) }
+    }
+
+    
+
+    
+    /**
+     * Takes the next chunk of the stream, about 64 KiB. Returning `false` stops the export.
+     */
+open func write(chunk: Data) -> Bool  {
+    return try!  FfiConverterBool.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_exporter_write(
+            self.uniffiCloneHandle(),
+        FfiConverterData.lower(chunk),$0
+    )
+})
+}
+    
+    /**
+     * Called once after the last chunk of an export that ran to the end.
+     */
+open func finish()  {try! rustCall() {
+    uniffi_queuefile_fn_method_exporter_finish(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+
+    
+}
+
+
+
+// Put the implementation in a struct so we don't pollute the top-level namespace
+fileprivate struct UniffiCallbackInterfaceExporter {
+
+    // Create the VTable using a series of closures.
+    // Swift automatically converts these into C callback functions.
+    //
+    // This creates 1-element array, since this seems to be the only way to construct a const
+    // pointer that we can pass to the Rust code.
+    static let vtable: [UniffiVTableCallbackInterfaceExporter] = [UniffiVTableCallbackInterfaceExporter(
+        uniffiFree: { (uniffiHandle: UInt64) -> () in
+            do {
+                try FfiConverterTypeExporter.handleMap.remove(handle: uniffiHandle)
+            } catch {
+                print("Uniffi callback interface Exporter: handle missing in uniffiFree")
+            }
+        },
+        uniffiClone: { (uniffiHandle: UInt64) -> UInt64 in
+            do {
+                return try FfiConverterTypeExporter.handleMap.clone(handle: uniffiHandle)
+            } catch {
+                fatalError("Uniffi callback interface Exporter: handle missing in uniffiClone")
+            }
+        },
+        write: { (
+            uniffiHandle: UInt64,
+            chunk: RustBuffer,
+            uniffiOutReturn: UnsafeMutablePointer<Int8>,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            let makeCall = {
+                () throws -> Bool in
+                guard let uniffiObj = try? FfiConverterTypeExporter.handleMap.get(handle: uniffiHandle) else {
+                    throw UniffiInternalError.unexpectedStaleHandle
+                }
+                return uniffiObj.write(
+                     chunk: try FfiConverterData.lift(chunk)
+                )
+            }
+
+            
+            let writeReturn = { uniffiOutReturn.pointee = FfiConverterBool.lower($0) }
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: makeCall,
+                writeReturn: writeReturn
+            )
+        },
+        finish: { (
+            uniffiHandle: UInt64,
+            uniffiOutReturn: UnsafeMutableRawPointer,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            let makeCall = {
+                () throws -> () in
+                guard let uniffiObj = try? FfiConverterTypeExporter.handleMap.get(handle: uniffiHandle) else {
+                    throw UniffiInternalError.unexpectedStaleHandle
+                }
+                return uniffiObj.finish(
+                )
+            }
+
+            
+            let writeReturn = { () }
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: makeCall,
+                writeReturn: writeReturn
+            )
+        }
+    )]
+}
+
+private func uniffiCallbackInitExporter() {
+    uniffi_queuefile_fn_init_callback_vtable_exporter(UniffiCallbackInterfaceExporter.vtable)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeExporter: FfiConverter {
+    fileprivate static let handleMap = UniffiHandleMap<Exporter>()
+
+    typealias FfiType = UInt64
+    typealias SwiftType = Exporter
+
+    public static func lift(_ handle: UInt64) throws -> Exporter {
+        if ((handle & 1) == 0) {
+            // Rust-generated handle, construct a new class that uses the handle to implement the
+            // interface
+            return ExporterImpl(unsafeFromHandle: handle)
+        } else {
+            // Swift-generated handle, get the object from the handle map
+            return try handleMap.remove(handle: handle)
+        }
+    }
+
+    public static func lower(_ value: Exporter) -> UInt64 {
+         if let rustImpl = value as? ExporterImpl {
+             // Rust-implemented object.  Clone the handle and return it
+            return rustImpl.uniffiCloneHandle()
+         } else {
+            // Swift object, generate a new vtable handle and return that.
+            return handleMap.insert(obj: value)
+         }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Exporter {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: Exporter, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeExporter_lift(_ handle: UInt64) throws -> Exporter {
+    return try FfiConverterTypeExporter.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeExporter_lower(_ value: Exporter) -> UInt64 {
+    return FfiConverterTypeExporter.lower(value)
+}
+
+
+
+
+
+
+/**
+ * Supplies the keys that `signature_key` names, typically from the keychain.
+ */
+public protocol KeyProvider: AnyObject, Sendable {
+    
+    /**
+     * The secret for `key_id`, or `None` if there is none.
+     */
+    func key(keyId: String)  -> Data?
+    
+}
+/**
+ * Supplies the keys that `signature_key` names, typically from the keychain.
+ */
+open class KeyProviderImpl: KeyProvider, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_keyprovider(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_keyprovider(handle, $0) }
+    }
+
+    
+
+    
+    /**
+     * The secret for `key_id`, or `None` if there is none.
+     */
+open func key(keyId: String) -> Data?  {
+    return try!  FfiConverterOptionData.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_keyprovider_key(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(keyId),$0
+    )
+})
+}
+    
+
+    
+}
+
+
+
+// Put the implementation in a struct so we don't pollute the top-level namespace
+fileprivate struct UniffiCallbackInterfaceKeyProvider {
+
+    // Create the VTable using a series of closures.
+    // Swift automatically converts these into C callback functions.
+    //
+    // This creates 1-element array, since this seems to be the only way to construct a const
+    // pointer that we can pass to the Rust code.
+    static let vtable: [UniffiVTableCallbackInterfaceKeyProvider] = [UniffiVTableCallbackInterfaceKeyProvider(
+        uniffiFree: { (uniffiHandle: UInt64) -> () in
+            do {
+                try FfiConverterTypeKeyProvider.handleMap.remove(handle: uniffiHandle)
+            } catch {
+                print("Uniffi callback interface KeyProvider: handle missing in uniffiFree")
+            }
+        },
+        uniffiClone: { (uniffiHandle: UInt64) -> UInt64 in
+            do {
+                return try FfiConverterTypeKeyProvider.handleMap.clone(handle: uniffiHandle)
+            } catch {
+                fatalError("Uniffi callback interface KeyProvider: handle missing in uniffiClone")
+            }
+        },
+        key: { (
+            uniffiHandle: UInt64,
+            keyId: RustBuffer,
+            uniffiOutReturn: UnsafeMutablePointer<RustBuffer>,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            let makeCall = {
+                () throws -> Data? in
+                guard let uniffiObj = try? FfiConverterTypeKeyProvider.handleMap.get(handle: uniffiHandle) else {
+                    throw UniffiInternalError.unexpectedStaleHandle
+                }
+                return uniffiObj.key(
+                     keyId: try FfiConverterString.lift(keyId)
+                )
+            }
+
+            
+            let writeReturn = { uniffiOutReturn.pointee = FfiConverterOptionData.lower($0) }
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: makeCall,
+                writeReturn: writeReturn
+            )
+        }
+    )]
+}
+
+private func uniffiCallbackInitKeyProvider() {
+    uniffi_queuefile_fn_init_callback_vtable_keyprovider(UniffiCallbackInterfaceKeyProvider.vtable)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeKeyProvider: FfiConverter {
+    fileprivate static let handleMap = UniffiHandleMap<KeyProvider>()
+
+    typealias FfiType = UInt64
+    typealias SwiftType = KeyProvider
+
+    public static func lift(_ handle: UInt64) throws -> KeyProvider {
+        if ((handle & 1) == 0) {
+            // Rust-generated handle, construct a new class that uses the handle to implement the
+            // interface
+            return KeyProviderImpl(unsafeFromHandle: handle)
+        } else {
+            // Swift-generated handle, get the object from the handle map
+            return try handleMap.remove(handle: handle)
+        }
+    }
+
+    public static func lower(_ value: KeyProvider) -> UInt64 {
+         if let rustImpl = value as? KeyProviderImpl {
+             // Rust-implemented object.  Clone the handle and return it
+            return rustImpl.uniffiCloneHandle()
+         } else {
+            // Swift object, generate a new vtable handle and return that.
+            return handleMap.insert(obj: value)
+         }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> KeyProvider {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: KeyProvider, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeKeyProvider_lift(_ handle: UInt64) throws -> KeyProvider {
+    return try FfiConverterTypeKeyProvider.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeKeyProvider_lower(_ value: KeyProvider) -> UInt64 {
+    return FfiConverterTypeKeyProvider.lower(value)
+}
+
+
+
+
+
+
+/**
+ * A queue file mapped read-only into memory, for tools that walk large dumps. Elements are
+ * borrowed straight from the mapping; only one that wraps around the end of the ring, or that
+ * was compressed against the queue's dictionary, is copied.
+ *
+ * The file must not be truncated while it is mapped, so map a copy rather than a file a queue
+ * still has open.
+ */
+public protocol MappedQueueProtocol: AnyObject, Sendable {
+    
+    /**
+     * The element at `index`, counting from the eldest. Fails with `IndexOutOfRange` past the
+     * last element.
+     */
+    func elementAt(index: UInt32) throws  -> Data
+    
+    func exportCsv(path: String) throws  -> UInt64
+    
+    func exportNdjson(path: String, encoding: ExportEncoding?) throws  -> UInt64
+    
+    /**
+     * Streams every element to `exporter`, framed as `QueueFile::export_with` does.
+     */
+    func exportWith(exporter: Exporter) throws  -> UInt64
+    
+    func getAll() throws  -> [Data]
+    
+    func isEmpty()  -> Bool
+    
+    /**
+     * Bytes of the file that are mapped.
+     */
+    func mappedLen()  -> UInt64
+    
+    /**
+     * The next element in order, or `None` after the last one.
+     */
+    func next() throws  -> Data?
+    
+    /**
+     * Starts `next` over from the eldest element.
+     */
+    func rewind() throws 
+    
+    func size()  -> UInt32
+    
+}
+/**
+ * A queue file mapped read-only into memory, for tools that walk large dumps. Elements are
+ * borrowed straight from the mapping; only one that wraps around the end of the ring, or that
+ * was compressed against the queue's dictionary, is copied.
+ *
+ * The file must not be truncated while it is mapped, so map a copy rather than a file a queue
+ * still has open.
+ */
+open class MappedQueue: MappedQueueProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_mappedqueue(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_mappedqueue(handle, $0) }
+    }
+
+    
+    /**
+     * Maps the queue file at `path` and follows its element frames, failing with
+     * `CorruptedFile` if any of them is damaged. No payload is read here. The dictionaries of
+     * a queue that compresses its elements are read from their `.dict` file next to `path`,
+     * so copy it along with the queue file.
+     */
+public static func openSnapshotMmap(path: String)throws  -> MappedQueue  {
+    return try  FfiConverterTypeMappedQueue_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_constructor_mappedqueue_open_snapshot_mmap(
+        FfiConverterString.lower(path),$0
+    )
+})
+}
+    
+
+    
+    /**
+     * The element at `index`, counting from the eldest. Fails with `IndexOutOfRange` past the
+     * last element.
+     */
+open func elementAt(index: UInt32)throws  -> Data  {
+    return try  FfiConverterData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_mappedqueue_element_at(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(index),$0
+    )
+})
+}
+    
+open func exportCsv(path: String)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_mappedqueue_export_csv(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(path),$0
+    )
+})
+}
+    
+open func exportNdjson(path: String, encoding: ExportEncoding?)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_mappedqueue_export_ndjson(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(path),
+        FfiConverterOptionTypeExportEncoding.lower(encoding),$0
+    )
+})
+}
+    
+    /**
+     * Streams every element to `exporter`, framed as `QueueFile::export_with` does.
+     */
+open func exportWith(exporter: Exporter)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_mappedqueue_export_with(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeExporter_lower(exporter),$0
+    )
+})
+}
+    
+open func getAll()throws  -> [Data]  {
+    return try  FfiConverterSequenceData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_mappedqueue_get_all(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func isEmpty() -> Bool  {
+    return try!  FfiConverterBool.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_mappedqueue_is_empty(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Bytes of the file that are mapped.
+     */
+open func mappedLen() -> UInt64  {
+    return try!  FfiConverterUInt64.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_mappedqueue_mapped_len(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The next element in order, or `None` after the last one.
+     */
+open func next()throws  -> Data?  {
+    return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_mappedqueue_next(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Starts `next` over from the eldest element.
+     */
+open func rewind()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_mappedqueue_rewind(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func size() -> UInt32  {
+    return try!  FfiConverterUInt32.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_mappedqueue_size(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeMappedQueue: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = MappedQueue
+
+    public static func lift(_ handle: UInt64) throws -> MappedQueue {
+        return MappedQueue(unsafeFromHandle: handle)
+    }
+
+    public static func lower(_ value: MappedQueue) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> MappedQueue {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: MappedQueue, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeMappedQueue_lift(_ handle: UInt64) throws -> MappedQueue {
+    return try FfiConverterTypeMappedQueue.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeMappedQueue_lower(_ value: MappedQueue) -> UInt64 {
+    return FfiConverterTypeMappedQueue.lower(value)
+}
+
+
+
+
+
+
+/**
+ * Delivers a queue's elements to a handler until `stop` is called. Each delivery is a job on
+ * the runtime's workers; waits between them are left to the timer thread, so no worker is
+ * held while the consumer has nothing to do.
+ */
+public protocol QueueConsumerProtocol: AnyObject, Sendable {
+    
+    func isRunning()  -> Bool
+    
+    func stats()  -> ConsumerStats
+    
+    /**
+     * Stops delivering and waits for a delivery in progress to finish. The element being
+     * handled is removed or kept according to the handler's answer.
+     */
+    func stop() 
+    
+}
+/**
+ * Delivers a queue's elements to a handler until `stop` is called. Each delivery is a job on
+ * the runtime's workers; waits between them are left to the timer thread, so no worker is
+ * held while the consumer has nothing to do.
+ */
+open class QueueConsumer: QueueConsumerProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_queueconsumer(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_queueconsumer(handle, $0) }
+    }
+
+    
+
+    
+open func isRunning() -> Bool  {
+    return try!  FfiConverterBool.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_queueconsumer_is_running(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func stats() -> ConsumerStats  {
+    return try!  FfiConverterTypeConsumerStats_lift(try! rustCall() {
+    uniffi_queuefile_fn_method_queueconsumer_stats(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Stops delivering and waits for a delivery in progress to finish. The element being
+     * handled is removed or kept according to the handler's answer.
+     */
+open func stop()  {try! rustCall() {
+    uniffi_queuefile_fn_method_queueconsumer_stop(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQueueConsumer: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = QueueConsumer
+
+    public static func lift(_ handle: UInt64) throws -> QueueConsumer {
+        return QueueConsumer(unsafeFromHandle: handle)
+    }
+
+    public static func lower(_ value: QueueConsumer) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueConsumer {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: QueueConsumer, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueConsumer_lift(_ handle: UInt64) throws -> QueueConsumer {
+    return try FfiConverterTypeQueueConsumer.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueConsumer_lower(_ value: QueueConsumer) -> UInt64 {
+    return FfiConverterTypeQueueConsumer.lower(value)
+}
+
+
+
+
+
+
+public protocol QueueFileProtocol: AnyObject, Sendable {
+    
+    func add(data: Data) throws  -> AddReceipt
+    
+    func addAsync(data: Data) async throws  -> AddReceipt
+    
+    /**
+     * Adds `data` split into elements of at most `chunk_len` bytes each (64 MiB by default),
+     * in one write, so payloads longer than the 2 GiB element limit can be queued. Read it back
+     * with `peek_chunked`, `pop_chunked_to_file` or `remove_chunked`; to the other methods each
+     * chunk is an element of its own, read back as its part of `data`. The chunks are told
+     * apart from other elements by how they are stored, so no payload passes for one.
+     */
+    func addChunked(data: Data, chunkLen: UInt32?) throws  -> AddReceipt
+    
+    /**
+     * `add_chunked` with the contents of the file at `src_path`, which may be longer than the
+     * 2 GiB element limit. The file is read and added one chunk at a time under the lock, so
+     * only one chunk is held in memory (all of them while writes are paused with `Buffer`);
+     * if a chunk fails to be added, the ones before it stay queued and `remove_chunked` drops
+     * them. Returns its length.
+     */
+    func addChunkedFromFile(srcPath: String, chunkLen: UInt32?) throws  -> UInt64
+    
+    /**
+     * Adds `envelope` encoded with `encode_envelope`, stamped with the current time if its
+     * `created_at` is `None`.
+     */
+    func addEnvelope(envelope: ElementEnvelope) throws  -> AddReceipt
+    
+    /**
+     * Adds the contents of the file at `src_path` as one element, read inside Rust so the
+     * payload never crosses the FFI boundary. queue-file writes an element in a single call,
+     * so the payload is still held in memory once while it is added; a file over 64 MiB, or
+     * over `max_read_bytes` when that is lower, fails with `TooLargeForRead` before any of it
+     * is read. `add_chunked_from_file` takes longer ones. Returns its length.
+     */
+    func addFromFile(srcPath: String) throws  -> UInt64
+    
+    func addFromFileAsync(srcPath: String) async throws  -> UInt64
+    
+    /**
+     * Adds `map` encoded with `encode_msgpack_map`.
+     */
+    func addMsgpackMap(map: [String: MsgpackValue]) throws  -> AddReceipt
+    
+    func addMultiple(items: [Data]) throws  -> AddReceipt
+    
+    func addMultipleAsync(items: [Data]) async throws  -> AddReceipt
+    
+    func addMultipleWithTimeout(items: [Data], timeout: TimeInterval) throws  -> AddReceipt
+    
+    /**
+     * `add` failing with `Timeout` once `timeout` has passed, whatever `operation_timeout`
+     * says. The `_with_timeout` calls run on a runtime worker while the caller waits.
+     */
+    func addWithTimeout(data: Data, timeout: TimeInterval) throws  -> AddReceipt
+    
+    func canAccept(len: UInt64) throws  -> Bool
+    
+    /**
+     * The fragmentation, growth since open and element sizes of the queue, with the action
+     * the library recommends: `CompactNow` when a `rewrite` would give back at least half the
+     * file and 1 MiB, `RaiseCapacity` after three or more growths from a smaller `capacity`,
+     * and `EnableCompression` for 1 MiB or more in 1000 or more uncompressed elements.
+     */
+    func capacityReport() throws  -> CapacityReport
+    
+    /**
+     * The changes made after the one with sequence number `since`, oldest first, from the
+     * journal kept under `journal_capacity`. Sequence numbers are `generation()` values, so
+     * `changes_since(generation())` is empty. Fails with `JournalGap` once the journal has
+     * dropped some of the changes asked for.
+     */
+    func changesSince(since: UInt64) throws  -> [ChangeRecord]
+    
+    func clear() throws 
+    
+    func clearAsync() async throws 
+    
+    func clearSlowOpListener() throws 
+    
+    func clearWatermarks() throws 
+    
+    func clearWithTimeout(timeout: TimeInterval) throws 
+    
+    /**
+     * A plain-text dump for support tickets: what `file_info` reports, the ring layout of
+     * `fragmentation_report`, and an offset, hex and ASCII preview of the first `max_elements`
+     * elements, each cut to `max_bytes_per_element` bytes. Previews show the bytes as stored,
+     * so a compressed element shows its zstd frame.
+     */
+    func debugDump(maxElements: UInt32, maxBytesPerElement: UInt32) throws  -> String
+    
+    func diskUsage() throws  -> UInt64
+    
+    /**
+     * Passes elements, eldest first, to `handler` and removes each one it accepts before
+     * passing the next, stopping at the first it rejects or after `max`. A crash while an
+     * element is being handled leaves it in the queue, so the handler must tolerate seeing an
+     * element again. Other callers wait until it returns, and the handler must not call back
+     * into this queue. Returns the number of elements removed.
+     */
+    func drainWith(handler: DrainHandler, max: UInt32) throws  -> UInt32
+    
+    func drainWithAsync(handler: DrainHandler, max: UInt32) async throws  -> UInt32
+    
+    /**
+     * Returns the element at `index`, counting from the eldest, without removing it. With
+     * `element_index` it is read from its recorded position; otherwise the elements before it
+     * are walked. Fails with `IndexOutOfRange` past the last element.
+     */
+    func elementAt(index: UInt32) throws  -> Data
+    
+    /**
+     * Streams every element to a new CSV file at `path` with `index,size,data` columns, the
+     * payload as quoted UTF-8 text. Returns the number of elements exported.
+     */
+    func exportCsv(path: String) throws  -> UInt64
+    
+    /**
+     * Streams every element to a new newline-delimited JSON file at `path`, one
+     * `{"index":N,"data":"..."}` object per line. `encoding` defaults to base64. Returns the
+     * number of elements exported.
+     */
+    func exportNdjson(path: String, encoding: ExportEncoding?) throws  -> UInt64
+    
+    /**
+     * Inserts every element, eldest first, into `table` of the SQLite database at `db_path`
+     * in one transaction, creating the database and the table as needed. A new table has an
+     * `id INTEGER PRIMARY KEY` column, which orders the rows as the queue does, and a
+     * `data BLOB` column; an existing one needs a `data` column. The queue is left as it is.
+     * Returns the number of elements exported.
+     */
+    func exportToSqlite(dbPath: String, table: String) throws  -> UInt64
+    
+    /**
+     * Streams every element to `exporter` as length-prefixed frames in chunks of about 64 KiB,
+     * so the queue never has to fit in memory at once. Writers wait until it returns, and the
+     * exporter must not call back into this queue. An exporter that returns `false` stops the
+     * export with an `Interrupted` I/O error, without `finish`. Returns the number of elements
+     * exported.
+     */
+    func exportWith(exporter: Exporter) throws  -> UInt64
+    
+    /**
+     * The header of the file as committed after writing out buffered adds and deferred
+     * removals, its file system times and the optional features in use.
+     */
+    func fileInfo() throws  -> FileInfo
+    
+    func fileLen() throws  -> UInt64
+    
+    /**
+     * Returns the positions, eldest first, of up to `limit` elements whose payload starts with
+     * `prefix`. Payloads are compared in place; none are copied unless they have to be
+     * decompressed.
+     */
+    func findIndices(prefix: Data, limit: UInt32) throws  -> [UInt32]
+    
+    /**
+     * Runs the scans `lazy_open` deferred, which the first call that needs them would
+     * otherwise run. Does nothing once they have run or when the queue was opened eagerly.
+     */
+    func finishOpen() throws 
+    
+    func finishOpenAsync() async throws 
+    
+    func flush() throws 
+    
+    func fragmentationReport() throws  -> FragmentationReport
+    
+    func freeze() throws 
+    
+    /**
+     * Commits the removals held under `deferred_removes` with one header write, giving their
+     * space back to the ring. `flush`, `sync_all`, closing the queue and any read other than
+     * `peek`, `size` and `is_empty` do the same.
+     */
+    func gc() throws 
+    
+    /**
+     * A counter bumped by every add, removal, clear, rewrite and swap that changes the
+     * elements, starting at 0 when the queue is opened, or where it stood at the last sync or
+     * close when `element_index` restores it. Two equal readings mean nothing changed in
+     * between.
+     */
+    func generation() throws  -> UInt64
+    
+    func getAll() throws  -> [Data]
+    
+    func getAllAsync() async throws  -> [Data]
+    
+    /**
+     * Runs quick checks on the header, the counters, free disk space, the lock and the time
+     * since the last sync, without walking the elements. Waits at most a second for the lock.
+     */
+    func healthCheck() throws  -> HealthReport
+    
+    /**
+     * Adds the values of `column` of every row of `table` in the SQLite database at `db_path`,
+     * in `rowid` order, for moving pending events out of an app's old SQLite store. Blobs and
+     * text are added as their bytes, numbers as their decimal text, and `NULL`s are skipped.
+     * Values are added in batches of about 1 MiB, so a failure part-way leaves the batches
+     * before it in the queue; the database is only read. Returns the number of elements added.
+     */
+    func importFromSqlite(dbPath: String, table: String, column: String) throws  -> UInt64
+    
+    func isEmpty() throws  -> Bool
+    
+    func isFrozen() throws  -> Bool
+    
+    /**
+     * An iterator over the elements queued now, which under `mode` (`FailOnChange` by
+     * default) either fails with `ConcurrentModification` once the queue changes or keeps
+     * returning the elements it pinned. Creating it flushes buffered adds.
+     */
+    func iterator(mode: IterationMode?) throws  -> QueueFileIterator
+    
+    /**
+     * The corrupt files opening renamed aside under `QuarantineAndStartFresh` before starting
+     * an empty queue. `None` when the file opened as it was.
+     */
+    func lastCorruptionRecovery() throws  -> CorruptionRecovery?
+    
+    /**
+     * What opening moved into `<path>.quarantine` under the `quarantine_corrupt` option or the
+     * `RepairInPlace` policy. `None` without either or when every element was intact.
+     */
+    func lastQuarantine() throws  -> QuarantineReport?
+    
+    /**
+     * How the file compared with the trailer the last `sync_all` wrote, checked on open with
+     * the `sync_trailer` option. `None` without the option or when no valid trailer existed.
+     */
+    func lastSyncCheck() throws  -> SyncCheck?
+    
+    func maxCommitDelay() throws  -> TimeInterval?
+    
+    func mirrorStatus() throws  -> MirrorStatus
+    
+    func overwriteOnRemove() throws  -> Bool
+    
+    func pauseWrites(mode: PauseMode) throws 
+    
+    func peek() throws  -> Data?
+    
+    func peekAsync() async throws  -> Data?
+    
+    /**
+     * The eldest chunked element reassembled, or `None` if the queue is empty. An element not
+     * added with `add_chunked` is returned as it is. Fails with `InvalidChunk` if the head of
+     * the queue holds only part of a chunked element.
+     */
+    func peekChunked() throws  -> Data?
+    
+    /**
+     * SHA-256 of the eldest element's payload, computed in Rust so only the 32-byte digest
+     * crosses the FFI boundary. `None` if the queue is empty.
+     */
+    func peekDigest() throws  -> Data?
+    
+    /**
+     * The eldest element decoded with `decode_envelope`, or `None` if the queue is empty.
+     */
+    func peekEnvelope() throws  -> ElementEnvelope?
+    
+    /**
+     * The eldest element's size, sequence id and envelope fields, or `None` if the queue is
+     * empty. The element is read and decoded in Rust and only its metadata is returned.
+     */
+    func peekMetadata() throws  -> ElementMeta?
+    
+    /**
+     * The eldest element decoded with `decode_msgpack_map`, or `None` if the queue is empty.
+     */
+    func peekMsgpackMap() throws  -> [String: MsgpackValue]?
+    
+    /**
+     * Returns a uniformly chosen element without removing it, reading only the elements up
+     * to it, or only that element with `element_index`. The same `seed` picks the same element
+     * from the same queue.
+     */
+    func peekRandom(seed: UInt64?) throws  -> Data?
+    
+    /**
+     * Writes the eldest element to a new file at `dest_path` and syncs it, so the payload never
+     * crosses the FFI boundary. It is copied a chunk at a time, however long it is. Returns its
+     * length, or `None` (creating no file) if the queue is empty.
+     */
+    func peekToFile(destPath: String) throws  -> UInt64?
+    
+    func peekToFileAsync(destPath: String) async throws  -> UInt64?
+    
+    func peekWithTimeout(timeout: TimeInterval) throws  -> Data?
+    
+    /**
+     * Writes the eldest chunked element to a new file at `dest_path` one chunk at a time, syncs
+     * it and removes the element's chunks. Returns its length, or `None` (creating no file) if
+     * the queue is empty.
+     */
+    func popChunkedToFile(destPath: String) throws  -> UInt64?
+    
+    /**
+     * Like `peek_to_file`, then removes the element once the file has been synced. The queue
+     * stays locked in between, so no other consumer can take the same element.
+     */
+    func popToFile(destPath: String) throws  -> UInt64?
+    
+    func popToFileAsync(destPath: String) async throws  -> UInt64?
+    
+    /**
+     * Returns false if no element, written or buffered, has exactly this payload. True may be
+     * a false positive when the `bloom_filter_elements` option is set (about 1% up to the
+     * configured element count); without it the queue is scanned and the answer is exact.
+     */
+    func probablyContains(data: Data) throws  -> Bool
+    
+    /**
+     * Keeps delivering elements to `handler` from the runtime's workers, eldest first, until
+     * the returned consumer is stopped. Unlike `drain_with`, the queue is not locked while an
+     * element is handled. An accepted element is removed; a rejected one is delivered again
+     * after the policy's backoff, and after `max_attempts` deliveries it is added to
+     * `dead_letter` and removed, or just removed without one.
+     */
+    func process(handler: DrainHandler, policy: ProcessPolicy, deadLetter: QueueFile?) throws  -> QueueConsumer
+    
+    func remainingCapacity() throws  -> CapacityInfo
+    
+    func remove() throws 
+    
+    func removeAsync() async throws 
+    
+    /**
+     * Removes the element at `index`, counting from the eldest, leaving the order of the
+     * others unchanged. The file is rewritten through a temporary copy, so this costs a pass
+     * over the whole queue; use it for the occasional poisoned element.
+     */
+    func removeAt(index: UInt32) throws 
+    
+    /**
+     * Removes the eldest chunked element, every chunk of it. The rest of one that a plain
+     * `remove` left behind, or one whose last chunks are missing, is removed up to the first
+     * element that does not continue it, so `InvalidChunk` never blocks the queue. Does
+     * nothing if the queue is empty.
+     */
+    func removeChunked() throws 
+    
+    func removeN(n: UInt32) throws 
+    
+    func removeNAsync(n: UInt32) async throws 
+    
+    func removeNWithTimeout(n: UInt32, timeout: TimeInterval) throws 
+    
+    func removeWithTimeout(timeout: TimeInterval) throws 
+    
+    /**
+     * Ends the current stats window and starts the next, returning the one that ended. Both
+     * happen under the queue's lock, so a periodic uploader that reports what this returns
+     * neither misses nor double-counts an operation.
+     */
+    func resetStats() throws  -> StatsWindow
+    
+    func resumeWrites() throws 
+    
+    /**
+     * Copies every element into a new file that stores it as `options` asks, for example to
+     * compress a queue created before compression was turned on, then renames the copy into
+     * place. Reads return the same payloads as before, in the same order. Like `remove_at` it
+     * costs a pass over the whole queue, and a crash leaves either the old file or the new one.
+     */
+    func rewrite(options: RewriteOptions) throws  -> RewriteReport
+    
+    /**
+     * Returns up to `n` distinct elements chosen uniformly, in queue order, copying only the
+     * chosen ones in a single pass.
+     */
+    func sample(n: UInt32, seed: UInt64?) throws  -> [Data]
+    
+    func setCacheOffsetPolicy(policy: OffsetCachePolicy) throws 
+    
+    func setExcludedFromBackup(excluded: Bool) throws 
+    
+    func setMaxCommitDelay(value: TimeInterval?) throws 
+    
+    func setOverwriteOnRemove(value: Bool) throws 
+    
+    func setReadBufferSize(size: UInt32) throws 
+    
+    /**
+     * Calls `listener` after every add, peek, removal, clear, sync or rewrite that took longer
+     * than `threshold`, timed from when it got hold of the queue.
+     */
+    func setSlowOpListener(threshold: TimeInterval, listener: SlowOpListener) throws 
+    
+    func setSyncWrites(value: Bool) throws 
+    
+    func setWatermarks(config: WatermarkConfig, listener: WatermarkListener) throws 
+    
+    func setWriteBuffering(config: WriteBufferConfig?) throws 
+    
+    /**
+     * Leaves deferred removals pending and does not count them.
+     */
+    func size() throws  -> UInt32
+    
+    /**
+     * Counts elements by payload size in one pass over the queue. `bucket_bounds` must be
+     * ascending; bucket `i` holds sizes in `bucket_bounds[i - 1]..bucket_bounds[i]`, so the
+     * result has one more entry than there are bounds.
+     */
+    func sizeHistogram(bucketBounds: [UInt64]) throws  -> [UInt64]
+    
+    /**
+     * A read view of the elements queued now, for iterating while adds and removals go on.
+     * Taking it holds the lock only to flush buffered adds and read the header.
+     */
+    func snapshot() throws  -> QueueSnapshot
+    
+    func spaceReport() throws  -> SpaceReport
+    
+    func stats() throws  -> QueueStats
+    
+    /**
+     * Starts a thread passing `listener` every element whose sequence id is after `seq`, or
+     * every element when `seq` is `None`. It first reads the ones already committed from the
+     * file, then receives each later commit's elements as they are written.
+     */
+    func subscribeFrom(seq: UInt64?, listener: ElementListener) throws  -> Subscription
+    
+    /**
+     * Exchanges the contents of this queue and `other` with one atomic rename, so a crash
+     * leaves both or neither swapped. Both files must be on the same volume. Each handle keeps
+     * its own path, options and stats.
+     */
+    func swapWith(other: QueueFile) throws 
+    
+    func syncAll() throws 
+    
+    func syncAllAsync() async throws 
+    
+    func syncAllWithTimeout(timeout: TimeInterval) throws 
+    
+    /**
+     * Brings this queue up to date with `source`, which is only read, for example a backup
+     * that fell behind its primary. Elements are matched by the SHA-256 of their payloads, so
+     * where the eldest elements of `source` are the newest ones here, only the difference is
+     * removed and added. Both queues are locked for the duration.
+     */
+    func syncFrom(source: QueueFile, strategy: SyncStrategy) throws  -> SyncReport
+    
+    func syncWrites() throws  -> Bool
+    
+    /**
+     * Trains a zstd dictionary on up to `sample_limit` elements from the front of the queue
+     * and compresses every element added after it against the dictionary. Elements already
+     * queued keep their stored form, and reads return payloads as they were added either way.
+     * Retraining keeps the earlier dictionaries, so nothing compressed against them becomes
+     * unreadable. Fails with an I/O error if the elements are too few or too alike to train on.
+     */
+    func trainDictionary(sampleLimit: UInt32) throws  -> TrainedDictionary
+    
+    func unfreeze() throws 
+    
+    /**
+     * Replaces the payload of the element at `index`, keeping its position. Like `remove_at`
+     * it rewrites the file, since a frame cannot change length in place.
+     */
+    func updateAt(index: UInt32, data: Data) throws 
+    
+    func usedBytes() throws  -> UInt64
+    
+    func watermarks() throws  -> WatermarkConfig?
+    
+    /**
+     * The stats counted since the last `reset_stats`, or since the queue was opened. `stats`
+     * keeps reporting the lifetime totals.
+     */
+    func windowStats() throws  -> StatsWindow
+    
+    func writeBuffering() throws  -> WriteBufferConfig?
+    
+}
+open class QueueFile: QueueFileProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_queuefile(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_queuefile(handle, $0) }
+    }
+
+    
+public static func `open`(path: String)throws  -> QueueFile  {
+    return try  FfiConverterTypeQueueFile_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_constructor_queuefile_open(
+        FfiConverterString.lower(path),$0
+    )
+})
+}
+    
+    /**
+     * `open` on a runtime worker thread, which also runs the scans `lazy_open` would defer,
+     * so the caller waits on neither the file nor the first call that needs them.
+     */
+public static func openAsync(path: String)async throws  -> QueueFile  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_constructor_queuefile_open_async(FfiConverterString.lower(path)
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_u64,
+            completeFunc: ffi_queuefile_rust_future_complete_u64,
+            freeFunc: ffi_queuefile_rust_future_free_u64,
+            liftFunc: FfiConverterTypeQueueFile_lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+public static func openWithOptions(path: String, options: QueueFileOptions)throws  -> QueueFile  {
+    return try  FfiConverterTypeQueueFile_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_constructor_queuefile_open_with_options(
+        FfiConverterString.lower(path),
+        FfiConverterTypeQueueFileOptions_lower(options),$0
+    )
+})
+}
+    
+    /**
+     * `open_with_options` on a runtime worker thread, followed by `finish_open`.
+     */
+public static func openWithOptionsAsync(path: String, options: QueueFileOptions)async throws  -> QueueFile  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_constructor_queuefile_open_with_options_async(FfiConverterString.lower(path),FfiConverterTypeQueueFileOptions_lower(options)
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_u64,
+            completeFunc: ffi_queuefile_rust_future_complete_u64,
+            freeFunc: ffi_queuefile_rust_future_free_u64,
+            liftFunc: FfiConverterTypeQueueFile_lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+public static func withCapacity(path: String, capacity: UInt64)throws  -> QueueFile  {
+    return try  FfiConverterTypeQueueFile_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_constructor_queuefile_with_capacity(
+        FfiConverterString.lower(path),
+        FfiConverterUInt64.lower(capacity),$0
+    )
+})
+}
+    
+
+    
+open func add(data: Data)throws  -> AddReceipt  {
+    return try  FfiConverterTypeAddReceipt_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_add(
+            self.uniffiCloneHandle(),
+        FfiConverterData.lower(data),$0
+    )
+})
+}
+    
+open func addAsync(data: Data)async throws  -> AddReceipt  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_add_async(
+                    self.uniffiCloneHandle(),
+                    FfiConverterData.lower(data)
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_rust_buffer,
+            completeFunc: ffi_queuefile_rust_future_complete_rust_buffer,
+            freeFunc: ffi_queuefile_rust_future_free_rust_buffer,
+            liftFunc: FfiConverterTypeAddReceipt_lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+    /**
+     * Adds `data` split into elements of at most `chunk_len` bytes each (64 MiB by default),
+     * in one write, so payloads longer than the 2 GiB element limit can be queued. Read it back
+     * with `peek_chunked`, `pop_chunked_to_file` or `remove_chunked`; to the other methods each
+     * chunk is an element of its own, read back as its part of `data`. The chunks are told
+     * apart from other elements by how they are stored, so no payload passes for one.
+     */
+open func addChunked(data: Data, chunkLen: UInt32?)throws  -> AddReceipt  {
+    return try  FfiConverterTypeAddReceipt_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_add_chunked(
+            self.uniffiCloneHandle(),
+        FfiConverterData.lower(data),
+        FfiConverterOptionUInt32.lower(chunkLen),$0
+    )
+})
+}
+    
+    /**
+     * `add_chunked` with the contents of the file at `src_path`, which may be longer than the
+     * 2 GiB element limit. The file is read and added one chunk at a time under the lock, so
+     * only one chunk is held in memory (all of them while writes are paused with `Buffer`);
+     * if a chunk fails to be added, the ones before it stay queued and `remove_chunked` drops
+     * them. Returns its length.
+     */
+open func addChunkedFromFile(srcPath: String, chunkLen: UInt32?)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_add_chunked_from_file(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(srcPath),
+        FfiConverterOptionUInt32.lower(chunkLen),$0
+    )
+})
+}
+    
+    /**
+     * Adds `envelope` encoded with `encode_envelope`, stamped with the current time if its
+     * `created_at` is `None`.
+     */
+open func addEnvelope(envelope: ElementEnvelope)throws  -> AddReceipt  {
+    return try  FfiConverterTypeAddReceipt_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_add_envelope(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeElementEnvelope_lower(envelope),$0
+    )
+})
+}
+    
+    /**
+     * Adds the contents of the file at `src_path` as one element, read inside Rust so the
+     * payload never crosses the FFI boundary. queue-file writes an element in a single call,
+     * so the payload is still held in memory once while it is added; a file over 64 MiB, or
+     * over `max_read_bytes` when that is lower, fails with `TooLargeForRead` before any of it
+     * is read. `add_chunked_from_file` takes longer ones. Returns its length.
+     */
+open func addFromFile(srcPath: String)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_add_from_file(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(srcPath),$0
+    )
+})
+}
+    
+open func addFromFileAsync(srcPath: String)async throws  -> UInt64  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_add_from_file_async(
+                    self.uniffiCloneHandle(),
+                    FfiConverterString.lower(srcPath)
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_u64,
+            completeFunc: ffi_queuefile_rust_future_complete_u64,
+            freeFunc: ffi_queuefile_rust_future_free_u64,
+            liftFunc: FfiConverterUInt64.lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+    /**
+     * Adds `map` encoded with `encode_msgpack_map`.
+     */
+open func addMsgpackMap(map: [String: MsgpackValue])throws  -> AddReceipt  {
+    return try  FfiConverterTypeAddReceipt_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_add_msgpack_map(
+            self.uniffiCloneHandle(),
+        FfiConverterDictionaryStringTypeMsgpackValue.lower(map),$0
+    )
+})
+}
+    
+open func addMultiple(items: [Data])throws  -> AddReceipt  {
+    return try  FfiConverterTypeAddReceipt_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_add_multiple(
+            self.uniffiCloneHandle(),
+        FfiConverterSequenceData.lower(items),$0
+    )
+})
+}
+    
+open func addMultipleAsync(items: [Data])async throws  -> AddReceipt  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_add_multiple_async(
+                    self.uniffiCloneHandle(),
+                    FfiConverterSequenceData.lower(items)
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_rust_buffer,
+            completeFunc: ffi_queuefile_rust_future_complete_rust_buffer,
+            freeFunc: ffi_queuefile_rust_future_free_rust_buffer,
+            liftFunc: FfiConverterTypeAddReceipt_lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+open func addMultipleWithTimeout(items: [Data], timeout: TimeInterval)throws  -> AddReceipt  {
+    return try  FfiConverterTypeAddReceipt_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_add_multiple_with_timeout(
+            self.uniffiCloneHandle(),
+        FfiConverterSequenceData.lower(items),
+        FfiConverterDuration.lower(timeout),$0
+    )
+})
+}
+    
+    /**
+     * `add` failing with `Timeout` once `timeout` has passed, whatever `operation_timeout`
+     * says. The `_with_timeout` calls run on a runtime worker while the caller waits.
+     */
+open func addWithTimeout(data: Data, timeout: TimeInterval)throws  -> AddReceipt  {
+    return try  FfiConverterTypeAddReceipt_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_add_with_timeout(
+            self.uniffiCloneHandle(),
+        FfiConverterData.lower(data),
+        FfiConverterDuration.lower(timeout),$0
+    )
+})
+}
+    
+open func canAccept(len: UInt64)throws  -> Bool  {
+    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_can_accept(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt64.lower(len),$0
+    )
+})
+}
+    
+    /**
+     * The fragmentation, growth since open and element sizes of the queue, with the action
+     * the library recommends: `CompactNow` when a `rewrite` would give back at least half the
+     * file and 1 MiB, `RaiseCapacity` after three or more growths from a smaller `capacity`,
+     * and `EnableCompression` for 1 MiB or more in 1000 or more uncompressed elements.
+     */
+open func capacityReport()throws  -> CapacityReport  {
+    return try  FfiConverterTypeCapacityReport_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_capacity_report(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The changes made after the one with sequence number `since`, oldest first, from the
+     * journal kept under `journal_capacity`. Sequence numbers are `generation()` values, so
+     * `changes_since(generation())` is empty. Fails with `JournalGap` once the journal has
+     * dropped some of the changes asked for.
+     */
+open func changesSince(since: UInt64)throws  -> [ChangeRecord]  {
+    return try  FfiConverterSequenceTypeChangeRecord.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_changes_since(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt64.lower(since),$0
+    )
+})
+}
+    
+open func clear()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_clear(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func clearAsync()async throws   {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_clear_async(
+                    self.uniffiCloneHandle()
+                    
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_void,
+            completeFunc: ffi_queuefile_rust_future_complete_void,
+            freeFunc: ffi_queuefile_rust_future_free_void,
+            liftFunc: { $0 },
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+open func clearSlowOpListener()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_clear_slow_op_listener(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func clearWatermarks()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_clear_watermarks(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func clearWithTimeout(timeout: TimeInterval)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_clear_with_timeout(
+            self.uniffiCloneHandle(),
+        FfiConverterDuration.lower(timeout),$0
+    )
+}
+}
+    
+    /**
+     * A plain-text dump for support tickets: what `file_info` reports, the ring layout of
+     * `fragmentation_report`, and an offset, hex and ASCII preview of the first `max_elements`
+     * elements, each cut to `max_bytes_per_element` bytes. Previews show the bytes as stored,
+     * so a compressed element shows its zstd frame.
+     */
+open func debugDump(maxElements: UInt32, maxBytesPerElement: UInt32)throws  -> String  {
+    return try  FfiConverterString.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_debug_dump(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(maxElements),
+        FfiConverterUInt32.lower(maxBytesPerElement),$0
+    )
+})
+}
+    
+open func diskUsage()throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_disk_usage(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Passes elements, eldest first, to `handler` and removes each one it accepts before
+     * passing the next, stopping at the first it rejects or after `max`. A crash while an
+     * element is being handled leaves it in the queue, so the handler must tolerate seeing an
+     * element again. Other callers wait until it returns, and the handler must not call back
+     * into this queue. Returns the number of elements removed.
+     */
+open func drainWith(handler: DrainHandler, max: UInt32)throws  -> UInt32  {
+    return try  FfiConverterUInt32.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_drain_with(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeDrainHandler_lower(handler),
+        FfiConverterUInt32.lower(max),$0
+    )
+})
+}
+    
+open func drainWithAsync(handler: DrainHandler, max: UInt32)async throws  -> UInt32  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_drain_with_async(
+                    self.uniffiCloneHandle(),
+                    FfiConverterTypeDrainHandler_lower(handler),FfiConverterUInt32.lower(max)
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_u32,
+            completeFunc: ffi_queuefile_rust_future_complete_u32,
+            freeFunc: ffi_queuefile_rust_future_free_u32,
+            liftFunc: FfiConverterUInt32.lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+    /**
+     * Returns the element at `index`, counting from the eldest, without removing it. With
+     * `element_index` it is read from its recorded position; otherwise the elements before it
+     * are walked. Fails with `IndexOutOfRange` past the last element.
+     */
+open func elementAt(index: UInt32)throws  -> Data  {
+    return try  FfiConverterData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_element_at(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(index),$0
+    )
+})
+}
+    
+    /**
+     * Streams every element to a new CSV file at `path` with `index,size,data` columns, the
+     * payload as quoted UTF-8 text. Returns the number of elements exported.
+     */
+open func exportCsv(path: String)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_export_csv(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(path),$0
+    )
+})
+}
+    
+    /**
+     * Streams every element to a new newline-delimited JSON file at `path`, one
+     * `{"index":N,"data":"..."}` object per line. `encoding` defaults to base64. Returns the
+     * number of elements exported.
+     */
+open func exportNdjson(path: String, encoding: ExportEncoding?)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_export_ndjson(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(path),
+        FfiConverterOptionTypeExportEncoding.lower(encoding),$0
+    )
+})
+}
+    
+    /**
+     * Inserts every element, eldest first, into `table` of the SQLite database at `db_path`
+     * in one transaction, creating the database and the table as needed. A new table has an
+     * `id INTEGER PRIMARY KEY` column, which orders the rows as the queue does, and a
+     * `data BLOB` column; an existing one needs a `data` column. The queue is left as it is.
+     * Returns the number of elements exported.
+     */
+open func exportToSqlite(dbPath: String, table: String)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_export_to_sqlite(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(dbPath),
+        FfiConverterString.lower(table),$0
+    )
+})
+}
+    
+    /**
+     * Streams every element to `exporter` as length-prefixed frames in chunks of about 64 KiB,
+     * so the queue never has to fit in memory at once. Writers wait until it returns, and the
+     * exporter must not call back into this queue. An exporter that returns `false` stops the
+     * export with an `Interrupted` I/O error, without `finish`. Returns the number of elements
+     * exported.
+     */
+open func exportWith(exporter: Exporter)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_export_with(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeExporter_lower(exporter),$0
+    )
+})
+}
+    
+    /**
+     * The header of the file as committed after writing out buffered adds and deferred
+     * removals, its file system times and the optional features in use.
+     */
+open func fileInfo()throws  -> FileInfo  {
+    return try  FfiConverterTypeFileInfo_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_file_info(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func fileLen()throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_file_len(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Returns the positions, eldest first, of up to `limit` elements whose payload starts with
+     * `prefix`. Payloads are compared in place; none are copied unless they have to be
+     * decompressed.
+     */
+open func findIndices(prefix: Data, limit: UInt32)throws  -> [UInt32]  {
+    return try  FfiConverterSequenceUInt32.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_find_indices(
+            self.uniffiCloneHandle(),
+        FfiConverterData.lower(prefix),
+        FfiConverterUInt32.lower(limit),$0
+    )
+})
+}
+    
+    /**
+     * Runs the scans `lazy_open` deferred, which the first call that needs them would
+     * otherwise run. Does nothing once they have run or when the queue was opened eagerly.
+     */
+open func finishOpen()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_finish_open(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func finishOpenAsync()async throws   {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_finish_open_async(
+                    self.uniffiCloneHandle()
+                    
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_void,
+            completeFunc: ffi_queuefile_rust_future_complete_void,
+            freeFunc: ffi_queuefile_rust_future_free_void,
+            liftFunc: { $0 },
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+open func flush()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_flush(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func fragmentationReport()throws  -> FragmentationReport  {
+    return try  FfiConverterTypeFragmentationReport_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_fragmentation_report(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func freeze()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_freeze(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+    /**
+     * Commits the removals held under `deferred_removes` with one header write, giving their
+     * space back to the ring. `flush`, `sync_all`, closing the queue and any read other than
+     * `peek`, `size` and `is_empty` do the same.
+     */
+open func gc()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_gc(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+    /**
+     * A counter bumped by every add, removal, clear, rewrite and swap that changes the
+     * elements, starting at 0 when the queue is opened, or where it stood at the last sync or
+     * close when `element_index` restores it. Two equal readings mean nothing changed in
+     * between.
+     */
+open func generation()throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_generation(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func getAll()throws  -> [Data]  {
+    return try  FfiConverterSequenceData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_get_all(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func getAllAsync()async throws  -> [Data]  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_get_all_async(
+                    self.uniffiCloneHandle()
+                    
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_rust_buffer,
+            completeFunc: ffi_queuefile_rust_future_complete_rust_buffer,
+            freeFunc: ffi_queuefile_rust_future_free_rust_buffer,
+            liftFunc: FfiConverterSequenceData.lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+    /**
+     * Runs quick checks on the header, the counters, free disk space, the lock and the time
+     * since the last sync, without walking the elements. Waits at most a second for the lock.
+     */
+open func healthCheck()throws  -> HealthReport  {
+    return try  FfiConverterTypeHealthReport_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_health_check(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Adds the values of `column` of every row of `table` in the SQLite database at `db_path`,
+     * in `rowid` order, for moving pending events out of an app's old SQLite store. Blobs and
+     * text are added as their bytes, numbers as their decimal text, and `NULL`s are skipped.
+     * Values are added in batches of about 1 MiB, so a failure part-way leaves the batches
+     * before it in the queue; the database is only read. Returns the number of elements added.
+     */
+open func importFromSqlite(dbPath: String, table: String, column: String)throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_import_from_sqlite(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(dbPath),
+        FfiConverterString.lower(table),
+        FfiConverterString.lower(column),$0
+    )
+})
+}
+    
+open func isEmpty()throws  -> Bool  {
+    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_is_empty(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func isFrozen()throws  -> Bool  {
+    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_is_frozen(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * An iterator over the elements queued now, which under `mode` (`FailOnChange` by
+     * default) either fails with `ConcurrentModification` once the queue changes or keeps
+     * returning the elements it pinned. Creating it flushes buffered adds.
+     */
+open func iterator(mode: IterationMode?)throws  -> QueueFileIterator  {
+    return try  FfiConverterTypeQueueFileIterator_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_iterator(
+            self.uniffiCloneHandle(),
+        FfiConverterOptionTypeIterationMode.lower(mode),$0
+    )
+})
+}
+    
+    /**
+     * The corrupt files opening renamed aside under `QuarantineAndStartFresh` before starting
+     * an empty queue. `None` when the file opened as it was.
+     */
+open func lastCorruptionRecovery()throws  -> CorruptionRecovery?  {
+    return try  FfiConverterOptionTypeCorruptionRecovery.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_last_corruption_recovery(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * What opening moved into `<path>.quarantine` under the `quarantine_corrupt` option or the
+     * `RepairInPlace` policy. `None` without either or when every element was intact.
+     */
+open func lastQuarantine()throws  -> QuarantineReport?  {
+    return try  FfiConverterOptionTypeQuarantineReport.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_last_quarantine(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * How the file compared with the trailer the last `sync_all` wrote, checked on open with
+     * the `sync_trailer` option. `None` without the option or when no valid trailer existed.
+     */
+open func lastSyncCheck()throws  -> SyncCheck?  {
+    return try  FfiConverterOptionTypeSyncCheck.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_last_sync_check(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func maxCommitDelay()throws  -> TimeInterval?  {
+    return try  FfiConverterOptionDuration.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_max_commit_delay(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func mirrorStatus()throws  -> MirrorStatus  {
+    return try  FfiConverterTypeMirrorStatus_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_mirror_status(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func overwriteOnRemove()throws  -> Bool  {
+    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_overwrite_on_remove(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func pauseWrites(mode: PauseMode)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_pause_writes(
+            self.uniffiCloneHandle(),
+        FfiConverterTypePauseMode_lower(mode),$0
+    )
+}
+}
+    
+open func peek()throws  -> Data?  {
+    return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_peek(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func peekAsync()async throws  -> Data?  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_peek_async(
+                    self.uniffiCloneHandle()
+                    
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_rust_buffer,
+            completeFunc: ffi_queuefile_rust_future_complete_rust_buffer,
+            freeFunc: ffi_queuefile_rust_future_free_rust_buffer,
+            liftFunc: FfiConverterOptionData.lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+    /**
+     * The eldest chunked element reassembled, or `None` if the queue is empty. An element not
+     * added with `add_chunked` is returned as it is. Fails with `InvalidChunk` if the head of
+     * the queue holds only part of a chunked element.
+     */
+open func peekChunked()throws  -> Data?  {
+    return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_peek_chunked(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * SHA-256 of the eldest element's payload, computed in Rust so only the 32-byte digest
+     * crosses the FFI boundary. `None` if the queue is empty.
+     */
+open func peekDigest()throws  -> Data?  {
+    return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_peek_digest(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The eldest element decoded with `decode_envelope`, or `None` if the queue is empty.
+     */
+open func peekEnvelope()throws  -> ElementEnvelope?  {
+    return try  FfiConverterOptionTypeElementEnvelope.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_peek_envelope(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The eldest element's size, sequence id and envelope fields, or `None` if the queue is
+     * empty. The element is read and decoded in Rust and only its metadata is returned.
+     */
+open func peekMetadata()throws  -> ElementMeta?  {
+    return try  FfiConverterOptionTypeElementMeta.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_peek_metadata(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The eldest element decoded with `decode_msgpack_map`, or `None` if the queue is empty.
+     */
+open func peekMsgpackMap()throws  -> [String: MsgpackValue]?  {
+    return try  FfiConverterOptionDictionaryStringTypeMsgpackValue.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_peek_msgpack_map(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Returns a uniformly chosen element without removing it, reading only the elements up
+     * to it, or only that element with `element_index`. The same `seed` picks the same element
+     * from the same queue.
+     */
+open func peekRandom(seed: UInt64?)throws  -> Data?  {
+    return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_peek_random(
+            self.uniffiCloneHandle(),
+        FfiConverterOptionUInt64.lower(seed),$0
+    )
+})
+}
+    
+    /**
+     * Writes the eldest element to a new file at `dest_path` and syncs it, so the payload never
+     * crosses the FFI boundary. It is copied a chunk at a time, however long it is. Returns its
+     * length, or `None` (creating no file) if the queue is empty.
+     */
+open func peekToFile(destPath: String)throws  -> UInt64?  {
+    return try  FfiConverterOptionUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_peek_to_file(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(destPath),$0
+    )
+})
+}
+    
+open func peekToFileAsync(destPath: String)async throws  -> UInt64?  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_peek_to_file_async(
+                    self.uniffiCloneHandle(),
+                    FfiConverterString.lower(destPath)
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_rust_buffer,
+            completeFunc: ffi_queuefile_rust_future_complete_rust_buffer,
+            freeFunc: ffi_queuefile_rust_future_free_rust_buffer,
+            liftFunc: FfiConverterOptionUInt64.lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+open func peekWithTimeout(timeout: TimeInterval)throws  -> Data?  {
+    return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_peek_with_timeout(
+            self.uniffiCloneHandle(),
+        FfiConverterDuration.lower(timeout),$0
+    )
+})
+}
+    
+    /**
+     * Writes the eldest chunked element to a new file at `dest_path` one chunk at a time, syncs
+     * it and removes the element's chunks. Returns its length, or `None` (creating no file) if
+     * the queue is empty.
+     */
+open func popChunkedToFile(destPath: String)throws  -> UInt64?  {
+    return try  FfiConverterOptionUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_pop_chunked_to_file(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(destPath),$0
+    )
+})
+}
+    
+    /**
+     * Like `peek_to_file`, then removes the element once the file has been synced. The queue
+     * stays locked in between, so no other consumer can take the same element.
+     */
+open func popToFile(destPath: String)throws  -> UInt64?  {
+    return try  FfiConverterOptionUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_pop_to_file(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(destPath),$0
+    )
+})
+}
+    
+open func popToFileAsync(destPath: String)async throws  -> UInt64?  {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_pop_to_file_async(
+                    self.uniffiCloneHandle(),
+                    FfiConverterString.lower(destPath)
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_rust_buffer,
+            completeFunc: ffi_queuefile_rust_future_complete_rust_buffer,
+            freeFunc: ffi_queuefile_rust_future_free_rust_buffer,
+            liftFunc: FfiConverterOptionUInt64.lift,
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+    /**
+     * Returns false if no element, written or buffered, has exactly this payload. True may be
+     * a false positive when the `bloom_filter_elements` option is set (about 1% up to the
+     * configured element count); without it the queue is scanned and the answer is exact.
+     */
+open func probablyContains(data: Data)throws  -> Bool  {
+    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_probably_contains(
+            self.uniffiCloneHandle(),
+        FfiConverterData.lower(data),$0
+    )
+})
+}
+    
+    /**
+     * Keeps delivering elements to `handler` from the runtime's workers, eldest first, until
+     * the returned consumer is stopped. Unlike `drain_with`, the queue is not locked while an
+     * element is handled. An accepted element is removed; a rejected one is delivered again
+     * after the policy's backoff, and after `max_attempts` deliveries it is added to
+     * `dead_letter` and removed, or just removed without one.
+     */
+open func process(handler: DrainHandler, policy: ProcessPolicy, deadLetter: QueueFile?)throws  -> QueueConsumer  {
+    return try  FfiConverterTypeQueueConsumer_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_process(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeDrainHandler_lower(handler),
+        FfiConverterTypeProcessPolicy_lower(policy),
+        FfiConverterOptionTypeQueueFile.lower(deadLetter),$0
+    )
+})
+}
+    
+open func remainingCapacity()throws  -> CapacityInfo  {
+    return try  FfiConverterTypeCapacityInfo_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_remaining_capacity(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func remove()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_remove(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func removeAsync()async throws   {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_remove_async(
+                    self.uniffiCloneHandle()
+                    
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_void,
+            completeFunc: ffi_queuefile_rust_future_complete_void,
+            freeFunc: ffi_queuefile_rust_future_free_void,
+            liftFunc: { $0 },
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+    /**
+     * Removes the element at `index`, counting from the eldest, leaving the order of the
+     * others unchanged. The file is rewritten through a temporary copy, so this costs a pass
+     * over the whole queue; use it for the occasional poisoned element.
+     */
+open func removeAt(index: UInt32)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_remove_at(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(index),$0
+    )
+}
+}
+    
+    /**
+     * Removes the eldest chunked element, every chunk of it. The rest of one that a plain
+     * `remove` left behind, or one whose last chunks are missing, is removed up to the first
+     * element that does not continue it, so `InvalidChunk` never blocks the queue. Does
+     * nothing if the queue is empty.
+     */
+open func removeChunked()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_remove_chunked(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func removeN(n: UInt32)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_remove_n(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(n),$0
+    )
+}
+}
+    
+open func removeNAsync(n: UInt32)async throws   {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_remove_n_async(
+                    self.uniffiCloneHandle(),
+                    FfiConverterUInt32.lower(n)
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_void,
+            completeFunc: ffi_queuefile_rust_future_complete_void,
+            freeFunc: ffi_queuefile_rust_future_free_void,
+            liftFunc: { $0 },
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+open func removeNWithTimeout(n: UInt32, timeout: TimeInterval)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_remove_n_with_timeout(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(n),
+        FfiConverterDuration.lower(timeout),$0
+    )
+}
+}
+    
+open func removeWithTimeout(timeout: TimeInterval)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_remove_with_timeout(
+            self.uniffiCloneHandle(),
+        FfiConverterDuration.lower(timeout),$0
+    )
+}
+}
+    
+    /**
+     * Ends the current stats window and starts the next, returning the one that ended. Both
+     * happen under the queue's lock, so a periodic uploader that reports what this returns
+     * neither misses nor double-counts an operation.
+     */
+open func resetStats()throws  -> StatsWindow  {
+    return try  FfiConverterTypeStatsWindow_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_reset_stats(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func resumeWrites()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_resume_writes(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+    /**
+     * Copies every element into a new file that stores it as `options` asks, for example to
+     * compress a queue created before compression was turned on, then renames the copy into
+     * place. Reads return the same payloads as before, in the same order. Like `remove_at` it
+     * costs a pass over the whole queue, and a crash leaves either the old file or the new one.
+     */
+open func rewrite(options: RewriteOptions)throws  -> RewriteReport  {
+    return try  FfiConverterTypeRewriteReport_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_rewrite(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeRewriteOptions_lower(options),$0
+    )
+})
+}
+    
+    /**
+     * Returns up to `n` distinct elements chosen uniformly, in queue order, copying only the
+     * chosen ones in a single pass.
+     */
+open func sample(n: UInt32, seed: UInt64?)throws  -> [Data]  {
+    return try  FfiConverterSequenceData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_sample(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(n),
+        FfiConverterOptionUInt64.lower(seed),$0
+    )
+})
+}
+    
+open func setCacheOffsetPolicy(policy: OffsetCachePolicy)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_set_cache_offset_policy(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeOffsetCachePolicy_lower(policy),$0
+    )
+}
+}
+    
+open func setExcludedFromBackup(excluded: Bool)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_set_excluded_from_backup(
+            self.uniffiCloneHandle(),
+        FfiConverterBool.lower(excluded),$0
+    )
+}
+}
+    
+open func setMaxCommitDelay(value: TimeInterval?)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_set_max_commit_delay(
+            self.uniffiCloneHandle(),
+        FfiConverterOptionDuration.lower(value),$0
+    )
+}
+}
+    
+open func setOverwriteOnRemove(value: Bool)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_set_overwrite_on_remove(
+            self.uniffiCloneHandle(),
+        FfiConverterBool.lower(value),$0
+    )
+}
+}
+    
+open func setReadBufferSize(size: UInt32)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_set_read_buffer_size(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(size),$0
+    )
+}
+}
+    
+    /**
+     * Calls `listener` after every add, peek, removal, clear, sync or rewrite that took longer
+     * than `threshold`, timed from when it got hold of the queue.
+     */
+open func setSlowOpListener(threshold: TimeInterval, listener: SlowOpListener)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_set_slow_op_listener(
+            self.uniffiCloneHandle(),
+        FfiConverterDuration.lower(threshold),
+        FfiConverterTypeSlowOpListener_lower(listener),$0
+    )
+}
+}
+    
+open func setSyncWrites(value: Bool)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_set_sync_writes(
+            self.uniffiCloneHandle(),
+        FfiConverterBool.lower(value),$0
+    )
+}
+}
+    
+open func setWatermarks(config: WatermarkConfig, listener: WatermarkListener)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_set_watermarks(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeWatermarkConfig_lower(config),
+        FfiConverterTypeWatermarkListener_lower(listener),$0
+    )
+}
+}
+    
+open func setWriteBuffering(config: WriteBufferConfig?)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_set_write_buffering(
+            self.uniffiCloneHandle(),
+        FfiConverterOptionTypeWriteBufferConfig.lower(config),$0
+    )
+}
+}
+    
+    /**
+     * Leaves deferred removals pending and does not count them.
+     */
+open func size()throws  -> UInt32  {
+    return try  FfiConverterUInt32.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_size(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Counts elements by payload size in one pass over the queue. `bucket_bounds` must be
+     * ascending; bucket `i` holds sizes in `bucket_bounds[i - 1]..bucket_bounds[i]`, so the
+     * result has one more entry than there are bounds.
+     */
+open func sizeHistogram(bucketBounds: [UInt64])throws  -> [UInt64]  {
+    return try  FfiConverterSequenceUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_size_histogram(
+            self.uniffiCloneHandle(),
+        FfiConverterSequenceUInt64.lower(bucketBounds),$0
+    )
+})
+}
+    
+    /**
+     * A read view of the elements queued now, for iterating while adds and removals go on.
+     * Taking it holds the lock only to flush buffered adds and read the header.
+     */
+open func snapshot()throws  -> QueueSnapshot  {
+    return try  FfiConverterTypeQueueSnapshot_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_snapshot(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func spaceReport()throws  -> SpaceReport  {
+    return try  FfiConverterTypeSpaceReport_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_space_report(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func stats()throws  -> QueueStats  {
+    return try  FfiConverterTypeQueueStats_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_stats(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Starts a thread passing `listener` every element whose sequence id is after `seq`, or
+     * every element when `seq` is `None`. It first reads the ones already committed from the
+     * file, then receives each later commit's elements as they are written.
+     */
+open func subscribeFrom(seq: UInt64?, listener: ElementListener)throws  -> Subscription  {
+    return try  FfiConverterTypeSubscription_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_subscribe_from(
+            self.uniffiCloneHandle(),
+        FfiConverterOptionUInt64.lower(seq),
+        FfiConverterTypeElementListener_lower(listener),$0
+    )
+})
+}
+    
+    /**
+     * Exchanges the contents of this queue and `other` with one atomic rename, so a crash
+     * leaves both or neither swapped. Both files must be on the same volume. Each handle keeps
+     * its own path, options and stats.
+     */
+open func swapWith(other: QueueFile)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_swap_with(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeQueueFile_lower(other),$0
+    )
+}
+}
+    
+open func syncAll()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_sync_all(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func syncAllAsync()async throws   {
+    return
+        try  await uniffiRustCallAsync(
+            rustFutureFunc: {
+                uniffi_queuefile_fn_method_queuefile_sync_all_async(
+                    self.uniffiCloneHandle()
+                    
+                )
+            },
+            pollFunc: ffi_queuefile_rust_future_poll_void,
+            completeFunc: ffi_queuefile_rust_future_complete_void,
+            freeFunc: ffi_queuefile_rust_future_free_void,
+            liftFunc: { $0 },
+            errorHandler: FfiConverterTypeQueueFileError_lift
+        )
+}
+    
+open func syncAllWithTimeout(timeout: TimeInterval)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_sync_all_with_timeout(
+            self.uniffiCloneHandle(),
+        FfiConverterDuration.lower(timeout),$0
+    )
+}
+}
+    
+    /**
+     * Brings this queue up to date with `source`, which is only read, for example a backup
+     * that fell behind its primary. Elements are matched by the SHA-256 of their payloads, so
+     * where the eldest elements of `source` are the newest ones here, only the difference is
+     * removed and added. Both queues are locked for the duration.
+     */
+open func syncFrom(source: QueueFile, strategy: SyncStrategy)throws  -> SyncReport  {
+    return try  FfiConverterTypeSyncReport_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_sync_from(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeQueueFile_lower(source),
+        FfiConverterTypeSyncStrategy_lower(strategy),$0
+    )
+})
+}
+    
+open func syncWrites()throws  -> Bool  {
+    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_sync_writes(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Trains a zstd dictionary on up to `sample_limit` elements from the front of the queue
+     * and compresses every element added after it against the dictionary. Elements already
+     * queued keep their stored form, and reads return payloads as they were added either way.
+     * Retraining keeps the earlier dictionaries, so nothing compressed against them becomes
+     * unreadable. Fails with an I/O error if the elements are too few or too alike to train on.
+     */
+open func trainDictionary(sampleLimit: UInt32)throws  -> TrainedDictionary  {
+    return try  FfiConverterTypeTrainedDictionary_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_train_dictionary(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(sampleLimit),$0
+    )
+})
+}
+    
+open func unfreeze()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_unfreeze(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+    /**
+     * Replaces the payload of the element at `index`, keeping its position. Like `remove_at`
+     * it rewrites the file, since a frame cannot change length in place.
+     */
+open func updateAt(index: UInt32, data: Data)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_update_at(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(index),
+        FfiConverterData.lower(data),$0
+    )
+}
+}
+    
+open func usedBytes()throws  -> UInt64  {
+    return try  FfiConverterUInt64.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_used_bytes(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func watermarks()throws  -> WatermarkConfig?  {
+    return try  FfiConverterOptionTypeWatermarkConfig.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_watermarks(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The stats counted since the last `reset_stats`, or since the queue was opened. `stats`
+     * keeps reporting the lifetime totals.
+     */
+open func windowStats()throws  -> StatsWindow  {
+    return try  FfiConverterTypeStatsWindow_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_window_stats(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func writeBuffering()throws  -> WriteBufferConfig?  {
+    return try  FfiConverterOptionTypeWriteBufferConfig.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefile_write_buffering(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQueueFile: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = QueueFile
+
+    public static func lift(_ handle: UInt64) throws -> QueueFile {
+        return QueueFile(unsafeFromHandle: handle)
+    }
+
+    public static func lower(_ value: QueueFile) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueFile {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: QueueFile, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueFile_lift(_ handle: UInt64) throws -> QueueFile {
+    return try FfiConverterTypeQueueFile.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueFile_lower(_ value: QueueFile) -> UInt64 {
+    return FfiConverterTypeQueueFile.lower(value)
+}
+
+
+
+
+
+
+/**
+ * The elements a queue held when `iterator` was called, in order from the eldest. Under
+ * `FailOnChange` they are read under the queue's lock, a batch at a time, and any add,
+ * removal, clear, rewrite or swap after that makes the next call fail with
+ * `ConcurrentModification` instead of returning elements from a queue that no longer matches.
+ * Under `Pinned` they come from a `QueueSnapshot`.
+ */
+public protocol QueueFileIteratorProtocol: AnyObject, Sendable {
+    
+    /**
+     * The `generation()` of the queue when the iterator was created.
+     */
+    func generation()  -> UInt64
+    
+    /**
+     * The next element, or `None` after the last one. Under `FailOnChange`, fails with
+     * `ConcurrentModification` once the queue has changed, even if the element was already
+     * read.
+     */
+    func next() throws  -> Data?
+    
+    /**
+     * Like `next`, returning the element's `ElementMeta` instead of its bytes. The element is
+     * read and decoded in Rust, so only its metadata and digest cross the FFI boundary.
+     */
+    func nextMetadata() throws  -> ElementMeta?
+    
+    /**
+     * Elements the queue held when the iterator was created.
+     */
+    func size()  -> UInt32
+    
+}
+/**
+ * The elements a queue held when `iterator` was called, in order from the eldest. Under
+ * `FailOnChange` they are read under the queue's lock, a batch at a time, and any add,
+ * removal, clear, rewrite or swap after that makes the next call fail with
+ * `ConcurrentModification` instead of returning elements from a queue that no longer matches.
+ * Under `Pinned` they come from a `QueueSnapshot`.
+ */
+open class QueueFileIterator: QueueFileIteratorProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_queuefileiterator(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_queuefileiterator(handle, $0) }
+    }
+
+    
+
+    
+    /**
+     * The `generation()` of the queue when the iterator was created.
+     */
+open func generation() -> UInt64  {
+    return try!  FfiConverterUInt64.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_queuefileiterator_generation(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The next element, or `None` after the last one. Under `FailOnChange`, fails with
+     * `ConcurrentModification` once the queue has changed, even if the element was already
+     * read.
+     */
+open func next()throws  -> Data?  {
+    return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefileiterator_next(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Like `next`, returning the element's `ElementMeta` instead of its bytes. The element is
+     * read and decoded in Rust, so only its metadata and digest cross the FFI boundary.
+     */
+open func nextMetadata()throws  -> ElementMeta?  {
+    return try  FfiConverterOptionTypeElementMeta.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuefileiterator_next_metadata(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Elements the queue held when the iterator was created.
+     */
+open func size() -> UInt32  {
+    return try!  FfiConverterUInt32.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_queuefileiterator_size(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQueueFileIterator: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = QueueFileIterator
+
+    public static func lift(_ handle: UInt64) throws -> QueueFileIterator {
+        return QueueFileIterator(unsafeFromHandle: handle)
+    }
+
+    public static func lower(_ value: QueueFileIterator) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueFileIterator {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: QueueFileIterator, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueFileIterator_lift(_ handle: UInt64) throws -> QueueFileIterator {
+    return try FfiConverterTypeQueueFileIterator.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueFileIterator_lower(_ value: QueueFileIterator) -> UInt64 {
+    return FfiConverterTypeQueueFileIterator.lower(value)
+}
+
+
+
+
+
+
+/**
+ * Opens queues under a cap on how many keep their files open at once. Once a call would take
+ * it past the cap, the least recently used queues are flushed, synced and closed as
+ * `idle_close_after` closes them, and reopen on their next call. A queue busy at that moment,
+ * or holding adds back for a pause, stays open and counted, and the next one is tried.
+ */
+public protocol QueueManagerProtocol: AnyObject, Sendable {
+    
+    /**
+     * Opens the queue at `path` as `QueueFile::open_with_options` does and counts it against
+     * the cap, possibly closing others. A handle shared with `AlreadyOpenPolicy::Share` stays
+     * with the manager that opened it first.
+     */
+    func `open`(path: String, options: QueueFileOptions) throws  -> QueueFile
+    
+    /**
+     * Those of them with their files open.
+     */
+    func openFileCount()  -> UInt32
+    
+    /**
+     * Queues opened through this manager that are still referenced.
+     */
+    func queueCount()  -> UInt32
+    
+}
+/**
+ * Opens queues under a cap on how many keep their files open at once. Once a call would take
+ * it past the cap, the least recently used queues are flushed, synced and closed as
+ * `idle_close_after` closes them, and reopen on their next call. A queue busy at that moment,
+ * or holding adds back for a pause, stays open and counted, and the next one is tried.
+ */
+open class QueueManager: QueueManagerProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_queuemanager(self.handle, $0) }
+    }
+    /**
+     * A manager keeping at most `max_open_files` of its queues open, at least one.
+     */
+public convenience init(maxOpenFiles: UInt32) {
+    let handle =
+        try! rustCall() {
+    uniffi_queuefile_fn_constructor_queuemanager_new(
+        FfiConverterUInt32.lower(maxOpenFiles),$0
+    )
+}
+    self.init(unsafeFromHandle: handle)
+}
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_queuemanager(handle, $0) }
+    }
+
+    
+
+    
+    /**
+     * Opens the queue at `path` as `QueueFile::open_with_options` does and counts it against
+     * the cap, possibly closing others. A handle shared with `AlreadyOpenPolicy::Share` stays
+     * with the manager that opened it first.
+     */
+open func `open`(path: String, options: QueueFileOptions)throws  -> QueueFile  {
+    return try  FfiConverterTypeQueueFile_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuemanager_open(
+            self.uniffiCloneHandle(),
+        FfiConverterString.lower(path),
+        FfiConverterTypeQueueFileOptions_lower(options),$0
+    )
+})
+}
+    
+    /**
+     * Those of them with their files open.
+     */
+open func openFileCount() -> UInt32  {
+    return try!  FfiConverterUInt32.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_queuemanager_open_file_count(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Queues opened through this manager that are still referenced.
+     */
+open func queueCount() -> UInt32  {
+    return try!  FfiConverterUInt32.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_queuemanager_queue_count(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQueueManager: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = QueueManager
+
+    public static func lift(_ handle: UInt64) throws -> QueueManager {
+        return QueueManager(unsafeFromHandle: handle)
+    }
+
+    public static func lower(_ value: QueueManager) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueManager {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: QueueManager, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueManager_lift(_ handle: UInt64) throws -> QueueManager {
+    return try FfiConverterTypeQueueManager.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueManager_lower(_ value: QueueManager) -> UInt64 {
+    return FfiConverterTypeQueueManager.lower(value)
+}
+
+
+
+
+
+
+/**
+ * The elements a queue held when `snapshot` was called, read in order from the eldest while
+ * the queue keeps changing. Elements stay in the file until the queue is about to overwrite or
+ * move them; only then are the ones not yet read copied into the snapshot.
+ */
+public protocol QueueSnapshotProtocol: AnyObject, Sendable {
+    
+    /**
+     * The queue's `generation()` when the snapshot was taken.
+     */
+    func generation()  -> UInt64
+    
+    func isEmpty()  -> Bool
+    
+    /**
+     * The next element in order, or `None` after the last one.
+     */
+    func next() throws  -> Data?
+    
+    /**
+     * Every element `next` has not returned yet.
+     */
+    func readRemaining() throws  -> [Data]
+    
+    /**
+     * Elements in the queue when the snapshot was taken.
+     */
+    func size()  -> UInt32
+    
+}
+/**
+ * The elements a queue held when `snapshot` was called, read in order from the eldest while
+ * the queue keeps changing. Elements stay in the file until the queue is about to overwrite or
+ * move them; only then are the ones not yet read copied into the snapshot.
+ */
+open class QueueSnapshot: QueueSnapshotProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_queuesnapshot(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_queuesnapshot(handle, $0) }
+    }
+
+    
+
+    
+    /**
+     * The queue's `generation()` when the snapshot was taken.
+     */
+open func generation() -> UInt64  {
+    return try!  FfiConverterUInt64.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_queuesnapshot_generation(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func isEmpty() -> Bool  {
+    return try!  FfiConverterBool.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_queuesnapshot_is_empty(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The next element in order, or `None` after the last one.
+     */
+open func next()throws  -> Data?  {
+    return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuesnapshot_next(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Every element `next` has not returned yet.
+     */
+open func readRemaining()throws  -> [Data]  {
+    return try  FfiConverterSequenceData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_queuesnapshot_read_remaining(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Elements in the queue when the snapshot was taken.
+     */
+open func size() -> UInt32  {
+    return try!  FfiConverterUInt32.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_queuesnapshot_size(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQueueSnapshot: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = QueueSnapshot
+
+    public static func lift(_ handle: UInt64) throws -> QueueSnapshot {
+        return QueueSnapshot(unsafeFromHandle: handle)
+    }
+
+    public static func lower(_ value: QueueSnapshot) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueSnapshot {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: QueueSnapshot, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueSnapshot_lift(_ handle: UInt64) throws -> QueueSnapshot {
+    return try FfiConverterTypeQueueSnapshot.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueSnapshot_lower(_ value: QueueSnapshot) -> UInt64 {
+    return FfiConverterTypeQueueSnapshot.lower(value)
+}
+
+
+
+
+
+
+public protocol RetentionListener: AnyObject, Sendable {
+    
+    /**
+     * Called with the segments one add or `enforce_retention` dropped, oldest first.
+     */
+    func onSegmentsDropped(dropped: [DroppedSegment]) 
+    
+}
+open class RetentionListenerImpl: RetentionListener, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_retentionlistener(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_retentionlistener(handle, $0) }
+    }
+
+    
+
+    
+    /**
+     * Called with the segments one add or `enforce_retention` dropped, oldest first.
+     */
+open func onSegmentsDropped(dropped: [DroppedSegment])  {try! rustCall() {
+    uniffi_queuefile_fn_method_retentionlistener_on_segments_dropped(
+            self.uniffiCloneHandle(),
+        FfiConverterSequenceTypeDroppedSegment.lower(dropped),$0
+    )
+}
+}
+    
+
+    
+}
+
+
+
+// Put the implementation in a struct so we don't pollute the top-level namespace
+fileprivate struct UniffiCallbackInterfaceRetentionListener {
+
+    // Create the VTable using a series of closures.
+    // Swift automatically converts these into C callback functions.
+    //
+    // This creates 1-element array, since this seems to be the only way to construct a const
+    // pointer that we can pass to the Rust code.
+    static let vtable: [UniffiVTableCallbackInterfaceRetentionListener] = [UniffiVTableCallbackInterfaceRetentionListener(
+        uniffiFree: { (uniffiHandle: UInt64) -> () in
+            do {
+                try FfiConverterTypeRetentionListener.handleMap.remove(handle: uniffiHandle)
+            } catch {
+                print("Uniffi callback interface RetentionListener: handle missing in uniffiFree")
+            }
+        },
+        uniffiClone: { (uniffiHandle: UInt64) -> UInt64 in
+            do {
+                return try FfiConverterTypeRetentionListener.handleMap.clone(handle: uniffiHandle)
+            } catch {
+                fatalError("Uniffi callback interface RetentionListener: handle missing in uniffiClone")
+            }
+        },
+        onSegmentsDropped: { (
+            uniffiHandle: UInt64,
+            dropped: RustBuffer,
+            uniffiOutReturn: UnsafeMutableRawPointer,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            let makeCall = {
+                () throws -> () in
+                guard let uniffiObj = try? FfiConverterTypeRetentionListener.handleMap.get(handle: uniffiHandle) else {
+                    throw UniffiInternalError.unexpectedStaleHandle
+                }
+                return uniffiObj.onSegmentsDropped(
+                     dropped: try FfiConverterSequenceTypeDroppedSegment.lift(dropped)
+                )
+            }
+
+            
+            let writeReturn = { () }
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: makeCall,
+                writeReturn: writeReturn
+            )
+        }
+    )]
+}
+
+private func uniffiCallbackInitRetentionListener() {
+    uniffi_queuefile_fn_init_callback_vtable_retentionlistener(UniffiCallbackInterfaceRetentionListener.vtable)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeRetentionListener: FfiConverter {
+    fileprivate static let handleMap = UniffiHandleMap<RetentionListener>()
+
+    typealias FfiType = UInt64
+    typealias SwiftType = RetentionListener
+
+    public static func lift(_ handle: UInt64) throws -> RetentionListener {
+        if ((handle & 1) == 0) {
+            // Rust-generated handle, construct a new class that uses the handle to implement the
+            // interface
+            return RetentionListenerImpl(unsafeFromHandle: handle)
+        } else {
+            // Swift-generated handle, get the object from the handle map
+            return try handleMap.remove(handle: handle)
+        }
+    }
+
+    public static func lower(_ value: RetentionListener) -> UInt64 {
+         if let rustImpl = value as? RetentionListenerImpl {
+             // Rust-implemented object.  Clone the handle and return it
+            return rustImpl.uniffiCloneHandle()
+         } else {
+            // Swift object, generate a new vtable handle and return that.
+            return handleMap.insert(obj: value)
+         }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> RetentionListener {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: RetentionListener, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRetentionListener_lift(_ handle: UInt64) throws -> RetentionListener {
+    return try FfiConverterTypeRetentionListener.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRetentionListener_lower(_ value: RetentionListener) -> UInt64 {
+    return FfiConverterTypeRetentionListener.lower(value)
+}
+
+
+
+
+
+
+/**
+ * Reads the elements of one segment of a `SegmentedQueue`. Each call sees the segment as it is
+ * at that moment: removals from the head segment shift the positions of what is left, and a
+ * segment that has been consumed or dropped fails with a `NotFound` I/O error.
+ */
+public protocol SegmentReaderProtocol: AnyObject, Sendable {
+    
+    func info() throws  -> SegmentInfo
+    
+    /**
+     * Up to `limit` elements starting at position `offset` within the segment.
+     */
+    func read(offset: UInt32, limit: UInt32) throws  -> [Data]
+    
+    func readAll() throws  -> [Data]
+    
+}
+/**
+ * Reads the elements of one segment of a `SegmentedQueue`. Each call sees the segment as it is
+ * at that moment: removals from the head segment shift the positions of what is left, and a
+ * segment that has been consumed or dropped fails with a `NotFound` I/O error.
+ */
+open class SegmentReader: SegmentReaderProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_segmentreader(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_segmentreader(handle, $0) }
+    }
+
+    
+
+    
+open func info()throws  -> SegmentInfo  {
+    return try  FfiConverterTypeSegmentInfo_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentreader_info(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Up to `limit` elements starting at position `offset` within the segment.
+     */
+open func read(offset: UInt32, limit: UInt32)throws  -> [Data]  {
+    return try  FfiConverterSequenceData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentreader_read(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(offset),
+        FfiConverterUInt32.lower(limit),$0
+    )
+})
+}
+    
+open func readAll()throws  -> [Data]  {
+    return try  FfiConverterSequenceData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentreader_read_all(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSegmentReader: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = SegmentReader
+
+    public static func lift(_ handle: UInt64) throws -> SegmentReader {
+        return SegmentReader(unsafeFromHandle: handle)
+    }
+
+    public static func lower(_ value: SegmentReader) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SegmentReader {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: SegmentReader, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSegmentReader_lift(_ handle: UInt64) throws -> SegmentReader {
+    return try FfiConverterTypeSegmentReader.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSegmentReader_lower(_ value: SegmentReader) -> UInt64 {
+    return FfiConverterTypeSegmentReader.lower(value)
+}
+
+
+
+
+
+
+/**
+ * A queue spread over a directory of fixed-size segment files, like a commit log. Adds append
+ * to the newest segment and start a new one once it is full; a segment is deleted as soon as
+ * its last element is removed, so space is given back without compacting anything.
+ *
+ * Every segment is an ordinary queue file, readable with `qf-inspect`.
+ */
+public protocol SegmentedQueueProtocol: AnyObject, Sendable {
+    
+    func add(data: Data) throws 
+    
+    /**
+     * Adds all items to the same segment, so they become visible together.
+     */
+    func addMultiple(items: [Data]) throws 
+    
+    /**
+     * Deletes every segment but one, which is emptied.
+     */
+    func clear() throws 
+    
+    func clearRetentionListener() throws 
+    
+    /**
+     * Applies the retention limits now. They are also applied after every add, but a queue
+     * that receives no adds only ages out segments when this is called.
+     */
+    func enforceRetention() throws  -> [DroppedSegment]
+    
+    func getAll() throws  -> [Data]
+    
+    func isEmpty() throws  -> Bool
+    
+    /**
+     * A reader over one segment, for inspecting or exporting it without removing anything.
+     */
+    func openSegmentReader(id: UInt64) throws  -> SegmentReader
+    
+    func peek() throws  -> Data?
+    
+    func remove() throws 
+    
+    func removeN(n: UInt32) throws 
+    
+    func segmentCount() throws  -> UInt32
+    
+    /**
+     * The segments, oldest first.
+     */
+    func segments() throws  -> [SegmentInfo]
+    
+    /**
+     * Called with the segments each retention pass drops; replaces any previous listener.
+     */
+    func setRetentionListener(listener: RetentionListener) throws 
+    
+    func size() throws  -> UInt32
+    
+    func syncAll() throws 
+    
+}
+/**
+ * A queue spread over a directory of fixed-size segment files, like a commit log. Adds append
+ * to the newest segment and start a new one once it is full; a segment is deleted as soon as
+ * its last element is removed, so space is given back without compacting anything.
+ *
+ * Every segment is an ordinary queue file, readable with `qf-inspect`.
+ */
+open class SegmentedQueue: SegmentedQueueProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_segmentedqueue(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_segmentedqueue(handle, $0) }
+    }
+
+    
+    /**
+     * Opens the segments in `dir`, creating the directory and a first segment if needed.
+     */
+public static func `open`(dir: String)throws  -> SegmentedQueue  {
+    return try  FfiConverterTypeSegmentedQueue_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_constructor_segmentedqueue_open(
+        FfiConverterString.lower(dir),$0
+    )
+})
+}
+    
+public static func openWithConfig(dir: String, config: SegmentedQueueConfig)throws  -> SegmentedQueue  {
+    return try  FfiConverterTypeSegmentedQueue_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_constructor_segmentedqueue_open_with_config(
+        FfiConverterString.lower(dir),
+        FfiConverterTypeSegmentedQueueConfig_lower(config),$0
+    )
+})
+}
+    
+
+    
+open func add(data: Data)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_add(
+            self.uniffiCloneHandle(),
+        FfiConverterData.lower(data),$0
+    )
+}
+}
+    
+    /**
+     * Adds all items to the same segment, so they become visible together.
+     */
+open func addMultiple(items: [Data])throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_add_multiple(
+            self.uniffiCloneHandle(),
+        FfiConverterSequenceData.lower(items),$0
+    )
+}
+}
+    
+    /**
+     * Deletes every segment but one, which is emptied.
+     */
+open func clear()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_clear(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func clearRetentionListener()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_clear_retention_listener(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+    /**
+     * Applies the retention limits now. They are also applied after every add, but a queue
+     * that receives no adds only ages out segments when this is called.
+     */
+open func enforceRetention()throws  -> [DroppedSegment]  {
+    return try  FfiConverterSequenceTypeDroppedSegment.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_enforce_retention(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func getAll()throws  -> [Data]  {
+    return try  FfiConverterSequenceData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_get_all(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func isEmpty()throws  -> Bool  {
+    return try  FfiConverterBool.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_is_empty(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * A reader over one segment, for inspecting or exporting it without removing anything.
+     */
+open func openSegmentReader(id: UInt64)throws  -> SegmentReader  {
+    return try  FfiConverterTypeSegmentReader_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_open_segment_reader(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt64.lower(id),$0
+    )
+})
+}
+    
+open func peek()throws  -> Data?  {
+    return try  FfiConverterOptionData.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_peek(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func remove()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_remove(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func removeN(n: UInt32)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_remove_n(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt32.lower(n),$0
+    )
+}
+}
+    
+open func segmentCount()throws  -> UInt32  {
+    return try  FfiConverterUInt32.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_segment_count(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The segments, oldest first.
+     */
+open func segments()throws  -> [SegmentInfo]  {
+    return try  FfiConverterSequenceTypeSegmentInfo.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_segments(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * Called with the segments each retention pass drops; replaces any previous listener.
+     */
+open func setRetentionListener(listener: RetentionListener)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_set_retention_listener(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeRetentionListener_lower(listener),$0
+    )
+}
+}
+    
+open func size()throws  -> UInt32  {
+    return try  FfiConverterUInt32.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_size(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+open func syncAll()throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_method_segmentedqueue_sync_all(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSegmentedQueue: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = SegmentedQueue
+
+    public static func lift(_ handle: UInt64) throws -> SegmentedQueue {
+        return SegmentedQueue(unsafeFromHandle: handle)
+    }
+
+    public static func lower(_ value: SegmentedQueue) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SegmentedQueue {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: SegmentedQueue, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSegmentedQueue_lift(_ handle: UInt64) throws -> SegmentedQueue {
+    return try FfiConverterTypeSegmentedQueue.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSegmentedQueue_lower(_ value: SegmentedQueue) -> UInt64 {
+    return FfiConverterTypeSegmentedQueue.lower(value)
+}
+
+
+
+
+
+
+public protocol SlowOpListener: AnyObject, Sendable {
+    
+    func onSlowOperation(operation: QueueOperation, duration: TimeInterval, bytes: UInt64) 
+    
+}
+open class SlowOpListenerImpl: SlowOpListener, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_slowoplistener(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_slowoplistener(handle, $0) }
+    }
+
+    
+
+    
+open func onSlowOperation(operation: QueueOperation, duration: TimeInterval, bytes: UInt64)  {try! rustCall() {
+    uniffi_queuefile_fn_method_slowoplistener_on_slow_operation(
+            self.uniffiCloneHandle(),
+        FfiConverterTypeQueueOperation_lower(operation),
+        FfiConverterDuration.lower(duration),
+        FfiConverterUInt64.lower(bytes),$0
+    )
+}
+}
+    
+
+    
+}
+
+
+
+// Put the implementation in a struct so we don't pollute the top-level namespace
+fileprivate struct UniffiCallbackInterfaceSlowOpListener {
+
+    // Create the VTable using a series of closures.
+    // Swift automatically converts these into C callback functions.
+    //
+    // This creates 1-element array, since this seems to be the only way to construct a const
+    // pointer that we can pass to the Rust code.
+    static let vtable: [UniffiVTableCallbackInterfaceSlowOpListener] = [UniffiVTableCallbackInterfaceSlowOpListener(
+        uniffiFree: { (uniffiHandle: UInt64) -> () in
+            do {
+                try FfiConverterTypeSlowOpListener.handleMap.remove(handle: uniffiHandle)
+            } catch {
+                print("Uniffi callback interface SlowOpListener: handle missing in uniffiFree")
+            }
+        },
+        uniffiClone: { (uniffiHandle: UInt64) -> UInt64 in
+            do {
+                return try FfiConverterTypeSlowOpListener.handleMap.clone(handle: uniffiHandle)
+            } catch {
+                fatalError("Uniffi callback interface SlowOpListener: handle missing in uniffiClone")
+            }
+        },
+        onSlowOperation: { (
+            uniffiHandle: UInt64,
+            operation: RustBuffer,
+            duration: RustBuffer,
+            bytes: UInt64,
+            uniffiOutReturn: UnsafeMutableRawPointer,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            let makeCall = {
+                () throws -> () in
+                guard let uniffiObj = try? FfiConverterTypeSlowOpListener.handleMap.get(handle: uniffiHandle) else {
+                    throw UniffiInternalError.unexpectedStaleHandle
+                }
+                return uniffiObj.onSlowOperation(
+                     operation: try FfiConverterTypeQueueOperation_lift(operation),
+                     duration: try FfiConverterDuration.lift(duration),
+                     bytes: try FfiConverterUInt64.lift(bytes)
+                )
+            }
+
+            
+            let writeReturn = { () }
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: makeCall,
+                writeReturn: writeReturn
+            )
+        }
+    )]
+}
+
+private func uniffiCallbackInitSlowOpListener() {
+    uniffi_queuefile_fn_init_callback_vtable_slowoplistener(UniffiCallbackInterfaceSlowOpListener.vtable)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSlowOpListener: FfiConverter {
+    fileprivate static let handleMap = UniffiHandleMap<SlowOpListener>()
+
+    typealias FfiType = UInt64
+    typealias SwiftType = SlowOpListener
+
+    public static func lift(_ handle: UInt64) throws -> SlowOpListener {
+        if ((handle & 1) == 0) {
+            // Rust-generated handle, construct a new class that uses the handle to implement the
+            // interface
+            return SlowOpListenerImpl(unsafeFromHandle: handle)
+        } else {
+            // Swift-generated handle, get the object from the handle map
+            return try handleMap.remove(handle: handle)
+        }
+    }
+
+    public static func lower(_ value: SlowOpListener) -> UInt64 {
+         if let rustImpl = value as? SlowOpListenerImpl {
+             // Rust-implemented object.  Clone the handle and return it
+            return rustImpl.uniffiCloneHandle()
+         } else {
+            // Swift object, generate a new vtable handle and return that.
+            return handleMap.insert(obj: value)
+         }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SlowOpListener {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: SlowOpListener, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSlowOpListener_lift(_ handle: UInt64) throws -> SlowOpListener {
+    return try FfiConverterTypeSlowOpListener.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSlowOpListener_lower(_ value: SlowOpListener) -> UInt64 {
+    return FfiConverterTypeSlowOpListener.lower(value)
+}
+
+
+
+
+
+
+/**
+ * A thread passing every element added to a queue to a listener until `cancel` is called.
+ */
+public protocol SubscriptionProtocol: AnyObject, Sendable {
+    
+    /**
+     * Stops delivering and waits for the element being delivered, if any.
+     */
+    func cancel() 
+    
+    func isActive()  -> Bool
+    
+    /**
+     * The sequence id of the last element delivered, or the one subscribed from.
+     */
+    func lastSeq()  -> UInt64?
+    
+}
+/**
+ * A thread passing every element added to a queue to a listener until `cancel` is called.
+ */
+open class Subscription: SubscriptionProtocol, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_subscription(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_subscription(handle, $0) }
+    }
+
+    
+
+    
+    /**
+     * Stops delivering and waits for the element being delivered, if any.
+     */
+open func cancel()  {try! rustCall() {
+    uniffi_queuefile_fn_method_subscription_cancel(
+            self.uniffiCloneHandle(),$0
+    )
+}
+}
+    
+open func isActive() -> Bool  {
+    return try!  FfiConverterBool.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_subscription_is_active(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+    /**
+     * The sequence id of the last element delivered, or the one subscribed from.
+     */
+open func lastSeq() -> UInt64?  {
+    return try!  FfiConverterOptionUInt64.lift(try! rustCall() {
+    uniffi_queuefile_fn_method_subscription_last_seq(
+            self.uniffiCloneHandle(),$0
+    )
+})
+}
+    
+
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSubscription: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = Subscription
+
+    public static func lift(_ handle: UInt64) throws -> Subscription {
+        return Subscription(unsafeFromHandle: handle)
+    }
+
+    public static func lower(_ value: Subscription) -> UInt64 {
+        return value.uniffiCloneHandle()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Subscription {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: Subscription, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSubscription_lift(_ handle: UInt64) throws -> Subscription {
+    return try FfiConverterTypeSubscription.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSubscription_lower(_ value: Subscription) -> UInt64 {
+    return FfiConverterTypeSubscription.lower(value)
+}
+
+
+
+
+
+
+public protocol WatermarkListener: AnyObject, Sendable {
+    
+    func onHighWatermark(value: UInt64) 
+    
+    func onLowWatermark(value: UInt64) 
+    
+}
+open class WatermarkListenerImpl: WatermarkListener, @unchecked Sendable {
+    fileprivate let handle: UInt64
+
+    /// Used to instantiate a [FFIObject] without an actual handle, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoHandle {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    required public init(unsafeFromHandle handle: UInt64) {
+        self.handle = handle
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noHandle: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing handle the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noHandle: NoHandle) {
+        self.handle = 0
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiCloneHandle() -> UInt64 {
+        return try! rustCall { uniffi_queuefile_fn_clone_watermarklistener(self.handle, $0) }
+    }
+    // No primary constructor declared for this class.
+
+    deinit {
+        try! rustCall { uniffi_queuefile_fn_free_watermarklistener(handle, $0) }
+    }
+
+    
+
+    
+open func onHighWatermark(value: UInt64)  {try! rustCall() {
+    uniffi_queuefile_fn_method_watermarklistener_on_high_watermark(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt64.lower(value),$0
+    )
+}
+}
+    
+open func onLowWatermark(value: UInt64)  {try! rustCall() {
+    uniffi_queuefile_fn_method_watermarklistener_on_low_watermark(
+            self.uniffiCloneHandle(),
+        FfiConverterUInt64.lower(value),$0
+    )
+}
+}
+    
+
+    
+}
+
+
+
+// Put the implementation in a struct so we don't pollute the top-level namespace
+fileprivate struct UniffiCallbackInterfaceWatermarkListener {
+
+    // Create the VTable using a series of closures.
+    // Swift automatically converts these into C callback functions.
+    //
+    // This creates 1-element array, since this seems to be the only way to construct a const
+    // pointer that we can pass to the Rust code.
+    static let vtable: [UniffiVTableCallbackInterfaceWatermarkListener] = [UniffiVTableCallbackInterfaceWatermarkListener(
+        uniffiFree: { (uniffiHandle: UInt64) -> () in
+            do {
+                try FfiConverterTypeWatermarkListener.handleMap.remove(handle: uniffiHandle)
+            } catch {
+                print("Uniffi callback interface WatermarkListener: handle missing in uniffiFree")
+            }
+        },
+        uniffiClone: { (uniffiHandle: UInt64) -> UInt64 in
+            do {
+                return try FfiConverterTypeWatermarkListener.handleMap.clone(handle: uniffiHandle)
+            } catch {
+                fatalError("Uniffi callback interface WatermarkListener: handle missing in uniffiClone")
+            }
+        },
+        onHighWatermark: { (
+            uniffiHandle: UInt64,
+            value: UInt64,
+            uniffiOutReturn: UnsafeMutableRawPointer,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            let makeCall = {
+                () throws -> () in
+                guard let uniffiObj = try? FfiConverterTypeWatermarkListener.handleMap.get(handle: uniffiHandle) else {
+                    throw UniffiInternalError.unexpectedStaleHandle
+                }
+                return uniffiObj.onHighWatermark(
+                     value: try FfiConverterUInt64.lift(value)
+                )
+            }
+
+            
+            let writeReturn = { () }
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: makeCall,
+                writeReturn: writeReturn
+            )
+        },
+        onLowWatermark: { (
+            uniffiHandle: UInt64,
+            value: UInt64,
+            uniffiOutReturn: UnsafeMutableRawPointer,
+            uniffiCallStatus: UnsafeMutablePointer<RustCallStatus>
+        ) in
+            let makeCall = {
+                () throws -> () in
+                guard let uniffiObj = try? FfiConverterTypeWatermarkListener.handleMap.get(handle: uniffiHandle) else {
+                    throw UniffiInternalError.unexpectedStaleHandle
+                }
+                return uniffiObj.onLowWatermark(
+                     value: try FfiConverterUInt64.lift(value)
+                )
+            }
+
+            
+            let writeReturn = { () }
+            uniffiTraitInterfaceCall(
+                callStatus: uniffiCallStatus,
+                makeCall: makeCall,
+                writeReturn: writeReturn
+            )
+        }
+    )]
+}
+
+private func uniffiCallbackInitWatermarkListener() {
+    uniffi_queuefile_fn_init_callback_vtable_watermarklistener(UniffiCallbackInterfaceWatermarkListener.vtable)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeWatermarkListener: FfiConverter {
+    fileprivate static let handleMap = UniffiHandleMap<WatermarkListener>()
+
+    typealias FfiType = UInt64
+    typealias SwiftType = WatermarkListener
+
+    public static func lift(_ handle: UInt64) throws -> WatermarkListener {
+        if ((handle & 1) == 0) {
+            // Rust-generated handle, construct a new class that uses the handle to implement the
+            // interface
+            return WatermarkListenerImpl(unsafeFromHandle: handle)
+        } else {
+            // Swift-generated handle, get the object from the handle map
+            return try handleMap.remove(handle: handle)
+        }
+    }
+
+    public static func lower(_ value: WatermarkListener) -> UInt64 {
+         if let rustImpl = value as? WatermarkListenerImpl {
+             // Rust-implemented object.  Clone the handle and return it
+            return rustImpl.uniffiCloneHandle()
+         } else {
+            // Swift object, generate a new vtable handle and return that.
+            return handleMap.insert(obj: value)
+         }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> WatermarkListener {
+        let handle: UInt64 = try readInt(&buf)
+        return try lift(handle)
+    }
+
+    public static func write(_ value: WatermarkListener, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeWatermarkListener_lift(_ handle: UInt64) throws -> WatermarkListener {
+    return try FfiConverterTypeWatermarkListener.lift(handle)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeWatermarkListener_lower(_ value: WatermarkListener) -> UInt64 {
+    return FfiConverterTypeWatermarkListener.lower(value)
+}
+
+
+
+
+/**
+ * What an add left behind, read under the same lock as the add.
+ */
+public struct AddReceipt: Equatable, Hashable {
+    /**
+     * Sequence id of the first element added; the rest of a batch follow on from it. Ids
+     * survive a reopen only with `element_index`, and otherwise restart from the queue's size.
+     */
+    public var seq: UInt64
+    /**
+     * Elements in the queue afterwards, as `size` counts them.
+     */
+    public var size: UInt32
+    /**
+     * Bytes the queue uses once buffered adds are written.
+     */
+    public var usedBytes: UInt64
+    /**
+     * Whether the add grew the file.
+     */
+    public var grew: Bool
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Sequence id of the first element added; the rest of a batch follow on from it. Ids
+         * survive a reopen only with `element_index`, and otherwise restart from the queue's size.
+         */seq: UInt64, 
+        /**
+         * Elements in the queue afterwards, as `size` counts them.
+         */size: UInt32, 
+        /**
+         * Bytes the queue uses once buffered adds are written.
+         */usedBytes: UInt64, 
+        /**
+         * Whether the add grew the file.
+         */grew: Bool) {
+        self.seq = seq
+        self.size = size
+        self.usedBytes = usedBytes
+        self.grew = grew
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension AddReceipt: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeAddReceipt: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> AddReceipt {
+        return
+            try AddReceipt(
+                seq: FfiConverterUInt64.read(from: &buf), 
+                size: FfiConverterUInt32.read(from: &buf), 
+                usedBytes: FfiConverterUInt64.read(from: &buf), 
+                grew: FfiConverterBool.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: AddReceipt, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.seq, into: &buf)
+        FfiConverterUInt32.write(value.size, into: &buf)
+        FfiConverterUInt64.write(value.usedBytes, into: &buf)
+        FfiConverterBool.write(value.grew, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeAddReceipt_lift(_ buf: RustBuffer) throws -> AddReceipt {
+    return try FfiConverterTypeAddReceipt.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeAddReceipt_lower(_ value: AddReceipt) -> RustBuffer {
+    return FfiConverterTypeAddReceipt.lower(value)
+}
+
+
+public struct CapacityInfo: Equatable, Hashable {
+    /**
+     * Elements that can still be added before `max_elements` is reached; `None` when uncapped.
+     */
+    public var elementsRemaining: UInt64?
+    /**
+     * Payload bytes that can still be added (in a single element) before `max_bytes` is
+     * reached; `None` when uncapped.
+     */
+    public var bytesRemaining: UInt64?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Elements that can still be added before `max_elements` is reached; `None` when uncapped.
+         */elementsRemaining: UInt64?, 
+        /**
+         * Payload bytes that can still be added (in a single element) before `max_bytes` is
+         * reached; `None` when uncapped.
+         */bytesRemaining: UInt64?) {
+        self.elementsRemaining = elementsRemaining
+        self.bytesRemaining = bytesRemaining
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension CapacityInfo: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeCapacityInfo: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> CapacityInfo {
+        return
+            try CapacityInfo(
+                elementsRemaining: FfiConverterOptionUInt64.read(from: &buf), 
+                bytesRemaining: FfiConverterOptionUInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: CapacityInfo, into buf: inout [UInt8]) {
+        FfiConverterOptionUInt64.write(value.elementsRemaining, into: &buf)
+        FfiConverterOptionUInt64.write(value.bytesRemaining, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCapacityInfo_lift(_ buf: RustBuffer) throws -> CapacityInfo {
+    return try FfiConverterTypeCapacityInfo.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCapacityInfo_lower(_ value: CapacityInfo) -> RustBuffer {
+    return FfiConverterTypeCapacityInfo.lower(value)
+}
+
+
+public struct CapacityReport: Equatable, Hashable {
+    public var fileLen: UInt64
+    public var usedBytes: UInt64
+    public var elementCount: UInt64
+    /**
+     * Stored bytes per element, without the length in front of each one. 0 when empty.
+     */
+    public var avgElementSize: UInt64
+    /**
+     * `reclaimable_bytes / file_len`: the share of the file a rewrite would give back.
+     */
+    public var fragmentationRatio: Double
+    public var reclaimableBytes: UInt64
+    /**
+     * Times adds grew the file since the queue was opened.
+     */
+    public var growths: UInt64
+    /**
+     * The latest of those growths, oldest first.
+     */
+    public var recentGrowths: [GrowthEvent]
+    public var compressed: Bool
+    public var recommendation: CapacityRecommendation
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(fileLen: UInt64, usedBytes: UInt64, elementCount: UInt64, 
+        /**
+         * Stored bytes per element, without the length in front of each one. 0 when empty.
+         */avgElementSize: UInt64, 
+        /**
+         * `reclaimable_bytes / file_len`: the share of the file a rewrite would give back.
+         */fragmentationRatio: Double, reclaimableBytes: UInt64, 
+        /**
+         * Times adds grew the file since the queue was opened.
+         */growths: UInt64, 
+        /**
+         * The latest of those growths, oldest first.
+         */recentGrowths: [GrowthEvent], compressed: Bool, recommendation: CapacityRecommendation) {
+        self.fileLen = fileLen
+        self.usedBytes = usedBytes
+        self.elementCount = elementCount
+        self.avgElementSize = avgElementSize
+        self.fragmentationRatio = fragmentationRatio
+        self.reclaimableBytes = reclaimableBytes
+        self.growths = growths
+        self.recentGrowths = recentGrowths
+        self.compressed = compressed
+        self.recommendation = recommendation
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension CapacityReport: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeCapacityReport: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> CapacityReport {
+        return
+            try CapacityReport(
+                fileLen: FfiConverterUInt64.read(from: &buf), 
+                usedBytes: FfiConverterUInt64.read(from: &buf), 
+                elementCount: FfiConverterUInt64.read(from: &buf), 
+                avgElementSize: FfiConverterUInt64.read(from: &buf), 
+                fragmentationRatio: FfiConverterDouble.read(from: &buf), 
+                reclaimableBytes: FfiConverterUInt64.read(from: &buf), 
+                growths: FfiConverterUInt64.read(from: &buf), 
+                recentGrowths: FfiConverterSequenceTypeGrowthEvent.read(from: &buf), 
+                compressed: FfiConverterBool.read(from: &buf), 
+                recommendation: FfiConverterTypeCapacityRecommendation.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: CapacityReport, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.fileLen, into: &buf)
+        FfiConverterUInt64.write(value.usedBytes, into: &buf)
+        FfiConverterUInt64.write(value.elementCount, into: &buf)
+        FfiConverterUInt64.write(value.avgElementSize, into: &buf)
+        FfiConverterDouble.write(value.fragmentationRatio, into: &buf)
+        FfiConverterUInt64.write(value.reclaimableBytes, into: &buf)
+        FfiConverterUInt64.write(value.growths, into: &buf)
+        FfiConverterSequenceTypeGrowthEvent.write(value.recentGrowths, into: &buf)
+        FfiConverterBool.write(value.compressed, into: &buf)
+        FfiConverterTypeCapacityRecommendation.write(value.recommendation, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCapacityReport_lift(_ buf: RustBuffer) throws -> CapacityReport {
+    return try FfiConverterTypeCapacityReport.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCapacityReport_lower(_ value: CapacityReport) -> RustBuffer {
+    return FfiConverterTypeCapacityReport.lower(value)
+}
+
+
+public struct ChangeRecord: Equatable, Hashable {
+    /**
+     * The queue's `generation()` once the change was made.
+     */
+    public var seq: UInt64
+    /**
+     * When the change was made.
+     */
+    public var timestamp: Date
+    public var change: Change
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * The queue's `generation()` once the change was made.
+         */seq: UInt64, 
+        /**
+         * When the change was made.
+         */timestamp: Date, change: Change) {
+        self.seq = seq
+        self.timestamp = timestamp
+        self.change = change
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension ChangeRecord: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeChangeRecord: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ChangeRecord {
+        return
+            try ChangeRecord(
+                seq: FfiConverterUInt64.read(from: &buf), 
+                timestamp: FfiConverterTimestamp.read(from: &buf), 
+                change: FfiConverterTypeChange.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: ChangeRecord, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.seq, into: &buf)
+        FfiConverterTimestamp.write(value.timestamp, into: &buf)
+        FfiConverterTypeChange.write(value.change, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeChangeRecord_lift(_ buf: RustBuffer) throws -> ChangeRecord {
+    return try FfiConverterTypeChangeRecord.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeChangeRecord_lower(_ value: ChangeRecord) -> RustBuffer {
+    return FfiConverterTypeChangeRecord.lower(value)
+}
+
+
+public struct ConsumerStats: Equatable, Hashable {
+    /**
+     * Elements the handler accepted.
+     */
+    public var delivered: UInt64
+    /**
+     * Deliveries the handler rejected.
+     */
+    public var rejected: UInt64
+    /**
+     * Elements given up on after `max_attempts`, whether moved to the dead-letter queue or
+     * dropped.
+     */
+    public var deadLettered: UInt64
+    /**
+     * Reads, removals and dead-letter adds that failed and were retried after `idle_poll`.
+     */
+    public var errors: UInt64
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Elements the handler accepted.
+         */delivered: UInt64, 
+        /**
+         * Deliveries the handler rejected.
+         */rejected: UInt64, 
+        /**
+         * Elements given up on after `max_attempts`, whether moved to the dead-letter queue or
+         * dropped.
+         */deadLettered: UInt64, 
+        /**
+         * Reads, removals and dead-letter adds that failed and were retried after `idle_poll`.
+         */errors: UInt64) {
+        self.delivered = delivered
+        self.rejected = rejected
+        self.deadLettered = deadLettered
+        self.errors = errors
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension ConsumerStats: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeConsumerStats: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ConsumerStats {
+        return
+            try ConsumerStats(
+                delivered: FfiConverterUInt64.read(from: &buf), 
+                rejected: FfiConverterUInt64.read(from: &buf), 
+                deadLettered: FfiConverterUInt64.read(from: &buf), 
+                errors: FfiConverterUInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: ConsumerStats, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.delivered, into: &buf)
+        FfiConverterUInt64.write(value.rejected, into: &buf)
+        FfiConverterUInt64.write(value.deadLettered, into: &buf)
+        FfiConverterUInt64.write(value.errors, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeConsumerStats_lift(_ buf: RustBuffer) throws -> ConsumerStats {
+    return try FfiConverterTypeConsumerStats.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeConsumerStats_lower(_ value: ConsumerStats) -> RustBuffer {
+    return FfiConverterTypeConsumerStats.lower(value)
+}
+
+
+/**
+ * What the `QuarantineAndStartFresh` policy did with a queue file that failed to open.
+ */
+public struct CorruptionRecovery: Equatable, Hashable {
+    /**
+     * The `CorruptedFile` message the open failed with.
+     */
+    public var reason: String
+    /**
+     * Where the queue file, its mirror and its sidecars were moved, each renamed to
+     * `<name>.corrupt-<unix time in ms>`. The queue file comes first.
+     */
+    public var movedFiles: [String]
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * The `CorruptedFile` message the open failed with.
+         */reason: String, 
+        /**
+         * Where the queue file, its mirror and its sidecars were moved, each renamed to
+         * `<name>.corrupt-<unix time in ms>`. The queue file comes first.
+         */movedFiles: [String]) {
+        self.reason = reason
+        self.movedFiles = movedFiles
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension CorruptionRecovery: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeCorruptionRecovery: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> CorruptionRecovery {
+        return
+            try CorruptionRecovery(
+                reason: FfiConverterString.read(from: &buf), 
+                movedFiles: FfiConverterSequenceString.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: CorruptionRecovery, into buf: inout [UInt8]) {
+        FfiConverterString.write(value.reason, into: &buf)
+        FfiConverterSequenceString.write(value.movedFiles, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCorruptionRecovery_lift(_ buf: RustBuffer) throws -> CorruptionRecovery {
+    return try FfiConverterTypeCorruptionRecovery.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCorruptionRecovery_lower(_ value: CorruptionRecovery) -> RustBuffer {
+    return FfiConverterTypeCorruptionRecovery.lower(value)
+}
+
+
+public struct CrashOutcome: Equatable, Hashable {
+    /**
+     * Position in the recorded write stream at which power was lost.
+     */
+    public var offset: UInt64
+    /**
+     * The checkpoint the reopened queue matches: the one before or the one after the
+     * interrupted operation. `None` means the queue did not recover to a consistent prefix.
+     */
+    public var recoveredCheckpoint: UInt32?
+    /**
+     * Why the crashed file could not be opened or read, if it could not.
+     */
+    public var error: String?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Position in the recorded write stream at which power was lost.
+         */offset: UInt64, 
+        /**
+         * The checkpoint the reopened queue matches: the one before or the one after the
+         * interrupted operation. `None` means the queue did not recover to a consistent prefix.
+         */recoveredCheckpoint: UInt32?, 
+        /**
+         * Why the crashed file could not be opened or read, if it could not.
+         */error: String?) {
+        self.offset = offset
+        self.recoveredCheckpoint = recoveredCheckpoint
+        self.error = error
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension CrashOutcome: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeCrashOutcome: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> CrashOutcome {
+        return
+            try CrashOutcome(
+                offset: FfiConverterUInt64.read(from: &buf), 
+                recoveredCheckpoint: FfiConverterOptionUInt32.read(from: &buf), 
+                error: FfiConverterOptionString.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: CrashOutcome, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.offset, into: &buf)
+        FfiConverterOptionUInt32.write(value.recoveredCheckpoint, into: &buf)
+        FfiConverterOptionString.write(value.error, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCrashOutcome_lift(_ buf: RustBuffer) throws -> CrashOutcome {
+    return try FfiConverterTypeCrashOutcome.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCrashOutcome_lower(_ value: CrashOutcome) -> RustBuffer {
+    return FfiConverterTypeCrashOutcome.lower(value)
+}
+
+
+/**
+ * A segment deleted by a retention limit, with elements that were never removed.
+ */
+public struct DroppedSegment: Equatable, Hashable {
+    public var id: UInt64
+    public var elements: UInt64
+    /**
+     * Bytes of the dropped elements, with their 4-byte frames.
+     */
+    public var bytes: UInt64
+    public var reason: RetentionReason
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(id: UInt64, elements: UInt64, 
+        /**
+         * Bytes of the dropped elements, with their 4-byte frames.
+         */bytes: UInt64, reason: RetentionReason) {
+        self.id = id
+        self.elements = elements
+        self.bytes = bytes
+        self.reason = reason
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension DroppedSegment: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeDroppedSegment: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> DroppedSegment {
+        return
+            try DroppedSegment(
+                id: FfiConverterUInt64.read(from: &buf), 
+                elements: FfiConverterUInt64.read(from: &buf), 
+                bytes: FfiConverterUInt64.read(from: &buf), 
+                reason: FfiConverterTypeRetentionReason.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: DroppedSegment, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.id, into: &buf)
+        FfiConverterUInt64.write(value.elements, into: &buf)
+        FfiConverterUInt64.write(value.bytes, into: &buf)
+        FfiConverterTypeRetentionReason.write(value.reason, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeDroppedSegment_lift(_ buf: RustBuffer) throws -> DroppedSegment {
+    return try FfiConverterTypeDroppedSegment.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeDroppedSegment_lower(_ value: DroppedSegment) -> RustBuffer {
+    return FfiConverterTypeDroppedSegment.lower(value)
+}
+
+
+/**
+ * An element with the metadata queues commonly carry alongside it, stored as the protobuf
+ * message `queuefile.ElementEnvelope` so backend tooling can read the same elements.
+ */
+public struct ElementEnvelope: Equatable, Hashable {
+    public var payload: Data
+    public var headers: [String: String]
+    /**
+     * When the element was first queued. `add_envelope` fills it in if it is `None`.
+     */
+    public var createdAt: Date?
+    /**
+     * Version of the payload's own format, for consumers that read several.
+     */
+    public var schemaVersion: UInt32
+    /**
+     * Deliveries of the element that failed so far.
+     */
+    public var attempts: UInt32
+    public var lastAttemptAt: Date?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(payload: Data, headers: [String: String], 
+        /**
+         * When the element was first queued. `add_envelope` fills it in if it is `None`.
+         */createdAt: Date? = nil, 
+        /**
+         * Version of the payload's own format, for consumers that read several.
+         */schemaVersion: UInt32 = UInt32(0), 
+        /**
+         * Deliveries of the element that failed so far.
+         */attempts: UInt32 = UInt32(0), lastAttemptAt: Date? = nil) {
+        self.payload = payload
+        self.headers = headers
+        self.createdAt = createdAt
+        self.schemaVersion = schemaVersion
+        self.attempts = attempts
+        self.lastAttemptAt = lastAttemptAt
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension ElementEnvelope: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeElementEnvelope: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ElementEnvelope {
+        return
+            try ElementEnvelope(
+                payload: FfiConverterData.read(from: &buf), 
+                headers: FfiConverterDictionaryStringString.read(from: &buf), 
+                createdAt: FfiConverterOptionTimestamp.read(from: &buf), 
+                schemaVersion: FfiConverterUInt32.read(from: &buf), 
+                attempts: FfiConverterUInt32.read(from: &buf), 
+                lastAttemptAt: FfiConverterOptionTimestamp.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: ElementEnvelope, into buf: inout [UInt8]) {
+        FfiConverterData.write(value.payload, into: &buf)
+        FfiConverterDictionaryStringString.write(value.headers, into: &buf)
+        FfiConverterOptionTimestamp.write(value.createdAt, into: &buf)
+        FfiConverterUInt32.write(value.schemaVersion, into: &buf)
+        FfiConverterUInt32.write(value.attempts, into: &buf)
+        FfiConverterOptionTimestamp.write(value.lastAttemptAt, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeElementEnvelope_lift(_ buf: RustBuffer) throws -> ElementEnvelope {
+    return try FfiConverterTypeElementEnvelope.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeElementEnvelope_lower(_ value: ElementEnvelope) -> RustBuffer {
+    return FfiConverterTypeElementEnvelope.lower(value)
+}
+
+
+/**
+ * What `peek_metadata` and `QueueFileIterator::next_metadata` tell about an element without
+ * handing over its bytes.
+ */
+public struct ElementMeta: Equatable, Hashable {
+    /**
+     * Length of the element as `peek` returns it.
+     */
+    public var size: UInt64
+    /**
+     * Sequence id, numbered as `AddReceipt.seq` numbers them.
+     */
+    public var seq: UInt64
+    /**
+     * The envelope's `created_at`, `attempts` and `"tag"` header, when the element was encoded
+     * by `encode_envelope` (as `add_envelope` does); `None`, 0 and `None` otherwise.
+     */
+    public var enqueuedAt: Date?
+    public var attempts: UInt32
+    public var tag: String?
+    /**
+     * SHA-256 of the element, as `peek_digest` computes it.
+     */
+    public var digest: Data
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Length of the element as `peek` returns it.
+         */size: UInt64, 
+        /**
+         * Sequence id, numbered as `AddReceipt.seq` numbers them.
+         */seq: UInt64, 
+        /**
+         * The envelope's `created_at`, `attempts` and `"tag"` header, when the element was encoded
+         * by `encode_envelope` (as `add_envelope` does); `None`, 0 and `None` otherwise.
+         */enqueuedAt: Date?, attempts: UInt32, tag: String?, 
+        /**
+         * SHA-256 of the element, as `peek_digest` computes it.
+         */digest: Data) {
+        self.size = size
+        self.seq = seq
+        self.enqueuedAt = enqueuedAt
+        self.attempts = attempts
+        self.tag = tag
+        self.digest = digest
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension ElementMeta: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeElementMeta: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ElementMeta {
+        return
+            try ElementMeta(
+                size: FfiConverterUInt64.read(from: &buf), 
+                seq: FfiConverterUInt64.read(from: &buf), 
+                enqueuedAt: FfiConverterOptionTimestamp.read(from: &buf), 
+                attempts: FfiConverterUInt32.read(from: &buf), 
+                tag: FfiConverterOptionString.read(from: &buf), 
+                digest: FfiConverterData.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: ElementMeta, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.size, into: &buf)
+        FfiConverterUInt64.write(value.seq, into: &buf)
+        FfiConverterOptionTimestamp.write(value.enqueuedAt, into: &buf)
+        FfiConverterUInt32.write(value.attempts, into: &buf)
+        FfiConverterOptionString.write(value.tag, into: &buf)
+        FfiConverterData.write(value.digest, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeElementMeta_lift(_ buf: RustBuffer) throws -> ElementMeta {
+    return try FfiConverterTypeElementMeta.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeElementMeta_lower(_ value: ElementMeta) -> RustBuffer {
+    return FfiConverterTypeElementMeta.lower(value)
+}
+
+
+/**
+ * The committed header of a queue file and what the handle has layered over it.
+ */
+public struct FileInfo: Equatable, Hashable {
+    public var format: FileFormat
+    /**
+     * `VERSIONED_FORMAT_VERSION` or `LEGACY_FORMAT_VERSION`.
+     */
+    public var version: UInt32
+    public var headerLen: UInt64
+    public var fileLen: UInt64
+    public var elementCount: UInt64
+    /**
+     * File offsets of the eldest and the newest element's frames.
+     */
+    public var firstPosition: UInt64
+    public var lastPosition: UInt64
+    /**
+     * From the file system, where it records them.
+     */
+    public var createdAt: Date?
+    public var modifiedAt: Date?
+    /**
+     * Adds are compressed against a trained dictionary.
+     */
+    public var compressed: Bool
+    /**
+     * The contents are signed in `<path>.sig` (`signature_key`).
+     */
+    public var signed: Bool
+    /**
+     * Syncs write the CRC-checked `<path>.sync` trailer (`sync_trailer`).
+     */
+    public var syncTrailer: Bool
+    public var redundantHeader: Bool
+    public var elementIndex: Bool
+    public var mirrored: Bool
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(format: FileFormat, 
+        /**
+         * `VERSIONED_FORMAT_VERSION` or `LEGACY_FORMAT_VERSION`.
+         */version: UInt32, headerLen: UInt64, fileLen: UInt64, elementCount: UInt64, 
+        /**
+         * File offsets of the eldest and the newest element's frames.
+         */firstPosition: UInt64, lastPosition: UInt64, 
+        /**
+         * From the file system, where it records them.
+         */createdAt: Date?, modifiedAt: Date?, 
+        /**
+         * Adds are compressed against a trained dictionary.
+         */compressed: Bool, 
+        /**
+         * The contents are signed in `<path>.sig` (`signature_key`).
+         */signed: Bool, 
+        /**
+         * Syncs write the CRC-checked `<path>.sync` trailer (`sync_trailer`).
+         */syncTrailer: Bool, redundantHeader: Bool, elementIndex: Bool, mirrored: Bool) {
+        self.format = format
+        self.version = version
+        self.headerLen = headerLen
+        self.fileLen = fileLen
+        self.elementCount = elementCount
+        self.firstPosition = firstPosition
+        self.lastPosition = lastPosition
+        self.createdAt = createdAt
+        self.modifiedAt = modifiedAt
+        self.compressed = compressed
+        self.signed = signed
+        self.syncTrailer = syncTrailer
+        self.redundantHeader = redundantHeader
+        self.elementIndex = elementIndex
+        self.mirrored = mirrored
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension FileInfo: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeFileInfo: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> FileInfo {
+        return
+            try FileInfo(
+                format: FfiConverterTypeFileFormat.read(from: &buf), 
+                version: FfiConverterUInt32.read(from: &buf), 
+                headerLen: FfiConverterUInt64.read(from: &buf), 
+                fileLen: FfiConverterUInt64.read(from: &buf), 
+                elementCount: FfiConverterUInt64.read(from: &buf), 
+                firstPosition: FfiConverterUInt64.read(from: &buf), 
+                lastPosition: FfiConverterUInt64.read(from: &buf), 
+                createdAt: FfiConverterOptionTimestamp.read(from: &buf), 
+                modifiedAt: FfiConverterOptionTimestamp.read(from: &buf), 
+                compressed: FfiConverterBool.read(from: &buf), 
+                signed: FfiConverterBool.read(from: &buf), 
+                syncTrailer: FfiConverterBool.read(from: &buf), 
+                redundantHeader: FfiConverterBool.read(from: &buf), 
+                elementIndex: FfiConverterBool.read(from: &buf), 
+                mirrored: FfiConverterBool.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: FileInfo, into buf: inout [UInt8]) {
+        FfiConverterTypeFileFormat.write(value.format, into: &buf)
+        FfiConverterUInt32.write(value.version, into: &buf)
+        FfiConverterUInt64.write(value.headerLen, into: &buf)
+        FfiConverterUInt64.write(value.fileLen, into: &buf)
+        FfiConverterUInt64.write(value.elementCount, into: &buf)
+        FfiConverterUInt64.write(value.firstPosition, into: &buf)
+        FfiConverterUInt64.write(value.lastPosition, into: &buf)
+        FfiConverterOptionTimestamp.write(value.createdAt, into: &buf)
+        FfiConverterOptionTimestamp.write(value.modifiedAt, into: &buf)
+        FfiConverterBool.write(value.compressed, into: &buf)
+        FfiConverterBool.write(value.signed, into: &buf)
+        FfiConverterBool.write(value.syncTrailer, into: &buf)
+        FfiConverterBool.write(value.redundantHeader, into: &buf)
+        FfiConverterBool.write(value.elementIndex, into: &buf)
+        FfiConverterBool.write(value.mirrored, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFileInfo_lift(_ buf: RustBuffer) throws -> FileInfo {
+    return try FfiConverterTypeFileInfo.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFileInfo_lower(_ value: FileInfo) -> RustBuffer {
+    return FfiConverterTypeFileInfo.lower(value)
+}
+
+
+public struct FragmentationReport: Equatable, Hashable {
+    public var fileLen: UInt64
+    public var usedBytes: UInt64
+    /**
+     * Bytes of the file that hold neither the header nor a live element.
+     */
+    public var wastedBytes: UInt64
+    /**
+     * True when the elements run past the end of the file and continue after the header.
+     */
+    public var wrapped: Bool
+    /**
+     * The free parts of the ring, in file order: one range when wrapped, up to two otherwise.
+     */
+    public var gaps: [FreeRange]
+    /**
+     * Length a copy of the queue rewritten into a fresh file would have: the capacity,
+     * doubled until the elements fit, as the queue grows it.
+     */
+    public var compactedLen: UInt64
+    /**
+     * `file_len - compacted_len`, what rewriting the queue would give back.
+     */
+    public var reclaimableBytes: UInt64
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(fileLen: UInt64, usedBytes: UInt64, 
+        /**
+         * Bytes of the file that hold neither the header nor a live element.
+         */wastedBytes: UInt64, 
+        /**
+         * True when the elements run past the end of the file and continue after the header.
+         */wrapped: Bool, 
+        /**
+         * The free parts of the ring, in file order: one range when wrapped, up to two otherwise.
+         */gaps: [FreeRange], 
+        /**
+         * Length a copy of the queue rewritten into a fresh file would have: the capacity,
+         * doubled until the elements fit, as the queue grows it.
+         */compactedLen: UInt64, 
+        /**
+         * `file_len - compacted_len`, what rewriting the queue would give back.
+         */reclaimableBytes: UInt64) {
+        self.fileLen = fileLen
+        self.usedBytes = usedBytes
+        self.wastedBytes = wastedBytes
+        self.wrapped = wrapped
+        self.gaps = gaps
+        self.compactedLen = compactedLen
+        self.reclaimableBytes = reclaimableBytes
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension FragmentationReport: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeFragmentationReport: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> FragmentationReport {
+        return
+            try FragmentationReport(
+                fileLen: FfiConverterUInt64.read(from: &buf), 
+                usedBytes: FfiConverterUInt64.read(from: &buf), 
+                wastedBytes: FfiConverterUInt64.read(from: &buf), 
+                wrapped: FfiConverterBool.read(from: &buf), 
+                gaps: FfiConverterSequenceTypeFreeRange.read(from: &buf), 
+                compactedLen: FfiConverterUInt64.read(from: &buf), 
+                reclaimableBytes: FfiConverterUInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: FragmentationReport, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.fileLen, into: &buf)
+        FfiConverterUInt64.write(value.usedBytes, into: &buf)
+        FfiConverterUInt64.write(value.wastedBytes, into: &buf)
+        FfiConverterBool.write(value.wrapped, into: &buf)
+        FfiConverterSequenceTypeFreeRange.write(value.gaps, into: &buf)
+        FfiConverterUInt64.write(value.compactedLen, into: &buf)
+        FfiConverterUInt64.write(value.reclaimableBytes, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFragmentationReport_lift(_ buf: RustBuffer) throws -> FragmentationReport {
+    return try FfiConverterTypeFragmentationReport.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFragmentationReport_lower(_ value: FragmentationReport) -> RustBuffer {
+    return FfiConverterTypeFragmentationReport.lower(value)
+}
+
+
+public struct FreeRange: Equatable, Hashable {
+    /**
+     * Byte offset of the range in the queue file.
+     */
+    public var offset: UInt64
+    public var len: UInt64
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Byte offset of the range in the queue file.
+         */offset: UInt64, len: UInt64) {
+        self.offset = offset
+        self.len = len
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension FreeRange: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeFreeRange: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> FreeRange {
+        return
+            try FreeRange(
+                offset: FfiConverterUInt64.read(from: &buf), 
+                len: FfiConverterUInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: FreeRange, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.offset, into: &buf)
+        FfiConverterUInt64.write(value.len, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFreeRange_lift(_ buf: RustBuffer) throws -> FreeRange {
+    return try FfiConverterTypeFreeRange.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFreeRange_lower(_ value: FreeRange) -> RustBuffer {
+    return FfiConverterTypeFreeRange.lower(value)
+}
+
+
+/**
+ * One time an add lengthened the queue file.
+ */
+public struct GrowthEvent: Equatable, Hashable {
+    public var at: Date
+    public var fromLen: UInt64
+    public var toLen: UInt64
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(at: Date, fromLen: UInt64, toLen: UInt64) {
+        self.at = at
+        self.fromLen = fromLen
+        self.toLen = toLen
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension GrowthEvent: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeGrowthEvent: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> GrowthEvent {
+        return
+            try GrowthEvent(
+                at: FfiConverterTimestamp.read(from: &buf), 
+                fromLen: FfiConverterUInt64.read(from: &buf), 
+                toLen: FfiConverterUInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: GrowthEvent, into buf: inout [UInt8]) {
+        FfiConverterTimestamp.write(value.at, into: &buf)
+        FfiConverterUInt64.write(value.fromLen, into: &buf)
+        FfiConverterUInt64.write(value.toLen, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeGrowthEvent_lift(_ buf: RustBuffer) throws -> GrowthEvent {
+    return try FfiConverterTypeGrowthEvent.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeGrowthEvent_lower(_ value: GrowthEvent) -> RustBuffer {
+    return FfiConverterTypeGrowthEvent.lower(value)
+}
+
+
+/**
+ * The outcome of `health_check`.
+ */
+public struct HealthReport: Equatable, Hashable {
+    /**
+     * The worst outcome of any check.
+     */
+    public var status: HealthStatus
+    /**
+     * One line for each check that did not pass.
+     */
+    public var problems: [String]
+    /**
+     * Whether the queue's lock was acquired within a second. If not, no other check ran.
+     */
+    public var lockResponsive: Bool
+    public var lockWait: TimeInterval
+    /**
+     * Whether the header on disk describes a ring that fits the file and matches the open
+     * queue.
+     */
+    public var headerOk: Bool
+    /**
+     * Whether the element counts kept in memory agree with each other.
+     */
+    public var countersOk: Bool
+    /**
+     * Free space on the volume, where the platform reports it.
+     */
+    public var availableBytes: UInt64?
+    /**
+     * Bytes written since the last sync. Always 0 with `sync_writes`.
+     */
+    public var unsyncedBytes: UInt64
+    /**
+     * Time since the last `sync_all`, or since the queue was opened.
+     */
+    public var lastSyncAge: TimeInterval
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * The worst outcome of any check.
+         */status: HealthStatus, 
+        /**
+         * One line for each check that did not pass.
+         */problems: [String], 
+        /**
+         * Whether the queue's lock was acquired within a second. If not, no other check ran.
+         */lockResponsive: Bool, lockWait: TimeInterval, 
+        /**
+         * Whether the header on disk describes a ring that fits the file and matches the open
+         * queue.
+         */headerOk: Bool, 
+        /**
+         * Whether the element counts kept in memory agree with each other.
+         */countersOk: Bool, 
+        /**
+         * Free space on the volume, where the platform reports it.
+         */availableBytes: UInt64?, 
+        /**
+         * Bytes written since the last sync. Always 0 with `sync_writes`.
+         */unsyncedBytes: UInt64, 
+        /**
+         * Time since the last `sync_all`, or since the queue was opened.
+         */lastSyncAge: TimeInterval) {
+        self.status = status
+        self.problems = problems
+        self.lockResponsive = lockResponsive
+        self.lockWait = lockWait
+        self.headerOk = headerOk
+        self.countersOk = countersOk
+        self.availableBytes = availableBytes
+        self.unsyncedBytes = unsyncedBytes
+        self.lastSyncAge = lastSyncAge
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension HealthReport: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeHealthReport: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> HealthReport {
+        return
+            try HealthReport(
+                status: FfiConverterTypeHealthStatus.read(from: &buf), 
+                problems: FfiConverterSequenceString.read(from: &buf), 
+                lockResponsive: FfiConverterBool.read(from: &buf), 
+                lockWait: FfiConverterDuration.read(from: &buf), 
+                headerOk: FfiConverterBool.read(from: &buf), 
+                countersOk: FfiConverterBool.read(from: &buf), 
+                availableBytes: FfiConverterOptionUInt64.read(from: &buf), 
+                unsyncedBytes: FfiConverterUInt64.read(from: &buf), 
+                lastSyncAge: FfiConverterDuration.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: HealthReport, into buf: inout [UInt8]) {
+        FfiConverterTypeHealthStatus.write(value.status, into: &buf)
+        FfiConverterSequenceString.write(value.problems, into: &buf)
+        FfiConverterBool.write(value.lockResponsive, into: &buf)
+        FfiConverterDuration.write(value.lockWait, into: &buf)
+        FfiConverterBool.write(value.headerOk, into: &buf)
+        FfiConverterBool.write(value.countersOk, into: &buf)
+        FfiConverterOptionUInt64.write(value.availableBytes, into: &buf)
+        FfiConverterUInt64.write(value.unsyncedBytes, into: &buf)
+        FfiConverterDuration.write(value.lastSyncAge, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeHealthReport_lift(_ buf: RustBuffer) throws -> HealthReport {
+    return try FfiConverterTypeHealthReport.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeHealthReport_lower(_ value: HealthReport) -> RustBuffer {
+    return FfiConverterTypeHealthReport.lower(value)
+}
+
+
+public struct IoLatencySummary: Equatable, Hashable {
+    public var call: IoCall
+    public var count: UInt64
+    public var p50: TimeInterval
+    public var p95: TimeInterval
+    public var p99: TimeInterval
+    public var max: TimeInterval
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(call: IoCall, count: UInt64, p50: TimeInterval, p95: TimeInterval, p99: TimeInterval, max: TimeInterval) {
+        self.call = call
+        self.count = count
+        self.p50 = p50
+        self.p95 = p95
+        self.p99 = p99
+        self.max = max
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension IoLatencySummary: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeIoLatencySummary: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> IoLatencySummary {
+        return
+            try IoLatencySummary(
+                call: FfiConverterTypeIoCall.read(from: &buf), 
+                count: FfiConverterUInt64.read(from: &buf), 
+                p50: FfiConverterDuration.read(from: &buf), 
+                p95: FfiConverterDuration.read(from: &buf), 
+                p99: FfiConverterDuration.read(from: &buf), 
+                max: FfiConverterDuration.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: IoLatencySummary, into buf: inout [UInt8]) {
+        FfiConverterTypeIoCall.write(value.call, into: &buf)
+        FfiConverterUInt64.write(value.count, into: &buf)
+        FfiConverterDuration.write(value.p50, into: &buf)
+        FfiConverterDuration.write(value.p95, into: &buf)
+        FfiConverterDuration.write(value.p99, into: &buf)
+        FfiConverterDuration.write(value.max, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeIoLatencySummary_lift(_ buf: RustBuffer) throws -> IoLatencySummary {
+    return try FfiConverterTypeIoLatencySummary.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeIoLatencySummary_lower(_ value: IoLatencySummary) -> RustBuffer {
+    return FfiConverterTypeIoLatencySummary.lower(value)
+}
+
+
+public struct LatencySummary: Equatable, Hashable {
+    public var operation: QueueOperation
+    public var count: UInt64
+    public var p50: TimeInterval
+    public var p95: TimeInterval
+    public var p99: TimeInterval
+    public var max: TimeInterval
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(operation: QueueOperation, count: UInt64, p50: TimeInterval, p95: TimeInterval, p99: TimeInterval, max: TimeInterval) {
+        self.operation = operation
+        self.count = count
+        self.p50 = p50
+        self.p95 = p95
+        self.p99 = p99
+        self.max = max
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension LatencySummary: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeLatencySummary: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> LatencySummary {
+        return
+            try LatencySummary(
+                operation: FfiConverterTypeQueueOperation.read(from: &buf), 
+                count: FfiConverterUInt64.read(from: &buf), 
+                p50: FfiConverterDuration.read(from: &buf), 
+                p95: FfiConverterDuration.read(from: &buf), 
+                p99: FfiConverterDuration.read(from: &buf), 
+                max: FfiConverterDuration.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: LatencySummary, into buf: inout [UInt8]) {
+        FfiConverterTypeQueueOperation.write(value.operation, into: &buf)
+        FfiConverterUInt64.write(value.count, into: &buf)
+        FfiConverterDuration.write(value.p50, into: &buf)
+        FfiConverterDuration.write(value.p95, into: &buf)
+        FfiConverterDuration.write(value.p99, into: &buf)
+        FfiConverterDuration.write(value.max, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeLatencySummary_lift(_ buf: RustBuffer) throws -> LatencySummary {
+    return try FfiConverterTypeLatencySummary.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeLatencySummary_lower(_ value: LatencySummary) -> RustBuffer {
+    return FfiConverterTypeLatencySummary.lower(value)
+}
+
+
+/**
+ * How `process` retries an element its handler rejects.
+ */
+public struct ProcessPolicy: Equatable, Hashable {
+    /**
+     * Deliveries of one element, including the first, before it is given up on.
+     */
+    public var maxAttempts: UInt32
+    /**
+     * Delay before the second delivery; doubled for each further one. `None` is 100 ms.
+     */
+    public var initialBackoff: TimeInterval?
+    /**
+     * `None` is 60 s.
+     */
+    public var maxBackoff: TimeInterval?
+    /**
+     * How long an empty queue, or one that failed to read, waits before it is checked again.
+     * `None` is 500 ms.
+     */
+    public var idlePoll: TimeInterval?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Deliveries of one element, including the first, before it is given up on.
+         */maxAttempts: UInt32 = UInt32(5), 
+        /**
+         * Delay before the second delivery; doubled for each further one. `None` is 100 ms.
+         */initialBackoff: TimeInterval? = nil, 
+        /**
+         * `None` is 60 s.
+         */maxBackoff: TimeInterval? = nil, 
+        /**
+         * How long an empty queue, or one that failed to read, waits before it is checked again.
+         * `None` is 500 ms.
+         */idlePoll: TimeInterval? = nil) {
+        self.maxAttempts = maxAttempts
+        self.initialBackoff = initialBackoff
+        self.maxBackoff = maxBackoff
+        self.idlePoll = idlePoll
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension ProcessPolicy: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeProcessPolicy: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ProcessPolicy {
+        return
+            try ProcessPolicy(
+                maxAttempts: FfiConverterUInt32.read(from: &buf), 
+                initialBackoff: FfiConverterOptionDuration.read(from: &buf), 
+                maxBackoff: FfiConverterOptionDuration.read(from: &buf), 
+                idlePoll: FfiConverterOptionDuration.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: ProcessPolicy, into buf: inout [UInt8]) {
+        FfiConverterUInt32.write(value.maxAttempts, into: &buf)
+        FfiConverterOptionDuration.write(value.initialBackoff, into: &buf)
+        FfiConverterOptionDuration.write(value.maxBackoff, into: &buf)
+        FfiConverterOptionDuration.write(value.idlePoll, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeProcessPolicy_lift(_ buf: RustBuffer) throws -> ProcessPolicy {
+    return try FfiConverterTypeProcessPolicy.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeProcessPolicy_lower(_ value: ProcessPolicy) -> RustBuffer {
+    return FfiConverterTypeProcessPolicy.lower(value)
+}
+
+
+/**
+ * What the `quarantine_corrupt` option moved out of the queue file when it was opened.
+ */
+public struct QuarantineReport: Equatable, Hashable {
+    /**
+     * Elements the queue opened with.
+     */
+    public var keptElements: UInt64
+    public var ranges: [QuarantinedRange]
+    /**
+     * The queue file the raw bytes were appended to.
+     */
+    public var quarantinePath: String
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Elements the queue opened with.
+         */keptElements: UInt64, ranges: [QuarantinedRange], 
+        /**
+         * The queue file the raw bytes were appended to.
+         */quarantinePath: String) {
+        self.keptElements = keptElements
+        self.ranges = ranges
+        self.quarantinePath = quarantinePath
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension QuarantineReport: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQuarantineReport: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QuarantineReport {
+        return
+            try QuarantineReport(
+                keptElements: FfiConverterUInt64.read(from: &buf), 
+                ranges: FfiConverterSequenceTypeQuarantinedRange.read(from: &buf), 
+                quarantinePath: FfiConverterString.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: QuarantineReport, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.keptElements, into: &buf)
+        FfiConverterSequenceTypeQuarantinedRange.write(value.ranges, into: &buf)
+        FfiConverterString.write(value.quarantinePath, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQuarantineReport_lift(_ buf: RustBuffer) throws -> QuarantineReport {
+    return try FfiConverterTypeQuarantineReport.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQuarantineReport_lower(_ value: QuarantineReport) -> RustBuffer {
+    return FfiConverterTypeQuarantineReport.lower(value)
+}
+
+
+/**
+ * A stretch of the queue file that opening moved aside.
+ */
+public struct QuarantinedRange: Equatable, Hashable {
+    /**
+     * Position in the queue of the first element the range held.
+     */
+    public var index: UInt64
+    /**
+     * Byte offset of the range in the queue file.
+     */
+    public var offset: UInt64
+    /**
+     * Raw bytes copied to the quarantine file; 0 when they could not be read at all.
+     */
+    public var bytes: UInt64
+    /**
+     * Elements lost with the range: 1 for an unreadable payload, every element from `index`
+     * on when the chain of frames breaks.
+     */
+    public var elements: UInt64
+    public var reason: String
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Position in the queue of the first element the range held.
+         */index: UInt64, 
+        /**
+         * Byte offset of the range in the queue file.
+         */offset: UInt64, 
+        /**
+         * Raw bytes copied to the quarantine file; 0 when they could not be read at all.
+         */bytes: UInt64, 
+        /**
+         * Elements lost with the range: 1 for an unreadable payload, every element from `index`
+         * on when the chain of frames breaks.
+         */elements: UInt64, reason: String) {
+        self.index = index
+        self.offset = offset
+        self.bytes = bytes
+        self.elements = elements
+        self.reason = reason
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension QuarantinedRange: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQuarantinedRange: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QuarantinedRange {
+        return
+            try QuarantinedRange(
+                index: FfiConverterUInt64.read(from: &buf), 
+                offset: FfiConverterUInt64.read(from: &buf), 
+                bytes: FfiConverterUInt64.read(from: &buf), 
+                elements: FfiConverterUInt64.read(from: &buf), 
+                reason: FfiConverterString.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: QuarantinedRange, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.index, into: &buf)
+        FfiConverterUInt64.write(value.offset, into: &buf)
+        FfiConverterUInt64.write(value.bytes, into: &buf)
+        FfiConverterUInt64.write(value.elements, into: &buf)
+        FfiConverterString.write(value.reason, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQuarantinedRange_lift(_ buf: RustBuffer) throws -> QuarantinedRange {
+    return try FfiConverterTypeQuarantinedRange.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQuarantinedRange_lower(_ value: QuarantinedRange) -> RustBuffer {
+    return FfiConverterTypeQuarantinedRange.lower(value)
+}
+
+
+public struct QueueFileOptions: Equatable, Hashable {
+    /**
+     * How to treat a missing or existing file; `None` behaves as `CreateIfMissing`.
+     */
+    public var mode: OpenMode?
+    /**
+     * Minimum file size in bytes; the file never shrinks below it.
+     */
+    public var capacity: UInt64
+    /**
+     * `clear` keeps the file at least this long (or at its current length, if shorter) instead
+     * of truncating it to `capacity`, so the next burst does not have to grow it again.
+     */
+    public var minRetainedLen: UInt64?
+    /**
+     * How the file grows when new elements do not fit; `None` behaves as `Double`. Only
+     * applies while the elements do not wrap around the end of the ring; a wrapped queue
+     * always doubles.
+     */
+    public var growth: GrowthStrategy?
+    /**
+     * Caps each growth step at this many bytes (several steps are taken if needed), with the
+     * same restriction to unwrapped queues as `growth`.
+     */
+    public var maxGrowthStep: UInt64?
+    /**
+     * Keeps a hot mirror of the queue at this path. Every mutation is applied to both files, and
+     * the mirror takes over if the primary is missing, fails to open or is corrupt.
+     */
+    public var mirrorPath: String?
+    /**
+     * Adds that would take the queue above this many elements fail with `QuotaExceeded`.
+     */
+    public var maxElements: UInt64?
+    /**
+     * Adds that would take `used_bytes` (payloads, 4-byte frame headers and the file header)
+     * above this value fail with `QuotaExceeded`.
+     */
+    public var maxBytes: UInt64?
+    /**
+     * Adds beyond these rates fail with `Backpressure` instead of being written.
+     */
+    public var rateLimit: RateLimit?
+    /**
+     * Retries transient I/O errors on open, read, write and fsync. `None` surfaces them
+     * immediately.
+     */
+    public var retryPolicy: RetryPolicy?
+    /**
+     * Operations that fail with `DataProtectionLocked` wait up to this long for
+     * `notify_unlocked()` and are then tried again. `None` fails them immediately.
+     */
+    public var waitForUnlock: TimeInterval?
+    /**
+     * Keeps two checksummed copies of the header in a `<path>.hdr` sidecar and restores the
+     * newest valid one on open if the file's own header was torn.
+     */
+    public var redundantHeader: Bool
+    /**
+     * Fsyncs the containing directory after open creates the queue file, its sidecars or
+     * mirror, or renames a restored copy into place, so the new directory entries survive
+     * power loss.
+     */
+    public var syncDirectory: Bool
+    /**
+     * Marks the queue file, its sidecar and mirror as excluded from iCloud and device backups
+     * on open. Has no effect on non-Apple platforms.
+     */
+    public var excludeFromBackup: Bool
+    /**
+     * What to do when this process already has the file open; `None` behaves as `Fail`.
+     */
+    public var alreadyOpen: AlreadyOpenPolicy?
+    /**
+     * Records the owning process in a `<path>.lock` file while the queue is open, failing with
+     * `LockedByOtherProcess` if a running process holds it and breaking it if its owner died.
+     */
+    public var lockFile: Bool
+    /**
+     * Keeps an in-memory counting bloom filter over payloads, sized for this many elements,
+     * for `probably_contains`. It is rebuilt from the file on open.
+     */
+    public var bloomFilterElements: UInt64?
+    /**
+     * Records the header and element directory in a `<path>.sync` trailer on every
+     * `sync_all`, and compares the file with it on open (see `last_sync_check`).
+     */
+    public var syncTrailer: Bool
+    /**
+     * Walks every element frame and reads every payload before opening returns, failing with
+     * `CorruptedFile` and a description of the first broken frame. Costs a full read of the file.
+     */
+    public var verifyOnOpen: Bool
+    /**
+     * Moves elements whose frames are broken or whose payloads cannot be read into a
+     * `<path>.quarantine` queue file and opens with the rest (see `last_quarantine`).
+     */
+    public var quarantineCorrupt: Bool
+    /**
+     * Keeps the most recent this many changes in memory for `changes_since`. Off when unset.
+     */
+    public var journalCapacity: UInt32?
+    /**
+     * Keeps the position of every element in memory for `element_at`, saved to `<path>.idx`
+     * by `sync_all` and on close. A saved index that still matches the file's header restores
+     * `generation()` and the sequence ids on open instead of a frame walk.
+     */
+    public var elementIndex: Bool
+    /**
+     * Returns from opening without the frame walks that fill the bloom filter and build the
+     * element index. They run on the first call that needs them, or on `finish_open`.
+     */
+    public var lazyOpen: Bool
+    /**
+     * Holds up to this many removals in memory, hidden from `peek` and `size`, before
+     * committing them with one header write. Uncommitted removals count against quotas and
+     * watermarks, and are lost on a crash. Off when unset.
+     */
+    public var deferredRemoves: UInt32?
+    /**
+     * Free space on the volume below which `health_check` reports `Degraded`.
+     */
+    public var freeSpaceReserve: UInt64?
+    /**
+     * How long written bytes may go without a sync before `health_check` reports `Degraded`.
+     */
+    public var maxUnsyncedAge: TimeInterval?
+    /**
+     * What to do when the file is corrupt on open; `None` behaves as `Fail`, unless
+     * `quarantine_corrupt` is set.
+     */
+    public var onCorruption: CorruptionPolicy?
+    /**
+     * The longest element a single peek or get may copy into memory. Longer ones fail with
+     * `TooLargeForRead` before anything is read; `peek_to_file` and `pop_to_file` still read
+     * them, a chunk at a time.
+     */
+    public var maxReadBytes: UInt64?
+    /**
+     * How long `add`, `add_multiple`, `peek`, `remove`, `remove_n`, `clear` and `sync_all`
+     * may take before failing with `Timeout`. When set, they run on a runtime worker while the
+     * caller waits; the `_async` calls are not limited. Unlimited when unset.
+     */
+    public var operationTimeout: TimeInterval?
+    /**
+     * Id of the key, asked of the `KeyProvider` set with `set_key_provider`, that signs the
+     * header and contents in `<path>.sig` on every sync and close. Opening a file that does
+     * not match its signature fails with `TamperDetected`. Unsigned when unset.
+     */
+    public var signatureKey: String?
+    /**
+     * Header layout of a file this open creates. An existing file, including one restored
+     * from the mirror, keeps its own, and rewrites keep it too. `None` is `Versioned`.
+     */
+    public var format: FileFormat?
+    /**
+     * Bytes queue-file reads ahead for sequential peeks, removals and iteration, as
+     * `set_read_buffer_size` sets later. `None` is 4 KiB.
+     */
+    public var readBufferSize: UInt32?
+    /**
+     * Bytes copied at a time by `peek_to_file` and `pop_to_file`, and payload bytes written to
+     * the new file per batch when `remove_at`, `update_at`, `rewrite` and `quarantine_corrupt`
+     * copy the queue. `None` is 64 KiB for the former and 1 MiB for the latter.
+     */
+    public var copyBufferSize: UInt32?
+    /**
+     * How long the queue may go unused before its file, and the mirror, header sidecar and
+     * element index, are synced and closed. The next call reopens them first. Open until
+     * closed when unset.
+     */
+    public var idleCloseAfter: TimeInterval?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * How to treat a missing or existing file; `None` behaves as `CreateIfMissing`.
+         */mode: OpenMode? = nil, 
+        /**
+         * Minimum file size in bytes; the file never shrinks below it.
+         */capacity: UInt64 = UInt64(4096), 
+        /**
+         * `clear` keeps the file at least this long (or at its current length, if shorter) instead
+         * of truncating it to `capacity`, so the next burst does not have to grow it again.
+         */minRetainedLen: UInt64? = nil, 
+        /**
+         * How the file grows when new elements do not fit; `None` behaves as `Double`. Only
+         * applies while the elements do not wrap around the end of the ring; a wrapped queue
+         * always doubles.
+         */growth: GrowthStrategy? = nil, 
+        /**
+         * Caps each growth step at this many bytes (several steps are taken if needed), with the
+         * same restriction to unwrapped queues as `growth`.
+         */maxGrowthStep: UInt64? = nil, 
+        /**
+         * Keeps a hot mirror of the queue at this path. Every mutation is applied to both files, and
+         * the mirror takes over if the primary is missing, fails to open or is corrupt.
+         */mirrorPath: String? = nil, 
+        /**
+         * Adds that would take the queue above this many elements fail with `QuotaExceeded`.
+         */maxElements: UInt64? = nil, 
+        /**
+         * Adds that would take `used_bytes` (payloads, 4-byte frame headers and the file header)
+         * above this value fail with `QuotaExceeded`.
+         */maxBytes: UInt64? = nil, 
+        /**
+         * Adds beyond these rates fail with `Backpressure` instead of being written.
+         */rateLimit: RateLimit? = nil, 
+        /**
+         * Retries transient I/O errors on open, read, write and fsync. `None` surfaces them
+         * immediately.
+         */retryPolicy: RetryPolicy? = nil, 
+        /**
+         * Operations that fail with `DataProtectionLocked` wait up to this long for
+         * `notify_unlocked()` and are then tried again. `None` fails them immediately.
+         */waitForUnlock: TimeInterval? = nil, 
+        /**
+         * Keeps two checksummed copies of the header in a `<path>.hdr` sidecar and restores the
+         * newest valid one on open if the file's own header was torn.
+         */redundantHeader: Bool = false, 
+        /**
+         * Fsyncs the containing directory after open creates the queue file, its sidecars or
+         * mirror, or renames a restored copy into place, so the new directory entries survive
+         * power loss.
+         */syncDirectory: Bool = true, 
+        /**
+         * Marks the queue file, its sidecar and mirror as excluded from iCloud and device backups
+         * on open. Has no effect on non-Apple platforms.
+         */excludeFromBackup: Bool = false, 
+        /**
+         * What to do when this process already has the file open; `None` behaves as `Fail`.
+         */alreadyOpen: AlreadyOpenPolicy? = nil, 
+        /**
+         * Records the owning process in a `<path>.lock` file while the queue is open, failing with
+         * `LockedByOtherProcess` if a running process holds it and breaking it if its owner died.
+         */lockFile: Bool = false, 
+        /**
+         * Keeps an in-memory counting bloom filter over payloads, sized for this many elements,
+         * for `probably_contains`. It is rebuilt from the file on open.
+         */bloomFilterElements: UInt64? = nil, 
+        /**
+         * Records the header and element directory in a `<path>.sync` trailer on every
+         * `sync_all`, and compares the file with it on open (see `last_sync_check`).
+         */syncTrailer: Bool = false, 
+        /**
+         * Walks every element frame and reads every payload before opening returns, failing with
+         * `CorruptedFile` and a description of the first broken frame. Costs a full read of the file.
+         */verifyOnOpen: Bool = false, 
+        /**
+         * Moves elements whose frames are broken or whose payloads cannot be read into a
+         * `<path>.quarantine` queue file and opens with the rest (see `last_quarantine`).
+         */quarantineCorrupt: Bool = false, 
+        /**
+         * Keeps the most recent this many changes in memory for `changes_since`. Off when unset.
+         */journalCapacity: UInt32? = nil, 
+        /**
+         * Keeps the position of every element in memory for `element_at`, saved to `<path>.idx`
+         * by `sync_all` and on close. A saved index that still matches the file's header restores
+         * `generation()` and the sequence ids on open instead of a frame walk.
+         */elementIndex: Bool = false, 
+        /**
+         * Returns from opening without the frame walks that fill the bloom filter and build the
+         * element index. They run on the first call that needs them, or on `finish_open`.
+         */lazyOpen: Bool = false, 
+        /**
+         * Holds up to this many removals in memory, hidden from `peek` and `size`, before
+         * committing them with one header write. Uncommitted removals count against quotas and
+         * watermarks, and are lost on a crash. Off when unset.
+         */deferredRemoves: UInt32? = nil, 
+        /**
+         * Free space on the volume below which `health_check` reports `Degraded`.
+         */freeSpaceReserve: UInt64? = nil, 
+        /**
+         * How long written bytes may go without a sync before `health_check` reports `Degraded`.
+         */maxUnsyncedAge: TimeInterval? = nil, 
+        /**
+         * What to do when the file is corrupt on open; `None` behaves as `Fail`, unless
+         * `quarantine_corrupt` is set.
+         */onCorruption: CorruptionPolicy? = nil, 
+        /**
+         * The longest element a single peek or get may copy into memory. Longer ones fail with
+         * `TooLargeForRead` before anything is read; `peek_to_file` and `pop_to_file` still read
+         * them, a chunk at a time.
+         */maxReadBytes: UInt64? = nil, 
+        /**
+         * How long `add`, `add_multiple`, `peek`, `remove`, `remove_n`, `clear` and `sync_all`
+         * may take before failing with `Timeout`. When set, they run on a runtime worker while the
+         * caller waits; the `_async` calls are not limited. Unlimited when unset.
+         */operationTimeout: TimeInterval? = nil, 
+        /**
+         * Id of the key, asked of the `KeyProvider` set with `set_key_provider`, that signs the
+         * header and contents in `<path>.sig` on every sync and close. Opening a file that does
+         * not match its signature fails with `TamperDetected`. Unsigned when unset.
+         */signatureKey: String? = nil, 
+        /**
+         * Header layout of a file this open creates. An existing file, including one restored
+         * from the mirror, keeps its own, and rewrites keep it too. `None` is `Versioned`.
+         */format: FileFormat? = nil, 
+        /**
+         * Bytes queue-file reads ahead for sequential peeks, removals and iteration, as
+         * `set_read_buffer_size` sets later. `None` is 4 KiB.
+         */readBufferSize: UInt32? = nil, 
+        /**
+         * Bytes copied at a time by `peek_to_file` and `pop_to_file`, and payload bytes written to
+         * the new file per batch when `remove_at`, `update_at`, `rewrite` and `quarantine_corrupt`
+         * copy the queue. `None` is 64 KiB for the former and 1 MiB for the latter.
+         */copyBufferSize: UInt32? = nil, 
+        /**
+         * How long the queue may go unused before its file, and the mirror, header sidecar and
+         * element index, are synced and closed. The next call reopens them first. Open until
+         * closed when unset.
+         */idleCloseAfter: TimeInterval? = nil) {
+        self.mode = mode
+        self.capacity = capacity
+        self.minRetainedLen = minRetainedLen
+        self.growth = growth
+        self.maxGrowthStep = maxGrowthStep
+        self.mirrorPath = mirrorPath
+        self.maxElements = maxElements
+        self.maxBytes = maxBytes
+        self.rateLimit = rateLimit
+        self.retryPolicy = retryPolicy
+        self.waitForUnlock = waitForUnlock
+        self.redundantHeader = redundantHeader
+        self.syncDirectory = syncDirectory
+        self.excludeFromBackup = excludeFromBackup
+        self.alreadyOpen = alreadyOpen
+        self.lockFile = lockFile
+        self.bloomFilterElements = bloomFilterElements
+        self.syncTrailer = syncTrailer
+        self.verifyOnOpen = verifyOnOpen
+        self.quarantineCorrupt = quarantineCorrupt
+        self.journalCapacity = journalCapacity
+        self.elementIndex = elementIndex
+        self.lazyOpen = lazyOpen
+        self.deferredRemoves = deferredRemoves
+        self.freeSpaceReserve = freeSpaceReserve
+        self.maxUnsyncedAge = maxUnsyncedAge
+        self.onCorruption = onCorruption
+        self.maxReadBytes = maxReadBytes
+        self.operationTimeout = operationTimeout
+        self.signatureKey = signatureKey
+        self.format = format
+        self.readBufferSize = readBufferSize
+        self.copyBufferSize = copyBufferSize
+        self.idleCloseAfter = idleCloseAfter
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension QueueFileOptions: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQueueFileOptions: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueFileOptions {
+        return
+            try QueueFileOptions(
+                mode: FfiConverterOptionTypeOpenMode.read(from: &buf), 
+                capacity: FfiConverterUInt64.read(from: &buf), 
+                minRetainedLen: FfiConverterOptionUInt64.read(from: &buf), 
+                growth: FfiConverterOptionTypeGrowthStrategy.read(from: &buf), 
+                maxGrowthStep: FfiConverterOptionUInt64.read(from: &buf), 
+                mirrorPath: FfiConverterOptionString.read(from: &buf), 
+                maxElements: FfiConverterOptionUInt64.read(from: &buf), 
+                maxBytes: FfiConverterOptionUInt64.read(from: &buf), 
+                rateLimit: FfiConverterOptionTypeRateLimit.read(from: &buf), 
+                retryPolicy: FfiConverterOptionTypeRetryPolicy.read(from: &buf), 
+                waitForUnlock: FfiConverterOptionDuration.read(from: &buf), 
+                redundantHeader: FfiConverterBool.read(from: &buf), 
+                syncDirectory: FfiConverterBool.read(from: &buf), 
+                excludeFromBackup: FfiConverterBool.read(from: &buf), 
+                alreadyOpen: FfiConverterOptionTypeAlreadyOpenPolicy.read(from: &buf), 
+                lockFile: FfiConverterBool.read(from: &buf), 
+                bloomFilterElements: FfiConverterOptionUInt64.read(from: &buf), 
+                syncTrailer: FfiConverterBool.read(from: &buf), 
+                verifyOnOpen: FfiConverterBool.read(from: &buf), 
+                quarantineCorrupt: FfiConverterBool.read(from: &buf), 
+                journalCapacity: FfiConverterOptionUInt32.read(from: &buf), 
+                elementIndex: FfiConverterBool.read(from: &buf), 
+                lazyOpen: FfiConverterBool.read(from: &buf), 
+                deferredRemoves: FfiConverterOptionUInt32.read(from: &buf), 
+                freeSpaceReserve: FfiConverterOptionUInt64.read(from: &buf), 
+                maxUnsyncedAge: FfiConverterOptionDuration.read(from: &buf), 
+                onCorruption: FfiConverterOptionTypeCorruptionPolicy.read(from: &buf), 
+                maxReadBytes: FfiConverterOptionUInt64.read(from: &buf), 
+                operationTimeout: FfiConverterOptionDuration.read(from: &buf), 
+                signatureKey: FfiConverterOptionString.read(from: &buf), 
+                format: FfiConverterOptionTypeFileFormat.read(from: &buf), 
+                readBufferSize: FfiConverterOptionUInt32.read(from: &buf), 
+                copyBufferSize: FfiConverterOptionUInt32.read(from: &buf), 
+                idleCloseAfter: FfiConverterOptionDuration.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: QueueFileOptions, into buf: inout [UInt8]) {
+        FfiConverterOptionTypeOpenMode.write(value.mode, into: &buf)
+        FfiConverterUInt64.write(value.capacity, into: &buf)
+        FfiConverterOptionUInt64.write(value.minRetainedLen, into: &buf)
+        FfiConverterOptionTypeGrowthStrategy.write(value.growth, into: &buf)
+        FfiConverterOptionUInt64.write(value.maxGrowthStep, into: &buf)
+        FfiConverterOptionString.write(value.mirrorPath, into: &buf)
+        FfiConverterOptionUInt64.write(value.maxElements, into: &buf)
+        FfiConverterOptionUInt64.write(value.maxBytes, into: &buf)
+        FfiConverterOptionTypeRateLimit.write(value.rateLimit, into: &buf)
+        FfiConverterOptionTypeRetryPolicy.write(value.retryPolicy, into: &buf)
+        FfiConverterOptionDuration.write(value.waitForUnlock, into: &buf)
+        FfiConverterBool.write(value.redundantHeader, into: &buf)
+        FfiConverterBool.write(value.syncDirectory, into: &buf)
+        FfiConverterBool.write(value.excludeFromBackup, into: &buf)
+        FfiConverterOptionTypeAlreadyOpenPolicy.write(value.alreadyOpen, into: &buf)
+        FfiConverterBool.write(value.lockFile, into: &buf)
+        FfiConverterOptionUInt64.write(value.bloomFilterElements, into: &buf)
+        FfiConverterBool.write(value.syncTrailer, into: &buf)
+        FfiConverterBool.write(value.verifyOnOpen, into: &buf)
+        FfiConverterBool.write(value.quarantineCorrupt, into: &buf)
+        FfiConverterOptionUInt32.write(value.journalCapacity, into: &buf)
+        FfiConverterBool.write(value.elementIndex, into: &buf)
+        FfiConverterBool.write(value.lazyOpen, into: &buf)
+        FfiConverterOptionUInt32.write(value.deferredRemoves, into: &buf)
+        FfiConverterOptionUInt64.write(value.freeSpaceReserve, into: &buf)
+        FfiConverterOptionDuration.write(value.maxUnsyncedAge, into: &buf)
+        FfiConverterOptionTypeCorruptionPolicy.write(value.onCorruption, into: &buf)
+        FfiConverterOptionUInt64.write(value.maxReadBytes, into: &buf)
+        FfiConverterOptionDuration.write(value.operationTimeout, into: &buf)
+        FfiConverterOptionString.write(value.signatureKey, into: &buf)
+        FfiConverterOptionTypeFileFormat.write(value.format, into: &buf)
+        FfiConverterOptionUInt32.write(value.readBufferSize, into: &buf)
+        FfiConverterOptionUInt32.write(value.copyBufferSize, into: &buf)
+        FfiConverterOptionDuration.write(value.idleCloseAfter, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueFileOptions_lift(_ buf: RustBuffer) throws -> QueueFileOptions {
+    return try FfiConverterTypeQueueFileOptions.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueFileOptions_lower(_ value: QueueFileOptions) -> RustBuffer {
+    return FfiConverterTypeQueueFileOptions.lower(value)
+}
+
+
+public struct QueueStats: Equatable, Hashable {
+    /**
+     * Operations re-attempted after a transient I/O error.
+     */
+    public var ioRetries: UInt64
+    /**
+     * Operations that still failed with a transient I/O error after the last attempt.
+     */
+    public var ioRetriesExhausted: UInt64
+    /**
+     * Operations resumed by `notify_unlocked` after failing with `DataProtectionLocked`.
+     */
+    public var unlockWaits: UInt64
+    /**
+     * Times a torn header was replaced from the redundant copy on open.
+     */
+    public var headersRestored: UInt64
+    /**
+     * Commits whose header could not be copied to the redundant header sidecar.
+     */
+    public var headerCopyFailures: UInt64
+    /**
+     * Payload bytes of the elements added.
+     */
+    public var payloadBytesWritten: UInt64
+    /**
+     * Bytes written to the queue file and its header sidecar for them and for removals:
+     * frame headers, header commits, the move of the wrapped part of the ring on growth and,
+     * with overwrite on remove, zero-fill. Derived from what each operation writes; a mirror
+     * writes about as much again.
+     */
+    public var bytesWritten: UInt64
+    /**
+     * `bytes_written / payload_bytes_written`, or 0 before anything was added.
+     */
+    public var writeAmplification: Double
+    /**
+     * The queue's `generation()` when the stats were taken.
+     */
+    public var generation: UInt64
+    /**
+     * Duration percentiles of each kind of operation that has run since the queue was opened.
+     */
+    public var latencies: [LatencySummary]
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Operations re-attempted after a transient I/O error.
+         */ioRetries: UInt64, 
+        /**
+         * Operations that still failed with a transient I/O error after the last attempt.
+         */ioRetriesExhausted: UInt64, 
+        /**
+         * Operations resumed by `notify_unlocked` after failing with `DataProtectionLocked`.
+         */unlockWaits: UInt64, 
+        /**
+         * Times a torn header was replaced from the redundant copy on open.
+         */headersRestored: UInt64, 
+        /**
+         * Commits whose header could not be copied to the redundant header sidecar.
+         */headerCopyFailures: UInt64, 
+        /**
+         * Payload bytes of the elements added.
+         */payloadBytesWritten: UInt64, 
+        /**
+         * Bytes written to the queue file and its header sidecar for them and for removals:
+         * frame headers, header commits, the move of the wrapped part of the ring on growth and,
+         * with overwrite on remove, zero-fill. Derived from what each operation writes; a mirror
+         * writes about as much again.
+         */bytesWritten: UInt64, 
+        /**
+         * `bytes_written / payload_bytes_written`, or 0 before anything was added.
+         */writeAmplification: Double, 
+        /**
+         * The queue's `generation()` when the stats were taken.
+         */generation: UInt64, 
+        /**
+         * Duration percentiles of each kind of operation that has run since the queue was opened.
+         */latencies: [LatencySummary]) {
+        self.ioRetries = ioRetries
+        self.ioRetriesExhausted = ioRetriesExhausted
+        self.unlockWaits = unlockWaits
+        self.headersRestored = headersRestored
+        self.headerCopyFailures = headerCopyFailures
+        self.payloadBytesWritten = payloadBytesWritten
+        self.bytesWritten = bytesWritten
+        self.writeAmplification = writeAmplification
+        self.generation = generation
+        self.latencies = latencies
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension QueueStats: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQueueStats: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueStats {
+        return
+            try QueueStats(
+                ioRetries: FfiConverterUInt64.read(from: &buf), 
+                ioRetriesExhausted: FfiConverterUInt64.read(from: &buf), 
+                unlockWaits: FfiConverterUInt64.read(from: &buf), 
+                headersRestored: FfiConverterUInt64.read(from: &buf), 
+                headerCopyFailures: FfiConverterUInt64.read(from: &buf), 
+                payloadBytesWritten: FfiConverterUInt64.read(from: &buf), 
+                bytesWritten: FfiConverterUInt64.read(from: &buf), 
+                writeAmplification: FfiConverterDouble.read(from: &buf), 
+                generation: FfiConverterUInt64.read(from: &buf), 
+                latencies: FfiConverterSequenceTypeLatencySummary.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: QueueStats, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.ioRetries, into: &buf)
+        FfiConverterUInt64.write(value.ioRetriesExhausted, into: &buf)
+        FfiConverterUInt64.write(value.unlockWaits, into: &buf)
+        FfiConverterUInt64.write(value.headersRestored, into: &buf)
+        FfiConverterUInt64.write(value.headerCopyFailures, into: &buf)
+        FfiConverterUInt64.write(value.payloadBytesWritten, into: &buf)
+        FfiConverterUInt64.write(value.bytesWritten, into: &buf)
+        FfiConverterDouble.write(value.writeAmplification, into: &buf)
+        FfiConverterUInt64.write(value.generation, into: &buf)
+        FfiConverterSequenceTypeLatencySummary.write(value.latencies, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueStats_lift(_ buf: RustBuffer) throws -> QueueStats {
+    return try FfiConverterTypeQueueStats.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueStats_lower(_ value: QueueStats) -> RustBuffer {
+    return FfiConverterTypeQueueStats.lower(value)
+}
+
+
+/**
+ * Sustained add rates above which adds fail with `Backpressure`. Each limit allows bursts of
+ * up to one second's worth.
+ */
+public struct RateLimit: Equatable, Hashable {
+    public var maxAddsPerSec: UInt32?
+    /**
+     * Payload bytes, not counting frame headers.
+     */
+    public var maxBytesPerSec: UInt64?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(maxAddsPerSec: UInt32? = nil, 
+        /**
+         * Payload bytes, not counting frame headers.
+         */maxBytesPerSec: UInt64? = nil) {
+        self.maxAddsPerSec = maxAddsPerSec
+        self.maxBytesPerSec = maxBytesPerSec
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension RateLimit: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeRateLimit: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> RateLimit {
+        return
+            try RateLimit(
+                maxAddsPerSec: FfiConverterOptionUInt32.read(from: &buf), 
+                maxBytesPerSec: FfiConverterOptionUInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: RateLimit, into buf: inout [UInt8]) {
+        FfiConverterOptionUInt32.write(value.maxAddsPerSec, into: &buf)
+        FfiConverterOptionUInt64.write(value.maxBytesPerSec, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRateLimit_lift(_ buf: RustBuffer) throws -> RateLimit {
+    return try FfiConverterTypeRateLimit.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRateLimit_lower(_ value: RateLimit) -> RustBuffer {
+    return FfiConverterTypeRateLimit.lower(value)
+}
+
+
+/**
+ * Retries for I/O errors that are expected to clear on their own (`EINTR`, `EAGAIN`, `EBUSY`,
+ * timeouts), as seen with iOS data protection and network-backed volumes.
+ */
+public struct RetryPolicy: Equatable, Hashable {
+    /**
+     * Total attempts per operation, including the first one.
+     */
+    public var maxAttempts: UInt32
+    /**
+     * Delay before the first retry; doubled for each further retry. `None` is 10 ms.
+     */
+    public var initialBackoff: TimeInterval?
+    /**
+     * `None` is 1 s.
+     */
+    public var maxBackoff: TimeInterval?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Total attempts per operation, including the first one.
+         */maxAttempts: UInt32 = UInt32(3), 
+        /**
+         * Delay before the first retry; doubled for each further retry. `None` is 10 ms.
+         */initialBackoff: TimeInterval? = nil, 
+        /**
+         * `None` is 1 s.
+         */maxBackoff: TimeInterval? = nil) {
+        self.maxAttempts = maxAttempts
+        self.initialBackoff = initialBackoff
+        self.maxBackoff = maxBackoff
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension RetryPolicy: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeRetryPolicy: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> RetryPolicy {
+        return
+            try RetryPolicy(
+                maxAttempts: FfiConverterUInt32.read(from: &buf), 
+                initialBackoff: FfiConverterOptionDuration.read(from: &buf), 
+                maxBackoff: FfiConverterOptionDuration.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: RetryPolicy, into buf: inout [UInt8]) {
+        FfiConverterUInt32.write(value.maxAttempts, into: &buf)
+        FfiConverterOptionDuration.write(value.initialBackoff, into: &buf)
+        FfiConverterOptionDuration.write(value.maxBackoff, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRetryPolicy_lift(_ buf: RustBuffer) throws -> RetryPolicy {
+    return try FfiConverterTypeRetryPolicy.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRetryPolicy_lower(_ value: RetryPolicy) -> RustBuffer {
+    return FfiConverterTypeRetryPolicy.lower(value)
+}
+
+
+/**
+ * How `rewrite` stores the elements it copies.
+ */
+public struct RewriteOptions: Equatable, Hashable {
+    /**
+     * Compresses every element against the queue's dictionary, training one first if there is
+     * none. `false` stores every element as it was added and stops compressing adds.
+     */
+    public var compress: Bool
+    /**
+     * Trains a new dictionary on the current elements even if the queue has one.
+     */
+    public var retrain: Bool
+    /**
+     * Elements a dictionary is trained on. `None` is 1000.
+     */
+    public var sampleLimit: UInt32?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Compresses every element against the queue's dictionary, training one first if there is
+         * none. `false` stores every element as it was added and stops compressing adds.
+         */compress: Bool = true, 
+        /**
+         * Trains a new dictionary on the current elements even if the queue has one.
+         */retrain: Bool = false, 
+        /**
+         * Elements a dictionary is trained on. `None` is 1000.
+         */sampleLimit: UInt32? = nil) {
+        self.compress = compress
+        self.retrain = retrain
+        self.sampleLimit = sampleLimit
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension RewriteOptions: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeRewriteOptions: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> RewriteOptions {
+        return
+            try RewriteOptions(
+                compress: FfiConverterBool.read(from: &buf), 
+                retrain: FfiConverterBool.read(from: &buf), 
+                sampleLimit: FfiConverterOptionUInt32.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: RewriteOptions, into buf: inout [UInt8]) {
+        FfiConverterBool.write(value.compress, into: &buf)
+        FfiConverterBool.write(value.retrain, into: &buf)
+        FfiConverterOptionUInt32.write(value.sampleLimit, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRewriteOptions_lift(_ buf: RustBuffer) throws -> RewriteOptions {
+    return try FfiConverterTypeRewriteOptions.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRewriteOptions_lower(_ value: RewriteOptions) -> RustBuffer {
+    return FfiConverterTypeRewriteOptions.lower(value)
+}
+
+
+/**
+ * What `rewrite` did.
+ */
+public struct RewriteReport: Equatable, Hashable {
+    public var elements: UInt64
+    /**
+     * `used_bytes()` before and after.
+     */
+    public var bytesBefore: UInt64
+    public var bytesAfter: UInt64
+    /**
+     * The dictionary trained for the rewrite, if one was.
+     */
+    public var trained: TrainedDictionary?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(elements: UInt64, 
+        /**
+         * `used_bytes()` before and after.
+         */bytesBefore: UInt64, bytesAfter: UInt64, 
+        /**
+         * The dictionary trained for the rewrite, if one was.
+         */trained: TrainedDictionary?) {
+        self.elements = elements
+        self.bytesBefore = bytesBefore
+        self.bytesAfter = bytesAfter
+        self.trained = trained
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension RewriteReport: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeRewriteReport: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> RewriteReport {
+        return
+            try RewriteReport(
+                elements: FfiConverterUInt64.read(from: &buf), 
+                bytesBefore: FfiConverterUInt64.read(from: &buf), 
+                bytesAfter: FfiConverterUInt64.read(from: &buf), 
+                trained: FfiConverterOptionTypeTrainedDictionary.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: RewriteReport, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.elements, into: &buf)
+        FfiConverterUInt64.write(value.bytesBefore, into: &buf)
+        FfiConverterUInt64.write(value.bytesAfter, into: &buf)
+        FfiConverterOptionTypeTrainedDictionary.write(value.trained, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRewriteReport_lift(_ buf: RustBuffer) throws -> RewriteReport {
+    return try FfiConverterTypeRewriteReport.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRewriteReport_lower(_ value: RewriteReport) -> RustBuffer {
+    return FfiConverterTypeRewriteReport.lower(value)
+}
+
+
+public struct RuntimeConfig: Equatable, Hashable {
+    /**
+     * Worker threads running the `_async` methods; at least one is started.
+     */
+    public var threads: UInt32
+    /**
+     * Workers are named `<prefix>-<n>`.
+     */
+    public var threadNamePrefix: String
+    /**
+     * Quality-of-service class of the workers on Apple platforms; `None` behaves as `Utility`.
+     * Ignored elsewhere.
+     */
+    public var qos: ThreadQos?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Worker threads running the `_async` methods; at least one is started.
+         */threads: UInt32 = UInt32(2), 
+        /**
+         * Workers are named `<prefix>-<n>`.
+         */threadNamePrefix: String = "queuefile", 
+        /**
+         * Quality-of-service class of the workers on Apple platforms; `None` behaves as `Utility`.
+         * Ignored elsewhere.
+         */qos: ThreadQos? = nil) {
+        self.threads = threads
+        self.threadNamePrefix = threadNamePrefix
+        self.qos = qos
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension RuntimeConfig: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeRuntimeConfig: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> RuntimeConfig {
+        return
+            try RuntimeConfig(
+                threads: FfiConverterUInt32.read(from: &buf), 
+                threadNamePrefix: FfiConverterString.read(from: &buf), 
+                qos: FfiConverterOptionTypeThreadQos.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: RuntimeConfig, into buf: inout [UInt8]) {
+        FfiConverterUInt32.write(value.threads, into: &buf)
+        FfiConverterString.write(value.threadNamePrefix, into: &buf)
+        FfiConverterOptionTypeThreadQos.write(value.qos, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRuntimeConfig_lift(_ buf: RustBuffer) throws -> RuntimeConfig {
+    return try FfiConverterTypeRuntimeConfig.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRuntimeConfig_lower(_ value: RuntimeConfig) -> RustBuffer {
+    return FfiConverterTypeRuntimeConfig.lower(value)
+}
+
+
+/**
+ * One segment file. Without per-element timestamps, the time range a segment covers is
+ * bounded by when its file was created and last written.
+ */
+public struct SegmentInfo: Equatable, Hashable {
+    public var id: UInt64
+    /**
+     * Elements not yet removed.
+     */
+    public var elements: UInt64
+    /**
+     * Bytes of those elements, with their 4-byte frames.
+     */
+    public var bytes: UInt64
+    /**
+     * `None` where the file system does not record it.
+     */
+    public var created: Date?
+    /**
+     * When the last add to the segment was made.
+     */
+    public var modified: Date
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(id: UInt64, 
+        /**
+         * Elements not yet removed.
+         */elements: UInt64, 
+        /**
+         * Bytes of those elements, with their 4-byte frames.
+         */bytes: UInt64, 
+        /**
+         * `None` where the file system does not record it.
+         */created: Date?, 
+        /**
+         * When the last add to the segment was made.
+         */modified: Date) {
+        self.id = id
+        self.elements = elements
+        self.bytes = bytes
+        self.created = created
+        self.modified = modified
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension SegmentInfo: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSegmentInfo: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SegmentInfo {
+        return
+            try SegmentInfo(
+                id: FfiConverterUInt64.read(from: &buf), 
+                elements: FfiConverterUInt64.read(from: &buf), 
+                bytes: FfiConverterUInt64.read(from: &buf), 
+                created: FfiConverterOptionTimestamp.read(from: &buf), 
+                modified: FfiConverterTimestamp.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: SegmentInfo, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.id, into: &buf)
+        FfiConverterUInt64.write(value.elements, into: &buf)
+        FfiConverterUInt64.write(value.bytes, into: &buf)
+        FfiConverterOptionTimestamp.write(value.created, into: &buf)
+        FfiConverterTimestamp.write(value.modified, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSegmentInfo_lift(_ buf: RustBuffer) throws -> SegmentInfo {
+    return try FfiConverterTypeSegmentInfo.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSegmentInfo_lower(_ value: SegmentInfo) -> RustBuffer {
+    return FfiConverterTypeSegmentInfo.lower(value)
+}
+
+
+public struct SegmentedQueueConfig: Equatable, Hashable {
+    /**
+     * Bytes of elements, with their 4-byte frames, after which adds go to a new segment. One
+     * add is never split, so a segment holding a single large add can be bigger.
+     */
+    public var segmentBytes: UInt64
+    /**
+     * Oldest segments are dropped while all segments together hold more than this many bytes.
+     */
+    public var maxTotalBytes: UInt64?
+    /**
+     * Segments last written longer ago than this are dropped.
+     */
+    public var maxSegmentAge: TimeInterval?
+    /**
+     * Oldest segments are dropped while there are more than this many.
+     */
+    public var maxSegments: UInt32?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Bytes of elements, with their 4-byte frames, after which adds go to a new segment. One
+         * add is never split, so a segment holding a single large add can be bigger.
+         */segmentBytes: UInt64 = UInt64(1048576), 
+        /**
+         * Oldest segments are dropped while all segments together hold more than this many bytes.
+         */maxTotalBytes: UInt64? = nil, 
+        /**
+         * Segments last written longer ago than this are dropped.
+         */maxSegmentAge: TimeInterval? = nil, 
+        /**
+         * Oldest segments are dropped while there are more than this many.
+         */maxSegments: UInt32? = nil) {
+        self.segmentBytes = segmentBytes
+        self.maxTotalBytes = maxTotalBytes
+        self.maxSegmentAge = maxSegmentAge
+        self.maxSegments = maxSegments
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension SegmentedQueueConfig: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSegmentedQueueConfig: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SegmentedQueueConfig {
+        return
+            try SegmentedQueueConfig(
+                segmentBytes: FfiConverterUInt64.read(from: &buf), 
+                maxTotalBytes: FfiConverterOptionUInt64.read(from: &buf), 
+                maxSegmentAge: FfiConverterOptionDuration.read(from: &buf), 
+                maxSegments: FfiConverterOptionUInt32.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: SegmentedQueueConfig, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.segmentBytes, into: &buf)
+        FfiConverterOptionUInt64.write(value.maxTotalBytes, into: &buf)
+        FfiConverterOptionDuration.write(value.maxSegmentAge, into: &buf)
+        FfiConverterOptionUInt32.write(value.maxSegments, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSegmentedQueueConfig_lift(_ buf: RustBuffer) throws -> SegmentedQueueConfig {
+    return try FfiConverterTypeSegmentedQueueConfig.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSegmentedQueueConfig_lower(_ value: SegmentedQueueConfig) -> RustBuffer {
+    return FfiConverterTypeSegmentedQueueConfig.lower(value)
+}
+
+
+public struct SpaceReport: Equatable, Hashable {
+    /**
+     * Logical length of the queue file, as returned by `file_len`.
+     */
+    public var fileLen: UInt64
+    /**
+     * Bytes holding the header and live elements, as returned by `used_bytes`.
+     */
+    public var usedBytes: UInt64
+    /**
+     * Bytes actually allocated on disk. Smaller than `file_len` for sparse regions and for
+     * blocks shared with an APFS clone.
+     */
+    public var diskUsage: UInt64
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Logical length of the queue file, as returned by `file_len`.
+         */fileLen: UInt64, 
+        /**
+         * Bytes holding the header and live elements, as returned by `used_bytes`.
+         */usedBytes: UInt64, 
+        /**
+         * Bytes actually allocated on disk. Smaller than `file_len` for sparse regions and for
+         * blocks shared with an APFS clone.
+         */diskUsage: UInt64) {
+        self.fileLen = fileLen
+        self.usedBytes = usedBytes
+        self.diskUsage = diskUsage
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension SpaceReport: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSpaceReport: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SpaceReport {
+        return
+            try SpaceReport(
+                fileLen: FfiConverterUInt64.read(from: &buf), 
+                usedBytes: FfiConverterUInt64.read(from: &buf), 
+                diskUsage: FfiConverterUInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: SpaceReport, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.fileLen, into: &buf)
+        FfiConverterUInt64.write(value.usedBytes, into: &buf)
+        FfiConverterUInt64.write(value.diskUsage, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSpaceReport_lift(_ buf: RustBuffer) throws -> SpaceReport {
+    return try FfiConverterTypeSpaceReport.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSpaceReport_lower(_ value: SpaceReport) -> RustBuffer {
+    return FfiConverterTypeSpaceReport.lower(value)
+}
+
+
+/**
+ * What the queue counted between the last `reset_stats` (or its open) and the moment these
+ * were taken.
+ */
+public struct StatsWindow: Equatable, Hashable {
+    public var startedAt: Date
+    public var duration: TimeInterval
+    /**
+     * The counters and latencies of the window alone. `generation` is the queue's at its end.
+     */
+    public var stats: QueueStats
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(startedAt: Date, duration: TimeInterval, 
+        /**
+         * The counters and latencies of the window alone. `generation` is the queue's at its end.
+         */stats: QueueStats) {
+        self.startedAt = startedAt
+        self.duration = duration
+        self.stats = stats
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension StatsWindow: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeStatsWindow: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> StatsWindow {
+        return
+            try StatsWindow(
+                startedAt: FfiConverterTimestamp.read(from: &buf), 
+                duration: FfiConverterDuration.read(from: &buf), 
+                stats: FfiConverterTypeQueueStats.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: StatsWindow, into buf: inout [UInt8]) {
+        FfiConverterTimestamp.write(value.startedAt, into: &buf)
+        FfiConverterDuration.write(value.duration, into: &buf)
+        FfiConverterTypeQueueStats.write(value.stats, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeStatsWindow_lift(_ buf: RustBuffer) throws -> StatsWindow {
+    return try FfiConverterTypeStatsWindow.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeStatsWindow_lower(_ value: StatsWindow) -> RustBuffer {
+    return FfiConverterTypeStatsWindow.lower(value)
+}
+
+
+/**
+ * What opening found, compared with the `.sync` trailer written by the last `sync_all`.
+ */
+public struct SyncCheck: Equatable, Hashable {
+    public var status: SyncStatus
+    /**
+     * Number of elements in the queue at the last sync.
+     */
+    public var syncedElements: UInt64
+    /**
+     * When the last sync was made, to the millisecond.
+     */
+    public var syncedAt: Date
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(status: SyncStatus, 
+        /**
+         * Number of elements in the queue at the last sync.
+         */syncedElements: UInt64, 
+        /**
+         * When the last sync was made, to the millisecond.
+         */syncedAt: Date) {
+        self.status = status
+        self.syncedElements = syncedElements
+        self.syncedAt = syncedAt
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension SyncCheck: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSyncCheck: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SyncCheck {
+        return
+            try SyncCheck(
+                status: FfiConverterTypeSyncStatus.read(from: &buf), 
+                syncedElements: FfiConverterUInt64.read(from: &buf), 
+                syncedAt: FfiConverterTimestamp.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: SyncCheck, into buf: inout [UInt8]) {
+        FfiConverterTypeSyncStatus.write(value.status, into: &buf)
+        FfiConverterUInt64.write(value.syncedElements, into: &buf)
+        FfiConverterTimestamp.write(value.syncedAt, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSyncCheck_lift(_ buf: RustBuffer) throws -> SyncCheck {
+    return try FfiConverterTypeSyncCheck.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSyncCheck_lower(_ value: SyncCheck) -> RustBuffer {
+    return FfiConverterTypeSyncCheck.lower(value)
+}
+
+
+public struct SyncReport: Equatable, Hashable {
+    /**
+     * Elements removed from the front of the target.
+     */
+    public var removed: UInt64
+    /**
+     * Elements copied from the source to the back of the target.
+     */
+    public var added: UInt64
+    /**
+     * Elements of the target left in place because the source holds them too.
+     */
+    public var kept: UInt64
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * Elements removed from the front of the target.
+         */removed: UInt64, 
+        /**
+         * Elements copied from the source to the back of the target.
+         */added: UInt64, 
+        /**
+         * Elements of the target left in place because the source holds them too.
+         */kept: UInt64) {
+        self.removed = removed
+        self.added = added
+        self.kept = kept
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension SyncReport: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSyncReport: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SyncReport {
+        return
+            try SyncReport(
+                removed: FfiConverterUInt64.read(from: &buf), 
+                added: FfiConverterUInt64.read(from: &buf), 
+                kept: FfiConverterUInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: SyncReport, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.removed, into: &buf)
+        FfiConverterUInt64.write(value.added, into: &buf)
+        FfiConverterUInt64.write(value.kept, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSyncReport_lift(_ buf: RustBuffer) throws -> SyncReport {
+    return try FfiConverterTypeSyncReport.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSyncReport_lower(_ value: SyncReport) -> RustBuffer {
+    return FfiConverterTypeSyncReport.lower(value)
+}
+
+
+/**
+ * What `train_dictionary` produced.
+ */
+public struct TrainedDictionary: Equatable, Hashable {
+    /**
+     * The id zstd gave the dictionary, recorded in every frame compressed against it.
+     */
+    public var id: UInt32
+    /**
+     * Length of the dictionary in bytes.
+     */
+    public var len: UInt64
+    /**
+     * Elements it was trained on.
+     */
+    public var samples: UInt32
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * The id zstd gave the dictionary, recorded in every frame compressed against it.
+         */id: UInt32, 
+        /**
+         * Length of the dictionary in bytes.
+         */len: UInt64, 
+        /**
+         * Elements it was trained on.
+         */samples: UInt32) {
+        self.id = id
+        self.len = len
+        self.samples = samples
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension TrainedDictionary: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeTrainedDictionary: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> TrainedDictionary {
+        return
+            try TrainedDictionary(
+                id: FfiConverterUInt32.read(from: &buf), 
+                len: FfiConverterUInt64.read(from: &buf), 
+                samples: FfiConverterUInt32.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: TrainedDictionary, into buf: inout [UInt8]) {
+        FfiConverterUInt32.write(value.id, into: &buf)
+        FfiConverterUInt64.write(value.len, into: &buf)
+        FfiConverterUInt32.write(value.samples, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeTrainedDictionary_lift(_ buf: RustBuffer) throws -> TrainedDictionary {
+    return try FfiConverterTypeTrainedDictionary.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeTrainedDictionary_lower(_ value: TrainedDictionary) -> RustBuffer {
+    return FfiConverterTypeTrainedDictionary.lower(value)
+}
+
+
+public struct WatermarkConfig: Equatable, Hashable {
+    public var metric: WatermarkMetric
+    /**
+     * `on_high_watermark` fires once the metric rises above this value.
+     */
+    public var high: UInt64
+    /**
+     * `on_low_watermark` fires once the metric, having crossed `high`, drops below this value.
+     */
+    public var low: UInt64
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(metric: WatermarkMetric, 
+        /**
+         * `on_high_watermark` fires once the metric rises above this value.
+         */high: UInt64, 
+        /**
+         * `on_low_watermark` fires once the metric, having crossed `high`, drops below this value.
+         */low: UInt64) {
+        self.metric = metric
+        self.high = high
+        self.low = low
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension WatermarkConfig: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeWatermarkConfig: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> WatermarkConfig {
+        return
+            try WatermarkConfig(
+                metric: FfiConverterTypeWatermarkMetric.read(from: &buf), 
+                high: FfiConverterUInt64.read(from: &buf), 
+                low: FfiConverterUInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: WatermarkConfig, into buf: inout [UInt8]) {
+        FfiConverterTypeWatermarkMetric.write(value.metric, into: &buf)
+        FfiConverterUInt64.write(value.high, into: &buf)
+        FfiConverterUInt64.write(value.low, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeWatermarkConfig_lift(_ buf: RustBuffer) throws -> WatermarkConfig {
+    return try FfiConverterTypeWatermarkConfig.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeWatermarkConfig_lower(_ value: WatermarkConfig) -> RustBuffer {
+    return FfiConverterTypeWatermarkConfig.lower(value)
+}
+
+
+public struct WriteBufferConfig: Equatable, Hashable {
+    public var maxBytes: UInt64
+    public var maxDelay: TimeInterval
+    /**
+     * Flushes once this many adds are buffered, whatever their size.
+     */
+    public var maxItems: UInt32?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(maxBytes: UInt64, maxDelay: TimeInterval, 
+        /**
+         * Flushes once this many adds are buffered, whatever their size.
+         */maxItems: UInt32? = nil) {
+        self.maxBytes = maxBytes
+        self.maxDelay = maxDelay
+        self.maxItems = maxItems
+    }
+
+    
+}
+
+#if compiler(>=6)
+extension WriteBufferConfig: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeWriteBufferConfig: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> WriteBufferConfig {
+        return
+            try WriteBufferConfig(
+                maxBytes: FfiConverterUInt64.read(from: &buf), 
+                maxDelay: FfiConverterDuration.read(from: &buf), 
+                maxItems: FfiConverterOptionUInt32.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: WriteBufferConfig, into buf: inout [UInt8]) {
+        FfiConverterUInt64.write(value.maxBytes, into: &buf)
+        FfiConverterDuration.write(value.maxDelay, into: &buf)
+        FfiConverterOptionUInt32.write(value.maxItems, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeWriteBufferConfig_lift(_ buf: RustBuffer) throws -> WriteBufferConfig {
+    return try FfiConverterTypeWriteBufferConfig.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeWriteBufferConfig_lower(_ value: WriteBufferConfig) -> RustBuffer {
+    return FfiConverterTypeWriteBufferConfig.lower(value)
+}
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum AlreadyOpenPolicy: Equatable, Hashable {
+    
+    /**
+     * Fails with `AlreadyOpen` if the file or its mirror is open through another handle.
+     */
+    case fail
+    /**
+     * Returns the existing handle for the same file; the options of the later open are
+     * ignored. Still fails if the path is open as another queue's mirror.
+     */
+    case share
+
+
+
+}
+
+#if compiler(>=6)
+extension AlreadyOpenPolicy: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeAlreadyOpenPolicy: FfiConverterRustBuffer {
+    typealias SwiftType = AlreadyOpenPolicy
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> AlreadyOpenPolicy {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .fail
+        
+        case 2: return .share
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: AlreadyOpenPolicy, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .fail:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .share:
+            writeInt(&buf, Int32(2))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeAlreadyOpenPolicy_lift(_ buf: RustBuffer) throws -> AlreadyOpenPolicy {
+    return try FfiConverterTypeAlreadyOpenPolicy.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeAlreadyOpenPolicy_lower(_ value: AlreadyOpenPolicy) -> RustBuffer {
+    return FfiConverterTypeAlreadyOpenPolicy.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+/**
+ * What `capacity_report` suggests doing about the queue's file, the most pressing first.
+ */
+
+public enum CapacityRecommendation: Equatable, Hashable {
+    
+    /**
+     * Nothing; the file fits its contents.
+     */
+    case none
+    /**
+     * Most of the file is free space a `rewrite` would give back.
+     */
+    case compactNow
+    /**
+     * The file keeps growing from the configured capacity; open with `capacity` instead.
+     */
+    case raiseCapacity(capacity: UInt64
+    )
+    /**
+     * The queue holds enough uncompressed data for `train_dictionary` to shrink it.
+     */
+    case enableCompression
+
+
+
+}
+
+#if compiler(>=6)
+extension CapacityRecommendation: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeCapacityRecommendation: FfiConverterRustBuffer {
+    typealias SwiftType = CapacityRecommendation
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> CapacityRecommendation {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .none
+        
+        case 2: return .compactNow
+        
+        case 3: return .raiseCapacity(capacity: try FfiConverterUInt64.read(from: &buf)
+        )
+        
+        case 4: return .enableCompression
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: CapacityRecommendation, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .none:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .compactNow:
+            writeInt(&buf, Int32(2))
+        
+        
+        case let .raiseCapacity(capacity):
+            writeInt(&buf, Int32(3))
+            FfiConverterUInt64.write(capacity, into: &buf)
+            
+        
+        case .enableCompression:
+            writeInt(&buf, Int32(4))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCapacityRecommendation_lift(_ buf: RustBuffer) throws -> CapacityRecommendation {
+    return try FfiConverterTypeCapacityRecommendation.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCapacityRecommendation_lower(_ value: CapacityRecommendation) -> RustBuffer {
+    return FfiConverterTypeCapacityRecommendation.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+/**
+ * What a journaled operation did to the elements.
+ */
+
+public enum Change: Equatable, Hashable {
+    
+    /**
+     * Elements added at the back, whether written or held in the write buffer.
+     */
+    case add(count: UInt32, bytes: UInt64
+    )
+    /**
+     * Elements removed from the front.
+     */
+    case remove(count: UInt32
+    )
+    /**
+     * Every element removed; `count` includes buffered adds that were discarded.
+     */
+    case clear(count: UInt32
+    )
+    case removeAt(index: UInt32
+    )
+    case updateAt(index: UInt32
+    )
+    /**
+     * The elements were exchanged with another queue's by `swap_with`.
+     */
+    case swap
+    /**
+     * Every element was copied, unchanged, into a file that stores them as `rewrite` asked.
+     */
+    case rewrite
+
+
+
+}
+
+#if compiler(>=6)
+extension Change: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeChange: FfiConverterRustBuffer {
+    typealias SwiftType = Change
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Change {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .add(count: try FfiConverterUInt32.read(from: &buf), bytes: try FfiConverterUInt64.read(from: &buf)
+        )
+        
+        case 2: return .remove(count: try FfiConverterUInt32.read(from: &buf)
+        )
+        
+        case 3: return .clear(count: try FfiConverterUInt32.read(from: &buf)
+        )
+        
+        case 4: return .removeAt(index: try FfiConverterUInt32.read(from: &buf)
+        )
+        
+        case 5: return .updateAt(index: try FfiConverterUInt32.read(from: &buf)
+        )
+        
+        case 6: return .swap
+        
+        case 7: return .rewrite
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: Change, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case let .add(count,bytes):
+            writeInt(&buf, Int32(1))
+            FfiConverterUInt32.write(count, into: &buf)
+            FfiConverterUInt64.write(bytes, into: &buf)
+            
+        
+        case let .remove(count):
+            writeInt(&buf, Int32(2))
+            FfiConverterUInt32.write(count, into: &buf)
+            
+        
+        case let .clear(count):
+            writeInt(&buf, Int32(3))
+            FfiConverterUInt32.write(count, into: &buf)
+            
+        
+        case let .removeAt(index):
+            writeInt(&buf, Int32(4))
+            FfiConverterUInt32.write(index, into: &buf)
+            
+        
+        case let .updateAt(index):
+            writeInt(&buf, Int32(5))
+            FfiConverterUInt32.write(index, into: &buf)
+            
+        
+        case .swap:
+            writeInt(&buf, Int32(6))
+        
+        
+        case .rewrite:
+            writeInt(&buf, Int32(7))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeChange_lift(_ buf: RustBuffer) throws -> Change {
+    return try FfiConverterTypeChange.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeChange_lower(_ value: Change) -> RustBuffer {
+    return FfiConverterTypeChange.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum CorruptionPolicy: Equatable, Hashable {
+    
+    /**
+     * Fails the open with `CorruptedFile`.
+     */
+    case fail
+    /**
+     * Opens with the elements that can still be read, as `quarantine_corrupt` does. A header
+     * that describes no ring still fails the open.
+     */
+    case repairInPlace
+    /**
+     * Renames the file and its sidecars to `<name>.corrupt-<timestamp>` and opens a new,
+     * empty queue (see `last_corruption_recovery`).
+     */
+    case quarantineAndStartFresh
+
+
+
+}
+
+#if compiler(>=6)
+extension CorruptionPolicy: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeCorruptionPolicy: FfiConverterRustBuffer {
+    typealias SwiftType = CorruptionPolicy
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> CorruptionPolicy {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .fail
+        
+        case 2: return .repairInPlace
+        
+        case 3: return .quarantineAndStartFresh
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: CorruptionPolicy, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .fail:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .repairInPlace:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .quarantineAndStartFresh:
+            writeInt(&buf, Int32(3))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCorruptionPolicy_lift(_ buf: RustBuffer) throws -> CorruptionPolicy {
+    return try FfiConverterTypeCorruptionPolicy.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCorruptionPolicy_lower(_ value: CorruptionPolicy) -> RustBuffer {
+    return FfiConverterTypeCorruptionPolicy.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum CrashMode: Equatable, Hashable {
+    
+    /**
+     * The interrupted write stops at the crash offset; the rest of it never reaches the disk.
+     */
+    case truncated
+    /**
+     * The unwritten rest of the interrupted write is replaced with garbage.
+     */
+    case garbled
+
+
+
+}
+
+#if compiler(>=6)
+extension CrashMode: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeCrashMode: FfiConverterRustBuffer {
+    typealias SwiftType = CrashMode
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> CrashMode {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .truncated
+        
+        case 2: return .garbled
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: CrashMode, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .truncated:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .garbled:
+            writeInt(&buf, Int32(2))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCrashMode_lift(_ buf: RustBuffer) throws -> CrashMode {
+    return try FfiConverterTypeCrashMode.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeCrashMode_lower(_ value: CrashMode) -> RustBuffer {
+    return FfiConverterTypeCrashMode.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum ExportEncoding: Equatable, Hashable {
+    
+    /**
+     * Standard base64 with padding; exact for any payload.
+     */
+    case base64
+    /**
+     * The payload as text, with invalid UTF-8 replaced by U+FFFD.
+     */
+    case utf8
+
+
+
+}
+
+#if compiler(>=6)
+extension ExportEncoding: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeExportEncoding: FfiConverterRustBuffer {
+    typealias SwiftType = ExportEncoding
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ExportEncoding {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .base64
+        
+        case 2: return .utf8
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: ExportEncoding, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .base64:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .utf8:
+            writeInt(&buf, Int32(2))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeExportEncoding_lift(_ buf: RustBuffer) throws -> ExportEncoding {
+    return try FfiConverterTypeExportEncoding.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeExportEncoding_lower(_ value: ExportEncoding) -> RustBuffer {
+    return FfiConverterTypeExportEncoding.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+/**
+ * The header layout of a new queue file.
+ */
+
+public enum FileFormat: Equatable, Hashable {
+    
+    /**
+     * The 32-byte header with 64-bit lengths and positions that queue-file writes.
+     */
+    case versioned
+    /**
+     * The 16-byte header with 31-bit lengths and positions of the original Java Tape format,
+     * readable by every Tape version. Files are limited to 2 GiB.
+     */
+    case legacy
+
+
+
+}
+
+#if compiler(>=6)
+extension FileFormat: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeFileFormat: FfiConverterRustBuffer {
+    typealias SwiftType = FileFormat
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> FileFormat {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .versioned
+        
+        case 2: return .legacy
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: FileFormat, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .versioned:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .legacy:
+            writeInt(&buf, Int32(2))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFileFormat_lift(_ buf: RustBuffer) throws -> FileFormat {
+    return try FfiConverterTypeFileFormat.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFileFormat_lower(_ value: FileFormat) -> RustBuffer {
+    return FfiConverterTypeFileFormat.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum GrowthStrategy: Equatable, Hashable {
+    
+    /**
+     * Doubles the file until the new elements fit, as queue-file does by itself.
+     */
+    case double
+    /**
+     * Grows the file in steps of `bytes`.
+     */
+    case fixedIncrement(bytes: UInt64
+    )
+    /**
+     * Grows the file by exactly the space the new elements need.
+     */
+    case exactFit
+
+
+
+}
+
+#if compiler(>=6)
+extension GrowthStrategy: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeGrowthStrategy: FfiConverterRustBuffer {
+    typealias SwiftType = GrowthStrategy
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> GrowthStrategy {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .double
+        
+        case 2: return .fixedIncrement(bytes: try FfiConverterUInt64.read(from: &buf)
+        )
+        
+        case 3: return .exactFit
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: GrowthStrategy, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .double:
+            writeInt(&buf, Int32(1))
+        
+        
+        case let .fixedIncrement(bytes):
+            writeInt(&buf, Int32(2))
+            FfiConverterUInt64.write(bytes, into: &buf)
+            
+        
+        case .exactFit:
+            writeInt(&buf, Int32(3))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeGrowthStrategy_lift(_ buf: RustBuffer) throws -> GrowthStrategy {
+    return try FfiConverterTypeGrowthStrategy.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeGrowthStrategy_lower(_ value: GrowthStrategy) -> RustBuffer {
+    return FfiConverterTypeGrowthStrategy.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum HealthStatus: Equatable, Hashable {
+    
+    case healthy
+    /**
+     * The queue works, but something needs attention soon: the volume is nearly full, a sync
+     * is overdue or the lock is slow.
+     */
+    case degraded
+    /**
+     * The file or the counters kept for it are inconsistent, or the lock is stuck. The queue
+     * should be repaired or reopened.
+     */
+    case unhealthy
+
+
+
+}
+
+#if compiler(>=6)
+extension HealthStatus: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeHealthStatus: FfiConverterRustBuffer {
+    typealias SwiftType = HealthStatus
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> HealthStatus {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .healthy
+        
+        case 2: return .degraded
+        
+        case 3: return .unhealthy
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: HealthStatus, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .healthy:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .degraded:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .unhealthy:
+            writeInt(&buf, Int32(3))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeHealthStatus_lift(_ buf: RustBuffer) throws -> HealthStatus {
+    return try FfiConverterTypeHealthStatus.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeHealthStatus_lower(_ value: HealthStatus) -> RustBuffer {
+    return FfiConverterTypeHealthStatus.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+/**
+ * The kinds of file system call whose latency is sampled.
+ */
+
+public enum IoCall: Equatable, Hashable {
+    
+    /**
+     * A read of a frame, header or payload.
+     */
+    case read
+    /**
+     * A write of elements, a header commit or a header sidecar slot.
+     */
+    case write
+    /**
+     * An fsync of the queue file, a sidecar or a parent directory.
+     */
+    case sync
+    /**
+     * Shortening the queue file to its committed length.
+     */
+    case truncate
+
+
+
+}
+
+#if compiler(>=6)
+extension IoCall: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeIoCall: FfiConverterRustBuffer {
+    typealias SwiftType = IoCall
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> IoCall {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .read
+        
+        case 2: return .write
+        
+        case 3: return .sync
+        
+        case 4: return .truncate
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: IoCall, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .read:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .write:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .sync:
+            writeInt(&buf, Int32(3))
+        
+        
+        case .truncate:
+            writeInt(&buf, Int32(4))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeIoCall_lift(_ buf: RustBuffer) throws -> IoCall {
+    return try FfiConverterTypeIoCall.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeIoCall_lower(_ value: IoCall) -> RustBuffer {
+    return FfiConverterTypeIoCall.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum IoErrorKind: Equatable, Hashable {
+    
+    case notFound
+    case permissionDenied
+    case alreadyExists
+    /**
+     * No space left on the device (`ENOSPC`).
+     */
+    case storageFull
+    case readOnlyFilesystem
+    case resourceBusy
+    case wouldBlock
+    case interrupted
+    case timedOut
+    case unexpectedEof
+    case invalidInput
+    case other
+
+
+
+}
+
+#if compiler(>=6)
+extension IoErrorKind: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeIoErrorKind: FfiConverterRustBuffer {
+    typealias SwiftType = IoErrorKind
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> IoErrorKind {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .notFound
+        
+        case 2: return .permissionDenied
+        
+        case 3: return .alreadyExists
+        
+        case 4: return .storageFull
+        
+        case 5: return .readOnlyFilesystem
+        
+        case 6: return .resourceBusy
+        
+        case 7: return .wouldBlock
+        
+        case 8: return .interrupted
+        
+        case 9: return .timedOut
+        
+        case 10: return .unexpectedEof
+        
+        case 11: return .invalidInput
+        
+        case 12: return .other
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: IoErrorKind, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .notFound:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .permissionDenied:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .alreadyExists:
+            writeInt(&buf, Int32(3))
+        
+        
+        case .storageFull:
+            writeInt(&buf, Int32(4))
+        
+        
+        case .readOnlyFilesystem:
+            writeInt(&buf, Int32(5))
+        
+        
+        case .resourceBusy:
+            writeInt(&buf, Int32(6))
+        
+        
+        case .wouldBlock:
+            writeInt(&buf, Int32(7))
+        
+        
+        case .interrupted:
+            writeInt(&buf, Int32(8))
+        
+        
+        case .timedOut:
+            writeInt(&buf, Int32(9))
+        
+        
+        case .unexpectedEof:
+            writeInt(&buf, Int32(10))
+        
+        
+        case .invalidInput:
+            writeInt(&buf, Int32(11))
+        
+        
+        case .other:
+            writeInt(&buf, Int32(12))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeIoErrorKind_lift(_ buf: RustBuffer) throws -> IoErrorKind {
+    return try FfiConverterTypeIoErrorKind.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeIoErrorKind_lower(_ value: IoErrorKind) -> RustBuffer {
+    return FfiConverterTypeIoErrorKind.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+/**
+ * How a `QueueFileIterator` deals with the queue changing underneath it.
+ */
+
+public enum IterationMode: Equatable, Hashable {
+    
+    /**
+     * Reads the queue under its lock and fails with `ConcurrentModification` once it changes.
+     */
+    case failOnChange
+    /**
+     * Pins the elements queued at creation in a snapshot and returns exactly those, however
+     * the queue changes. Elements the queue removes, or moves by clearing, growing or swapping,
+     * before the iterator reaches them are first copied into memory.
+     */
+    case pinned
+
+
+
+}
+
+#if compiler(>=6)
+extension IterationMode: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeIterationMode: FfiConverterRustBuffer {
+    typealias SwiftType = IterationMode
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> IterationMode {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .failOnChange
+        
+        case 2: return .pinned
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: IterationMode, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .failOnChange:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .pinned:
+            writeInt(&buf, Int32(2))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeIterationMode_lift(_ buf: RustBuffer) throws -> IterationMode {
+    return try FfiConverterTypeIterationMode.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeIterationMode_lower(_ value: IterationMode) -> RustBuffer {
+    return FfiConverterTypeIterationMode.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum MirrorStatus: Equatable, Hashable {
+    
+    /**
+     * No mirror path was configured.
+     */
+    case disabled
+    /**
+     * Both files are open and every mutation is applied to both.
+     */
+    case inSync
+    /**
+     * The primary was missing or unreadable and has been rebuilt from the mirror.
+     */
+    case primaryRestored
+    /**
+     * The primary could not be rebuilt; the mirror is the only copy in use.
+     */
+    case runningOnMirror
+    /**
+     * The mirror could not be opened or a write to it failed; only the primary is in use.
+     */
+    case mirrorFailed
+
+
+
+}
+
+#if compiler(>=6)
+extension MirrorStatus: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeMirrorStatus: FfiConverterRustBuffer {
+    typealias SwiftType = MirrorStatus
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> MirrorStatus {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .disabled
+        
+        case 2: return .inSync
+        
+        case 3: return .primaryRestored
+        
+        case 4: return .runningOnMirror
+        
+        case 5: return .mirrorFailed
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: MirrorStatus, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .disabled:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .inSync:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .primaryRestored:
+            writeInt(&buf, Int32(3))
+        
+        
+        case .runningOnMirror:
+            writeInt(&buf, Int32(4))
+        
+        
+        case .mirrorFailed:
+            writeInt(&buf, Int32(5))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeMirrorStatus_lift(_ buf: RustBuffer) throws -> MirrorStatus {
+    return try FfiConverterTypeMirrorStatus.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeMirrorStatus_lower(_ value: MirrorStatus) -> RustBuffer {
+    return FfiConverterTypeMirrorStatus.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+/**
+ * A value of a MessagePack map element.
+ */
+
+public enum MsgpackValue: Equatable, Hashable {
+    
+    case `nil`
+    case bool(value: Bool
+    )
+    /**
+     * Any MessagePack integer that fits in an `i64`.
+     */
+    case int(value: Int64
+    )
+    /**
+     * A 32-bit float widens to this when decoded.
+     */
+    case float(value: Double
+    )
+    case string(value: String
+    )
+    case binary(value: Data
+    )
+    case array(values: [MsgpackValue]
+    )
+    /**
+     * A nested map. Keys must be strings.
+     */
+    case map(entries: [String: MsgpackValue]
+    )
+
+
+
+}
+
+#if compiler(>=6)
+extension MsgpackValue: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeMsgpackValue: FfiConverterRustBuffer {
+    typealias SwiftType = MsgpackValue
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> MsgpackValue {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .`nil`
+        
+        case 2: return .bool(value: try FfiConverterBool.read(from: &buf)
+        )
+        
+        case 3: return .int(value: try FfiConverterInt64.read(from: &buf)
+        )
+        
+        case 4: return .float(value: try FfiConverterDouble.read(from: &buf)
+        )
+        
+        case 5: return .string(value: try FfiConverterString.read(from: &buf)
+        )
+        
+        case 6: return .binary(value: try FfiConverterData.read(from: &buf)
+        )
+        
+        case 7: return .array(values: try FfiConverterSequenceTypeMsgpackValue.read(from: &buf)
+        )
+        
+        case 8: return .map(entries: try FfiConverterDictionaryStringTypeMsgpackValue.read(from: &buf)
+        )
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: MsgpackValue, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .`nil`:
+            writeInt(&buf, Int32(1))
+        
+        
+        case let .bool(value):
+            writeInt(&buf, Int32(2))
+            FfiConverterBool.write(value, into: &buf)
+            
+        
+        case let .int(value):
+            writeInt(&buf, Int32(3))
+            FfiConverterInt64.write(value, into: &buf)
+            
+        
+        case let .float(value):
+            writeInt(&buf, Int32(4))
+            FfiConverterDouble.write(value, into: &buf)
+            
+        
+        case let .string(value):
+            writeInt(&buf, Int32(5))
+            FfiConverterString.write(value, into: &buf)
+            
+        
+        case let .binary(value):
+            writeInt(&buf, Int32(6))
+            FfiConverterData.write(value, into: &buf)
+            
+        
+        case let .array(values):
+            writeInt(&buf, Int32(7))
+            FfiConverterSequenceTypeMsgpackValue.write(values, into: &buf)
+            
+        
+        case let .map(entries):
+            writeInt(&buf, Int32(8))
+            FfiConverterDictionaryStringTypeMsgpackValue.write(entries, into: &buf)
+            
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeMsgpackValue_lift(_ buf: RustBuffer) throws -> MsgpackValue {
+    return try FfiConverterTypeMsgpackValue.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeMsgpackValue_lower(_ value: MsgpackValue) -> RustBuffer {
+    return FfiConverterTypeMsgpackValue.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum OffsetCachePolicy: Equatable, Hashable {
+    
+    case none
+    case linear(offset: UInt32
+    )
+    case quadratic
+
+
+
+}
+
+#if compiler(>=6)
+extension OffsetCachePolicy: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeOffsetCachePolicy: FfiConverterRustBuffer {
+    typealias SwiftType = OffsetCachePolicy
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> OffsetCachePolicy {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .none
+        
+        case 2: return .linear(offset: try FfiConverterUInt32.read(from: &buf)
+        )
+        
+        case 3: return .quadratic
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: OffsetCachePolicy, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .none:
+            writeInt(&buf, Int32(1))
+        
+        
+        case let .linear(offset):
+            writeInt(&buf, Int32(2))
+            FfiConverterUInt32.write(offset, into: &buf)
+            
+        
+        case .quadratic:
+            writeInt(&buf, Int32(3))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeOffsetCachePolicy_lift(_ buf: RustBuffer) throws -> OffsetCachePolicy {
+    return try FfiConverterTypeOffsetCachePolicy.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeOffsetCachePolicy_lower(_ value: OffsetCachePolicy) -> RustBuffer {
+    return FfiConverterTypeOffsetCachePolicy.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum OpenMode: Equatable, Hashable {
+    
+    /**
+     * Opens the queue, creating an empty one if neither the file nor its mirror exists.
+     */
+    case createIfMissing
+    /**
+     * Creates a new queue; fails with an `AlreadyExists` I/O error if the file or its mirror
+     * exists.
+     */
+    case createNew
+    /**
+     * Opens an existing queue; fails with a `NotFound` I/O error if neither the file nor its
+     * mirror exists.
+     */
+    case mustExist
+
+
+
+}
+
+#if compiler(>=6)
+extension OpenMode: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeOpenMode: FfiConverterRustBuffer {
+    typealias SwiftType = OpenMode
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> OpenMode {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .createIfMissing
+        
+        case 2: return .createNew
+        
+        case 3: return .mustExist
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: OpenMode, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .createIfMissing:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .createNew:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .mustExist:
+            writeInt(&buf, Int32(3))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeOpenMode_lift(_ buf: RustBuffer) throws -> OpenMode {
+    return try FfiConverterTypeOpenMode.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeOpenMode_lower(_ value: OpenMode) -> RustBuffer {
+    return FfiConverterTypeOpenMode.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum PauseMode: Equatable, Hashable {
+    
+    /**
+     * Adds wait for `resume_writes`, failing with `WritesPaused` after `timeout`.
+     */
+    case block(timeout: TimeInterval
+    )
+    /**
+     * Adds are accepted into memory and written as one batch by `resume_writes`. They count
+     * towards quotas but are not visible to reads until then.
+     */
+    case buffer
+
+
+
+}
+
+#if compiler(>=6)
+extension PauseMode: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypePauseMode: FfiConverterRustBuffer {
+    typealias SwiftType = PauseMode
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> PauseMode {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .block(timeout: try FfiConverterDuration.read(from: &buf)
+        )
+        
+        case 2: return .buffer
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: PauseMode, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case let .block(timeout):
+            writeInt(&buf, Int32(1))
+            FfiConverterDuration.write(timeout, into: &buf)
+            
+        
+        case .buffer:
+            writeInt(&buf, Int32(2))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypePauseMode_lift(_ buf: RustBuffer) throws -> PauseMode {
+    return try FfiConverterTypePauseMode.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypePauseMode_lower(_ value: PauseMode) -> RustBuffer {
+    return FfiConverterTypePauseMode.lower(value)
+}
+
+
+
+public enum QueueFileError: Swift.Error, Equatable, Hashable, Foundation.LocalizedError {
+
+    
+    
+    case IoError(
+        /**
+         * The OS error code (`errno`), when the failure came from a system call.
+         */errno: Int32?, kind: IoErrorKind, 
+        /**
+         * The file the failing operation was working on, when known.
+         */path: String?, message: String
+    )
+    case EmptyQueue
+    case ElementTooBig
+    case TooManyElements
+    case CorruptedFile(message: String, 
+        /**
+         * Byte offset in the file of the damaged header or frame, when known.
+         */offset: UInt64?, 
+        /**
+         * Position in the queue of the damaged element, when one was involved.
+         */elementIndex: UInt64?, 
+        /**
+         * What a consistent file would hold there, and what this one holds.
+         */expected: String?, found: String?, 
+        /**
+         * Whether opening with `quarantine_corrupt` keeps the elements before the damage,
+         * rather than failing the same way.
+         */salvageable: Bool
+    )
+    case UnsupportedVersion(detected: UInt32, supported: UInt32
+    )
+    case LockError
+    case QuotaExceeded
+    /**
+     * The file's data-protection class makes it unreadable while the device is locked.
+     */
+    case DataProtectionLocked(path: String?
+    )
+    /**
+     * The file, or the mirror, is already open through another handle in this process.
+     */
+    case AlreadyOpen(path: String
+    )
+    /**
+     * The queue's `.lock` file names another process that is still running.
+     */
+    case LockedByOtherProcess(pid: UInt32
+    )
+    /**
+     * The handle was frozen with `freeze()`; adds, removals and clears are refused.
+     */
+    case Frozen
+    /**
+     * An add waited out its timeout while writes were paused with `PauseMode::Block`.
+     */
+    case WritesPaused
+    /**
+     * An element position was not below the number of elements in the file.
+     */
+    case IndexOutOfRange(index: UInt32, size: UInt32
+    )
+    /**
+     * `configure_runtime` was called after the worker threads had started.
+     */
+    case RuntimeAlreadyStarted
+    /**
+     * `changes_since` was asked for changes the journal no longer holds, or for a sequence
+     * number the queue has not reached. `earliest` is the oldest one it can answer from.
+     */
+    case JournalGap(since: UInt64, earliest: UInt64
+    )
+    /**
+     * An add went over the queue's `rate_limit`. Nothing was added; the same add can succeed
+     * after `retry_after`.
+     */
+    case Backpressure(retryAfter: TimeInterval
+    )
+    /**
+     * The queue changed after an iterator was created. `expected` is the `generation()` the
+     * iterator started from and `found` the one the queue has reached.
+     */
+    case ConcurrentModification(expected: UInt64, found: UInt64
+    )
+    /**
+     * An element is longer than the queue's `max_read_bytes`, so it was not read. Use
+     * `peek_to_file` or `pop_to_file`, which stream it to a file instead. From
+     * `add_from_file`, the file is longer than it reads into memory; use
+     * `add_chunked_from_file`.
+     */
+    case TooLargeForRead(len: UInt64, limit: UInt64
+    )
+    /**
+     * A call outlasted its `_with_timeout` deadline or the queue's `operation_timeout`.
+     * `started` is false when the operation had not begun and never will; when true it was
+     * under way, may still complete, and the queue should be checked before it is retried.
+     */
+    case Timeout(operation: QueueOperation, after: TimeInterval, started: Bool
+    )
+    /**
+     * The queue file does not match the signature of `signature_key`: it was changed outside
+     * the queue, its signature is missing, or it was not synced or closed after its last
+     * change.
+     */
+    case TamperDetected(path: String
+    )
+    /**
+     * The `KeyProvider` had no key for `signature_key`, or none was set.
+     */
+    case KeyUnavailable(keyId: String
+    )
+    /**
+     * An element read as an `ElementEnvelope` is not a well-formed envelope message.
+     */
+    case InvalidEnvelope(message: String
+    )
+    /**
+     * An element read as a MessagePack map is not one.
+     */
+    case InvalidMessagePack(message: String
+    )
+    /**
+     * The elements at the head of the queue do not form a whole chunked element, for example
+     * after a plain `remove` took only its first chunk.
+     */
+    case InvalidChunk(message: String
+    )
+    /**
+     * The queue's file was used while `idle_close_after` or a `QueueManager` had it closed,
+     * without reopening it first.
+     */
+    case ClosedForInactivity
+    /**
+     * SQLite failed to open, read or write the database of an SQLite import or export.
+     */
+    case SqliteError(
+        /**
+         * The database file.
+         */path: String?, message: String
+    )
+
+    
+
+    
+    public var errorDescription: String? {
+        String(reflecting: self)
+    }
+    
+}
+
+#if compiler(>=6)
+extension QueueFileError: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQueueFileError: FfiConverterRustBuffer {
+    typealias SwiftType = QueueFileError
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueFileError {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+
+        
+
+        
+        case 1: return .IoError(
+            errno: try FfiConverterOptionInt32.read(from: &buf), 
+            kind: try FfiConverterTypeIoErrorKind.read(from: &buf), 
+            path: try FfiConverterOptionString.read(from: &buf), 
+            message: try FfiConverterString.read(from: &buf)
+            )
+        case 2: return .EmptyQueue
+        case 3: return .ElementTooBig
+        case 4: return .TooManyElements
+        case 5: return .CorruptedFile(
+            message: try FfiConverterString.read(from: &buf), 
+            offset: try FfiConverterOptionUInt64.read(from: &buf), 
+            elementIndex: try FfiConverterOptionUInt64.read(from: &buf), 
+            expected: try FfiConverterOptionString.read(from: &buf), 
+            found: try FfiConverterOptionString.read(from: &buf), 
+            salvageable: try FfiConverterBool.read(from: &buf)
+            )
+        case 6: return .UnsupportedVersion(
+            detected: try FfiConverterUInt32.read(from: &buf), 
+            supported: try FfiConverterUInt32.read(from: &buf)
+            )
+        case 7: return .LockError
+        case 8: return .QuotaExceeded
+        case 9: return .DataProtectionLocked(
+            path: try FfiConverterOptionString.read(from: &buf)
+            )
+        case 10: return .AlreadyOpen(
+            path: try FfiConverterString.read(from: &buf)
+            )
+        case 11: return .LockedByOtherProcess(
+            pid: try FfiConverterUInt32.read(from: &buf)
+            )
+        case 12: return .Frozen
+        case 13: return .WritesPaused
+        case 14: return .IndexOutOfRange(
+            index: try FfiConverterUInt32.read(from: &buf), 
+            size: try FfiConverterUInt32.read(from: &buf)
+            )
+        case 15: return .RuntimeAlreadyStarted
+        case 16: return .JournalGap(
+            since: try FfiConverterUInt64.read(from: &buf), 
+            earliest: try FfiConverterUInt64.read(from: &buf)
+            )
+        case 17: return .Backpressure(
+            retryAfter: try FfiConverterDuration.read(from: &buf)
+            )
+        case 18: return .ConcurrentModification(
+            expected: try FfiConverterUInt64.read(from: &buf), 
+            found: try FfiConverterUInt64.read(from: &buf)
+            )
+        case 19: return .TooLargeForRead(
+            len: try FfiConverterUInt64.read(from: &buf), 
+            limit: try FfiConverterUInt64.read(from: &buf)
+            )
+        case 20: return .Timeout(
+            operation: try FfiConverterTypeQueueOperation.read(from: &buf), 
+            after: try FfiConverterDuration.read(from: &buf), 
+            started: try FfiConverterBool.read(from: &buf)
+            )
+        case 21: return .TamperDetected(
+            path: try FfiConverterString.read(from: &buf)
+            )
+        case 22: return .KeyUnavailable(
+            keyId: try FfiConverterString.read(from: &buf)
+            )
+        case 23: return .InvalidEnvelope(
+            message: try FfiConverterString.read(from: &buf)
+            )
+        case 24: return .InvalidMessagePack(
+            message: try FfiConverterString.read(from: &buf)
+            )
+        case 25: return .InvalidChunk(
+            message: try FfiConverterString.read(from: &buf)
+            )
+        case 26: return .ClosedForInactivity
+        case 27: return .SqliteError(
+            path: try FfiConverterOptionString.read(from: &buf), 
+            message: try FfiConverterString.read(from: &buf)
+            )
+
+         default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: QueueFileError, into buf: inout [UInt8]) {
+        switch value {
+
+        
+
+        
+        
+        case let .IoError(errno,kind,path,message):
+            writeInt(&buf, Int32(1))
+            FfiConverterOptionInt32.write(errno, into: &buf)
+            FfiConverterTypeIoErrorKind.write(kind, into: &buf)
+            FfiConverterOptionString.write(path, into: &buf)
+            FfiConverterString.write(message, into: &buf)
+            
+        
+        case .EmptyQueue:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .ElementTooBig:
+            writeInt(&buf, Int32(3))
+        
+        
+        case .TooManyElements:
+            writeInt(&buf, Int32(4))
+        
+        
+        case let .CorruptedFile(message,offset,elementIndex,expected,found,salvageable):
+            writeInt(&buf, Int32(5))
+            FfiConverterString.write(message, into: &buf)
+            FfiConverterOptionUInt64.write(offset, into: &buf)
+            FfiConverterOptionUInt64.write(elementIndex, into: &buf)
+            FfiConverterOptionString.write(expected, into: &buf)
+            FfiConverterOptionString.write(found, into: &buf)
+            FfiConverterBool.write(salvageable, into: &buf)
+            
+        
+        case let .UnsupportedVersion(detected,supported):
+            writeInt(&buf, Int32(6))
+            FfiConverterUInt32.write(detected, into: &buf)
+            FfiConverterUInt32.write(supported, into: &buf)
+            
+        
+        case .LockError:
+            writeInt(&buf, Int32(7))
+        
+        
+        case .QuotaExceeded:
+            writeInt(&buf, Int32(8))
+        
+        
+        case let .DataProtectionLocked(path):
+            writeInt(&buf, Int32(9))
+            FfiConverterOptionString.write(path, into: &buf)
+            
+        
+        case let .AlreadyOpen(path):
+            writeInt(&buf, Int32(10))
+            FfiConverterString.write(path, into: &buf)
+            
+        
+        case let .LockedByOtherProcess(pid):
+            writeInt(&buf, Int32(11))
+            FfiConverterUInt32.write(pid, into: &buf)
+            
+        
+        case .Frozen:
+            writeInt(&buf, Int32(12))
+        
+        
+        case .WritesPaused:
+            writeInt(&buf, Int32(13))
+        
+        
+        case let .IndexOutOfRange(index,size):
+            writeInt(&buf, Int32(14))
+            FfiConverterUInt32.write(index, into: &buf)
+            FfiConverterUInt32.write(size, into: &buf)
+            
+        
+        case .RuntimeAlreadyStarted:
+            writeInt(&buf, Int32(15))
+        
+        
+        case let .JournalGap(since,earliest):
+            writeInt(&buf, Int32(16))
+            FfiConverterUInt64.write(since, into: &buf)
+            FfiConverterUInt64.write(earliest, into: &buf)
+            
+        
+        case let .Backpressure(retryAfter):
+            writeInt(&buf, Int32(17))
+            FfiConverterDuration.write(retryAfter, into: &buf)
+            
+        
+        case let .ConcurrentModification(expected,found):
+            writeInt(&buf, Int32(18))
+            FfiConverterUInt64.write(expected, into: &buf)
+            FfiConverterUInt64.write(found, into: &buf)
+            
+        
+        case let .TooLargeForRead(len,limit):
+            writeInt(&buf, Int32(19))
+            FfiConverterUInt64.write(len, into: &buf)
+            FfiConverterUInt64.write(limit, into: &buf)
+            
+        
+        case let .Timeout(operation,after,started):
+            writeInt(&buf, Int32(20))
+            FfiConverterTypeQueueOperation.write(operation, into: &buf)
+            FfiConverterDuration.write(after, into: &buf)
+            FfiConverterBool.write(started, into: &buf)
+            
+        
+        case let .TamperDetected(path):
+            writeInt(&buf, Int32(21))
+            FfiConverterString.write(path, into: &buf)
+            
+        
+        case let .KeyUnavailable(keyId):
+            writeInt(&buf, Int32(22))
+            FfiConverterString.write(keyId, into: &buf)
+            
+        
+        case let .InvalidEnvelope(message):
+            writeInt(&buf, Int32(23))
+            FfiConverterString.write(message, into: &buf)
+            
+        
+        case let .InvalidMessagePack(message):
+            writeInt(&buf, Int32(24))
+            FfiConverterString.write(message, into: &buf)
+            
+        
+        case let .InvalidChunk(message):
+            writeInt(&buf, Int32(25))
+            FfiConverterString.write(message, into: &buf)
+            
+        
+        case .ClosedForInactivity:
+            writeInt(&buf, Int32(26))
+        
+        
+        case let .SqliteError(path,message):
+            writeInt(&buf, Int32(27))
+            FfiConverterOptionString.write(path, into: &buf)
+            FfiConverterString.write(message, into: &buf)
+            
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueFileError_lift(_ buf: RustBuffer) throws -> QueueFileError {
+    return try FfiConverterTypeQueueFileError.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueFileError_lower(_ value: QueueFileError) -> RustBuffer {
+    return FfiConverterTypeQueueFileError.lower(value)
+}
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+/**
+ * The queue operations whose duration is measured.
+ */
+
+public enum QueueOperation: Equatable, Hashable {
+    
+    /**
+     * An add or `add_multiple`; bytes are the payload added.
+     */
+    case add
+    /**
+     * A peek; bytes are the payload read.
+     */
+    case peek
+    /**
+     * `remove` or `remove_n`; bytes are the space freed, frames included.
+     */
+    case remove
+    /**
+     * `clear`; bytes are the space freed, frames included.
+     */
+    case clear
+    /**
+     * `sync_all` or a group commit; bytes are those written since the previous sync.
+     */
+    case sync
+    /**
+     * `remove_at` or `update_at` rewriting the file; bytes are the size of the new copy.
+     */
+    case rewrite
+
+
+
+}
+
+#if compiler(>=6)
+extension QueueOperation: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeQueueOperation: FfiConverterRustBuffer {
+    typealias SwiftType = QueueOperation
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> QueueOperation {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .add
+        
+        case 2: return .peek
+        
+        case 3: return .remove
+        
+        case 4: return .clear
+        
+        case 5: return .sync
+        
+        case 6: return .rewrite
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: QueueOperation, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .add:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .peek:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .remove:
+            writeInt(&buf, Int32(3))
+        
+        
+        case .clear:
+            writeInt(&buf, Int32(4))
+        
+        
+        case .sync:
+            writeInt(&buf, Int32(5))
+        
+        
+        case .rewrite:
+            writeInt(&buf, Int32(6))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueOperation_lift(_ buf: RustBuffer) throws -> QueueOperation {
+    return try FfiConverterTypeQueueOperation.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeQueueOperation_lower(_ value: QueueOperation) -> RustBuffer {
+    return FfiConverterTypeQueueOperation.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum RetentionReason: Equatable, Hashable {
+    
+    /**
+     * The segments together held more than `max_total_bytes`.
+     */
+    case totalBytes
+    /**
+     * The segment was last written longer than `max_segment_age` ago.
+     */
+    case segmentAge
+    /**
+     * There were more than `max_segments` segments.
+     */
+    case segmentCount
+
+
+
+}
+
+#if compiler(>=6)
+extension RetentionReason: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeRetentionReason: FfiConverterRustBuffer {
+    typealias SwiftType = RetentionReason
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> RetentionReason {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .totalBytes
+        
+        case 2: return .segmentAge
+        
+        case 3: return .segmentCount
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: RetentionReason, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .totalBytes:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .segmentAge:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .segmentCount:
+            writeInt(&buf, Int32(3))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRetentionReason_lift(_ buf: RustBuffer) throws -> RetentionReason {
+    return try FfiConverterTypeRetentionReason.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeRetentionReason_lower(_ value: RetentionReason) -> RustBuffer {
+    return FfiConverterTypeRetentionReason.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum SyncStatus: Equatable, Hashable {
+    
+    /**
+     * The file is exactly as the last `sync_all` left it.
+     */
+    case unchanged
+    /**
+     * Operations were committed after the last sync and the file is consistent.
+     */
+    case modifiedSinceSync
+    /**
+     * The header changed after the last sync and the element chain it describes is broken:
+     * the file was torn by a crash during a write that followed the last sync.
+     */
+    case tornSinceSync
+    /**
+     * The header is the one the last sync recorded, but the elements it describes are not:
+     * the file changed without going through the queue.
+     */
+    case corrupted
+
+
+
+}
+
+#if compiler(>=6)
+extension SyncStatus: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSyncStatus: FfiConverterRustBuffer {
+    typealias SwiftType = SyncStatus
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SyncStatus {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .unchanged
+        
+        case 2: return .modifiedSinceSync
+        
+        case 3: return .tornSinceSync
+        
+        case 4: return .corrupted
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: SyncStatus, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .unchanged:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .modifiedSinceSync:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .tornSinceSync:
+            writeInt(&buf, Int32(3))
+        
+        
+        case .corrupted:
+            writeInt(&buf, Int32(4))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSyncStatus_lift(_ buf: RustBuffer) throws -> SyncStatus {
+    return try FfiConverterTypeSyncStatus.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSyncStatus_lower(_ value: SyncStatus) -> RustBuffer {
+    return FfiConverterTypeSyncStatus.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum SyncStrategy: Equatable, Hashable {
+    
+    /**
+     * Adds the source's elements that come after the newest ones both queues hold, keeping
+     * every element already in the target.
+     */
+    case appendMissing
+    /**
+     * Leaves the target holding exactly the source's elements, in the same order.
+     */
+    case mirror
+
+
+
+}
+
+#if compiler(>=6)
+extension SyncStrategy: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeSyncStrategy: FfiConverterRustBuffer {
+    typealias SwiftType = SyncStrategy
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SyncStrategy {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .appendMissing
+        
+        case 2: return .mirror
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: SyncStrategy, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .appendMissing:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .mirror:
+            writeInt(&buf, Int32(2))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSyncStrategy_lift(_ buf: RustBuffer) throws -> SyncStrategy {
+    return try FfiConverterTypeSyncStrategy.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeSyncStrategy_lower(_ value: SyncStrategy) -> RustBuffer {
+    return FfiConverterTypeSyncStrategy.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum ThreadQos: Equatable, Hashable {
+    
+    case userInitiated
+    case `default`
+    case utility
+    case background
+
+
+
+}
+
+#if compiler(>=6)
+extension ThreadQos: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeThreadQos: FfiConverterRustBuffer {
+    typealias SwiftType = ThreadQos
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ThreadQos {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .userInitiated
+        
+        case 2: return .`default`
+        
+        case 3: return .utility
+        
+        case 4: return .background
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: ThreadQos, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .userInitiated:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .`default`:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .utility:
+            writeInt(&buf, Int32(3))
+        
+        
+        case .background:
+            writeInt(&buf, Int32(4))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeThreadQos_lift(_ buf: RustBuffer) throws -> ThreadQos {
+    return try FfiConverterTypeThreadQos.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeThreadQos_lower(_ value: ThreadQos) -> RustBuffer {
+    return FfiConverterTypeThreadQos.lower(value)
+}
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum WatermarkMetric: Equatable, Hashable {
+    
+    /**
+     * Number of elements, including adds still held by the write buffer.
+     */
+    case elements
+    /**
+     * Bytes used by the queue, including the frames of buffered adds.
+     */
+    case bytes
+
+
+
+}
+
+#if compiler(>=6)
+extension WatermarkMetric: Sendable {}
+#endif
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeWatermarkMetric: FfiConverterRustBuffer {
+    typealias SwiftType = WatermarkMetric
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> WatermarkMetric {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .elements
+        
+        case 2: return .bytes
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: WatermarkMetric, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .elements:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .bytes:
+            writeInt(&buf, Int32(2))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeWatermarkMetric_lift(_ buf: RustBuffer) throws -> WatermarkMetric {
+    return try FfiConverterTypeWatermarkMetric.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeWatermarkMetric_lower(_ value: WatermarkMetric) -> RustBuffer {
+    return FfiConverterTypeWatermarkMetric.lower(value)
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionUInt32: FfiConverterRustBuffer {
+    typealias SwiftType = UInt32?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterUInt32.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterUInt32.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionInt32: FfiConverterRustBuffer {
+    typealias SwiftType = Int32?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterInt32.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterInt32.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionUInt64: FfiConverterRustBuffer {
+    typealias SwiftType = UInt64?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterUInt64.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterUInt64.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionString: FfiConverterRustBuffer {
+    typealias SwiftType = String?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterString.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterString.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionData: FfiConverterRustBuffer {
+    typealias SwiftType = Data?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterData.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterData.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTimestamp: FfiConverterRustBuffer {
+    typealias SwiftType = Date?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTimestamp.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTimestamp.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionDuration: FfiConverterRustBuffer {
+    typealias SwiftType = TimeInterval?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterDuration.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterDuration.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeQueueFile: FfiConverterRustBuffer {
+    typealias SwiftType = QueueFile?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeQueueFile.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeQueueFile.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeCorruptionRecovery: FfiConverterRustBuffer {
+    typealias SwiftType = CorruptionRecovery?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeCorruptionRecovery.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeCorruptionRecovery.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeElementEnvelope: FfiConverterRustBuffer {
+    typealias SwiftType = ElementEnvelope?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeElementEnvelope.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeElementEnvelope.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeElementMeta: FfiConverterRustBuffer {
+    typealias SwiftType = ElementMeta?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeElementMeta.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeElementMeta.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeQuarantineReport: FfiConverterRustBuffer {
+    typealias SwiftType = QuarantineReport?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeQuarantineReport.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeQuarantineReport.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeRateLimit: FfiConverterRustBuffer {
+    typealias SwiftType = RateLimit?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeRateLimit.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeRateLimit.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeRetryPolicy: FfiConverterRustBuffer {
+    typealias SwiftType = RetryPolicy?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeRetryPolicy.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeRetryPolicy.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeSyncCheck: FfiConverterRustBuffer {
+    typealias SwiftType = SyncCheck?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeSyncCheck.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeSyncCheck.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeTrainedDictionary: FfiConverterRustBuffer {
+    typealias SwiftType = TrainedDictionary?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeTrainedDictionary.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeTrainedDictionary.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeWatermarkConfig: FfiConverterRustBuffer {
+    typealias SwiftType = WatermarkConfig?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeWatermarkConfig.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeWatermarkConfig.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeWriteBufferConfig: FfiConverterRustBuffer {
+    typealias SwiftType = WriteBufferConfig?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeWriteBufferConfig.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeWriteBufferConfig.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeAlreadyOpenPolicy: FfiConverterRustBuffer {
+    typealias SwiftType = AlreadyOpenPolicy?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeAlreadyOpenPolicy.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeAlreadyOpenPolicy.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeCorruptionPolicy: FfiConverterRustBuffer {
+    typealias SwiftType = CorruptionPolicy?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeCorruptionPolicy.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeCorruptionPolicy.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeExportEncoding: FfiConverterRustBuffer {
+    typealias SwiftType = ExportEncoding?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeExportEncoding.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeExportEncoding.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeFileFormat: FfiConverterRustBuffer {
+    typealias SwiftType = FileFormat?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeFileFormat.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeFileFormat.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeGrowthStrategy: FfiConverterRustBuffer {
+    typealias SwiftType = GrowthStrategy?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeGrowthStrategy.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeGrowthStrategy.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeIterationMode: FfiConverterRustBuffer {
+    typealias SwiftType = IterationMode?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeIterationMode.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeIterationMode.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeOpenMode: FfiConverterRustBuffer {
+    typealias SwiftType = OpenMode?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeOpenMode.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeOpenMode.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeThreadQos: FfiConverterRustBuffer {
+    typealias SwiftType = ThreadQos?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeThreadQos.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeThreadQos.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionDictionaryStringTypeMsgpackValue: FfiConverterRustBuffer {
+    typealias SwiftType = [String: MsgpackValue]?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterDictionaryStringTypeMsgpackValue.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterDictionaryStringTypeMsgpackValue.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceUInt32: FfiConverterRustBuffer {
+    typealias SwiftType = [UInt32]
+
+    public static func write(_ value: [UInt32], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterUInt32.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [UInt32] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [UInt32]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterUInt32.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceUInt64: FfiConverterRustBuffer {
+    typealias SwiftType = [UInt64]
+
+    public static func write(_ value: [UInt64], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterUInt64.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [UInt64] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [UInt64]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterUInt64.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceString: FfiConverterRustBuffer {
+    typealias SwiftType = [String]
+
+    public static func write(_ value: [String], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterString.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [String] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [String]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterString.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceData: FfiConverterRustBuffer {
+    typealias SwiftType = [Data]
+
+    public static func write(_ value: [Data], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterData.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [Data] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [Data]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterData.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeChangeRecord: FfiConverterRustBuffer {
+    typealias SwiftType = [ChangeRecord]
+
+    public static func write(_ value: [ChangeRecord], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeChangeRecord.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [ChangeRecord] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [ChangeRecord]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeChangeRecord.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeCrashOutcome: FfiConverterRustBuffer {
+    typealias SwiftType = [CrashOutcome]
+
+    public static func write(_ value: [CrashOutcome], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeCrashOutcome.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [CrashOutcome] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [CrashOutcome]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeCrashOutcome.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeDroppedSegment: FfiConverterRustBuffer {
+    typealias SwiftType = [DroppedSegment]
+
+    public static func write(_ value: [DroppedSegment], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeDroppedSegment.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [DroppedSegment] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [DroppedSegment]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeDroppedSegment.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeFreeRange: FfiConverterRustBuffer {
+    typealias SwiftType = [FreeRange]
+
+    public static func write(_ value: [FreeRange], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeFreeRange.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [FreeRange] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [FreeRange]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeFreeRange.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeGrowthEvent: FfiConverterRustBuffer {
+    typealias SwiftType = [GrowthEvent]
+
+    public static func write(_ value: [GrowthEvent], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeGrowthEvent.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [GrowthEvent] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [GrowthEvent]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeGrowthEvent.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeIoLatencySummary: FfiConverterRustBuffer {
+    typealias SwiftType = [IoLatencySummary]
+
+    public static func write(_ value: [IoLatencySummary], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeIoLatencySummary.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [IoLatencySummary] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [IoLatencySummary]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeIoLatencySummary.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeLatencySummary: FfiConverterRustBuffer {
+    typealias SwiftType = [LatencySummary]
+
+    public static func write(_ value: [LatencySummary], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeLatencySummary.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [LatencySummary] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [LatencySummary]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeLatencySummary.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeQuarantinedRange: FfiConverterRustBuffer {
+    typealias SwiftType = [QuarantinedRange]
+
+    public static func write(_ value: [QuarantinedRange], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeQuarantinedRange.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [QuarantinedRange] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [QuarantinedRange]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeQuarantinedRange.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeSegmentInfo: FfiConverterRustBuffer {
+    typealias SwiftType = [SegmentInfo]
+
+    public static func write(_ value: [SegmentInfo], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeSegmentInfo.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [SegmentInfo] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [SegmentInfo]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeSegmentInfo.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeMsgpackValue: FfiConverterRustBuffer {
+    typealias SwiftType = [MsgpackValue]
+
+    public static func write(_ value: [MsgpackValue], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeMsgpackValue.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [MsgpackValue] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [MsgpackValue]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeMsgpackValue.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterDictionaryStringString: FfiConverterRustBuffer {
+    public static func write(_ value: [String: String], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for (key, value) in value {
+            FfiConverterString.write(key, into: &buf)
+            FfiConverterString.write(value, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [String: String] {
+        let len: Int32 = try readInt(&buf)
+        var dict = [String: String]()
+        dict.reserveCapacity(Int(len))
+        for _ in 0..<len {
+            let key = try FfiConverterString.read(from: &buf)
+            let value = try FfiConverterString.read(from: &buf)
+            dict[key] = value
+        }
+        return dict
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterDictionaryStringTypeMsgpackValue: FfiConverterRustBuffer {
+    public static func write(_ value: [String: MsgpackValue], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for (key, value) in value {
+            FfiConverterString.write(key, into: &buf)
+            FfiConverterTypeMsgpackValue.write(value, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [String: MsgpackValue] {
+        let len: Int32 = try readInt(&buf)
+        var dict = [String: MsgpackValue]()
+        dict.reserveCapacity(Int(len))
+        for _ in 0..<len {
+            let key = try FfiConverterString.read(from: &buf)
+            let value = try FfiConverterTypeMsgpackValue.read(from: &buf)
+            dict[key] = value
+        }
+        return dict
+    }
+}
+private let UNIFFI_RUST_FUTURE_POLL_READY: Int8 = 0
+private let UNIFFI_RUST_FUTURE_POLL_WAKE: Int8 = 1
+
+fileprivate let uniffiContinuationHandleMap = UniffiHandleMap<UnsafeContinuation<Int8, Never>>()
+
+fileprivate func uniffiRustCallAsync<F, T>(
+    rustFutureFunc: () -> UInt64,
+    pollFunc: (UInt64, @escaping UniffiRustFutureContinuationCallback, UInt64) -> (),
+    completeFunc: (UInt64, UnsafeMutablePointer<RustCallStatus>) -> F,
+    freeFunc: (UInt64) -> (),
+    liftFunc: (F) throws -> T,
+    errorHandler: ((RustBuffer) throws -> Swift.Error)?
+) async throws -> T {
+    // Make sure to call the ensure init function since future creation doesn't have a
+    // RustCallStatus param, so doesn't use makeRustCall()
+    uniffiEnsureQueuefileInitialized()
+    let rustFuture = rustFutureFunc()
+    defer {
+        freeFunc(rustFuture)
+    }
+    var pollResult: Int8;
+    repeat {
+        pollResult = await withUnsafeContinuation {
+            pollFunc(
+                rustFuture,
+                { handle, pollResult in
+                    uniffiFutureContinuationCallback(handle: handle, pollResult: pollResult)
+                },
+                uniffiContinuationHandleMap.insert(obj: $0)
+            )
+        }
+    } while pollResult != UNIFFI_RUST_FUTURE_POLL_READY
+
+    return try liftFunc(makeRustCall(
+        { completeFunc(rustFuture, $0) },
+        errorHandler: errorHandler
+    ))
+}
+
+// Callback handlers for an async calls.  These are invoked by Rust when the future is ready.  They
+// lift the return value or error and resume the suspended function.
+fileprivate func uniffiFutureContinuationCallback(handle: UInt64, pollResult: Int8) {
+    if let continuation = try? uniffiContinuationHandleMap.remove(handle: handle) {
+        continuation.resume(returning: pollResult)
+    } else {
+        print("uniffiFutureContinuationCallback invalid handle")
+    }
+}
+/**
+ * Starts the worker threads behind the `_async` methods. Must be called before the first of
+ * them; fails with `RuntimeAlreadyStarted` afterwards.
+ */
+public func configureRuntime(config: RuntimeConfig)throws   {try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_func_configure_runtime(
+        FfiConverterTypeRuntimeConfig_lower(config),$0
+    )
+}
+}
+/**
+ * Decodes a `queuefile.ElementEnvelope` message. Fields this version does not know are
+ * skipped, so envelopes written by newer tooling still decode. Fails with `InvalidEnvelope`
+ * if `bytes` is not a well-formed message.
+ */
+public func decodeEnvelope(bytes: Data)throws  -> ElementEnvelope  {
+    return try  FfiConverterTypeElementEnvelope_lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_func_decode_envelope(
+        FfiConverterData.lower(bytes),$0
+    )
+})
+}
+/**
+ * Decodes a MessagePack map with string keys, the format `encode_msgpack_map` writes. Fails
+ * with `InvalidMessagePack` for anything else, including extension types, integers above
+ * `i64::MAX` and trailing bytes.
+ */
+public func decodeMsgpackMap(bytes: Data)throws  -> [String: MsgpackValue]  {
+    return try  FfiConverterDictionaryStringTypeMsgpackValue.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_func_decode_msgpack_map(
+        FfiConverterData.lower(bytes),$0
+    )
+})
+}
+/**
+ * Encodes `envelope` as a `queuefile.ElementEnvelope` message, starting with the `mark` field.
+ * Headers are written sorted by key, so equal envelopes encode to equal bytes.
+ */
+public func encodeEnvelope(envelope: ElementEnvelope) -> Data  {
+    return try!  FfiConverterData.lift(try! rustCall() {
+    uniffi_queuefile_fn_func_encode_envelope(
+        FfiConverterTypeElementEnvelope_lower(envelope),$0
+    )
+})
+}
+/**
+ * Encodes `map` as a MessagePack map with its keys sorted, so equal maps encode to equal
+ * bytes. Integers take the shortest encoding.
+ */
+public func encodeMsgpackMap(map: [String: MsgpackValue]) -> Data  {
+    return try!  FfiConverterData.lift(try! rustCall() {
+    uniffi_queuefile_fn_func_encode_msgpack_map(
+        FfiConverterDictionaryStringTypeMsgpackValue.lower(map),$0
+    )
+})
+}
+/**
+ * The length a queue file opened with `options` reaches once it holds `element_count` elements
+ * of `avg_element_size` bytes each: their payloads, the length in front of each one and the
+ * file header, rounded up the way the queue grows from `options.capacity`. Passing it as the
+ * capacity creates the file at that length, so none of those adds has to grow it. Payloads a
+ * dictionary compresses take less than estimated.
+ */
+public func estimateFileLen(elementCount: UInt64, avgElementSize: UInt64, options: QueueFileOptions) -> UInt64  {
+    return try!  FfiConverterUInt64.lift(try! rustCall() {
+    uniffi_queuefile_fn_func_estimate_file_len(
+        FfiConverterUInt64.lower(elementCount),
+        FfiConverterUInt64.lower(avgElementSize),
+        FfiConverterTypeQueueFileOptions_lower(options),$0
+    )
+})
+}
+/**
+ * Writes out and fsyncs every queue open in this process, then freezes it, for
+ * `applicationWillTerminate` or an expiring background task. A later operation on a frozen
+ * handle fails with `Frozen` rather than writing after the app has been told it is done. If
+ * a queue fails, the rest are still closed and the first error is returned.
+ */
+public func flushAndCloseAll()throws  -> UInt32  {
+    return try  FfiConverterUInt32.lift(try rustCallWithError(FfiConverterTypeQueueFileError_lift) {
+    uniffi_queuefile_fn_func_flush_and_close_all($0
+    )
+})
+}
+/**
+ * Latency percentiles of each kind of file system call made on behalf of any queue in this
+ * process since it started, apart from calls never sampled: the writes of a queue with
+ * `sync_writes` on, which sync inside the same call, and those made while opening and
+ * rewriting files. Slow `Read`/`Sync` with ordinary queue-level latencies point at the device.
+ */
+public func ioLatencies() -> [IoLatencySummary]  {
+    return try!  FfiConverterSequenceTypeIoLatencySummary.lift(try! rustCall() {
+    uniffi_queuefile_fn_func_io_latencies($0
+    )
+})
+}
+/**
+ * Tells queues waiting on a data-protection-locked file that protected data is available
+ * again, typically from `UIApplication.protectedDataDidBecomeAvailableNotification`.
+ */
+public func notifyUnlocked()  {try! rustCall() {
+    uniffi_queuefile_fn_func_notify_unlocked($0
+    )
+}
+}
+/**
+ * Sets the provider asked for the key of every queue opened with `signature_key` from now on.
+ */
+public func setKeyProvider(provider: KeyProvider)  {try! rustCall() {
+    uniffi_queuefile_fn_func_set_key_provider(
+        FfiConverterTypeKeyProvider_lower(provider),$0
+    )
+}
+}
+/**
+ * The format versions this library can open: `LEGACY_FORMAT_VERSION` and
+ * `VERSIONED_FORMAT_VERSION`.
+ */
+public func supportedFormatVersions() -> [UInt32]  {
+    return try!  FfiConverterSequenceUInt32.lift(try! rustCall() {
+    uniffi_queuefile_fn_func_supported_format_versions($0
+    )
+})
+}
+
+private enum InitializationResult {
+    case ok
+    case contractVersionMismatch
+    case apiChecksumMismatch
+}
+// Use a global variable to perform the versioning checks. Swift ensures that
+// the code inside is only computed once.
+private let initializationResult: InitializationResult = {
+    // Get the bindings contract version from our ComponentInterface
+    let bindings_contract_version = 30
+    // Get the scaffolding contract version by calling the into the dylib
+    let scaffolding_contract_version = ffi_queuefile_uniffi_contract_version()
+    if bindings_contract_version != scaffolding_contract_version {
+        return InitializationResult.contractVersionMismatch
+    }
+    if (uniffi_queuefile_checksum_func_configure_runtime() != 62818) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_decode_envelope() != 20154) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_decode_msgpack_map() != 31925) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_encode_envelope() != 53232) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_encode_msgpack_map() != 20147) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_estimate_file_len() != 43481) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_flush_and_close_all() != 11095) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_io_latencies() != 46756) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_notify_unlocked() != 59700) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_set_key_provider() != 49580) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_func_supported_format_versions() != 52607) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_archivedqueue_export_csv() != 32495) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_archivedqueue_export_ndjson() != 9138) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_archivedqueue_export_with() != 10309) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_archivedqueue_get_all() != 26847) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_archivedqueue_is_empty() != 20459) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_archivedqueue_next() != 17746) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_archivedqueue_rewind() != 22935) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_archivedqueue_size() != 50014) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_archivedqueue_verify() != 23037) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_crashsimulator_checkpoint() != 44835) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_crashsimulator_replay() != 64852) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_crashsimulator_verify() != 17703) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_crashsimulator_write_len() != 27450) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_drainhandler_handle() != 42686) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_elementlistener_on_element() != 58706) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_exporter_write() != 42390) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_exporter_finish() != 43942) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_keyprovider_key() != 41822) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_element_at() != 25229) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_export_csv() != 7945) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_export_ndjson() != 38450) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_export_with() != 26819) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_get_all() != 56588) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_is_empty() != 21054) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_mapped_len() != 62100) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_next() != 12664) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_rewind() != 54621) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_mappedqueue_size() != 16733) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queueconsumer_is_running() != 5011) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queueconsumer_stats() != 32631) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queueconsumer_stop() != 35062) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add() != 8) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_async() != 49599) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_chunked() != 2779) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_chunked_from_file() != 61337) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_envelope() != 41788) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_from_file() != 12566) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_from_file_async() != 1962) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_msgpack_map() != 14859) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_multiple() != 20758) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_multiple_async() != 41628) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_multiple_with_timeout() != 41597) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_add_with_timeout() != 61314) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_can_accept() != 28673) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_capacity_report() != 41412) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_changes_since() != 43319) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_clear() != 34945) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_clear_async() != 38115) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_clear_slow_op_listener() != 23584) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_clear_watermarks() != 32275) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_clear_with_timeout() != 49558) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_debug_dump() != 60932) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_disk_usage() != 1532) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_drain_with() != 9279) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_drain_with_async() != 58160) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_element_at() != 44163) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_export_csv() != 8196) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_export_ndjson() != 3440) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_export_to_sqlite() != 45193) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_export_with() != 28715) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_file_info() != 294) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_file_len() != 15704) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_find_indices() != 45491) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_finish_open() != 63668) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_finish_open_async() != 27930) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_flush() != 36912) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_fragmentation_report() != 60720) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_freeze() != 18361) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_gc() != 61684) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_generation() != 4876) {
         return InitializationResult.apiChecksumMismatch
     }
     if (uniffi_queuefile_checksum_method_queuefile_get_all() != 40395) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_get_all_async() != 38326) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_health_check() != 11287) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_import_from_sqlite() != 62210) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_is_empty() != 29460) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_is_frozen() != 17720) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_iterator() != 27763) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_last_corruption_recovery() != 63919) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_last_quarantine() != 29019) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_last_sync_check() != 44309) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_max_commit_delay() != 20200) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_mirror_status() != 43130) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_overwrite_on_remove() != 41459) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_pause_writes() != 25535) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_peek() != 1958) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_async() != 36760) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_chunked() != 29267) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_digest() != 63703) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_envelope() != 52927) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_metadata() != 24965) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_msgpack_map() != 10070) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_random() != 56618) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_to_file() != 38464) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_to_file_async() != 28498) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_peek_with_timeout() != 34871) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_pop_chunked_to_file() != 57179) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_pop_to_file() != 5587) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_pop_to_file_async() != 65175) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_probably_contains() != 29710) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_process() != 12583) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_remaining_capacity() != 49054) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_remove() != 23992) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_remove_async() != 26700) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_remove_at() != 48754) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_remove_chunked() != 61867) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_remove_n() != 24922) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_remove_n_async() != 11705) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_remove_n_with_timeout() != 23896) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_remove_with_timeout() != 590) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_reset_stats() != 16956) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_resume_writes() != 15795) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_rewrite() != 16612) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_sample() != 52957) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_set_cache_offset_policy() != 47790) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_set_excluded_from_backup() != 37408) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_set_max_commit_delay() != 24063) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_set_overwrite_on_remove() != 19230) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_set_read_buffer_size() != 3495) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_set_slow_op_listener() != 43772) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_set_sync_writes() != 45240) {
         return InitializationResult.apiChecksumMismatch
     }
-    if (uniffi_queuefile_checksum_method_queuefile_size() != 633) {
+    if (uniffi_queuefile_checksum_method_queuefile_set_watermarks() != 2033) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_set_write_buffering() != 7575) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_size() != 41608) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_size_histogram() != 32004) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_snapshot() != 37735) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_space_report() != 55201) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_stats() != 5516) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_subscribe_from() != 48785) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_swap_with() != 16761) {
         return InitializationResult.apiChecksumMismatch
     }
     if (uniffi_queuefile_checksum_method_queuefile_sync_all() != 31101) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_sync_all_async() != 27732) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_sync_all_with_timeout() != 27177) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_sync_from() != 5994) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_sync_writes() != 11479) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_train_dictionary() != 36721) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_unfreeze() != 58153) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_update_at() != 9217) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_method_queuefile_used_bytes() != 411) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_method_queuefile_watermarks() != 1817) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_window_stats() != 26447) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefile_write_buffering() != 55958) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefileiterator_generation() != 56525) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefileiterator_next() != 38936) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefileiterator_next_metadata() != 64319) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuefileiterator_size() != 12106) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuemanager_open() != 61391) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuemanager_open_file_count() != 3104) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuemanager_queue_count() != 50644) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuesnapshot_generation() != 57684) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuesnapshot_is_empty() != 37016) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuesnapshot_next() != 16692) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuesnapshot_read_remaining() != 37135) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_queuesnapshot_size() != 14227) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_retentionlistener_on_segments_dropped() != 3771) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentreader_info() != 37013) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentreader_read() != 12938) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentreader_read_all() != 32735) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_add() != 56555) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_add_multiple() != 62156) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_clear() != 25169) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_clear_retention_listener() != 20263) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_enforce_retention() != 18857) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_get_all() != 12627) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_is_empty() != 30472) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_open_segment_reader() != 64484) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_peek() != 53283) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_remove() != 30781) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_remove_n() != 55832) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_segment_count() != 39706) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_segments() != 14943) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_set_retention_listener() != 42629) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_size() != 39300) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_segmentedqueue_sync_all() != 18598) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_slowoplistener_on_slow_operation() != 26871) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_subscription_cancel() != 45978) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_subscription_is_active() != 15218) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_subscription_last_seq() != 32496) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_watermarklistener_on_high_watermark() != 37411) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_method_watermarklistener_on_low_watermark() != 61066) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_constructor_archivedqueue_open_compressed_read_only() != 25693) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_constructor_crashsimulator_new() != 45270) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_constructor_mappedqueue_open_snapshot_mmap() != 11405) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_constructor_queuefile_open() != 9145) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_constructor_queuefile_open_async() != 62947) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_constructor_queuefile_open_with_options() != 50587) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_constructor_queuefile_open_with_options_async() != 5252) {
+        return InitializationResult.apiChecksumMismatch
+    }
     if (uniffi_queuefile_checksum_constructor_queuefile_with_capacity() != 30355) {
         return InitializationResult.apiChecksumMismatch
     }
+    if (uniffi_queuefile_checksum_constructor_queuemanager_new() != 61589) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_constructor_segmentedqueue_open() != 26774) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_queuefile_checksum_constructor_segmentedqueue_open_with_config() != 5570) {
+        return InitializationResult.apiChecksumMismatch
+    }
 
+    uniffiCallbackInitDrainHandler()
+    uniffiCallbackInitElementListener()
+    uniffiCallbackInitExporter()
+    uniffiCallbackInitKeyProvider()
+    uniffiCallbackInitRetentionListener()
+    uniffiCallbackInitSlowOpListener()
+    uniffiCallbackInitWatermarkListener()
     return InitializationResult.ok
 }()
 
@@ -7,6 +7,8 @@ public enum QueueFileSwiftError: Error {
     case decodingError
 }
 
+/// Adds, reads, removals and syncs run on the Rust worker threads configured with
+/// `configureRuntime(config:)`, so they never block the caller's executor.
 public actor QueueFileSwiftQueue {
     private let queue: QueueFile
 
@@ -18,28 +20,242 @@ public actor QueueFileSwiftQueue {
         self.queue = try QueueFile.withCapacity(path: path, capacity: capacity)
     }
 
-    public func add(_ data: Data) throws {
-        try queue.add(data: data)
+    public init(path: String, options: QueueFileOptions) throws {
+        self.queue = try QueueFile.openWithOptions(path: path, options: options)
     }
 
-    public func addMultiple(_ items: [Data]) throws {
-        try queue.addMultiple(items: items)
+    /// Opens the queue through `manager`, which counts it against its cap on open files.
+    public init(
+        manager: QueueManager,
+        path: String,
+        options: QueueFileOptions = QueueFileOptions()
+    ) throws {
+        self.queue = try manager.open(path: path, options: options)
     }
 
-    public func peek() throws -> Data? {
-        return try queue.peek()
+    private init(queue: QueueFile) {
+        self.queue = queue
     }
 
-    public func remove() throws {
-        try queue.remove()
+    /// Opens the queue on a Rust worker thread, including the scans `lazyOpen` would defer, so
+    /// a large or cold file never blocks the caller.
+    public static func open(
+        path: String,
+        options: QueueFileOptions = QueueFileOptions()
+    ) async throws -> QueueFileSwiftQueue {
+        return QueueFileSwiftQueue(
+            queue: try await QueueFile.openWithOptionsAsync(path: path, options: options)
+        )
     }
 
-    public func removeN(_ n: UInt32) throws {
-        try queue.removeN(n: n)
+    @discardableResult
+    public func add(_ data: Data) async throws -> AddReceipt {
+        return try await queue.addAsync(data: data)
     }
 
-    public func clear() throws {
-        try queue.clear()
+    /// Enqueues a file's contents without reading it into `Data` first. The contents are still
+    /// held in memory once while they are added, so a file over 64 MiB (or `maxReadBytes`)
+    /// throws `TooLargeForRead`; use `addChunkedFromFile` for those.
+    @discardableResult
+    public func addFromFile(_ path: String) async throws -> UInt64 {
+        return try await queue.addFromFileAsync(srcPath: path)
+    }
+
+    @discardableResult
+    public func addMultiple(_ items: [Data]) async throws -> AddReceipt {
+        return try await queue.addMultipleAsync(items: items)
+    }
+
+    public func peek() async throws -> Data? {
+        return try await queue.peekAsync()
+    }
+
+    /// Enqueues `envelope` in the protobuf format of `proto/element_envelope.proto`.
+    @discardableResult
+    public func addEnvelope(_ envelope: ElementEnvelope) throws -> AddReceipt {
+        return try queue.addEnvelope(envelope: envelope)
+    }
+
+    public func peekEnvelope() throws -> ElementEnvelope? {
+        return try queue.peekEnvelope()
+    }
+
+    /// The head's size, sequence id and envelope fields, without its payload.
+    public func peekMetadata() throws -> ElementMeta? {
+        return try queue.peekMetadata()
+    }
+
+    /// Enqueues `map` as a MessagePack map, serialized in Rust.
+    @discardableResult
+    public func addMsgpackMap(_ map: [String: MsgpackValue]) throws -> AddReceipt {
+        return try queue.addMsgpackMap(map: map)
+    }
+
+    public func peekMsgpackMap() throws -> [String: MsgpackValue]? {
+        return try queue.peekMsgpackMap()
+    }
+
+    /// Enqueues `data` split into elements of at most `chunkLen` bytes, for payloads over the
+    /// 2 GiB element limit.
+    @discardableResult
+    public func addChunked(_ data: Data, chunkLen: UInt32? = nil) throws -> AddReceipt {
+        return try queue.addChunked(data: data, chunkLen: chunkLen)
+    }
+
+    /// Enqueues the file at `path` like `addChunked`, reading it one chunk at a time. If it
+    /// throws part way, the chunks already added stay queued until `removeChunked`.
+    @discardableResult
+    public func addChunkedFromFile(_ path: String, chunkLen: UInt32? = nil) throws -> UInt64 {
+        return try queue.addChunkedFromFile(srcPath: path, chunkLen: chunkLen)
+    }
+
+    public func peekChunked() throws -> Data? {
+        return try queue.peekChunked()
+    }
+
+    @discardableResult
+    public func popChunkedToFile(_ path: String) throws -> UInt64? {
+        return try queue.popChunkedToFile(destPath: path)
+    }
+
+    public func removeChunked() throws {
+        try queue.removeChunked()
+    }
+
+    public func peekRandom(seed: UInt64? = nil) throws -> Data? {
+        return try queue.peekRandom(seed: seed)
+    }
+
+    public func elementAt(_ index: UInt32) throws -> Data {
+        return try queue.elementAt(index: index)
+    }
+
+    public func sample(_ n: UInt32, seed: UInt64? = nil) throws -> [Data] {
+        return try queue.sample(n: n, seed: seed)
+    }
+
+    @discardableResult
+    public func exportNdjson(to path: String, encoding: ExportEncoding? = nil) throws -> UInt64 {
+        return try queue.exportNdjson(path: path, encoding: encoding)
+    }
+
+    @discardableResult
+    public func exportCsv(to path: String) throws -> UInt64 {
+        return try queue.exportCsv(path: path)
+    }
+
+    /// Adds `column` of every row of an SQLite table, in rowid order.
+    @discardableResult
+    public func importFromSqlite(_ dbPath: String, table: String, column: String) throws -> UInt64 {
+        return try queue.importFromSqlite(dbPath: dbPath, table: table, column: column)
+    }
+
+    @discardableResult
+    public func exportToSqlite(_ dbPath: String, table: String) throws -> UInt64 {
+        return try queue.exportToSqlite(dbPath: dbPath, table: table)
+    }
+
+    public func export(with exporter: Exporter) throws -> UInt64 {
+        return try queue.exportWith(exporter: exporter)
+    }
+
+    public func probablyContains(_ data: Data) throws -> Bool {
+        return try queue.probablyContains(data: data)
+    }
+
+    public func findIndices(prefix: Data, limit: UInt32) throws -> [UInt32] {
+        return try queue.findIndices(prefix: prefix, limit: limit)
+    }
+
+    public func remove() async throws {
+        try await queue.removeAsync()
+    }
+
+    /// SHA-256 of the oldest item, without transferring its payload; nil if the queue is empty.
+    public func peekDigest() throws -> Data? {
+        return try queue.peekDigest()
+    }
+
+    /// Writes the oldest item to `path` without loading it into `Data`; nil if the queue is empty.
+    @discardableResult
+    public func peekToFile(_ path: String) async throws -> UInt64? {
+        return try await queue.peekToFileAsync(destPath: path)
+    }
+
+    /// Like `peekToFile`, then removes the item once the file is on disk.
+    @discardableResult
+    public func popToFile(_ path: String) async throws -> UInt64? {
+        return try await queue.popToFileAsync(destPath: path)
+    }
+
+    public func removeN(_ n: UInt32) async throws {
+        try await queue.removeNAsync(n: n)
+    }
+
+    /// Hands elements to `handler` on a Rust worker thread, removing each one it accepts.
+    @discardableResult
+    public func drain(with handler: DrainHandler, max: UInt32) async throws -> UInt32 {
+        return try await queue.drainWithAsync(handler: handler, max: max)
+    }
+
+    /// Delivers elements to `handler` on the Rust worker threads until the consumer is stopped.
+    public func process(
+        with handler: DrainHandler,
+        policy: ProcessPolicy = ProcessPolicy(),
+        deadLetter: QueueFileSwiftQueue? = nil
+    ) throws -> QueueConsumer {
+        return try queue.process(handler: handler, policy: policy, deadLetter: deadLetter?.queue)
+    }
+
+    /// Passes every element after sequence id `seq` to `listener` on a dedicated thread, the
+    /// queued ones first and then each add as it commits, until the subscription is cancelled.
+    public func subscribe(from seq: UInt64? = nil, listener: ElementListener) throws -> Subscription {
+        return try queue.subscribeFrom(seq: seq, listener: listener)
+    }
+
+    public func removeAt(_ index: UInt32) throws {
+        try queue.removeAt(index: index)
+    }
+
+    public func updateAt(_ index: UInt32, data: Data) throws {
+        try queue.updateAt(index: index, data: data)
+    }
+
+    public func rewrite(_ options: RewriteOptions = RewriteOptions()) throws -> RewriteReport {
+        return try queue.rewrite(options: options)
+    }
+
+    public func clear() async throws {
+        try await queue.clearAsync()
+    }
+
+    public func swapWith(_ other: QueueFileSwiftQueue) throws {
+        try queue.swapWith(other: other.queue)
+    }
+
+    public func sync(from source: QueueFileSwiftQueue, strategy: SyncStrategy) throws -> SyncReport {
+        return try queue.syncFrom(source: source.queue, strategy: strategy)
+    }
+
+    public func freeze() throws {
+        try queue.freeze()
+    }
+
+    public func unfreeze() throws {
+        try queue.unfreeze()
+    }
+
+    public func isFrozen() throws -> Bool {
+        return try queue.isFrozen()
+    }
+
+    public func pauseWrites(_ mode: PauseMode) throws {
+        try queue.pauseWrites(mode: mode)
+    }
+
+    /// Not isolated to the actor, so it cannot be stuck behind an add blocked by the pause.
+    public nonisolated func resumeWrites() throws {
+        try queue.resumeWrites()
     }
 
     public func isEmpty() throws -> Bool {
@@ -58,12 +274,75 @@ public actor QueueFileSwiftQueue {
         return try queue.usedBytes()
     }
 
-    public func getAll() throws -> [Data] {
-        return try queue.getAll()
+    public func diskUsage() throws -> UInt64 {
+        return try queue.diskUsage()
     }
 
-    public func syncAll() throws {
-        try queue.syncAll()
+    public func spaceReport() throws -> SpaceReport {
+        return try queue.spaceReport()
+    }
+
+    public func fileInfo() throws -> FileInfo {
+        return try queue.fileInfo()
+    }
+
+    public func debugDump(maxElements: UInt32 = 10, maxBytesPerElement: UInt32 = 64) throws -> String {
+        return try queue.debugDump(maxElements: maxElements, maxBytesPerElement: maxBytesPerElement)
+    }
+
+    public func fragmentationReport() throws -> FragmentationReport {
+        return try queue.fragmentationReport()
+    }
+
+    public func capacityReport() throws -> CapacityReport {
+        return try queue.capacityReport()
+    }
+
+    public func sizeHistogram(_ bucketBounds: [UInt64]) throws -> [UInt64] {
+        return try queue.sizeHistogram(bucketBounds: bucketBounds)
+    }
+
+    public func trainDictionary(sampleLimit: UInt32) throws -> TrainedDictionary {
+        return try queue.trainDictionary(sampleLimit: sampleLimit)
+    }
+
+    public func getAll() async throws -> [Data] {
+        return try await queue.getAllAsync()
+    }
+
+    public func snapshot() throws -> QueueSnapshot {
+        return try queue.snapshot()
+    }
+
+    /// Iterates the elements queued now. `.failOnChange` throws `ConcurrentModification` once
+    /// they change; `.pinned` keeps returning them.
+    public func iterator(mode: IterationMode = .failOnChange) throws -> QueueFileIterator {
+        return try queue.iterator(mode: mode)
+    }
+
+    public func syncAll() async throws {
+        try await queue.syncAllAsync()
+    }
+
+    /// Runs the scans a `lazyOpen` queue deferred, on a Rust worker thread.
+    public func finishOpen() async throws {
+        try await queue.finishOpenAsync()
+    }
+
+    public func flush() throws {
+        try queue.flush()
+    }
+
+    public func gc() throws {
+        try queue.gc()
+    }
+
+    public func setWriteBuffering(_ config: WriteBufferConfig?) throws {
+        try queue.setWriteBuffering(config: config)
+    }
+
+    public func writeBuffering() throws -> WriteBufferConfig? {
+        return try queue.writeBuffering()
     }
 
     public func setSyncWrites(_ value: Bool) throws {
@@ -74,6 +353,14 @@ public actor QueueFileSwiftQueue {
         return try queue.syncWrites()
     }
 
+    public func setMaxCommitDelay(_ value: TimeInterval?) throws {
+        try queue.setMaxCommitDelay(value: value)
+    }
+
+    public func maxCommitDelay() throws -> TimeInterval? {
+        return try queue.maxCommitDelay()
+    }
+
     public func setOverwriteOnRemove(_ value: Bool) throws {
         try queue.setOverwriteOnRemove(value: value)
     }
@@ -85,6 +372,84 @@ public actor QueueFileSwiftQueue {
     public func setCacheOffsetPolicy(_ policy: OffsetCachePolicy) throws {
         try queue.setCacheOffsetPolicy(policy: policy)
     }
+
+    public func remainingCapacity() throws -> CapacityInfo {
+        return try queue.remainingCapacity()
+    }
+
+    public func canAccept(_ len: UInt64) throws -> Bool {
+        return try queue.canAccept(len: len)
+    }
+
+    public func setWatermarks(_ config: WatermarkConfig, listener: WatermarkListener) throws {
+        try queue.setWatermarks(config: config, listener: listener)
+    }
+
+    public func clearWatermarks() throws {
+        try queue.clearWatermarks()
+    }
+
+    public func watermarks() throws -> WatermarkConfig? {
+        return try queue.watermarks()
+    }
+
+    public func setSlowOpListener(threshold: TimeInterval, listener: SlowOpListener) throws {
+        try queue.setSlowOpListener(threshold: threshold, listener: listener)
+    }
+
+    public func clearSlowOpListener() throws {
+        try queue.clearSlowOpListener()
+    }
+
+    public func setExcludedFromBackup(_ excluded: Bool) throws {
+        try queue.setExcludedFromBackup(excluded: excluded)
+    }
+
+    public func mirrorStatus() throws -> MirrorStatus {
+        return try queue.mirrorStatus()
+    }
+
+    public func lastSyncCheck() throws -> SyncCheck? {
+        return try queue.lastSyncCheck()
+    }
+
+    public func lastQuarantine() throws -> QuarantineReport? {
+        return try queue.lastQuarantine()
+    }
+
+    public func lastCorruptionRecovery() throws -> CorruptionRecovery? {
+        return try queue.lastCorruptionRecovery()
+    }
+
+    public func generation() throws -> UInt64 {
+        return try queue.generation()
+    }
+
+    public func changes(since seq: UInt64) throws -> [ChangeRecord] {
+        return try queue.changesSince(since: seq)
+    }
+
+    public func stats() throws -> QueueStats {
+        return try queue.stats()
+    }
+
+    public func windowStats() throws -> StatsWindow {
+        return try queue.windowStats()
+    }
+
+    /// Returns the stats since the previous reset and starts counting again from zero.
+    @discardableResult
+    public func resetStats() throws -> StatsWindow {
+        return try queue.resetStats()
+    }
+
+    public func healthCheck() throws -> HealthReport {
+        return try queue.healthCheck()
+    }
+
+    public func setReadBufferSize(_ size: UInt32) throws {
+        try queue.setReadBufferSize(size: size)
+    }
 }
 
 public actor CodableQueueFile<T: Codable & Sendable> {
@@ -100,14 +465,32 @@ public actor CodableQueueFile<T: Codable & Sendable> {
         self.queue = try QueueFileSwiftQueue(path: path, capacity: capacity)
     }
 
-    public func add(_ item: T) async throws {
+    public init(path: String, options: QueueFileOptions) throws {
+        self.queue = try QueueFileSwiftQueue(path: path, options: options)
+    }
+
+    private init(queue: QueueFileSwiftQueue) {
+        self.queue = queue
+    }
+
+    /// Opens the queue on a Rust worker thread; see `QueueFileSwiftQueue.open(path:options:)`.
+    public static func open(
+        path: String,
+        options: QueueFileOptions = QueueFileOptions()
+    ) async throws -> CodableQueueFile<T> {
+        return CodableQueueFile(queue: try await QueueFileSwiftQueue.open(path: path, options: options))
+    }
+
+    @discardableResult
+    public func add(_ item: T) async throws -> AddReceipt {
         let data = try encoder.encode(item)
-        try await queue.add(data)
+        return try await queue.add(data)
     }
 
-    public func addMultiple(_ items: [T]) async throws {
+    @discardableResult
+    public func addMultiple(_ items: [T]) async throws -> AddReceipt {
         let dataItems = try items.map { try encoder.encode($0) }
-        try await queue.addMultiple(dataItems)
+        return try await queue.addMultiple(dataItems)
     }
 
     public func peek() async throws -> T? {
@@ -125,6 +508,15 @@ public actor CodableQueueFile<T: Codable & Sendable> {
         try await queue.removeN(n)
     }
 
+    public func removeAt(_ index: UInt32) async throws {
+        try await queue.removeAt(index)
+    }
+
+    public func updateAt(_ index: UInt32, _ item: T) async throws {
+        let data = try encoder.encode(item)
+        try await queue.updateAt(index, data: data)
+    }
+
     public func clear() async throws {
         try await queue.clear()
     }
@@ -142,7 +534,21 @@ public actor CodableQueueFile<T: Codable & Sendable> {
         return try dataItems.map { try decoder.decode(T.self, from: $0) }
     }
 
+    public func snapshot() async throws -> QueueSnapshot {
+        return try await queue.snapshot()
+    }
+
+    /// Iterates the elements queued now. `.failOnChange` throws `ConcurrentModification` once
+    /// they change; `.pinned` keeps returning them.
+    public func iterator(mode: IterationMode = .failOnChange) async throws -> QueueFileIterator {
+        return try await queue.iterator(mode: mode)
+    }
+
     public func syncAll() async throws {
         try await queue.syncAll()
     }
+
+    public func flush() async throws {
+        try await queue.flush()
+    }
 }
@@ -78,3 +78,47 @@ struct TestItem: Codable, Sendable, Equatable {
 
     try FileManager.default.removeItem(atPath: queuePath)
 }
+
+@Test func testMirrorRestoresPrimary() async throws {
+    let tempDir = FileManager.default.temporaryDirectory
+    let id = UUID().uuidString
+    let queuePath = tempDir.appendingPathComponent("test_mirror_\(id).qf").path
+    let mirrorPath = tempDir.appendingPathComponent("test_mirror_\(id).mirror.qf").path
+    let options = QueueFileOptions(mirrorPath: mirrorPath)
+
+    do {
+        let queue = try QueueFileSwiftQueue(path: queuePath, options: options)
+        #expect(try await queue.mirrorStatus() == .inSync)
+        try await queue.add("Mirrored".data(using: .utf8)!)
+    }
+
+    try FileManager.default.removeItem(atPath: queuePath)
+
+    let restored = try QueueFileSwiftQueue(path: queuePath, options: options)
+    #expect(try await restored.mirrorStatus() == .primaryRestored)
+    #expect(try await restored.peek() == "Mirrored".data(using: .utf8)!)
+
+    try FileManager.default.removeItem(atPath: queuePath)
+    try FileManager.default.removeItem(atPath: mirrorPath)
+}
+
+@Test func testCrashSimulationRecoversPrefix() async throws {
+    let tempDir = FileManager.default.temporaryDirectory
+    let queuePath = tempDir.appendingPathComponent("test_crash_\(UUID().uuidString).qf").path
+
+    let queue = try QueueFile.open(path: queuePath)
+    try queue.setSyncWrites(value: true)
+    let simulator = try CrashSimulator(path: queuePath)
+
+    for i in 0..<8 {
+        try queue.add(data: Data(repeating: UInt8(i), count: 700))
+        _ = try simulator.checkpoint()
+    }
+    try queue.removeN(n: 3)
+    _ = try simulator.checkpoint()
+
+    #expect(try simulator.verify(mode: .truncated).isEmpty)
+    #expect(try simulator.verify(mode: .garbled).isEmpty)
+
+    try FileManager.default.removeItem(atPath: queuePath)
+}
@@ -0,0 +1,21 @@
+fn main() {
+    #[cfg(feature = "c-api")]
+    generate_c_header();
+}
+
+/// Writes include/queuefile_c.h for the `extern "C"` functions in src/c_api.rs.
+#[cfg(feature = "c-api")]
+fn generate_c_header() {
+    println!("cargo:rerun-if-changed=src/c_api.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml"))
+        .expect("invalid cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("failed to generate the C header")
+        .write_to_file(format!("{crate_dir}/include/queuefile_c.h"));
+}
@@ -0,0 +1,137 @@
+#ifndef QUEUEFILE_C_H
+#define QUEUEFILE_C_H
+
+/* Generated by cbindgen from src/c_api.rs with the `c-api` feature. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The header version of versioned files. queue-file opens no other and fails with
+ * `UnsupportedVersion` for the rest.
+ */
+#define VERSIONED_FORMAT_VERSION (VERSIONED_HEADER & 2147483647)
+
+/**
+ * Reported as the version of legacy files, whose header has no version field.
+ */
+#define LEGACY_FORMAT_VERSION 0
+
+typedef enum QfStatus {
+  QF_STATUS_OK = 0,
+  QF_STATUS_IO_ERROR,
+  QF_STATUS_EMPTY_QUEUE,
+  QF_STATUS_ELEMENT_TOO_BIG,
+  QF_STATUS_TOO_MANY_ELEMENTS,
+  QF_STATUS_CORRUPTED_FILE,
+  QF_STATUS_UNSUPPORTED_VERSION,
+  QF_STATUS_LOCK_ERROR,
+  QF_STATUS_QUOTA_EXCEEDED,
+  QF_STATUS_DATA_PROTECTION_LOCKED,
+  QF_STATUS_ALREADY_OPEN,
+  QF_STATUS_LOCKED_BY_OTHER_PROCESS,
+  QF_STATUS_FROZEN,
+  QF_STATUS_WRITES_PAUSED,
+  QF_STATUS_RUNTIME_ALREADY_STARTED,
+  QF_STATUS_INDEX_OUT_OF_RANGE,
+  QF_STATUS_JOURNAL_GAP,
+  QF_STATUS_BACKPRESSURE,
+  QF_STATUS_CONCURRENT_MODIFICATION,
+  QF_STATUS_TOO_LARGE_FOR_READ,
+  QF_STATUS_TIMEOUT,
+  QF_STATUS_TAMPER_DETECTED,
+  QF_STATUS_KEY_UNAVAILABLE,
+  QF_STATUS_INVALID_ENVELOPE,
+  QF_STATUS_INVALID_MESSAGE_PACK,
+  QF_STATUS_SQLITE_ERROR,
+  QF_STATUS_INVALID_CHUNK,
+  QF_STATUS_CLOSED_FOR_INACTIVITY,
+  /**
+   * A required pointer was null or the path was not valid UTF-8.
+   */
+  QF_STATUS_INVALID_ARGUMENT,
+} QfStatus;
+
+/**
+ * An open queue. Created by `qf_open` and released with `qf_free`.
+ */
+typedef struct QfQueue QfQueue;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Opens or creates the queue file at `path` and stores the handle in `*out`.
+ *
+ * # Safety
+ *
+ * `path` must be a NUL-terminated string and `out` must be valid for writes.
+ */
+enum QfStatus qf_open(const char *path, struct QfQueue **out);
+
+/**
+ * Appends `len` bytes from `data` as one element.
+ *
+ * # Safety
+ *
+ * `queue` must come from `qf_open` and `data` must be valid for reads of `len` bytes (it may
+ * be null when `len` is 0).
+ */
+enum QfStatus qf_add(const struct QfQueue *queue, const uint8_t *data, size_t len);
+
+/**
+ * Copies the eldest element into a new buffer stored in `*data` and `*len`, to be released
+ * with `qf_buffer_free`. An empty queue yields a null buffer and `QF_STATUS_OK`.
+ *
+ * # Safety
+ *
+ * `queue` must come from `qf_open`; `data` and `len` must be valid for writes.
+ */
+enum QfStatus qf_peek(const struct QfQueue *queue, uint8_t **data, size_t *len);
+
+/**
+ * Removes the eldest element, if any.
+ *
+ * # Safety
+ *
+ * `queue` must come from `qf_open`.
+ */
+enum QfStatus qf_remove(const struct QfQueue *queue);
+
+/**
+ * Stores the number of elements in `*out`.
+ *
+ * # Safety
+ *
+ * `queue` must come from `qf_open` and `out` must be valid for writes.
+ */
+enum QfStatus qf_size(const struct QfQueue *queue, uint32_t *out);
+
+/**
+ * Releases a buffer returned by `qf_peek`. Null is ignored.
+ *
+ * # Safety
+ *
+ * `data` and `len` must be exactly as returned by `qf_peek`, and the buffer must not be used
+ * afterwards.
+ */
+void qf_buffer_free(uint8_t *data, size_t len);
+
+/**
+ * Flushes and closes the queue. Null is ignored.
+ *
+ * # Safety
+ *
+ * `queue` must come from `qf_open` and must not be used afterwards.
+ */
+void qf_free(struct QfQueue *queue);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* QUEUEFILE_C_H */
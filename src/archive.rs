@@ -0,0 +1,310 @@
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use flate2::read::MultiGzDecoder;
+
+use crate::dictionary::{self, Codec};
+use crate::export::{self, ChunkSink, FileExporter};
+use crate::verify::{self, Corruption, Header};
+use crate::{ExportEncoding, Exporter, QueueFileError};
+
+/// A queue file compressed with gzip, read by decompressing it as it goes. Elements can only be
+/// read in order; `rewind` starts over from the eldest.
+#[derive(uniffi::Object)]
+pub struct ArchivedQueue {
+    path: PathBuf,
+    /// The dictionaries of the archived queue, if it compressed its elements.
+    codec: Codec,
+    size: u32,
+    cursor: Mutex<ElementStream>,
+}
+
+/// Reads the elements of a compressed queue file in order.
+struct ElementStream {
+    path: PathBuf,
+    header: Header,
+    reader: MultiGzDecoder<BufReader<File>>,
+    /// Position of `reader` in the uncompressed file.
+    pos: u64,
+    /// Elements read so far.
+    read: u64,
+}
+
+impl ElementStream {
+    fn open(path: &Path) -> io::Result<Result<Self, Corruption>> {
+        let mut reader = decoder(path)?;
+        let mut bytes = [0; 32];
+        let header_len = match read_full(&mut reader, &mut bytes[..16])? {
+            16 if bytes[..4] == verify::VERSIONED_HEADER.to_be_bytes() => 32,
+            16 => 16,
+            _ => 0,
+        };
+        if header_len == 0 || read_full(&mut reader, &mut bytes[16..header_len])? < header_len - 16
+        {
+            return Ok(Err(Corruption::header(
+                "file is shorter than its header".to_string(),
+                "a complete header".to_string(),
+                "end of file".to_string(),
+            )));
+        }
+        let header = Header::parse(&bytes);
+        let (file_len, count) = (header.file_len, header.count);
+        if file_len < header.header_len + 4 {
+            return Ok(Err(Corruption::header(
+                format!("header file length {file_len} is shorter than the header"),
+                format!("header file length >= {}", header.header_len + 4),
+                format!("header file length {file_len}"),
+            )));
+        }
+        let ring = header.header_len..file_len;
+        if count > 0 && !(ring.contains(&header.first) && ring.contains(&header.last)) {
+            return Ok(Err(Corruption::header(
+                format!(
+                    "element offsets {} and {} lie outside the ring",
+                    header.first, header.last
+                ),
+                format!("element offsets in {}..{file_len}", header.header_len),
+                format!("element offsets {} and {}", header.first, header.last),
+            )));
+        }
+        let mut stream = ElementStream {
+            path: path.to_path_buf(),
+            pos: header.header_len,
+            header,
+            reader,
+            read: 0,
+        };
+        if count > 0 {
+            stream.skip_to(stream.header.first)?;
+        }
+        Ok(Ok(stream))
+    }
+
+    /// Reads the next element into `buf`, or returns `false` after the last one.
+    fn next_into(&mut self, buf: &mut Vec<u8>) -> io::Result<Result<bool, Corruption>> {
+        let (index, pos) = (self.read, self.pos);
+        if index == self.header.count {
+            return Ok(Ok(false));
+        }
+        let mut len = [0; 4];
+        self.read_ring(&mut len)?;
+        let len = u64::from(u32::from_be_bytes(len));
+        let max_len = self.header.file_len - self.header.header_len - 4;
+        if len > max_len {
+            return Ok(Err(Corruption::element(
+                index,
+                pos,
+                &format!("at offset {pos} has impossible length {len}"),
+                format!("length <= {max_len}"),
+                format!("length {len}"),
+            )));
+        }
+        let last = self.header.last;
+        if index + 1 == self.header.count && pos != last {
+            return Ok(Err(Corruption::element(
+                index,
+                pos,
+                &format!("found at offset {pos}, but the header puts the last element at {last}"),
+                format!("last element at offset {last}"),
+                format!("last element at offset {pos}"),
+            )));
+        }
+        buf.resize(len as usize, 0);
+        self.read_ring(buf)?;
+        self.read += 1;
+        Ok(Ok(true))
+    }
+
+    /// Reads from the ring at the current position. Reaching the end of the ring starts a new
+    /// decoder and skips it to just after the header, so wrapped elements need no buffering.
+    fn read_ring(&mut self, mut buf: &mut [u8]) -> io::Result<()> {
+        while !buf.is_empty() {
+            let take = (self.header.file_len - self.pos).min(buf.len() as u64) as usize;
+            let (head, rest) = buf.split_at_mut(take);
+            self.reader.read_exact(head)?;
+            self.pos += take as u64;
+            buf = rest;
+            if self.pos == self.header.file_len {
+                self.reader = decoder(&self.path)?;
+                self.pos = 0;
+                self.skip_to(self.header.header_len)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_to(&mut self, pos: u64) -> io::Result<()> {
+        let n = pos - self.pos;
+        if io::copy(&mut self.reader.by_ref().take(n), &mut io::sink())? < n {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.pos = pos;
+        Ok(())
+    }
+}
+
+/// Where the queue archived at `path` kept its files: `queue.qf` for `queue.qf.gz`.
+fn queue_path(path: &Path) -> PathBuf {
+    match path.extension().is_some_and(|extension| extension == "gz") {
+        true => path.with_extension(""),
+        false => path.to_path_buf(),
+    }
+}
+
+fn decoder(path: &Path) -> io::Result<MultiGzDecoder<BufReader<File>>> {
+    Ok(MultiGzDecoder::new(BufReader::new(File::open(path)?)))
+}
+
+/// Like `read_exact`, but returns how much was read before the end of the stream.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// Turns the outcome of reading the archive at `path` into the queue's error type.
+fn tagged<T>(path: &Path, result: io::Result<Result<T, Corruption>>) -> Result<T, QueueFileError> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(corruption)) => Err(corruption.into_error(path)),
+        Err(e) => Err(QueueFileError::from(e).with_path(path)),
+    }
+}
+
+impl ArchivedQueue {
+    fn stream(&self) -> Result<ElementStream, QueueFileError> {
+        tagged(&self.path, ElementStream::open(&self.path))
+    }
+
+    fn next_of(
+        &self,
+        stream: &mut ElementStream,
+        buf: &mut Vec<u8>,
+    ) -> Result<bool, QueueFileError> {
+        let read = tagged(&self.path, stream.next_into(buf))?;
+        if read {
+            *buf = self.codec.decode(std::mem::take(buf), None)?;
+        }
+        Ok(read)
+    }
+
+    /// Calls `each` with every element of a fresh pass over the archive.
+    fn for_each(
+        &self,
+        mut each: impl FnMut(&[u8]) -> Result<(), QueueFileError>,
+    ) -> Result<(), QueueFileError> {
+        let mut stream = self.stream()?;
+        let mut element = Vec::new();
+        while self.next_of(&mut stream, &mut element)? {
+            each(&element)?;
+        }
+        Ok(())
+    }
+
+    fn export(&self, dest: &str, format: export::Format) -> Result<u64, QueueFileError> {
+        let mut exporter = FileExporter::create(Path::new(dest), format)?;
+        self.for_each(|element| exporter.write(element))?;
+        exporter.finish()
+    }
+}
+
+#[uniffi::export]
+impl ArchivedQueue {
+    /// Opens a gzip-compressed queue file (such as a `.qf.gz` archive) for reading. Only the
+    /// header is read here, along with the dictionaries of a queue that compresses its
+    /// elements, which stay uncompressed next to the archive under the queue's name
+    /// (`queue.qf.dict` for `queue.qf.gz`).
+    #[uniffi::constructor]
+    pub fn open_compressed_read_only(path: String) -> Result<Arc<Self>, QueueFileError> {
+        let path = PathBuf::from(path);
+        let stream = tagged(&path, ElementStream::open(&path))?;
+        let queue = queue_path(&path);
+        let codec = Codec::load(&queue)
+            .map_err(|e| QueueFileError::from(e).with_path(&dictionary::dictionary_path(&queue)))?;
+        Ok(Arc::new(ArchivedQueue {
+            codec,
+            size: stream.header.count as u32,
+            cursor: Mutex::new(stream),
+            path,
+        }))
+    }
+
+    #[uniffi::method]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    #[uniffi::method]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// The next element in order, or `None` after the last one.
+    #[uniffi::method]
+    pub fn next(&self) -> Result<Option<Vec<u8>>, QueueFileError> {
+        let mut stream = self.cursor.lock().map_err(|_| QueueFileError::LockError)?;
+        let mut element = Vec::new();
+        Ok(self.next_of(&mut stream, &mut element)?.then_some(element))
+    }
+
+    /// Starts `next` over from the eldest element.
+    #[uniffi::method]
+    pub fn rewind(&self) -> Result<(), QueueFileError> {
+        let stream = self.stream()?;
+        *self.cursor.lock().map_err(|_| QueueFileError::LockError)? = stream;
+        Ok(())
+    }
+
+    #[uniffi::method]
+    pub fn get_all(&self) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        let mut items = Vec::with_capacity(self.size as usize);
+        self.for_each(|element| {
+            items.push(element.to_vec());
+            Ok(())
+        })?;
+        Ok(items)
+    }
+
+    #[uniffi::method]
+    pub fn export_ndjson(
+        &self,
+        path: String,
+        encoding: Option<ExportEncoding>,
+    ) -> Result<u64, QueueFileError> {
+        let format = export::Format::Ndjson(encoding.unwrap_or(ExportEncoding::Base64));
+        self.export(&path, format)
+    }
+
+    #[uniffi::method]
+    pub fn export_csv(&self, path: String) -> Result<u64, QueueFileError> {
+        self.export(&path, export::Format::Csv)
+    }
+
+    /// Streams every element to `exporter`, framed as `QueueFile::export_with` does.
+    #[uniffi::method]
+    pub fn export_with(&self, exporter: Arc<dyn Exporter>) -> Result<u64, QueueFileError> {
+        let mut sink = ChunkSink::new(exporter);
+        self.for_each(|element| sink.write(element))?;
+        sink.finish()
+    }
+
+    /// Decompresses the whole archive, checking every element frame and the gzip checksums and
+    /// decoding every element compressed against a dictionary, and fails with `CorruptedFile` or an I/O error at the first problem.
+    #[uniffi::method]
+    pub fn verify(&self) -> Result<(), QueueFileError> {
+        let mut stream = self.stream()?;
+        let mut element = Vec::new();
+        while self.next_of(&mut stream, &mut element)? {}
+        // The gzip trailer is only checked once the stream has been read to its end.
+        io::copy(&mut stream.reader, &mut io::sink())
+            .map_err(|e| QueueFileError::from(e).with_path(&self.path))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use std::io;
+use std::path::Path;
+
+/// Marks `path` as excluded from iCloud and device backups, or clears the mark. Setting this
+/// extended attribute is what `URLResourceValues.isExcludedFromBackup` does.
+#[cfg(target_vendor = "apple")]
+pub(crate) fn set_excluded(path: &Path, excluded: bool) -> io::Result<()> {
+    use std::ffi::{CString, c_char, c_int, c_void};
+    use std::os::unix::ffi::OsStrExt;
+
+    unsafe extern "C" {
+        fn setxattr(
+            path: *const c_char,
+            name: *const c_char,
+            value: *const c_void,
+            size: usize,
+            position: u32,
+            options: c_int,
+        ) -> c_int;
+        fn removexattr(path: *const c_char, name: *const c_char, options: c_int) -> c_int;
+    }
+
+    const NAME: &std::ffi::CStr = c"com.apple.metadata:com_apple_backup_excludeItem";
+    const ENOATTR: i32 = 93;
+    /// The binary property list for the string "com.apple.backupd".
+    const VALUE: &[u8] = b"bplist00\x5f\x10\x11com.apple.backupd\x08\
+          \0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x1c";
+
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    // SAFETY: both strings are NUL-terminated and `VALUE` is valid for `VALUE.len()` bytes.
+    let status = unsafe {
+        match excluded {
+            true => setxattr(
+                path.as_ptr(),
+                NAME.as_ptr(),
+                VALUE.as_ptr().cast(),
+                VALUE.len(),
+                0,
+                0,
+            ),
+            false => removexattr(path.as_ptr(), NAME.as_ptr(), 0),
+        }
+    };
+    match status {
+        0 => Ok(()),
+        _ => match io::Error::last_os_error() {
+            e if !excluded && e.raw_os_error() == Some(ENOATTR) => Ok(()),
+            e => Err(e),
+        },
+    }
+}
+
+/// Only Apple platforms back up app containers; elsewhere there is nothing to mark.
+#[cfg(not(target_vendor = "apple"))]
+pub(crate) fn set_excluded(_path: &Path, _excluded: bool) -> io::Result<()> {
+    Ok(())
+}
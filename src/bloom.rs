@@ -0,0 +1,65 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Counters per expected element; with `HASHES` probes this gives about 1% false positives
+/// until the queue holds more elements than the filter was sized for.
+const SLOTS_PER_ELEMENT: u64 = 10;
+const HASHES: u64 = 7;
+
+/// A counting bloom filter over element payloads, so removals can be taken out again.
+/// Counters stick at 255 once saturated, which keeps them from ever under-counting.
+pub(crate) struct Bloom {
+    counters: Vec<u8>,
+}
+
+/// The two base hashes an element's probe positions are derived from.
+#[derive(Clone, Copy)]
+pub(crate) struct Probe(u64, u64);
+
+impl Probe {
+    pub(crate) fn of(data: &[u8]) -> Self {
+        let hash = |seed: u8| {
+            let mut hasher = DefaultHasher::new();
+            seed.hash(&mut hasher);
+            data.hash(&mut hasher);
+            hasher.finish()
+        };
+        Probe(hash(0), hash(1) | 1)
+    }
+}
+
+impl Bloom {
+    pub(crate) fn new(expected_elements: u64) -> Self {
+        let len = expected_elements.max(1).saturating_mul(SLOTS_PER_ELEMENT);
+        Bloom {
+            counters: vec![0; usize::try_from(len).unwrap_or(usize::MAX)],
+        }
+    }
+
+    fn slots(&self, probe: Probe) -> impl Iterator<Item = usize> + use<> {
+        let len = self.counters.len() as u64;
+        (0..HASHES).map(move |i| (probe.0.wrapping_add(i.wrapping_mul(probe.1)) % len) as usize)
+    }
+
+    pub(crate) fn insert(&mut self, probe: Probe) {
+        for slot in self.slots(probe) {
+            self.counters[slot] = self.counters[slot].saturating_add(1);
+        }
+    }
+
+    pub(crate) fn remove(&mut self, probe: Probe) {
+        for slot in self.slots(probe) {
+            let counter = &mut self.counters[slot];
+            if *counter != u8::MAX {
+                *counter = counter.saturating_sub(1);
+            }
+        }
+    }
+
+    pub(crate) fn contains(&self, probe: Probe) -> bool {
+        self.slots(probe).all(|slot| self.counters[slot] > 0)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.counters.fill(0);
+    }
+}
@@ -0,0 +1,227 @@
+//! Plain C API for consumers that cannot use the UniFFI bindings. Enabled by the `c-api`
+//! feature; `include/queuefile_c.h` is generated from this file by the build script.
+
+use std::ffi::{CStr, c_char};
+use std::ptr;
+use std::sync::Arc;
+
+use crate::{QueueFile, QueueFileError};
+
+/// An open queue. Created by `qf_open` and released with `qf_free`.
+pub struct QfQueue {
+    queue: Arc<QueueFile>,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QfStatus {
+    Ok = 0,
+    IoError,
+    EmptyQueue,
+    ElementTooBig,
+    TooManyElements,
+    CorruptedFile,
+    UnsupportedVersion,
+    LockError,
+    QuotaExceeded,
+    DataProtectionLocked,
+    AlreadyOpen,
+    LockedByOtherProcess,
+    Frozen,
+    WritesPaused,
+    RuntimeAlreadyStarted,
+    IndexOutOfRange,
+    JournalGap,
+    Backpressure,
+    ConcurrentModification,
+    TooLargeForRead,
+    Timeout,
+    TamperDetected,
+    KeyUnavailable,
+    InvalidEnvelope,
+    InvalidMessagePack,
+    SqliteError,
+    InvalidChunk,
+    ClosedForInactivity,
+    /// A required pointer was null or the path was not valid UTF-8.
+    InvalidArgument,
+}
+
+impl From<QueueFileError> for QfStatus {
+    fn from(e: QueueFileError) -> Self {
+        match e {
+            QueueFileError::IoError { .. } => QfStatus::IoError,
+            QueueFileError::EmptyQueue => QfStatus::EmptyQueue,
+            QueueFileError::ElementTooBig => QfStatus::ElementTooBig,
+            QueueFileError::TooManyElements => QfStatus::TooManyElements,
+            QueueFileError::CorruptedFile { .. } => QfStatus::CorruptedFile,
+            QueueFileError::UnsupportedVersion { .. } => QfStatus::UnsupportedVersion,
+            QueueFileError::LockError => QfStatus::LockError,
+            QueueFileError::QuotaExceeded => QfStatus::QuotaExceeded,
+            QueueFileError::DataProtectionLocked { .. } => QfStatus::DataProtectionLocked,
+            QueueFileError::AlreadyOpen { .. } => QfStatus::AlreadyOpen,
+            QueueFileError::LockedByOtherProcess { .. } => QfStatus::LockedByOtherProcess,
+            QueueFileError::Frozen => QfStatus::Frozen,
+            QueueFileError::WritesPaused => QfStatus::WritesPaused,
+            QueueFileError::RuntimeAlreadyStarted => QfStatus::RuntimeAlreadyStarted,
+            QueueFileError::IndexOutOfRange { .. } => QfStatus::IndexOutOfRange,
+            QueueFileError::JournalGap { .. } => QfStatus::JournalGap,
+            QueueFileError::Backpressure { .. } => QfStatus::Backpressure,
+            QueueFileError::ConcurrentModification { .. } => QfStatus::ConcurrentModification,
+            QueueFileError::TooLargeForRead { .. } => QfStatus::TooLargeForRead,
+            QueueFileError::Timeout { .. } => QfStatus::Timeout,
+            QueueFileError::TamperDetected { .. } => QfStatus::TamperDetected,
+            QueueFileError::KeyUnavailable { .. } => QfStatus::KeyUnavailable,
+            QueueFileError::InvalidEnvelope { .. } => QfStatus::InvalidEnvelope,
+            QueueFileError::InvalidMessagePack { .. } => QfStatus::InvalidMessagePack,
+            QueueFileError::SqliteError { .. } => QfStatus::SqliteError,
+            QueueFileError::InvalidChunk { .. } => QfStatus::InvalidChunk,
+            QueueFileError::ClosedForInactivity => QfStatus::ClosedForInactivity,
+        }
+    }
+}
+
+fn status(result: Result<(), QueueFileError>) -> QfStatus {
+    match result {
+        Ok(()) => QfStatus::Ok,
+        Err(e) => e.into(),
+    }
+}
+
+/// Opens or creates the queue file at `path` and stores the handle in `*out`.
+///
+/// # Safety
+///
+/// `path` must be a NUL-terminated string and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn qf_open(path: *const c_char, out: *mut *mut QfQueue) -> QfStatus {
+    if path.is_null() || out.is_null() {
+        return QfStatus::InvalidArgument;
+    }
+    let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+        return QfStatus::InvalidArgument;
+    };
+    match QueueFile::open(path.to_string()) {
+        Ok(queue) => {
+            unsafe { *out = Box::into_raw(Box::new(QfQueue { queue })) };
+            QfStatus::Ok
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Appends `len` bytes from `data` as one element.
+///
+/// # Safety
+///
+/// `queue` must come from `qf_open` and `data` must be valid for reads of `len` bytes (it may
+/// be null when `len` is 0).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn qf_add(queue: *const QfQueue, data: *const u8, len: usize) -> QfStatus {
+    let Some(queue) = (unsafe { queue.as_ref() }) else {
+        return QfStatus::InvalidArgument;
+    };
+    let data = match (data.is_null(), len) {
+        (_, 0) => Vec::new(),
+        (true, _) => return QfStatus::InvalidArgument,
+        (false, _) => unsafe { std::slice::from_raw_parts(data, len) }.to_vec(),
+    };
+    status(queue.queue.add(data).map(|_| ()))
+}
+
+/// Copies the eldest element into a new buffer stored in `*data` and `*len`, to be released
+/// with `qf_buffer_free`. An empty queue yields a null buffer and `QF_STATUS_OK`.
+///
+/// # Safety
+///
+/// `queue` must come from `qf_open`; `data` and `len` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn qf_peek(
+    queue: *const QfQueue,
+    data: *mut *mut u8,
+    len: *mut usize,
+) -> QfStatus {
+    let Some(queue) = (unsafe { queue.as_ref() }) else {
+        return QfStatus::InvalidArgument;
+    };
+    if data.is_null() || len.is_null() {
+        return QfStatus::InvalidArgument;
+    }
+    match queue.queue.peek() {
+        Ok(element) => {
+            let (buffer, size) = match element {
+                Some(element) => {
+                    let size = element.len();
+                    (Box::into_raw(element.into_boxed_slice()).cast::<u8>(), size)
+                }
+                None => (ptr::null_mut(), 0),
+            };
+            unsafe {
+                *data = buffer;
+                *len = size;
+            }
+            QfStatus::Ok
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Removes the eldest element, if any.
+///
+/// # Safety
+///
+/// `queue` must come from `qf_open`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn qf_remove(queue: *const QfQueue) -> QfStatus {
+    match unsafe { queue.as_ref() } {
+        Some(queue) => status(queue.queue.remove()),
+        None => QfStatus::InvalidArgument,
+    }
+}
+
+/// Stores the number of elements in `*out`.
+///
+/// # Safety
+///
+/// `queue` must come from `qf_open` and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn qf_size(queue: *const QfQueue, out: *mut u32) -> QfStatus {
+    let Some(queue) = (unsafe { queue.as_ref() }) else {
+        return QfStatus::InvalidArgument;
+    };
+    if out.is_null() {
+        return QfStatus::InvalidArgument;
+    }
+    match queue.queue.size() {
+        Ok(size) => {
+            unsafe { *out = size };
+            QfStatus::Ok
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Releases a buffer returned by `qf_peek`. Null is ignored.
+///
+/// # Safety
+///
+/// `data` and `len` must be exactly as returned by `qf_peek`, and the buffer must not be used
+/// afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn qf_buffer_free(data: *mut u8, len: usize) {
+    if !data.is_null() {
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(data, len)) });
+    }
+}
+
+/// Flushes and closes the queue. Null is ignored.
+///
+/// # Safety
+///
+/// `queue` must come from `qf_open` and must not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn qf_free(queue: *mut QfQueue) {
+    if !queue.is_null() {
+        drop(unsafe { Box::from_raw(queue) });
+    }
+}
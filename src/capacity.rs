@@ -0,0 +1,121 @@
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use crate::FragmentationReport;
+
+/// Growths `capacity_report` lists; older ones are only counted.
+const RECENT_GROWTHS: usize = 16;
+/// Below this, rewriting the file gives back too little to be worth recommending.
+const MIN_RECLAIM: u64 = 1 << 20;
+/// Growths since open after which the configured capacity is considered too small.
+const FREQUENT_GROWTHS: u64 = 3;
+/// Stored bytes and elements a queue needs before a trained dictionary is likely to pay off.
+const MIN_COMPRESS_BYTES: u64 = 1 << 20;
+const MIN_COMPRESS_ELEMENTS: u64 = 1000;
+
+/// One time an add lengthened the queue file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct GrowthEvent {
+    pub at: SystemTime,
+    pub from_len: u64,
+    pub to_len: u64,
+}
+
+/// What `capacity_report` suggests doing about the queue's file, the most pressing first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum CapacityRecommendation {
+    /// Nothing; the file fits its contents.
+    None,
+    /// Most of the file is free space a `rewrite` would give back.
+    CompactNow,
+    /// The file keeps growing from the configured capacity; open with `capacity` instead.
+    RaiseCapacity { capacity: u64 },
+    /// The queue holds enough uncompressed data for `train_dictionary` to shrink it.
+    EnableCompression,
+}
+
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct CapacityReport {
+    pub file_len: u64,
+    pub used_bytes: u64,
+    pub element_count: u64,
+    /// Stored bytes per element, without the length in front of each one. 0 when empty.
+    pub avg_element_size: u64,
+    /// `reclaimable_bytes / file_len`: the share of the file a rewrite would give back.
+    pub fragmentation_ratio: f64,
+    pub reclaimable_bytes: u64,
+    /// Times adds grew the file since the queue was opened.
+    pub growths: u64,
+    /// The latest of those growths, oldest first.
+    pub recent_growths: Vec<GrowthEvent>,
+    pub compressed: bool,
+    pub recommendation: CapacityRecommendation,
+}
+
+/// The growths of a queue's file since it was opened.
+#[derive(Default)]
+pub(crate) struct Growths {
+    count: u64,
+    recent: VecDeque<GrowthEvent>,
+}
+
+impl Growths {
+    pub(crate) fn record(&mut self, from_len: u64, to_len: u64) {
+        if self.recent.len() == RECENT_GROWTHS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(GrowthEvent {
+            at: SystemTime::now(),
+            from_len,
+            to_len,
+        });
+        self.count += 1;
+    }
+}
+
+/// The report for a queue with the layout `fragmentation`, `element_count` elements behind a
+/// `header_len`-byte header and the configured `capacity`.
+pub(crate) fn report(
+    fragmentation: &FragmentationReport,
+    header_len: u64,
+    element_count: u64,
+    capacity: u64,
+    compressed: bool,
+    growths: &Growths,
+) -> CapacityReport {
+    let (file_len, used_bytes) = (fragmentation.file_len, fragmentation.used_bytes);
+    let stored = used_bytes.saturating_sub(header_len + 4 * element_count);
+    let reclaimable_bytes = fragmentation.reclaimable_bytes;
+    let peak = growths
+        .recent
+        .iter()
+        .map(|growth| growth.to_len)
+        .max()
+        .unwrap_or(0)
+        .max(file_len);
+    let recommendation = if reclaimable_bytes >= MIN_RECLAIM && reclaimable_bytes * 2 >= file_len {
+        CapacityRecommendation::CompactNow
+    } else if growths.count >= FREQUENT_GROWTHS && capacity < peak {
+        CapacityRecommendation::RaiseCapacity { capacity: peak }
+    } else if !compressed && stored >= MIN_COMPRESS_BYTES && element_count >= MIN_COMPRESS_ELEMENTS
+    {
+        CapacityRecommendation::EnableCompression
+    } else {
+        CapacityRecommendation::None
+    };
+    CapacityReport {
+        file_len,
+        used_bytes,
+        element_count,
+        avg_element_size: stored.checked_div(element_count).unwrap_or(0),
+        fragmentation_ratio: match file_len {
+            0 => 0.0,
+            len => reclaimable_bytes as f64 / len as f64,
+        },
+        reclaimable_bytes,
+        growths: growths.count,
+        recent_growths: growths.recent.iter().copied().collect(),
+        compressed,
+        recommendation,
+    }
+}
@@ -0,0 +1,141 @@
+use std::io::{self, Read};
+
+use crate::QueueFileError;
+
+const MAGIC: &[u8; 4] = b"QFCK";
+/// Magic, then the chunk's index and the number of chunks, both big-endian `u32`s.
+pub(crate) const HEADER_LEN: usize = 12;
+/// Payload bytes per chunk when the caller does not choose.
+pub(crate) const DEFAULT_CHUNK_LEN: u32 = 64 << 20;
+/// Longest chunk payload that still fits queue-file's 32-bit element length with its header.
+const MAX_CHUNK_LEN: usize = i32::MAX as usize - HEADER_LEN;
+
+/// `item` as it is handed to the codec. A payload that happens to start with the chunk magic
+/// is framed as the only chunk of itself, so every element that starts with the magic was
+/// framed here and no payload is ever mistaken for a chunk.
+pub(crate) fn escape(item: Vec<u8>) -> Vec<u8> {
+    match item.starts_with(MAGIC) {
+        true => frame(0, 1, &item),
+        false => item,
+    }
+}
+
+/// The payload of an element `escape` or `split` framed, given as it was handed to the codec.
+pub(crate) fn unframe(framed: &[u8]) -> &[u8] {
+    match header(framed) {
+        Some(_) => &framed[HEADER_LEN..],
+        None => framed,
+    }
+}
+
+/// Like `unframe`, for an element already read into memory.
+pub(crate) fn unframe_owned(mut framed: Vec<u8>) -> Vec<u8> {
+    if header(&framed).is_some() {
+        framed.drain(..HEADER_LEN);
+    }
+    framed
+}
+
+/// The payload bytes per chunk and the number of chunks of a chunked element of `len` bytes.
+/// Empty data still takes one chunk.
+pub(crate) fn layout(len: u64, chunk_len: Option<u32>) -> Result<(usize, u32), QueueFileError> {
+    let chunk_len = (chunk_len.unwrap_or(DEFAULT_CHUNK_LEN) as usize).clamp(1, MAX_CHUNK_LEN);
+    let count = len.div_ceil(chunk_len as u64).max(1);
+    let count = u32::try_from(count).map_err(|_| QueueFileError::TooManyElements)?;
+    Ok((chunk_len, count))
+}
+
+/// `data` as the elements of one chunked element, each holding at most `chunk_len` bytes of it.
+pub(crate) fn split(data: &[u8], chunk_len: Option<u32>) -> Result<Vec<Vec<u8>>, QueueFileError> {
+    let (chunk_len, count) = layout(data.len() as u64, chunk_len)?;
+    let chunks = (0..count).map(|index| {
+        let start = index as usize * chunk_len;
+        frame(
+            index,
+            count,
+            &data[start..data.len().min(start + chunk_len)],
+        )
+    });
+    Ok(chunks.collect())
+}
+
+/// The chunk at `index` of `count`, its payload the next `chunk_len` bytes of `src` or the rest
+/// of them for the last chunk. Fails with `UnexpectedEof` if `src` ends before that.
+pub(crate) fn read_chunk(
+    src: &mut impl Read,
+    index: u32,
+    count: u32,
+    chunk_len: usize,
+) -> io::Result<Vec<u8>> {
+    let mut chunk = frame(index, count, &[]);
+    src.take(chunk_len as u64).read_to_end(&mut chunk)?;
+    if index + 1 < count && chunk.len() < HEADER_LEN + chunk_len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(chunk)
+}
+
+fn frame(index: u32, count: u32, part: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(HEADER_LEN + part.len());
+    chunk.extend_from_slice(MAGIC);
+    chunk.extend_from_slice(&index.to_be_bytes());
+    chunk.extend_from_slice(&count.to_be_bytes());
+    chunk.extend_from_slice(part);
+    chunk
+}
+
+/// Number of elements the chunked element whose first chunk has `head` as its header spans,
+/// out of the `available` ones in the queue. An element that is not a chunk counts as a whole
+/// element of its own.
+pub(crate) fn count(head: Option<(u32, u32)>, available: usize) -> Result<u32, QueueFileError> {
+    match head {
+        Some((0, count)) if count as usize <= available => Ok(count),
+        Some((0, count)) => Err(invalid(format!(
+            "the eldest element has {count} chunks but the queue holds {available} elements"
+        ))),
+        Some((index, count)) => Err(invalid(format!(
+            "the eldest element is chunk {index} of {count}, not the first"
+        ))),
+        None => Ok(1),
+    }
+}
+
+/// Number of leading elements, given their `headers`, that are left of the chunked element
+/// the first one belongs to, even when it is not its first chunk or later chunks are missing.
+pub(crate) fn remaining(headers: &[Option<(u32, u32)>]) -> u32 {
+    let Some(Some((first, count))) = headers.first().copied() else {
+        return headers.len().min(1) as u32;
+    };
+    let continued = headers
+        .iter()
+        .zip(first..count)
+        .take_while(|(header, index)| **header == Some((*index, count)))
+        .count();
+    continued as u32
+}
+
+/// The payload of `element`, the chunk at `index` of one spanning `count` elements.
+pub(crate) fn payload(element: &[u8], index: u32, count: u32) -> Result<&[u8], QueueFileError> {
+    match header(element) {
+        Some(found) if found == (index, count) => Ok(&element[HEADER_LEN..]),
+        None if count == 1 && index == 0 => Ok(element),
+        _ => Err(invalid(format!(
+            "chunk {index} of {count} is missing or out of order"
+        ))),
+    }
+}
+
+/// The chunk index and count of an element as it was handed to the codec, or `None` if it was
+/// not framed.
+pub(crate) fn header(element: &[u8]) -> Option<(u32, u32)> {
+    if element.len() < HEADER_LEN || &element[..4] != MAGIC {
+        return None;
+    }
+    let u32_at = |at: usize| u32::from_be_bytes(element[at..at + 4].try_into().unwrap());
+    let (index, count) = (u32_at(4), u32_at(8));
+    (count > 0 && index < count).then_some((index, count))
+}
+
+fn invalid(message: String) -> QueueFileError {
+    QueueFileError::InvalidChunk { message }
+}
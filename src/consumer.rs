@@ -0,0 +1,250 @@
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+use crate::{DrainHandler, QueueFile, QueueFileError, flush_timer, runtime};
+
+/// How `process` retries an element its handler rejects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct ProcessPolicy {
+    /// Deliveries of one element, including the first, before it is given up on.
+    #[uniffi(default = 5)]
+    pub max_attempts: u32,
+    /// Delay before the second delivery; doubled for each further one. `None` is 100 ms.
+    #[uniffi(default = None)]
+    pub initial_backoff: Option<Duration>,
+    /// `None` is 60 s.
+    #[uniffi(default = None)]
+    pub max_backoff: Option<Duration>,
+    /// How long an empty queue, or one that failed to read, waits before it is checked again.
+    /// `None` is 500 ms.
+    #[uniffi(default = None)]
+    pub idle_poll: Option<Duration>,
+}
+
+impl Default for ProcessPolicy {
+    fn default() -> Self {
+        ProcessPolicy {
+            max_attempts: 5,
+            initial_backoff: None,
+            max_backoff: None,
+            idle_poll: None,
+        }
+    }
+}
+
+impl ProcessPolicy {
+    fn idle_poll(&self) -> Duration {
+        self.idle_poll.unwrap_or(Duration::from_millis(500))
+    }
+
+    /// Delay after delivery number `attempt` of one element was rejected.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let initial = self.initial_backoff.unwrap_or(Duration::from_millis(100));
+        let max = self.max_backoff.unwrap_or(Duration::from_secs(60));
+        let factor = 1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX);
+        initial.saturating_mul(factor).min(max)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, uniffi::Record)]
+pub struct ConsumerStats {
+    /// Elements the handler accepted.
+    pub delivered: u64,
+    /// Deliveries the handler rejected.
+    pub rejected: u64,
+    /// Elements given up on after `max_attempts`, whether moved to the dead-letter queue or
+    /// dropped.
+    pub dead_lettered: u64,
+    /// Reads, removals and dead-letter adds that failed and were retried after `idle_poll`.
+    pub errors: u64,
+}
+
+/// Delivers a queue's elements to a handler until `stop` is called. Each delivery is a job on
+/// the runtime's workers; waits between them are left to the timer thread, so no worker is
+/// held while the consumer has nothing to do.
+#[derive(uniffi::Object)]
+pub struct QueueConsumer {
+    worker: Arc<Worker>,
+}
+
+struct Worker {
+    queue: Arc<QueueFile>,
+    handler: Arc<dyn DrainHandler>,
+    policy: ProcessPolicy,
+    dead_letter: Option<Arc<QueueFile>>,
+    progress: Mutex<Progress>,
+    /// Signalled when a delivery finishes.
+    delivered: Condvar,
+    stats: Mutex<ConsumerStats>,
+}
+
+#[derive(Default)]
+struct Progress {
+    stopped: bool,
+    /// The thread running a delivery, if one is under way.
+    delivering: Option<ThreadId>,
+    /// Sequence id of the element being retried and how many times it has been delivered.
+    retrying: Option<(u64, u32)>,
+}
+
+impl Worker {
+    fn progress(&self) -> MutexGuard<'_, Progress> {
+        self.progress.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn count(&self, op: impl FnOnce(&mut ConsumerStats)) {
+        op(&mut self.stats.lock().unwrap_or_else(PoisonError::into_inner));
+    }
+
+    /// Runs the next delivery on a worker, after `delay` if there is one. Only a weak reference
+    /// waits, so releasing the consumer meanwhile stops it.
+    fn schedule(self: &Arc<Self>, delay: Option<Duration>) {
+        let worker = Arc::downgrade(self);
+        let step = move || {
+            if let Some(worker) = worker.upgrade() {
+                worker.step();
+            }
+        };
+        match delay {
+            None => runtime::spawn(step),
+            Some(delay) => {
+                flush_timer::schedule_job(Instant::now() + delay, move || runtime::spawn(step))
+            }
+        }
+    }
+
+    fn step(self: &Arc<Self>) {
+        {
+            let mut progress = self.progress();
+            if progress.stopped {
+                return;
+            }
+            progress.delivering = Some(thread::current().id());
+        }
+        let delay = self.deliver();
+        let stopped = {
+            let mut progress = self.progress();
+            progress.delivering = None;
+            progress.stopped
+        };
+        self.delivered.notify_all();
+        if !stopped {
+            self.schedule(delay);
+        }
+    }
+
+    /// Delivers the eldest element once, and returns how long to wait before the next delivery.
+    fn deliver(&self) -> Option<Duration> {
+        let policy = &self.policy;
+        let (seq, element) = match self.queue.peek_front() {
+            Ok(Some(front)) => front,
+            Ok(None) => return Some(policy.idle_poll()),
+            Err(_) => {
+                self.count(|stats| stats.errors += 1);
+                return Some(policy.idle_poll());
+            }
+        };
+        let attempts = match self.progress().retrying {
+            Some((retrying, attempts)) if retrying == seq => attempts + 1,
+            _ => 1,
+        };
+        self.progress().retrying = Some((seq, attempts));
+        if self.handler.handle(element.clone()) {
+            self.progress().retrying = None;
+            return match self.queue.remove_if_front(seq) {
+                Ok(_) => {
+                    self.count(|stats| stats.delivered += 1);
+                    None
+                }
+                Err(_) => {
+                    self.count(|stats| stats.errors += 1);
+                    Some(policy.idle_poll())
+                }
+            };
+        }
+        self.count(|stats| stats.rejected += 1);
+        if attempts < policy.max_attempts.max(1) {
+            return Some(policy.backoff(attempts));
+        }
+        // Added before the removal, so a crash in between leaves a copy in both queues.
+        let moved = self
+            .dead_letter
+            .as_ref()
+            .map_or(Ok(()), |dead_letter| dead_letter.add(element).map(|_| ()));
+        match moved.and_then(|()| self.queue.remove_if_front(seq)) {
+            Ok(_) => {
+                self.progress().retrying = None;
+                self.count(|stats| stats.dead_lettered += 1);
+                None
+            }
+            Err(_) => {
+                self.count(|stats| stats.errors += 1);
+                Some(policy.idle_poll())
+            }
+        }
+    }
+}
+
+impl QueueConsumer {
+    pub(crate) fn start(
+        queue: Arc<QueueFile>,
+        handler: Arc<dyn DrainHandler>,
+        policy: ProcessPolicy,
+        dead_letter: Option<Arc<QueueFile>>,
+    ) -> Result<Arc<Self>, QueueFileError> {
+        let worker = Arc::new(Worker {
+            queue,
+            handler,
+            policy,
+            dead_letter,
+            progress: Mutex::default(),
+            delivered: Condvar::new(),
+            stats: Mutex::default(),
+        });
+        worker.schedule(None);
+        Ok(Arc::new(QueueConsumer { worker }))
+    }
+}
+
+#[uniffi::export]
+impl QueueConsumer {
+    /// Stops delivering and waits for a delivery in progress to finish. The element being
+    /// handled is removed or kept according to the handler's answer.
+    #[uniffi::method]
+    pub fn stop(&self) {
+        let mut progress = self.worker.progress();
+        progress.stopped = true;
+        // A handler running `stop` itself cannot wait for its own delivery.
+        let me = thread::current().id();
+        let progress = self
+            .worker
+            .delivered
+            .wait_while(progress, |progress| {
+                progress.delivering.is_some_and(|thread| thread != me)
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+        drop(progress);
+    }
+
+    #[uniffi::method]
+    pub fn is_running(&self) -> bool {
+        !self.worker.progress().stopped
+    }
+
+    #[uniffi::method]
+    pub fn stats(&self) -> ConsumerStats {
+        *self
+            .worker
+            .stats
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for QueueConsumer {
+    /// Dropping the last reference stops the consumer without waiting for it.
+    fn drop(&mut self) {
+        self.worker.progress().stopped = true;
+    }
+}
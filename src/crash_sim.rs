@@ -0,0 +1,346 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use queue_file::QueueFile as RustQueueFile;
+
+use crate::QueueFileError;
+
+/// queue-file commits every operation by rewriting this header; it is treated as written
+/// atomically, like the single sector it occupies.
+const HEADER_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum CrashMode {
+    /// The interrupted write stops at the crash offset; the rest of it never reaches the disk.
+    Truncated,
+    /// The unwritten rest of the interrupted write is replaced with garbage.
+    Garbled,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct CrashOutcome {
+    /// Position in the recorded write stream at which power was lost.
+    pub offset: u64,
+    /// The checkpoint the reopened queue matches: the one before or the one after the
+    /// interrupted operation. `None` means the queue did not recover to a consistent prefix.
+    pub recovered_checkpoint: Option<u32>,
+    /// Why the crashed file could not be opened or read, if it could not.
+    pub error: Option<String>,
+}
+
+enum Event {
+    SetLen(u64),
+    Write {
+        pos: usize,
+        data: Vec<u8>,
+        atomic: bool,
+    },
+}
+
+struct Recording {
+    path: PathBuf,
+    base: Vec<u8>,
+    last: Vec<u8>,
+    /// `steps[k]` turns checkpoint `k` into checkpoint `k + 1`.
+    steps: Vec<Vec<Event>>,
+    /// The elements the queue held at each checkpoint; `contents[0]` is the starting state.
+    contents: Vec<Vec<Vec<u8>>>,
+}
+
+/// Test support for power-loss testing. Records the writes a queue makes to its file between
+/// checkpoints, then rebuilds the file as it would be after losing power at any byte of that
+/// write stream and checks that it reopens to the state before or after the interrupted
+/// operation.
+///
+/// Writes are reconstructed by diffing the file at consecutive checkpoints and ordered around
+/// the header update the way queue-file issues them. This models a queue with
+/// `set_sync_writes(true)`, where each operation reaches the disk in order before the next one
+/// starts, and assumes the versioned (non-legacy) file format.
+#[derive(uniffi::Object)]
+pub struct CrashSimulator {
+    inner: Mutex<Recording>,
+}
+
+#[uniffi::export]
+impl CrashSimulator {
+    /// Starts recording from the current contents of the queue file at `path`.
+    #[uniffi::constructor]
+    pub fn new(path: String) -> Result<Self, QueueFileError> {
+        let path = PathBuf::from(path);
+        let base = fs::read(&path).map_err(|e| QueueFileError::from(e).with_path(&path))?;
+        let contents = read_image(&path, &base)?;
+        Ok(CrashSimulator {
+            inner: Mutex::new(Recording {
+                path,
+                last: base.clone(),
+                base,
+                steps: Vec::new(),
+                contents: vec![contents],
+            }),
+        })
+    }
+
+    /// Records the writes made since the previous checkpoint as one operation. Call it after
+    /// each operation under test, once that operation has returned. Returns the checkpoint's
+    /// number.
+    #[uniffi::method]
+    pub fn checkpoint(&self) -> Result<u32, QueueFileError> {
+        let mut recording = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
+        let path = recording.path.clone();
+        let current = fs::read(&path).map_err(|e| QueueFileError::from(e).with_path(&path))?;
+        let contents = read_image(&path, &current)?;
+        let step = diff(&recording.last, &current);
+        recording.steps.push(step);
+        recording.contents.push(contents);
+        recording.last = current;
+        Ok(recording.steps.len() as u32)
+    }
+
+    /// Total bytes in the recorded write stream; crash offsets run from 0 to this value.
+    #[uniffi::method]
+    pub fn write_len(&self) -> Result<u64, QueueFileError> {
+        let recording = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
+        Ok(recording
+            .steps
+            .iter()
+            .flatten()
+            .map(event_len)
+            .sum::<usize>() as u64)
+    }
+
+    /// Loses power after `offset` bytes of the write stream and reopens the result.
+    #[uniffi::method]
+    pub fn replay(&self, offset: u64, mode: CrashMode) -> Result<CrashOutcome, QueueFileError> {
+        let recording = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
+        Ok(recording.replay(offset as usize, mode))
+    }
+
+    /// Replays a crash at every offset of the write stream and returns the ones that did not
+    /// recover to a consistent prefix, so a test can assert that the result is empty.
+    #[uniffi::method]
+    pub fn verify(&self, mode: CrashMode) -> Result<Vec<CrashOutcome>, QueueFileError> {
+        let recording = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
+        let total: usize = recording.steps.iter().flatten().map(event_len).sum();
+        Ok((0..=total)
+            .map(|offset| recording.replay(offset, mode))
+            .filter(|outcome| outcome.recovered_checkpoint.is_none())
+            .collect())
+    }
+}
+
+impl Recording {
+    fn replay(&self, offset: usize, mode: CrashMode) -> CrashOutcome {
+        let mut image = self.base.clone();
+        let mut pos = 0;
+        // The operation the crash interrupts; a crash on a boundary counts as interrupting the
+        // next one, which either outcome satisfies.
+        let mut step_index = self.steps.len().saturating_sub(1);
+        'steps: for (index, step) in self.steps.iter().enumerate() {
+            step_index = index;
+            for event in step {
+                let len = event_len(event);
+                if pos + len <= offset && !(len == 0 && pos == offset) {
+                    apply(&mut image, event, len);
+                    pos += len;
+                    continue;
+                }
+                if let Event::Write {
+                    pos: at,
+                    data,
+                    atomic: false,
+                } = event
+                    && offset > pos
+                {
+                    let written = offset - pos;
+                    extend(&mut image, at + data.len());
+                    image[*at..at + written].copy_from_slice(&data[..written]);
+                    if mode == CrashMode::Garbled {
+                        garble(&mut image[at + written..at + data.len()], offset as u64);
+                    }
+                }
+                break 'steps;
+            }
+        }
+
+        let recovered = read_image(&self.path, &image);
+        let (recovered_checkpoint, error) = match recovered {
+            Ok(contents) => {
+                let matched = [step_index, step_index + 1]
+                    .into_iter()
+                    .find(|&k| self.contents.get(k) == Some(&contents));
+                (matched.map(|k| k as u32), None)
+            }
+            Err(e) => (None, Some(e.to_string())),
+        };
+        CrashOutcome {
+            offset: offset as u64,
+            recovered_checkpoint,
+            error,
+        }
+    }
+}
+
+fn event_len(event: &Event) -> usize {
+    match event {
+        Event::SetLen(_) => 0,
+        Event::Write { data, .. } => data.len(),
+    }
+}
+
+fn apply(image: &mut Vec<u8>, event: &Event, len: usize) {
+    match event {
+        Event::SetLen(new_len) => image.resize(*new_len as usize, 0),
+        Event::Write { pos, data, .. } => {
+            extend(image, pos + len);
+            image[*pos..pos + len].copy_from_slice(data);
+        }
+    }
+}
+
+fn extend(image: &mut Vec<u8>, len: usize) {
+    if image.len() < len {
+        image.resize(len, 0);
+    }
+}
+
+/// Overwrites `bytes` with a deterministic pseudo-random pattern.
+fn garble(bytes: &mut [u8], seed: u64) {
+    let mut x = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    for byte in bytes {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        *byte = x as u8;
+    }
+}
+
+/// The writes that turn `before` into `after`, in the order queue-file issues them: growth,
+/// the move of the wrapped part of the ring and new data first, then the header that commits
+/// them, then erasure of removed elements and
+/// shrinking, which queue-file only does once the header no longer points at that space.
+fn diff(before: &[u8], after: &[u8]) -> Vec<Event> {
+    let mut resized = before.to_vec();
+    resized.resize(after.len(), 0);
+
+    let header_end = HEADER_LEN.min(after.len());
+    let header = changed_runs(&resized, after, 0, header_end);
+    // The header is one write however many of its fields changed.
+    let header = header.first().zip(header.last()).map(|(f, l)| (f.0, l.1));
+    let grew = after.len() > before.len();
+    let live = LiveRegion::of(before);
+    let (erased, mut written): (Vec<_>, Vec<_>) =
+        changed_runs(&resized, after, header_end, after.len())
+            .into_iter()
+            .partition(|&(start, _)| !grew && live.is_some_and(|live| live.contains(start)));
+    // Growth first moves the wrapped part of the ring into the new space, then writes.
+    written.sort_by_key(|&(start, _)| (start < before.len(), start));
+
+    let write = |(start, end): (usize, usize)| Event::Write {
+        pos: start,
+        data: after[start..end].to_vec(),
+        atomic: start < HEADER_LEN,
+    };
+    let mut events = Vec::new();
+    if grew {
+        events.push(Event::SetLen(after.len() as u64));
+    }
+    events.extend(written.into_iter().map(write));
+    events.extend(header.map(write));
+    events.extend(erased.into_iter().map(write));
+    if after.len() < before.len() {
+        events.push(Event::SetLen(after.len() as u64));
+    }
+    events
+}
+
+/// The part of the ring holding elements, according to a versioned header.
+#[derive(Clone, Copy)]
+struct LiveRegion {
+    first: usize,
+    end: usize,
+    file_len: usize,
+}
+
+impl LiveRegion {
+    const VERSIONED_HEADER: u32 = 0x8000_0001;
+
+    fn of(image: &[u8]) -> Option<Self> {
+        let field = |at: usize, len: usize| {
+            let bytes = image.get(at..at + len)?;
+            Some(bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize))
+        };
+        if field(0, 4)? != Self::VERSIONED_HEADER as usize || field(12, 4)? == 0 {
+            return None;
+        }
+        let file_len = field(4, 8)?.min(image.len());
+        let first = field(16, 8)?;
+        let last = field(24, 8)?;
+        let wrap = |pos: usize| {
+            if pos < file_len {
+                pos
+            } else {
+                HEADER_LEN + pos - file_len
+            }
+        };
+        let last_len = (0..4).try_fold(0usize, |acc, i| {
+            Some((acc << 8) | *image.get(wrap(last + i))? as usize)
+        })?;
+        let end = wrap(last + 4 + last_len);
+        Some(LiveRegion {
+            first,
+            end,
+            file_len,
+        })
+    }
+
+    fn contains(&self, pos: usize) -> bool {
+        if pos >= self.file_len {
+            return false;
+        }
+        if self.first < self.end {
+            (self.first..self.end).contains(&pos)
+        } else {
+            pos >= self.first || (HEADER_LEN..self.end).contains(&pos)
+        }
+    }
+}
+
+/// Maximal runs of bytes in `from..to` that differ between `a` and `b`.
+fn changed_runs(a: &[u8], b: &[u8], from: usize, to: usize) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for i in from..to {
+        match (start, a[i] != b[i]) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                runs.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        runs.push((s, to));
+    }
+    runs
+}
+
+/// Opens `image` as a queue file at `path` and returns its elements. The file is removed again
+/// afterwards.
+fn read_image(path: &Path, image: &[u8]) -> Result<Vec<Vec<u8>>, QueueFileError> {
+    let scratch = {
+        let mut scratch = path.to_path_buf().into_os_string();
+        scratch.push(".snapshot");
+        PathBuf::from(scratch)
+    };
+    fs::write(&scratch, image)?;
+    let contents = RustQueueFile::open(&scratch).map(|mut queue| {
+        queue
+            .iter()
+            .map(|element| element.to_vec())
+            .collect::<Vec<_>>()
+    });
+    let _ = fs::remove_file(&scratch);
+    Ok(contents?)
+}
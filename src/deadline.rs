@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
+
+use crate::{QueueFile, QueueFileError, QueueOperation, runtime};
+
+const PENDING: u8 = 0;
+const STARTED: u8 = 1;
+const ABANDONED: u8 = 2;
+
+/// Runs `job` on `queue` from a runtime worker and waits up to `timeout` for it. On timeout
+/// the caller gets `Timeout` straight away. A job that had not started by then never runs; one
+/// already under way finishes on the worker, under the queue's lock as always, so the queue is
+/// left as a completed call would leave it.
+pub(crate) fn run<T: Send + 'static>(
+    queue: Arc<QueueFile>,
+    operation: QueueOperation,
+    timeout: Duration,
+    job: impl FnOnce(&QueueFile) -> Result<T, QueueFileError> + Send + 'static,
+) -> Result<T, QueueFileError> {
+    let progress = Arc::new(AtomicU8::new(PENDING));
+    let (sender, receiver) = mpsc::sync_channel(1);
+    {
+        let progress = Arc::clone(&progress);
+        runtime::spawn(move || {
+            let claimed =
+                progress.compare_exchange(PENDING, STARTED, Ordering::AcqRel, Ordering::Acquire);
+            if claimed.is_ok() {
+                let _ = sender.send(job(&queue));
+            }
+        });
+    }
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            let abandoned =
+                progress.compare_exchange(PENDING, ABANDONED, Ordering::AcqRel, Ordering::Acquire);
+            // The job may have finished between the timeout and the exchange.
+            if abandoned.is_err()
+                && let Ok(result) = receiver.try_recv()
+            {
+                return result;
+            }
+            Err(QueueFileError::Timeout {
+                operation,
+                after: timeout,
+                started: abandoned.is_err(),
+            })
+        }
+        // The job panicked on the worker.
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(QueueFileError::LockError),
+    }
+}
@@ -0,0 +1,326 @@
+use std::borrow::Cow;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use zstd::bulk::{Compressor, Decompressor};
+use zstd::dict::DecoderDictionary;
+use zstd::zstd_safe;
+
+use crate::{QueueFileError, chunking, read_limit};
+
+/// zstd's default level, which already gets most of what a dictionary offers on small payloads.
+const LEVEL: i32 = 3;
+/// Longest dictionary `train` produces. Payloads of a few hundred bytes gain little past this.
+const MAX_DICTIONARY_LEN: usize = 16 * 1024;
+const MAGIC: &[u8; 4] = b"QFDC";
+/// The bytes every zstd frame starts with.
+const FRAME_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// What `train_dictionary` produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct TrainedDictionary {
+    /// The id zstd gave the dictionary, recorded in every frame compressed against it.
+    pub id: u32,
+    /// Length of the dictionary in bytes.
+    pub len: u64,
+    /// Elements it was trained on.
+    pub samples: u32,
+}
+
+/// How a queue's payloads are stored: as given until a dictionary is trained, then as zstd
+/// frames compressed against it. Cheap to clone, so readers outside the queue's lock can
+/// decode too.
+#[derive(Clone, Default)]
+pub(crate) struct Codec(Option<Arc<Dictionaries>>);
+
+struct Dictionaries {
+    /// Every dictionary trained for the queue, oldest first. Each frame names the one it was
+    /// compressed against, so elements compressed before a retrain stay readable.
+    entries: Vec<Entry>,
+    /// The entry adds compress against.
+    current: usize,
+    compressor: Mutex<Compressor<'static>>,
+}
+
+struct Entry {
+    id: u32,
+    bytes: Vec<u8>,
+    decoder: DecoderDictionary<'static>,
+}
+
+pub(crate) fn dictionary_path(path: &Path) -> PathBuf {
+    let mut dictionary = path.to_path_buf().into_os_string();
+    dictionary.push(".dict");
+    PathBuf::from(dictionary)
+}
+
+impl Codec {
+    /// The dictionaries saved for the queue at `path`, if any.
+    pub(crate) fn load(path: &Path) -> io::Result<Codec> {
+        let bytes = match fs::read(dictionary_path(path)) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Codec::default()),
+            Err(e) => return Err(e),
+        };
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "damaged dictionary file");
+        let body_len = bytes.len().checked_sub(4).ok_or_else(invalid)?;
+        let (body, crc) = bytes.split_at(body_len);
+        if body.len() < 12 || &body[..4] != MAGIC || crc32fast::hash(body).to_be_bytes() != crc {
+            return Err(invalid());
+        }
+        let u32_at = |at: usize| {
+            body.get(at..at + 4)
+                .map(|b| u32::from_be_bytes(b.try_into().unwrap()))
+        };
+        let current = u32_at(4).ok_or_else(invalid)?;
+        let count = u32_at(8).ok_or_else(invalid)?;
+        let mut entries = Vec::new();
+        let mut at = 12;
+        for _ in 0..count {
+            let len = u32_at(at).ok_or_else(invalid)? as usize;
+            let dictionary = body.get(at + 4..at + 4 + len).ok_or_else(invalid)?;
+            entries.push(Entry::new(dictionary.to_vec())?);
+            at += 4 + len;
+        }
+        let current = entries
+            .iter()
+            .position(|entry| entry.id == current)
+            .ok_or_else(invalid)?;
+        Codec::from_entries(entries, current)
+    }
+
+    fn from_entries(entries: Vec<Entry>, current: usize) -> io::Result<Codec> {
+        let compressor = Compressor::with_dictionary(LEVEL, &entries[current].bytes)?;
+        Ok(Codec(Some(Arc::new(Dictionaries {
+            entries,
+            current,
+            compressor: Mutex::new(compressor),
+        }))))
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Writes the dictionaries next to the queue at `path`, through a rename so a crash leaves
+    /// the previous set intact. A codec without any removes the saved ones.
+    pub(crate) fn save(&self, path: &Path) -> io::Result<()> {
+        let Some(dictionaries) = &self.0 else {
+            return match fs::remove_file(dictionary_path(path)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        };
+        let entries = &dictionaries.entries;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&entries[dictionaries.current].id.to_be_bytes());
+        bytes.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+        for entry in entries {
+            bytes.extend_from_slice(&(entry.bytes.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(&entry.bytes);
+        }
+        bytes.extend_from_slice(&crc32fast::hash(&bytes).to_be_bytes());
+
+        let dictionary = dictionary_path(path);
+        let mut staging = dictionary.clone().into_os_string();
+        staging.push(".tmp");
+        let staging = PathBuf::from(staging);
+        let mut file = File::create(&staging)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        fs::rename(&staging, &dictionary)
+    }
+
+    /// Trains a dictionary on `samples` and returns the codec that compresses against it,
+    /// keeping the ones before it for reading.
+    pub(crate) fn train(&self, samples: &[Vec<u8>]) -> io::Result<(Codec, TrainedDictionary)> {
+        let entry = Entry::new(zstd::dict::from_samples(samples, MAX_DICTIONARY_LEN)?)?;
+        let trained = TrainedDictionary {
+            id: entry.id,
+            len: entry.bytes.len() as u64,
+            samples: samples.len() as u32,
+        };
+        Ok((self.with(vec![entry], trained.id)?, trained))
+    }
+
+    /// This codec, also able to read what `other` wrote, for a queue whose file is about to be
+    /// exchanged with `other`'s. Adds keep compressing against this codec's dictionary.
+    pub(crate) fn merged(&self, other: &Codec) -> io::Result<Codec> {
+        let Some(theirs) = &other.0 else {
+            return Ok(self.clone());
+        };
+        let copies = theirs
+            .entries
+            .iter()
+            .map(|entry| Entry::new(entry.bytes.clone()))
+            .collect::<io::Result<Vec<_>>>()?;
+        let current = match &self.0 {
+            Some(ours) => ours.entries[ours.current].id,
+            None => theirs.entries[theirs.current].id,
+        };
+        self.with(copies, current)
+    }
+
+    /// This codec without the dictionaries adds no longer compress against.
+    pub(crate) fn current_only(&self) -> io::Result<Codec> {
+        let Some(ours) = &self.0 else {
+            return Ok(Codec::default());
+        };
+        let entry = Entry::new(ours.entries[ours.current].bytes.clone())?;
+        Codec::from_entries(vec![entry], 0)
+    }
+
+    /// The dictionaries of this codec plus those of `added` it lacks, compressing against the
+    /// one with id `current`.
+    fn with(&self, added: Vec<Entry>, current: u32) -> io::Result<Codec> {
+        let mut entries = match &self.0 {
+            Some(ours) => ours
+                .entries
+                .iter()
+                .map(|entry| Entry::new(entry.bytes.clone()))
+                .collect::<io::Result<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        for entry in added {
+            if entries.iter().all(|existing| existing.id != entry.id) {
+                entries.push(entry);
+            }
+        }
+        let current = entries
+            .iter()
+            .position(|entry| entry.id == current)
+            .unwrap();
+        Codec::from_entries(entries, current)
+    }
+
+    /// `item` as it is to be stored.
+    pub(crate) fn encode(&self, item: &[u8]) -> io::Result<Vec<u8>> {
+        match &self.0 {
+            Some(dictionaries) => dictionaries
+                .compressor
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .compress(item),
+            None => Ok(item.to_vec()),
+        }
+    }
+
+    /// `items` as they are to be stored.
+    pub(crate) fn encode_all<'a>(&self, items: &'a [Vec<u8>]) -> io::Result<Cow<'a, [Vec<u8>]>> {
+        let Some(dictionaries) = &self.0 else {
+            return Ok(Cow::Borrowed(items));
+        };
+        let mut compressor = dictionaries
+            .compressor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let encoded = items
+            .iter()
+            .map(|item| compressor.compress(item))
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Cow::Owned(encoded))
+    }
+
+    /// The payload of a `stored` element. Fails with `TooLargeForRead` if it would decompress
+    /// to more than `limit` bytes.
+    pub(crate) fn view<'a>(
+        &self,
+        stored: &'a [u8],
+        limit: Option<u64>,
+    ) -> Result<Cow<'a, [u8]>, QueueFileError> {
+        Ok(match self.view_framed(stored, limit)? {
+            Cow::Borrowed(framed) => Cow::Borrowed(chunking::unframe(framed)),
+            Cow::Owned(framed) => Cow::Owned(chunking::unframe_owned(framed)),
+        })
+    }
+
+    /// Like `view`, keeping the `chunking` header of an element that has one.
+    pub(crate) fn view_framed<'a>(
+        &self,
+        stored: &'a [u8],
+        limit: Option<u64>,
+    ) -> Result<Cow<'a, [u8]>, QueueFileError> {
+        let Some(dictionaries) = &self.0 else {
+            return Ok(Cow::Borrowed(stored));
+        };
+        if !stored.starts_with(&FRAME_MAGIC) {
+            return Ok(Cow::Borrowed(stored));
+        }
+        // Elements added before the first dictionary are stored as given.
+        let Some(id) = zstd_safe::get_dict_id_from_frame(stored) else {
+            return Ok(Cow::Borrowed(stored));
+        };
+        let Some(entry) = dictionaries
+            .entries
+            .iter()
+            .find(|entry| entry.id == id.get())
+        else {
+            return Err(QueueFileError::CorruptedFile {
+                message: format!("element compressed against unknown dictionary {id}"),
+                offset: None,
+                element_index: None,
+                expected: None,
+                found: None,
+                salvageable: false,
+            });
+        };
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "damaged compressed element");
+        let len = zstd_safe::get_frame_content_size(stored)
+            .ok()
+            .flatten()
+            .ok_or_else(invalid)?;
+        read_limit::check(len, limit)?;
+        let mut decompressor = Decompressor::with_prepared_dictionary(&entry.decoder)?;
+        Ok(Cow::Owned(decompressor.decompress(stored, len as usize)?))
+    }
+
+    /// Length of the payload of a `stored` element, read from its frame header.
+    pub(crate) fn payload_len(&self, stored: &[u8]) -> u64 {
+        let compressed = self.is_active()
+            && stored.starts_with(&FRAME_MAGIC)
+            && zstd_safe::get_dict_id_from_frame(stored).is_some();
+        match compressed {
+            true => zstd_safe::get_frame_content_size(stored)
+                .ok()
+                .flatten()
+                .unwrap_or(0),
+            false => chunking::unframe(stored).len() as u64,
+        }
+    }
+
+    /// Like `view`, for an element already read into memory.
+    pub(crate) fn decode(
+        &self,
+        stored: Vec<u8>,
+        limit: Option<u64>,
+    ) -> Result<Vec<u8>, QueueFileError> {
+        Ok(chunking::unframe_owned(self.decode_framed(stored, limit)?))
+    }
+
+    /// Like `view_framed`, for an element already read into memory.
+    pub(crate) fn decode_framed(
+        &self,
+        stored: Vec<u8>,
+        limit: Option<u64>,
+    ) -> Result<Vec<u8>, QueueFileError> {
+        match self.view_framed(&stored, limit)? {
+            Cow::Borrowed(_) => Ok(stored),
+            Cow::Owned(payload) => Ok(payload),
+        }
+    }
+}
+
+impl Entry {
+    fn new(bytes: Vec<u8>) -> io::Result<Entry> {
+        let id = zstd_safe::get_dict_id_from_dict(&bytes)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not a zstd dictionary"))?;
+        Ok(Entry {
+            id: id.get(),
+            decoder: DecoderDictionary::copy(&bytes),
+            bytes,
+        })
+    }
+}
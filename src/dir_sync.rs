@@ -0,0 +1,23 @@
+use std::io;
+use std::path::Path;
+
+#[cfg(unix)]
+use crate::io_latency::{self, IoCall};
+
+/// Flushes the directory entry of `path` by fsyncing its parent directory, so a file that was
+/// just created or renamed into place survives power loss.
+#[cfg(unix)]
+pub(crate) fn sync_parent(path: &Path) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let dir = std::fs::File::open(parent)?;
+    io_latency::measure(IoCall::Sync, || dir.sync_all())
+}
+
+/// Directory handles cannot be fsynced here; entries are made durable by the filesystem.
+#[cfg(not(unix))]
+pub(crate) fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::QueueFileError;
+use crate::state::QueueState;
+
+/// Consumes the elements `drain_with` passes to it.
+#[uniffi::export(with_foreign)]
+pub trait DrainHandler: Send + Sync {
+    /// Processes the eldest element. Returning `true` removes it; `false` leaves it at the
+    /// front of the queue and ends the drain.
+    fn handle(&self, element: Vec<u8>) -> bool;
+}
+
+/// Hands up to `max` elements to `handler`, removing each one it accepts before reading the
+/// next. Returns how many were removed.
+pub(crate) fn drain(
+    state: &mut QueueState,
+    handler: &dyn DrainHandler,
+    max: u32,
+) -> Result<u32, QueueFileError> {
+    state.check_not_frozen()?;
+    let mut drained = 0;
+    while drained < max {
+        let Some(element) = state.peek_front()? else {
+            break;
+        };
+        if !handler.handle(element) {
+            break;
+        }
+        state.remove_n(1)?;
+        drained += 1;
+    }
+    Ok(drained)
+}
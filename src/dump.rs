@@ -0,0 +1,131 @@
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::QueueFileError;
+use crate::fragmentation;
+use crate::info::{self, FileInfo};
+use crate::read_limit::FrameReader;
+use crate::state::QueueState;
+
+/// Bytes per line of the hex preview.
+const LINE: usize = 16;
+
+/// The header, ring layout and a preview of the first `max_elements` elements of `state`'s
+/// committed file, each cut to `max_bytes` of its stored bytes.
+pub(crate) fn render(
+    state: &QueueState,
+    max_elements: u32,
+    max_bytes: u32,
+) -> Result<String, QueueFileError> {
+    let with_path = |e: std::io::Error| QueueFileError::from(e).with_path(&state.path);
+    let info = info::read(state)?;
+    let used_bytes = state.queue.used_bytes();
+    let layout = fragmentation::report(&state.path, used_bytes, state.options.capacity)
+        .map_err(with_path)?;
+
+    let mut out = String::new();
+    header(&mut out, state, &info, used_bytes);
+    let _ = writeln!(out, "ring:");
+    let _ = writeln!(out, "  wrapped:      {}", layout.wrapped);
+    let _ = writeln!(out, "  wasted bytes: {}", layout.wasted_bytes);
+    for gap in &layout.gaps {
+        let _ = writeln!(out, "  free:         {} bytes at {}", gap.len, gap.offset);
+    }
+
+    let _ = writeln!(out, "elements:");
+    let mut reader = FrameReader::open(&state.path).map_err(with_path)?;
+    let mut shown = 0;
+    while shown < max_elements {
+        let Some(frame) = reader.next_frame().map_err(with_path)? else {
+            break;
+        };
+        let preview = reader
+            .read_prefix(frame, max_bytes as usize)
+            .map_err(with_path)?;
+        let _ = writeln!(out, "  [{shown}] {} bytes at {}", frame.len, frame.pos);
+        hex(&mut out, &preview);
+        if frame.len > preview.len() as u64 {
+            let _ = writeln!(
+                out,
+                "      ... {} more bytes not shown",
+                frame.len - preview.len() as u64
+            );
+        }
+        shown += 1;
+    }
+    if info.element_count > u64::from(shown) {
+        let _ = writeln!(
+            out,
+            "  ... {} more not shown",
+            info.element_count - u64::from(shown)
+        );
+    }
+    Ok(out)
+}
+
+fn header(out: &mut String, state: &QueueState, info: &FileInfo, used_bytes: u64) {
+    let seconds = |time: Option<SystemTime>| {
+        time.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or("unknown".to_string(), |d| {
+                format!("{} (unix seconds)", d.as_secs())
+            })
+    };
+    let features = [
+        (info.compressed, "compressed"),
+        (info.signed, "signed"),
+        (info.sync_trailer, "sync trailer"),
+        (info.redundant_header, "redundant header"),
+        (info.element_index, "element index"),
+        (info.mirrored, "mirrored"),
+    ]
+    .iter()
+    .filter(|(on, _)| *on)
+    .map(|(_, name)| *name)
+    .collect::<Vec<_>>();
+    let _ = writeln!(out, "file:         {}", state.path.display());
+    let _ = writeln!(
+        out,
+        "format:       {:?} version {} ({}-byte header)",
+        info.format, info.version, info.header_len
+    );
+    let _ = writeln!(out, "file len:     {}", info.file_len);
+    let _ = writeln!(out, "used bytes:   {used_bytes}");
+    let _ = writeln!(out, "elements:     {}", info.element_count);
+    let _ = writeln!(out, "first pos:    {}", info.first_position);
+    let _ = writeln!(out, "last pos:     {}", info.last_position);
+    let _ = writeln!(out, "created:      {}", seconds(info.created_at));
+    let _ = writeln!(out, "modified:     {}", seconds(info.modified_at));
+    let _ = writeln!(
+        out,
+        "features:     {}",
+        match features.is_empty() {
+            true => "none".to_string(),
+            false => features.join(", "),
+        }
+    );
+}
+
+/// `bytes` as offset, hex and ASCII columns, `LINE` bytes to a line.
+fn hex(out: &mut String, bytes: &[u8]) {
+    for (line, chunk) in bytes.chunks(LINE).enumerate() {
+        let _ = write!(out, "      {:08x} ", line * LINE);
+        for i in 0..LINE {
+            match chunk.get(i) {
+                Some(byte) => {
+                    let _ = write!(out, " {byte:02x}");
+                }
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str("  |");
+        out.extend(
+            chunk
+                .iter()
+                .map(|&b| match b.is_ascii_graphic() || b == b' ' {
+                    true => b as char,
+                    false => '.',
+                }),
+        );
+        out.push_str("|\n");
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+use crate::QueueFileError;
+
+// Field numbers of `proto/element_envelope.proto`.
+const PAYLOAD: u32 = 1;
+const CREATED_AT: u32 = 2;
+const HEADERS: u32 = 3;
+const SCHEMA_VERSION: u32 = 4;
+const ATTEMPTS: u32 = 5;
+const LAST_ATTEMPT_AT: u32 = 6;
+const MARK: u32 = 7;
+const HEADER_KEY: u32 = 1;
+const HEADER_VALUE: u32 = 2;
+/// The envelope header `peek_metadata` reports as the element's tag.
+const TAG_HEADER: &str = "tag";
+
+const VARINT: u32 = 0;
+const FIXED64: u32 = 1;
+const BYTES: u32 = 2;
+const FIXED32: u32 = 5;
+
+/// The `MARK` field `encode_envelope` writes first, which tells `meta` the element is an
+/// envelope rather than other bytes that happen to parse as one. `0x39` is its key as a fixed64.
+const MARKED: &[u8; 9] = b"\x39QFENVLP\x01";
+
+/// An element with the metadata queues commonly carry alongside it, stored as the protobuf
+/// message `queuefile.ElementEnvelope` so backend tooling can read the same elements.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct ElementEnvelope {
+    pub payload: Vec<u8>,
+    pub headers: HashMap<String, String>,
+    /// When the element was first queued. `add_envelope` fills it in if it is `None`.
+    #[uniffi(default = None)]
+    pub created_at: Option<SystemTime>,
+    /// Version of the payload's own format, for consumers that read several.
+    #[uniffi(default = 0)]
+    pub schema_version: u32,
+    /// Deliveries of the element that failed so far.
+    #[uniffi(default = 0)]
+    pub attempts: u32,
+    #[uniffi(default = None)]
+    pub last_attempt_at: Option<SystemTime>,
+}
+
+/// What `peek_metadata` and `QueueFileIterator::next_metadata` tell about an element without
+/// handing over its bytes.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct ElementMeta {
+    /// Length of the element as `peek` returns it.
+    pub size: u64,
+    /// Sequence id, numbered as `AddReceipt.seq` numbers them.
+    pub seq: u64,
+    /// The envelope's `created_at`, `attempts` and `"tag"` header, when the element was encoded
+    /// by `encode_envelope` (as `add_envelope` does); `None`, 0 and `None` otherwise.
+    pub enqueued_at: Option<SystemTime>,
+    pub attempts: u32,
+    pub tag: Option<String>,
+    /// SHA-256 of the element, as `peek_digest` computes it.
+    pub digest: Vec<u8>,
+}
+
+/// The metadata of `element`, which has sequence id `seq`.
+pub(crate) fn meta(element: Vec<u8>, seq: u64) -> ElementMeta {
+    let size = element.len() as u64;
+    let digest = Sha256::digest(&element).to_vec();
+    let envelope = match element.starts_with(MARKED) {
+        true => decode_envelope(element).ok(),
+        false => None,
+    };
+    ElementMeta {
+        size,
+        seq,
+        enqueued_at: envelope.as_ref().and_then(|envelope| envelope.created_at),
+        attempts: envelope.as_ref().map_or(0, |envelope| envelope.attempts),
+        tag: envelope.and_then(|mut envelope| envelope.headers.remove(TAG_HEADER)),
+        digest,
+    }
+}
+
+/// Encodes `envelope` as a `queuefile.ElementEnvelope` message, starting with the `mark` field.
+/// Headers are written sorted by key, so equal envelopes encode to equal bytes.
+#[uniffi::export]
+pub fn encode_envelope(envelope: ElementEnvelope) -> Vec<u8> {
+    let mut out = Vec::with_capacity(envelope.payload.len() + 32);
+    out.extend_from_slice(MARKED);
+    put_bytes(&mut out, PAYLOAD, &envelope.payload);
+    if let Some(created_at) = envelope.created_at {
+        put_varint_field(&mut out, CREATED_AT, micros(created_at) as u64);
+    }
+    let mut headers: Vec<_> = envelope.headers.iter().collect();
+    headers.sort();
+    for (key, value) in headers {
+        let mut entry = Vec::with_capacity(key.len() + value.len() + 4);
+        put_bytes(&mut entry, HEADER_KEY, key.as_bytes());
+        put_bytes(&mut entry, HEADER_VALUE, value.as_bytes());
+        put_bytes(&mut out, HEADERS, &entry);
+    }
+    if envelope.schema_version != 0 {
+        put_varint_field(&mut out, SCHEMA_VERSION, u64::from(envelope.schema_version));
+    }
+    if envelope.attempts != 0 {
+        put_varint_field(&mut out, ATTEMPTS, u64::from(envelope.attempts));
+    }
+    if let Some(last_attempt_at) = envelope.last_attempt_at {
+        put_varint_field(&mut out, LAST_ATTEMPT_AT, micros(last_attempt_at) as u64);
+    }
+    out
+}
+
+/// Decodes a `queuefile.ElementEnvelope` message. Fields this version does not know are
+/// skipped, so envelopes written by newer tooling still decode. Fails with `InvalidEnvelope`
+/// if `bytes` is not a well-formed message.
+#[uniffi::export]
+pub fn decode_envelope(bytes: Vec<u8>) -> Result<ElementEnvelope, QueueFileError> {
+    let mut envelope = ElementEnvelope {
+        payload: Vec::new(),
+        headers: HashMap::new(),
+        created_at: None,
+        schema_version: 0,
+        attempts: 0,
+        last_attempt_at: None,
+    };
+    let mut reader = Reader(&bytes);
+    while let Some((field, value)) = reader.field()? {
+        match (field, value) {
+            (PAYLOAD, Value::Bytes(payload)) => envelope.payload = payload.to_vec(),
+            (CREATED_AT, Value::Varint(at)) => envelope.created_at = Some(time(at as i64)),
+            (HEADERS, Value::Bytes(entry)) => {
+                let (key, value) = header(entry)?;
+                envelope.headers.insert(key, value);
+            }
+            (SCHEMA_VERSION, Value::Varint(version)) => envelope.schema_version = version as u32,
+            (ATTEMPTS, Value::Varint(attempts)) => envelope.attempts = attempts as u32,
+            (LAST_ATTEMPT_AT, Value::Varint(at)) => {
+                envelope.last_attempt_at = Some(time(at as i64))
+            }
+            (MARK, Value::Fixed) => {}
+            (PAYLOAD..=MARK, _) => {
+                return Err(invalid(format!("field {field} has the wrong wire type")));
+            }
+            _ => {}
+        }
+    }
+    Ok(envelope)
+}
+
+fn header(entry: &[u8]) -> Result<(String, String), QueueFileError> {
+    let (mut key, mut value) = (String::new(), String::new());
+    let mut reader = Reader(entry);
+    while let Some((field, found)) = reader.field()? {
+        let text = match found {
+            Value::Bytes(text) => String::from_utf8(text.to_vec())
+                .map_err(|_| invalid("header is not valid UTF-8".to_string()))?,
+            _ => continue,
+        };
+        match field {
+            HEADER_KEY => key = text,
+            HEADER_VALUE => value = text,
+            _ => {}
+        }
+    }
+    Ok((key, value))
+}
+
+fn invalid(message: String) -> QueueFileError {
+    QueueFileError::InvalidEnvelope { message }
+}
+
+/// Microseconds since the Unix epoch, negative before it.
+fn micros(at: SystemTime) -> i64 {
+    match at.duration_since(UNIX_EPOCH) {
+        Ok(since) => i64::try_from(since.as_micros()).unwrap_or(i64::MAX),
+        Err(before) => i64::try_from(before.duration().as_micros()).map_or(i64::MIN, |us| -us),
+    }
+}
+
+fn time(micros: i64) -> SystemTime {
+    let offset = Duration::from_micros(micros.unsigned_abs());
+    match micros < 0 {
+        true => UNIX_EPOCH - offset,
+        false => UNIX_EPOCH + offset,
+    }
+}
+
+fn put_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn put_varint_field(out: &mut Vec<u8>, field: u32, value: u64) {
+    put_varint(out, u64::from(field << 3 | VARINT));
+    put_varint(out, value);
+}
+
+fn put_bytes(out: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    put_varint(out, u64::from(field << 3 | BYTES));
+    put_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+enum Value<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    /// A fixed-width value. Only `MARK` is one, and decoding has no use for its value.
+    Fixed,
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    /// The next field number and value, or `None` at the end of the message.
+    fn field(&mut self) -> Result<Option<(u32, Value<'a>)>, QueueFileError> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+        let key = self.varint()?;
+        let field = u32::try_from(key >> 3).map_err(|_| invalid(format!("bad field key {key}")))?;
+        let value = match (key & 7) as u32 {
+            VARINT => Value::Varint(self.varint()?),
+            BYTES => {
+                let len = self.varint()?;
+                Value::Bytes(self.take(usize::try_from(len).unwrap_or(usize::MAX))?)
+            }
+            FIXED64 => self.take(8).map(|_| Value::Fixed)?,
+            FIXED32 => self.take(4).map(|_| Value::Fixed)?,
+            wire => {
+                return Err(invalid(format!(
+                    "field {field} has unsupported wire type {wire}"
+                )));
+            }
+        };
+        Ok(Some((field, value)))
+    }
+
+    fn varint(&mut self) -> Result<u64, QueueFileError> {
+        let mut value = 0u64;
+        for (i, &byte) in self.0.iter().enumerate().take(10) {
+            value |= u64::from(byte & 0x7f) << (7 * i);
+            if byte & 0x80 == 0 {
+                self.0 = &self.0[i + 1..];
+                return Ok(value);
+            }
+        }
+        Err(invalid("truncated or overlong varint".to_string()))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], QueueFileError> {
+        if len > self.0.len() {
+            return Err(invalid(
+                "field runs past the end of the message".to_string(),
+            ));
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+}
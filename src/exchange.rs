@@ -0,0 +1,52 @@
+use std::io;
+use std::path::Path;
+
+/// Atomically swaps the files at `a` and `b`, which must be on the same volume. Either both
+/// names move or neither does, so a crash cannot leave one queue holding both contents.
+#[cfg(any(target_vendor = "apple", target_os = "linux"))]
+pub(crate) fn exchange(a: &Path, b: &Path) -> io::Result<()> {
+    use std::ffi::{CString, c_char, c_int, c_uint};
+    use std::os::unix::ffi::OsStrExt;
+
+    unsafe extern "C" {
+        #[cfg(target_vendor = "apple")]
+        fn renamex_np(from: *const c_char, to: *const c_char, flags: c_uint) -> c_int;
+        #[cfg(target_os = "linux")]
+        fn renameat2(
+            olddirfd: c_int,
+            oldpath: *const c_char,
+            newdirfd: c_int,
+            newpath: *const c_char,
+            flags: c_uint,
+        ) -> c_int;
+    }
+
+    let a = CString::new(a.as_os_str().as_bytes())?;
+    let b = CString::new(b.as_os_str().as_bytes())?;
+    // SAFETY: both paths are NUL-terminated strings that outlive the call.
+    #[cfg(target_vendor = "apple")]
+    let status = {
+        const RENAME_SWAP: c_uint = 0x2;
+        unsafe { renamex_np(a.as_ptr(), b.as_ptr(), RENAME_SWAP) }
+    };
+    // SAFETY: as above; `AT_FDCWD` resolves both paths against the working directory.
+    #[cfg(target_os = "linux")]
+    let status = {
+        const AT_FDCWD: c_int = -100;
+        const RENAME_EXCHANGE: c_uint = 0x2;
+        unsafe { renameat2(AT_FDCWD, a.as_ptr(), AT_FDCWD, b.as_ptr(), RENAME_EXCHANGE) }
+    };
+    match status {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// Without an exchanging rename, a swap would need three renames and could be torn.
+#[cfg(not(any(target_vendor = "apple", target_os = "linux")))]
+pub(crate) fn exchange(_a: &Path, _b: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "atomic file exchange is not available on this platform",
+    ))
+}
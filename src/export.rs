@@ -0,0 +1,238 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use queue_file::QueueFile as RustQueueFile;
+
+use crate::QueueFileError;
+use crate::dictionary::Codec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum ExportEncoding {
+    /// Standard base64 with padding; exact for any payload.
+    Base64,
+    /// The payload as text, with invalid UTF-8 replaced by U+FFFD.
+    Utf8,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Format {
+    /// `{"index":0,"data":"..."}` per line.
+    Ndjson(ExportEncoding),
+    /// An `index,size,data` header, then one row per element with the payload as quoted text.
+    Csv,
+}
+
+/// Size at which buffered frames are handed to an `Exporter`.
+const CHUNK_BYTES: usize = 64 * 1024;
+
+/// Receives a queue's elements as a byte stream. The stream is each element in order, eldest
+/// first, framed as a 4-byte big-endian length followed by the payload. Chunk boundaries carry
+/// no meaning: a frame may be split across chunks.
+#[uniffi::export(with_foreign)]
+pub trait Exporter: Send + Sync {
+    /// Takes the next chunk of the stream, about 64 KiB. Returning `false` stops the export.
+    fn write(&self, chunk: Vec<u8>) -> bool;
+    /// Called once after the last chunk of an export that ran to the end.
+    fn finish(&self);
+}
+
+/// Streams every element of `queue` to `exporter`. Returns the number of elements exported.
+pub(crate) fn export_with(
+    queue: &mut RustQueueFile,
+    codec: &Codec,
+    exporter: Arc<dyn Exporter>,
+) -> Result<u64, QueueFileError> {
+    let mut sink = ChunkSink::new(exporter);
+    let mut iter = queue.iter();
+    while let Some(element) = iter.borrowed_next() {
+        sink.write(&codec.view(element, None)?)?;
+    }
+    sink.finish()
+}
+
+/// Frames elements for an `Exporter`, handing them over in chunks of `CHUNK_BYTES`.
+pub(crate) struct ChunkSink {
+    exporter: Arc<dyn Exporter>,
+    chunk: Vec<u8>,
+    count: u64,
+}
+
+impl ChunkSink {
+    pub(crate) fn new(exporter: Arc<dyn Exporter>) -> Self {
+        ChunkSink {
+            exporter,
+            chunk: Vec::with_capacity(CHUNK_BYTES),
+            count: 0,
+        }
+    }
+
+    pub(crate) fn write(&mut self, element: &[u8]) -> Result<(), QueueFileError> {
+        let len = element.len() as u32;
+        let mut rest = element;
+        self.chunk.extend_from_slice(&len.to_be_bytes());
+        loop {
+            let take = CHUNK_BYTES.saturating_sub(self.chunk.len()).min(rest.len());
+            self.chunk.extend_from_slice(&rest[..take]);
+            rest = &rest[take..];
+            if self.chunk.len() >= CHUNK_BYTES {
+                self.send()?;
+            }
+            if rest.is_empty() {
+                break;
+            }
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    fn send(&mut self) -> Result<(), QueueFileError> {
+        let chunk = std::mem::replace(&mut self.chunk, Vec::with_capacity(CHUNK_BYTES));
+        match self.exporter.write(chunk) {
+            true => Ok(()),
+            false => Err(
+                io::Error::new(io::ErrorKind::Interrupted, "exporter stopped the export").into(),
+            ),
+        }
+    }
+
+    /// Sends what is left and returns the number of elements exported.
+    pub(crate) fn finish(mut self) -> Result<u64, QueueFileError> {
+        if !self.chunk.is_empty() {
+            self.send()?;
+        }
+        self.exporter.finish();
+        Ok(self.count)
+    }
+}
+
+/// Streams every element of `queue` to a new file at `dest`, one line each. Returns the number
+/// of elements written. A failed export removes the partial file.
+pub(crate) fn export(
+    queue: &mut RustQueueFile,
+    codec: &Codec,
+    dest: &Path,
+    format: Format,
+) -> Result<u64, QueueFileError> {
+    let mut exporter = FileExporter::create(dest, format)?;
+    let mut iter = queue.iter();
+    while let Some(element) = iter.borrowed_next() {
+        exporter.write(&codec.view(element, None)?)?;
+    }
+    exporter.finish()
+}
+
+/// Writes elements to a new file one line each. Dropping it before `finish` removes the
+/// partial file.
+pub(crate) struct FileExporter {
+    out: Option<BufWriter<File>>,
+    dest: PathBuf,
+    format: Format,
+    count: u64,
+}
+
+impl FileExporter {
+    pub(crate) fn create(dest: &Path, format: Format) -> Result<Self, QueueFileError> {
+        let file = File::create(dest).map_err(|e| QueueFileError::from(e).with_path(dest))?;
+        let mut exporter = FileExporter {
+            out: Some(BufWriter::new(file)),
+            dest: dest.to_path_buf(),
+            format,
+            count: 0,
+        };
+        if let Format::Csv = format {
+            let result = exporter
+                .out
+                .as_mut()
+                .unwrap()
+                .write_all(b"index,size,data\r\n");
+            exporter.tag(result)?;
+        }
+        Ok(exporter)
+    }
+
+    pub(crate) fn write(&mut self, element: &[u8]) -> Result<(), QueueFileError> {
+        let result = self.write_line(element);
+        self.tag(result)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn write_line(&mut self, element: &[u8]) -> io::Result<()> {
+        let (out, count) = (self.out.as_mut().unwrap(), self.count);
+        match self.format {
+            Format::Ndjson(encoding) => {
+                write!(out, "{{\"index\":{count},\"data\":\"")?;
+                match encoding {
+                    ExportEncoding::Base64 => write_base64(out, element)?,
+                    ExportEncoding::Utf8 => {
+                        write_json_escaped(out, &String::from_utf8_lossy(element))?
+                    }
+                }
+                out.write_all(b"\"}\n")
+            }
+            Format::Csv => {
+                write!(out, "{count},{},\"", element.len())?;
+                let text = String::from_utf8_lossy(element);
+                out.write_all(text.replace('"', "\"\"").as_bytes())?;
+                out.write_all(b"\"\r\n")
+            }
+        }
+    }
+
+    /// Syncs the file and returns the number of elements written.
+    pub(crate) fn finish(mut self) -> Result<u64, QueueFileError> {
+        let out = self.out.take().unwrap();
+        let result = out.into_inner().map_err(|e| e.into_error());
+        let result = result.and_then(|file| file.sync_all());
+        if result.is_err() {
+            let _ = std::fs::remove_file(&self.dest);
+        }
+        self.tag(result)?;
+        Ok(self.count)
+    }
+
+    fn tag<T>(&self, result: io::Result<T>) -> Result<T, QueueFileError> {
+        result.map_err(|e| QueueFileError::from(e).with_path(&self.dest))
+    }
+}
+
+impl Drop for FileExporter {
+    fn drop(&mut self) {
+        if self.out.take().is_some() {
+            let _ = std::fs::remove_file(&self.dest);
+        }
+    }
+}
+
+fn write_json_escaped(out: &mut impl Write, text: &str) -> io::Result<()> {
+    for c in text.chars() {
+        match c {
+            '"' => out.write_all(b"\\\"")?,
+            '\\' => out.write_all(b"\\\\")?,
+            '\n' => out.write_all(b"\\n")?,
+            '\r' => out.write_all(b"\\r")?,
+            '\t' => out.write_all(b"\\t")?,
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{c}")?,
+        }
+    }
+    Ok(())
+}
+
+fn write_base64(out: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+        let mut quad = [b'='; 4];
+        for (i, c) in quad.iter_mut().enumerate().take(chunk.len() + 1) {
+            *c = ALPHABET[(n >> (18 - 6 * i)) as usize & 63];
+        }
+        out.write_all(&quad)?;
+    }
+    Ok(())
+}
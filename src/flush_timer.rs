@@ -0,0 +1,113 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::{Mutex, OnceLock, PoisonError, Weak, mpsc};
+use std::time::Instant;
+
+use crate::QueueFile;
+
+/// The thread that flushes write buffers whose delay ran out without another add arriving,
+/// closes queues left idle for `idle_close_after`, and resumes consumers after a backoff.
+static TIMER: OnceLock<Option<Mutex<mpsc::Sender<Due>>>> = OnceLock::new();
+
+struct Due {
+    at: Instant,
+    action: Action,
+}
+
+enum Action {
+    Queue(Weak<QueueFile>, Task),
+    Job(Box<dyn FnOnce() + Send>),
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Task {
+    Flush,
+    CloseIfIdle,
+}
+
+impl PartialEq for Due {
+    fn eq(&self, other: &Self) -> bool {
+        self.at == other.at
+    }
+}
+
+impl Eq for Due {}
+
+impl PartialOrd for Due {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Due {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.at.cmp(&other.at)
+    }
+}
+
+/// Runs `task` on `queue` at `at`: flushes its write buffer if due by then, or closes its file
+/// if it has been idle since. A closed queue is skipped. If the thread cannot be started,
+/// buffers are only flushed by the next add as before, and idle files stay open.
+pub(crate) fn schedule(at: Instant, queue: Weak<QueueFile>, task: Task) {
+    send(Due {
+        at,
+        action: Action::Queue(queue, task),
+    });
+}
+
+/// Calls `job` on the timer thread at `at`, which must hand anything slow to the runtime. If
+/// the thread cannot be started, `job` is dropped uncalled.
+pub(crate) fn schedule_job(at: Instant, job: impl FnOnce() + Send + 'static) {
+    send(Due {
+        at,
+        action: Action::Job(Box::new(job)),
+    });
+}
+
+fn send(due: Due) {
+    let sender = TIMER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("queuefile-flush".to_string())
+            .spawn(move || run(receiver))
+            .ok()
+            .map(|_| Mutex::new(sender))
+    });
+    if let Some(sender) = sender {
+        let sender = sender.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = sender.send(due);
+    }
+}
+
+fn run(receiver: mpsc::Receiver<Due>) {
+    let mut pending = BinaryHeap::new();
+    loop {
+        let next = pending.peek().map(|Reverse(due): &Reverse<Due>| due.at);
+        let received = match next {
+            Some(at) => receiver.recv_timeout(at.saturating_duration_since(Instant::now())),
+            None => receiver
+                .recv()
+                .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(due) => pending.push(Reverse(due)),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+        while let Some(Reverse(due)) = pending.peek()
+            && due.at <= Instant::now()
+        {
+            let Some(Reverse(due)) = pending.pop() else {
+                break;
+            };
+            match due.action {
+                Action::Queue(queue, task) => match (queue.upgrade(), task) {
+                    (Some(queue), Task::Flush) => queue.flush_if_due(),
+                    (Some(queue), Task::CloseIfIdle) => queue.close_if_idle(),
+                    (None, _) => {}
+                },
+                Action::Job(job) => job(),
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::io_latency::{self, IoCall};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct FreeRange {
+    /// Byte offset of the range in the queue file.
+    pub offset: u64,
+    pub len: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct FragmentationReport {
+    pub file_len: u64,
+    pub used_bytes: u64,
+    /// Bytes of the file that hold neither the header nor a live element.
+    pub wasted_bytes: u64,
+    /// True when the elements run past the end of the file and continue after the header.
+    pub wrapped: bool,
+    /// The free parts of the ring, in file order: one range when wrapped, up to two otherwise.
+    pub gaps: Vec<FreeRange>,
+    /// Length a copy of the queue rewritten into a fresh file would have: the capacity,
+    /// doubled until the elements fit, as the queue grows it.
+    pub compacted_len: u64,
+    /// `file_len - compacted_len`, what rewriting the queue would give back.
+    pub reclaimable_bytes: u64,
+}
+
+const VERSIONED_HEADER: u32 = 0x8000_0001;
+
+/// Where the elements sit in the ring, from the committed header of a queue file.
+pub(crate) struct Layout {
+    header_len: u64,
+    file_len: u64,
+    count: u64,
+    first: u64,
+    /// Where the last element ends, before wrapping back to the start of the ring.
+    end: u64,
+}
+
+impl Layout {
+    pub(crate) fn read(path: &Path) -> io::Result<Layout> {
+        let mut file = File::open(path)?;
+        let mut header = [0u8; 32];
+        file.read_exact(&mut header[..16])?;
+        let be = |bytes: &[u8]| bytes.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+        let (header_len, file_len, count, first, last) =
+            if be(&header[..4]) == u64::from(VERSIONED_HEADER) {
+                file.read_exact(&mut header[16..])?;
+                let fields = (
+                    be(&header[12..16]),
+                    be(&header[16..24]),
+                    be(&header[24..32]),
+                );
+                (32, be(&header[4..12]), fields.0, fields.1, fields.2)
+            } else {
+                let fields = (be(&header[4..8]), be(&header[8..12]), be(&header[12..16]));
+                (16, be(&header[..4]), fields.0, fields.1, fields.2)
+            };
+
+        let mut end = header_len;
+        if count > 0 {
+            let mut len = [0u8; 4];
+            read_ring(&mut file, last, &mut len, header_len, file_len)?;
+            end = last + 4 + be(&len);
+            if first > last {
+                end += file_len - header_len;
+            }
+        }
+        Ok(Layout {
+            header_len,
+            file_len,
+            count,
+            first,
+            end,
+        })
+    }
+
+    /// True when the elements run past the end of the file and continue after the header.
+    pub(crate) fn wrapped(&self) -> bool {
+        self.end > self.file_len
+    }
+
+    /// Bytes of elements stored after the header because they wrapped past the end.
+    pub(crate) fn wrapped_len(&self) -> u64 {
+        self.end.saturating_sub(self.file_len)
+    }
+
+    /// The free parts of the ring, in file order.
+    fn gaps(&self) -> Vec<FreeRange> {
+        let range = |from: u64, to: u64| {
+            (to > from).then_some(FreeRange {
+                offset: from,
+                len: to - from,
+            })
+        };
+        if self.count == 0 {
+            return range(self.header_len, self.file_len).into_iter().collect();
+        }
+        match self.wrapped() {
+            true => range(self.end - (self.file_len - self.header_len), self.first)
+                .into_iter()
+                .collect(),
+            false => [
+                range(self.header_len, self.first),
+                range(self.end, self.file_len),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        }
+    }
+}
+
+/// Describes the layout of the queue file at `path` from its committed header.
+pub(crate) fn report(
+    path: &Path,
+    used_bytes: u64,
+    capacity: u64,
+) -> io::Result<FragmentationReport> {
+    let layout = Layout::read(path)?;
+    let file_len = layout.file_len;
+    let mut compacted_len = capacity.max(layout.header_len);
+    while compacted_len < used_bytes {
+        compacted_len <<= 1;
+    }
+    Ok(FragmentationReport {
+        file_len,
+        used_bytes,
+        wasted_bytes: file_len.saturating_sub(used_bytes),
+        wrapped: layout.wrapped(),
+        gaps: layout.gaps(),
+        compacted_len,
+        reclaimable_bytes: file_len.saturating_sub(compacted_len),
+    })
+}
+
+/// Reads `buf.len()` bytes of the ring starting at `pos`, continuing after the header if the
+/// read runs past the end of the file.
+pub(crate) fn read_ring(
+    file: &mut File,
+    pos: u64,
+    buf: &mut [u8],
+    header_len: u64,
+    file_len: u64,
+) -> io::Result<()> {
+    let before_end = (file_len.saturating_sub(pos) as usize).min(buf.len());
+    let (head, tail) = buf.split_at_mut(before_end);
+    io_latency::measure(IoCall::Read, || {
+        file.seek(SeekFrom::Start(pos))?;
+        file.read_exact(head)
+    })?;
+    if !tail.is_empty() {
+        io_latency::measure(IoCall::Read, || {
+            file.seek(SeekFrom::Start(header_len))?;
+            file.read_exact(tail)
+        })?;
+    }
+    Ok(())
+}
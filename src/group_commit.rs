@@ -0,0 +1,99 @@
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+use crate::QueueFileError;
+
+/// Coalesces the fsyncs of concurrent adders: the first writer to wait becomes the leader,
+/// sleeps for the commit delay and then syncs on behalf of every write recorded so far.
+#[derive(Debug, Default)]
+pub(crate) struct GroupCommit {
+    state: Mutex<CommitState>,
+    synced: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct CommitState {
+    max_delay: Option<Duration>,
+    written: u64,
+    durable: u64,
+    failed_through: u64,
+    last_error: Option<QueueFileError>,
+    leader_active: bool,
+}
+
+impl GroupCommit {
+    pub(crate) fn max_delay(&self) -> Result<Option<Duration>, QueueFileError> {
+        let state = self.state.lock().map_err(|_| QueueFileError::LockError)?;
+        Ok(state.max_delay)
+    }
+
+    pub(crate) fn set_max_delay(&self, delay: Option<Duration>) -> Result<(), QueueFileError> {
+        let mut state = self.state.lock().map_err(|_| QueueFileError::LockError)?;
+        state.max_delay = delay;
+        Ok(())
+    }
+
+    /// Records a write that still needs to be synced. Must be called while the queue lock is
+    /// held so that the sequence numbers follow the order of the writes in the file.
+    pub(crate) fn record_write(&self) -> Result<u64, QueueFileError> {
+        let mut state = self.state.lock().map_err(|_| QueueFileError::LockError)?;
+        state.written += 1;
+        Ok(state.written)
+    }
+
+    /// Returns the sequence number of the latest recorded write. Called by `sync` while it holds
+    /// the queue lock, right before syncing the file.
+    pub(crate) fn written(&self) -> Result<u64, QueueFileError> {
+        let state = self.state.lock().map_err(|_| QueueFileError::LockError)?;
+        Ok(state.written)
+    }
+
+    /// Blocks until the write `seq` is durable. `sync` must sync the file and return the
+    /// sequence number of the latest write it covers along with the outcome of the sync.
+    pub(crate) fn wait_durable(
+        &self,
+        seq: u64,
+        sync: impl FnOnce() -> (u64, Result<(), QueueFileError>),
+    ) -> Result<(), QueueFileError> {
+        let mut state = self.state.lock().map_err(|_| QueueFileError::LockError)?;
+        loop {
+            if state.durable >= seq {
+                return Ok(());
+            }
+            if state.failed_through >= seq {
+                return Err(state
+                    .last_error
+                    .clone()
+                    .unwrap_or(QueueFileError::LockError));
+            }
+            if !state.leader_active {
+                break;
+            }
+            state = self
+                .synced
+                .wait(state)
+                .map_err(|_| QueueFileError::LockError)?;
+        }
+
+        state.leader_active = true;
+        let delay = state.max_delay.unwrap_or_default();
+        drop(state);
+
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+        let (target, result) = sync();
+
+        let mut state = self.state.lock().map_err(|_| QueueFileError::LockError)?;
+        state.leader_active = false;
+        match &result {
+            Ok(()) => state.durable = state.durable.max(target),
+            Err(e) => {
+                state.failed_through = state.failed_through.max(target);
+                state.last_error = Some(e.clone());
+            }
+        }
+        self.synced.notify_all();
+        result
+    }
+}
@@ -0,0 +1,192 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::io_latency::{self, IoCall};
+
+const HEADER_LEN: usize = 32;
+/// Generation, header copy and CRC-32 of both.
+pub(crate) const SLOT_LEN: usize = 8 + HEADER_LEN + 4;
+const VERSIONED_HEADER: u32 = 0x8000_0001;
+
+#[derive(Clone, Copy)]
+struct Slot {
+    generation: u64,
+    header: [u8; HEADER_LEN],
+}
+
+impl Slot {
+    fn encode(&self) -> [u8; SLOT_LEN] {
+        let mut bytes = [0; SLOT_LEN];
+        bytes[..8].copy_from_slice(&self.generation.to_be_bytes());
+        bytes[8..8 + HEADER_LEN].copy_from_slice(&self.header);
+        let crc = crc32fast::hash(&bytes[..8 + HEADER_LEN]);
+        bytes[8 + HEADER_LEN..].copy_from_slice(&crc.to_be_bytes());
+        bytes
+    }
+
+    fn decode(bytes: &[u8; SLOT_LEN]) -> Option<Slot> {
+        let crc = u32::from_be_bytes(bytes[8 + HEADER_LEN..].try_into().ok()?);
+        if crc32fast::hash(&bytes[..8 + HEADER_LEN]) != crc {
+            return None;
+        }
+        Some(Slot {
+            generation: u64::from_be_bytes(bytes[..8].try_into().ok()?),
+            header: bytes[8..8 + HEADER_LEN].try_into().ok()?,
+        })
+    }
+}
+
+/// Two checksummed copies of the queue file's header, kept in a `.hdr` sidecar. Each commit is
+/// copied into the older slot under the next generation number, so a torn write can only ever
+/// damage one of them.
+pub(crate) struct HeaderSlots {
+    sidecar: File,
+    primary: File,
+    last: Slot,
+}
+
+impl HeaderSlots {
+    pub(crate) fn sidecar_path(path: &Path) -> PathBuf {
+        let mut sidecar = path.to_path_buf().into_os_string();
+        sidecar.push(".hdr");
+        PathBuf::from(sidecar)
+    }
+
+    /// Before the queue is opened: if the header of the file at `path` is not self-consistent,
+    /// replaces it with the newest valid copy that is. Returns true if the header was restored.
+    pub(crate) fn recover(path: &Path) -> io::Result<bool> {
+        let slots = match fs::read(Self::sidecar_path(path)) {
+            Ok(bytes) => read_slots(&bytes),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        let mut file = match OpenOptions::new().read(true).write(true).open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        let current = read_header(&mut file)?;
+        if slots.first().is_some_and(|slot| slot.header == current)
+            || is_plausible(&mut file, &current)?
+        {
+            return Ok(false);
+        }
+        for slot in slots {
+            if is_plausible(&mut file, &slot.header)? {
+                file.seek(SeekFrom::Start(0))?;
+                file.write_all(&slot.header)?;
+                file.sync_all()?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Opens or creates the sidecar for the queue file at `path` and records its header.
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        let mut sidecar = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(Self::sidecar_path(path))?;
+        let mut bytes = Vec::new();
+        sidecar.read_to_end(&mut bytes)?;
+        let last = read_slots(&bytes).first().copied().unwrap_or(Slot {
+            generation: 0,
+            header: [0; HEADER_LEN],
+        });
+        let mut slots = HeaderSlots {
+            sidecar,
+            primary: File::open(path)?,
+            last,
+        };
+        slots.record(true)?;
+        Ok(slots)
+    }
+
+    /// Copies the queue file's current header into the older slot, if it changed.
+    pub(crate) fn record(&mut self, sync: bool) -> io::Result<()> {
+        let header = read_header(&mut self.primary)?;
+        if header == self.last.header {
+            return Ok(());
+        }
+        let slot = Slot {
+            generation: self.last.generation + 1,
+            header,
+        };
+        let offset = (slot.generation % 2) * SLOT_LEN as u64;
+        io_latency::measure(IoCall::Write, || {
+            self.sidecar.seek(SeekFrom::Start(offset))?;
+            self.sidecar.write_all(&slot.encode())
+        })?;
+        if sync {
+            self.sync()?;
+        }
+        self.last = slot;
+        Ok(())
+    }
+
+    pub(crate) fn sync(&self) -> io::Result<()> {
+        io_latency::measure(IoCall::Sync, || self.sidecar.sync_data())
+    }
+}
+
+/// Valid slots, newest first.
+fn read_slots(bytes: &[u8]) -> Vec<Slot> {
+    let (chunks, _) = bytes.as_chunks::<SLOT_LEN>();
+    let mut slots: Vec<Slot> = chunks.iter().filter_map(Slot::decode).collect();
+    slots.sort_by_key(|slot| std::cmp::Reverse(slot.generation));
+    slots
+}
+
+fn read_header(file: &mut File) -> io::Result<[u8; HEADER_LEN]> {
+    let mut header = [0; HEADER_LEN];
+    file.seek(SeekFrom::Start(0))?;
+    match file.read_exact(&mut header) {
+        Ok(()) => Ok(header),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok([0; HEADER_LEN]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Checks that a versioned header describes a queue that fits in `file`: positions inside the
+/// ring and first/last frames whose lengths fit.
+fn is_plausible(file: &mut File, header: &[u8; HEADER_LEN]) -> io::Result<bool> {
+    let u64_at = |at: usize| u64::from_be_bytes(header[at..at + 8].try_into().unwrap());
+    let marker = u32::from_be_bytes(header[..4].try_into().unwrap());
+    let count = i32::from_be_bytes(header[12..16].try_into().unwrap());
+    let (file_len, first, last) = (u64_at(4), u64_at(16), u64_at(24));
+    let header_len = HEADER_LEN as u64;
+
+    if marker != VERSIONED_HEADER
+        || count < 0
+        || file_len < header_len
+        || file_len > file.metadata()?.len()
+    {
+        return Ok(false);
+    }
+    if count == 0 {
+        return Ok(first == 0 && last == 0);
+    }
+    let in_ring = |pos: u64| (header_len..file_len).contains(&pos);
+    if !in_ring(first) || !in_ring(last) || (count == 1 && first != last) {
+        return Ok(false);
+    }
+    for pos in [first, last] {
+        let mut len = [0; 4];
+        for (i, byte) in len.iter_mut().enumerate() {
+            let mut at = pos + i as u64;
+            if at >= file_len {
+                at = header_len + at - file_len;
+            }
+            file.seek(SeekFrom::Start(at))?;
+            file.read_exact(std::slice::from_mut(byte))?;
+        }
+        if u32::from_be_bytes(len) as u64 > file_len - header_len {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
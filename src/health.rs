@@ -0,0 +1,174 @@
+use std::fs::File;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use crate::space;
+use crate::state::QueueState;
+use crate::verify::Header;
+
+/// How long `health_check` waits for the queue's lock before reporting it unresponsive.
+pub(crate) const LOCK_TIMEOUT: Duration = Duration::from_secs(1);
+/// A lock wait beyond this is reported as `Degraded`.
+const SLOW_LOCK: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, uniffi::Enum)]
+pub enum HealthStatus {
+    Healthy,
+    /// The queue works, but something needs attention soon: the volume is nearly full, a sync
+    /// is overdue or the lock is slow.
+    Degraded,
+    /// The file or the counters kept for it are inconsistent, or the lock is stuck. The queue
+    /// should be repaired or reopened.
+    Unhealthy,
+}
+
+/// The outcome of `health_check`.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct HealthReport {
+    /// The worst outcome of any check.
+    pub status: HealthStatus,
+    /// One line for each check that did not pass.
+    pub problems: Vec<String>,
+    /// Whether the queue's lock was acquired within a second. If not, no other check ran.
+    pub lock_responsive: bool,
+    pub lock_wait: Duration,
+    /// Whether the header on disk describes a ring that fits the file and matches the open
+    /// queue.
+    pub header_ok: bool,
+    /// Whether the element counts kept in memory agree with each other.
+    pub counters_ok: bool,
+    /// Free space on the volume, where the platform reports it.
+    pub available_bytes: Option<u64>,
+    /// Bytes written since the last sync. Always 0 with `sync_writes`.
+    pub unsynced_bytes: u64,
+    /// Time since the last `sync_all`, or since the queue was opened.
+    pub last_sync_age: Duration,
+}
+
+/// The report when the lock could not be taken within `LOCK_TIMEOUT`.
+pub(crate) fn unresponsive(waited: Duration) -> HealthReport {
+    HealthReport {
+        status: HealthStatus::Unhealthy,
+        problems: vec![format!(
+            "lock not acquired within {} ms",
+            waited.as_millis()
+        )],
+        lock_responsive: false,
+        lock_wait: waited,
+        header_ok: false,
+        counters_ok: false,
+        available_bytes: None,
+        unsynced_bytes: 0,
+        last_sync_age: Duration::ZERO,
+    }
+}
+
+/// Runs every check on `state`, whose lock took `waited` to acquire.
+pub(crate) fn check(state: &QueueState, waited: Duration) -> HealthReport {
+    let mut status = HealthStatus::Healthy;
+    let mut problems = Vec::new();
+    let mut report = |severity: HealthStatus, problem: String| {
+        status = status.max(severity);
+        problems.push(problem);
+    };
+
+    if waited > SLOW_LOCK {
+        report(
+            HealthStatus::Degraded,
+            format!("lock took {} ms to acquire", waited.as_millis()),
+        );
+    }
+
+    let header_problems = header_problems(state);
+    let header_ok = header_problems.is_empty();
+    for problem in header_problems {
+        report(HealthStatus::Unhealthy, problem);
+    }
+
+    let counter_problems = state.counter_problems();
+    let counters_ok = counter_problems.is_empty();
+    for problem in counter_problems {
+        report(HealthStatus::Unhealthy, problem);
+    }
+
+    // Platforms without a way to ask skip the check.
+    let available_bytes = space::available_bytes(&state.path).ok();
+    if let (Some(available), Some(reserve)) = (available_bytes, state.options.free_space_reserve)
+        && available < reserve
+    {
+        report(
+            HealthStatus::Degraded,
+            format!("{available} bytes free on the volume, below the {reserve}-byte reserve"),
+        );
+    }
+
+    let (unsynced_bytes, synced_at) = state.unsynced();
+    let age = Instant::now().saturating_duration_since(synced_at);
+    if let Some(max_age) = state.options.max_unsynced_age
+        && unsynced_bytes > 0
+        && age > max_age
+    {
+        report(
+            HealthStatus::Degraded,
+            format!(
+                "{unsynced_bytes} bytes unsynced for {} ms, over the {} ms limit",
+                age.as_millis(),
+                max_age.as_millis()
+            ),
+        );
+    }
+
+    HealthReport {
+        status,
+        problems,
+        lock_responsive: true,
+        lock_wait: waited,
+        header_ok,
+        counters_ok,
+        available_bytes,
+        unsynced_bytes,
+        last_sync_age: age,
+    }
+}
+
+/// Where the header on disk does not fit the file or disagrees with the open queue.
+fn header_problems(state: &QueueState) -> Vec<String> {
+    let read = File::open(&state.path).and_then(|mut file| {
+        let physical_len = file.metadata()?.len();
+        let mut bytes = [0; 32];
+        file.read_exact(&mut bytes)?;
+        Ok((Header::parse(&bytes), physical_len))
+    });
+    let (header, physical_len) = match read {
+        Ok(read) => read,
+        Err(e) => return vec![format!("header could not be read: {e}")],
+    };
+    let (header_len, file_len) = (header.header_len, header.file_len);
+    let mut problems = Vec::new();
+    if file_len < header_len + 4 || file_len > physical_len {
+        problems.push(format!(
+            "header file length {file_len} does not fit the {physical_len}-byte file"
+        ));
+    }
+    let ring = header_len..file_len;
+    if header.count > 0 && !(ring.contains(&header.first) && ring.contains(&header.last)) {
+        problems.push(format!(
+            "element offsets {} and {} lie outside the ring",
+            header.first, header.last
+        ));
+    }
+    if file_len != state.queue.file_len() {
+        problems.push(format!(
+            "header file length {file_len}, but the open queue has {}",
+            state.queue.file_len()
+        ));
+    }
+    if header.count != state.queue.size() as u64 {
+        problems.push(format!(
+            "header counts {} elements, but the open queue has {}",
+            header.count,
+            state.queue.size()
+        ));
+    }
+    problems
+}
@@ -0,0 +1,155 @@
+use std::time::{Duration, Instant};
+
+use queue_file::{OffsetCacheKind, QueueFile as RustQueueFile};
+
+use crate::QueueFileError;
+
+/// The queue-file handle of a queue, which `idle_close_after` closes while the queue is idle.
+/// `QueueFile::lock` reopens it before handing out the state; anything reaching a closed
+/// handle otherwise gets `ClosedForInactivity`.
+pub(crate) enum QueueHandle {
+    Open(Box<RustQueueFile>),
+    /// Closed with the settings the reopened handle gets back.
+    Closed(Settings),
+}
+
+/// What a closed handle still answers, and the settings the reopened handle gets back.
+#[derive(Clone, Copy)]
+pub(crate) struct Settings {
+    pub(crate) size: usize,
+    pub(crate) used_bytes: u64,
+    pub(crate) file_len: u64,
+    pub(crate) sync_writes: bool,
+    pub(crate) overwrite_on_remove: bool,
+    pub(crate) cache_offset_policy: Option<OffsetCacheKind>,
+    /// What else was open and is reopened with the queue.
+    pub(crate) mirror: bool,
+    pub(crate) header_slots: bool,
+    pub(crate) index: bool,
+}
+
+impl QueueHandle {
+    pub(crate) fn is_closed(&self) -> bool {
+        matches!(self, QueueHandle::Closed(_))
+    }
+
+    /// The open queue-file handle, for everything but the header fields and settings below,
+    /// which a closed handle answers from what it recorded.
+    pub(crate) fn get_mut(&mut self) -> Result<&mut RustQueueFile, QueueFileError> {
+        match self {
+            QueueHandle::Open(queue) => Ok(queue),
+            QueueHandle::Closed(_) => Err(QueueFileError::ClosedForInactivity),
+        }
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        match self {
+            QueueHandle::Open(queue) => queue.size(),
+            QueueHandle::Closed(settings) => settings.size,
+        }
+    }
+
+    pub(crate) fn used_bytes(&self) -> u64 {
+        match self {
+            QueueHandle::Open(queue) => queue.used_bytes(),
+            QueueHandle::Closed(settings) => settings.used_bytes,
+        }
+    }
+
+    pub(crate) fn file_len(&self) -> u64 {
+        match self {
+            QueueHandle::Open(queue) => queue.file_len(),
+            QueueHandle::Closed(settings) => settings.file_len,
+        }
+    }
+
+    pub(crate) fn sync_writes(&self) -> bool {
+        match self {
+            QueueHandle::Open(queue) => queue.sync_writes(),
+            QueueHandle::Closed(settings) => settings.sync_writes,
+        }
+    }
+
+    pub(crate) fn overwrite_on_remove(&self) -> bool {
+        match self {
+            QueueHandle::Open(queue) => queue.overwrite_on_remove(),
+            QueueHandle::Closed(settings) => settings.overwrite_on_remove,
+        }
+    }
+
+    pub(crate) fn cache_offset_policy(&self) -> Option<OffsetCacheKind> {
+        match self {
+            QueueHandle::Open(queue) => queue.cache_offset_policy(),
+            QueueHandle::Closed(settings) => settings.cache_offset_policy,
+        }
+    }
+
+    pub(crate) fn set_sync_writes(&mut self, value: bool) {
+        match self {
+            QueueHandle::Open(queue) => queue.set_sync_writes(value),
+            QueueHandle::Closed(settings) => settings.sync_writes = value,
+        }
+    }
+
+    pub(crate) fn set_overwrite_on_remove(&mut self, value: bool) {
+        match self {
+            QueueHandle::Open(queue) => queue.set_overwrite_on_remove(value),
+            QueueHandle::Closed(settings) => settings.overwrite_on_remove = value,
+        }
+    }
+
+    pub(crate) fn set_cache_offset_policy(&mut self, kind: impl Into<Option<OffsetCacheKind>>) {
+        match self {
+            QueueHandle::Open(queue) => queue.set_cache_offset_policy(kind),
+            QueueHandle::Closed(settings) => settings.cache_offset_policy = kind.into(),
+        }
+    }
+
+    /// A closed handle gets `QueueState.read_buffer_size` back when it is reopened.
+    pub(crate) fn set_read_buffer_size(&mut self, size: usize) {
+        if let QueueHandle::Open(queue) = self {
+            queue.set_read_buffer_size(size);
+        }
+    }
+}
+
+impl From<RustQueueFile> for QueueHandle {
+    fn from(queue: RustQueueFile) -> Self {
+        QueueHandle::Open(Box::new(queue))
+    }
+}
+
+/// When a queue opened with `idle_close_after` was last used.
+pub(crate) struct IdleClose {
+    after: Duration,
+    last_used: Instant,
+    /// Set while the timer holds a check for this queue, so uses do not schedule more.
+    check_pending: bool,
+}
+
+impl IdleClose {
+    pub(crate) fn new(after: Duration) -> Self {
+        IdleClose {
+            after,
+            last_used: Instant::now(),
+            check_pending: false,
+        }
+    }
+
+    /// Records a use, returning when to check for idleness unless a check is already pending.
+    pub(crate) fn touch(&mut self) -> Option<Instant> {
+        self.last_used = Instant::now();
+        (!std::mem::replace(&mut self.check_pending, true)).then_some(self.last_used + self.after)
+    }
+
+    /// Called by the pending check: `None` once the queue has been idle for long enough,
+    /// otherwise when to check again, the check staying pending.
+    pub(crate) fn recheck(&mut self) -> Option<Instant> {
+        let due = self.last_used + self.after;
+        if due > Instant::now() {
+            return Some(due);
+        }
+        self.check_pending = false;
+        None
+    }
+}
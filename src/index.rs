@@ -0,0 +1,255 @@
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::verify::{Header, Scan};
+use crate::{QueueFileError, fragmentation};
+
+const MAGIC: &[u8; 4] = b"QFIX";
+const HEADER_LEN: usize = 32;
+/// Magic, generation, next sequence id, header copy and element count.
+const PREFIX_LEN: usize = 4 + 8 + 8 + HEADER_LEN + 8;
+/// Position, payload length and sequence id.
+const ENTRY_LEN: usize = 8 + 4 + 8;
+
+#[derive(Clone, Copy)]
+struct Entry {
+    pos: u64,
+    len: u32,
+    seq: u64,
+}
+
+/// Counters that outlive the handle when a saved index is still valid on open.
+pub(crate) struct Restored {
+    pub(crate) generation: u64,
+    pub(crate) next_seq: u64,
+}
+
+/// Where every element of the queue file starts, kept in step with each commit so an element
+/// can be read by position without walking the frames before it.
+pub(crate) struct ElementIndex {
+    entries: VecDeque<Entry>,
+    header_len: u64,
+    /// A read-only handle on the file the entries describe.
+    file: File,
+}
+
+pub(crate) fn index_path(path: &Path) -> PathBuf {
+    let mut index = path.to_path_buf().into_os_string();
+    index.push(".idx");
+    PathBuf::from(index)
+}
+
+impl ElementIndex {
+    /// Loads the index saved for the file at `path` if it describes the file's current header,
+    /// and otherwise builds one, numbering the elements from 0.
+    pub(crate) fn open(path: &Path) -> Result<(ElementIndex, Option<Restored>), QueueFileError> {
+        let (file, header) = open_file(path)?;
+        let Some((entries, restored)) = load(path, &header)? else {
+            return Ok((ElementIndex::build(path, None)?, None));
+        };
+        let index = ElementIndex {
+            entries,
+            header_len: Header::parse(&header).header_len,
+            file,
+        };
+        Ok((index, Some(restored)))
+    }
+
+    /// Walks the frames of the file at `path`, numbering the elements so the newest is just
+    /// before `next_seq`, or from 0.
+    pub(crate) fn build(path: &Path, next_seq: Option<u64>) -> Result<Self, QueueFileError> {
+        let (file, header) = open_file(path)?;
+        let frames = scan(path)?;
+        let first_seq = next_seq.map_or(0, |next| next.saturating_sub(frames.len() as u64));
+        let entries = frames
+            .into_iter()
+            .zip(first_seq..)
+            .map(|((pos, len), seq)| Entry { pos, len, seq })
+            .collect();
+        Ok(ElementIndex {
+            entries,
+            header_len: Header::parse(&header).header_len,
+            file,
+        })
+    }
+
+    /// Rereads the positions after a new file was renamed into place at `path`. If it holds
+    /// as many elements as before, they keep their sequence ids.
+    pub(crate) fn relocate(&mut self, path: &Path, next_seq: u64) -> Result<(), QueueFileError> {
+        let mut index = ElementIndex::build(path, Some(next_seq))?;
+        if index.entries.len() == self.entries.len() {
+            for (entry, old) in index.entries.iter_mut().zip(&self.entries) {
+                entry.seq = old.seq;
+            }
+        }
+        *self = index;
+        Ok(())
+    }
+
+    /// Records elements of `lens` bytes just committed with sequence ids from `first_seq`.
+    /// `old_file_len` is the length before the commit: if queue-file grew a wrapped ring, it
+    /// moved the elements after the header to the old end of the file.
+    pub(crate) fn added(
+        &mut self,
+        lens: impl Iterator<Item = usize>,
+        first_seq: u64,
+        old_file_len: u64,
+        file_len: u64,
+    ) {
+        let header_len = self.header_len;
+        if file_len > old_file_len
+            && let Some(first) = self.entries.front().map(|entry| entry.pos)
+        {
+            for entry in self.entries.iter_mut().filter(|entry| entry.pos < first) {
+                entry.pos += old_file_len - header_len;
+            }
+        }
+        let wrap = |pos: u64| match pos >= file_len {
+            true => header_len + pos - file_len,
+            false => pos,
+        };
+        let mut pos = self
+            .entries
+            .back()
+            .map_or(header_len, |last| wrap(last.pos + 4 + u64::from(last.len)));
+        for (len, seq) in lens.zip(first_seq..) {
+            self.entries.push_back(Entry {
+                pos,
+                len: len as u32,
+                seq,
+            });
+            pos = wrap(pos + 4 + len as u64);
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The payload length of the element at `index`, without reading it.
+    pub(crate) fn payload_len(&self, index: usize) -> Option<u64> {
+        self.entries.get(index).map(|entry| u64::from(entry.len))
+    }
+
+    pub(crate) fn removed(&mut self, n: usize) {
+        self.entries.drain(..n.min(self.entries.len()));
+    }
+
+    pub(crate) fn remove_at(&mut self, index: usize) {
+        self.entries.remove(index);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Reads the payload of the element at `index` straight from its recorded position.
+    pub(crate) fn read(&mut self, index: usize, file_len: u64) -> io::Result<Option<Vec<u8>>> {
+        let Some(entry) = self.entries.get(index).copied() else {
+            return Ok(None);
+        };
+        let start = match entry.pos + 4 >= file_len {
+            true => self.header_len + entry.pos + 4 - file_len,
+            false => entry.pos + 4,
+        };
+        let mut payload = vec![0; entry.len as usize];
+        fragmentation::read_ring(
+            &mut self.file,
+            start,
+            &mut payload,
+            self.header_len,
+            file_len,
+        )?;
+        Ok(Some(payload))
+    }
+
+    /// Writes the index next to the file at `path`, together with the header it matches and
+    /// the counters to restore, replacing the previous one through a rename.
+    pub(crate) fn save(&mut self, path: &Path, generation: u64, next_seq: u64) -> io::Result<()> {
+        let mut header = [0; HEADER_LEN];
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.read_exact(&mut header)?;
+        let mut bytes = Vec::with_capacity(PREFIX_LEN + self.entries.len() * ENTRY_LEN + 4);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&generation.to_be_bytes());
+        bytes.extend_from_slice(&next_seq.to_be_bytes());
+        bytes.extend_from_slice(&header);
+        bytes.extend_from_slice(&(self.entries.len() as u64).to_be_bytes());
+        for entry in &self.entries {
+            bytes.extend_from_slice(&entry.pos.to_be_bytes());
+            bytes.extend_from_slice(&entry.len.to_be_bytes());
+            bytes.extend_from_slice(&entry.seq.to_be_bytes());
+        }
+        bytes.extend_from_slice(&crc32fast::hash(&bytes).to_be_bytes());
+
+        let index = index_path(path);
+        let mut staging = index.clone().into_os_string();
+        staging.push(".tmp");
+        let staging = PathBuf::from(staging);
+        let mut file = File::create(&staging)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        fs::rename(&staging, &index)
+    }
+}
+
+fn open_file(path: &Path) -> io::Result<(File, [u8; HEADER_LEN])> {
+    let mut file = File::open(path)?;
+    let mut header = [0; HEADER_LEN];
+    file.read_exact(&mut header)?;
+    Ok((file, header))
+}
+
+/// The saved index, if there is one, it is intact and it was saved with `header`.
+fn load(path: &Path, header: &[u8; HEADER_LEN]) -> io::Result<Option<(VecDeque<Entry>, Restored)>> {
+    let bytes = match fs::read(index_path(path)) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if bytes.len() < PREFIX_LEN + 4 {
+        return Ok(None);
+    }
+    let (body, crc) = bytes.split_at(bytes.len() - 4);
+    if &body[..4] != MAGIC || crc32fast::hash(body).to_be_bytes() != crc {
+        return Ok(None);
+    }
+    let u64_at = |at: usize| u64::from_be_bytes(body[at..at + 8].try_into().unwrap());
+    let count = u64_at(PREFIX_LEN - 8);
+    if body[20..20 + HEADER_LEN] != header[..]
+        || count != Header::parse(header).count
+        || (body.len() - PREFIX_LEN) as u64 != count * ENTRY_LEN as u64
+    {
+        return Ok(None);
+    }
+    let entries = body[PREFIX_LEN..]
+        .as_chunks::<ENTRY_LEN>()
+        .0
+        .iter()
+        .map(|entry| Entry {
+            pos: u64::from_be_bytes(entry[..8].try_into().unwrap()),
+            len: u32::from_be_bytes(entry[8..12].try_into().unwrap()),
+            seq: u64::from_be_bytes(entry[12..].try_into().unwrap()),
+        })
+        .collect();
+    let restored = Restored {
+        generation: u64_at(4),
+        next_seq: u64_at(12),
+    };
+    Ok(Some((entries, restored)))
+}
+
+/// The position and payload length of every element, walking the frames from the header.
+fn scan(path: &Path) -> Result<Vec<(u64, u32)>, QueueFileError> {
+    let scan = Scan::open(path)?.map_err(|corruption| corruption.into_error(path))?;
+    if let Some(broken) = scan.broken {
+        return Err(broken.problem.into_error(path));
+    }
+    Ok(scan
+        .frames
+        .iter()
+        .map(|frame| (frame.pos, frame.len as u32))
+        .collect())
+}
@@ -0,0 +1,78 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::time::SystemTime;
+
+use crate::state::QueueState;
+use crate::verify::{Header, VERSIONED_HEADER};
+use crate::{FileFormat, QueueFileError};
+
+/// The header version of versioned files. queue-file opens no other and fails with
+/// `UnsupportedVersion` for the rest.
+pub const VERSIONED_FORMAT_VERSION: u32 = VERSIONED_HEADER & 0x7fff_ffff;
+/// Reported as the version of legacy files, whose header has no version field.
+pub const LEGACY_FORMAT_VERSION: u32 = 0;
+
+/// The format versions this library can open: `LEGACY_FORMAT_VERSION` and
+/// `VERSIONED_FORMAT_VERSION`.
+#[uniffi::export]
+pub fn supported_format_versions() -> Vec<u32> {
+    vec![LEGACY_FORMAT_VERSION, VERSIONED_FORMAT_VERSION]
+}
+
+/// The committed header of a queue file and what the handle has layered over it.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct FileInfo {
+    pub format: FileFormat,
+    /// `VERSIONED_FORMAT_VERSION` or `LEGACY_FORMAT_VERSION`.
+    pub version: u32,
+    pub header_len: u64,
+    pub file_len: u64,
+    pub element_count: u64,
+    /// File offsets of the eldest and the newest element's frames.
+    pub first_position: u64,
+    pub last_position: u64,
+    /// From the file system, where it records them.
+    pub created_at: Option<SystemTime>,
+    pub modified_at: Option<SystemTime>,
+    /// Adds are compressed against a trained dictionary.
+    pub compressed: bool,
+    /// The contents are signed in `<path>.sig` (`signature_key`).
+    pub signed: bool,
+    /// Syncs write the CRC-checked `<path>.sync` trailer (`sync_trailer`).
+    pub sync_trailer: bool,
+    pub redundant_header: bool,
+    pub element_index: bool,
+    pub mirrored: bool,
+}
+
+/// The info of `state`'s file, whose header must be committed.
+pub(crate) fn read(state: &QueueState) -> Result<FileInfo, QueueFileError> {
+    let with_path = |e: std::io::Error| QueueFileError::from(e).with_path(&state.path);
+    let mut bytes = [0; 32];
+    File::open(&state.path)
+        .and_then(|mut file| file.read_exact(&mut bytes))
+        .map_err(with_path)?;
+    let header = Header::parse(&bytes);
+    let metadata = fs::metadata(&state.path).map_err(with_path)?;
+    let (format, version) = match header.header_len {
+        32 => (FileFormat::Versioned, VERSIONED_FORMAT_VERSION),
+        _ => (FileFormat::Legacy, LEGACY_FORMAT_VERSION),
+    };
+    Ok(FileInfo {
+        format,
+        version,
+        header_len: header.header_len,
+        file_len: header.file_len,
+        element_count: header.count,
+        first_position: header.first,
+        last_position: header.last,
+        created_at: metadata.created().ok(),
+        modified_at: metadata.modified().ok(),
+        compressed: state.codec.is_active(),
+        signed: state.options.signature_key.is_some(),
+        sync_trailer: state.options.sync_trailer,
+        redundant_header: state.options.redundant_header,
+        element_index: state.options.element_index,
+        mirrored: state.mirror.is_some(),
+    })
+}
@@ -0,0 +1,65 @@
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::latency::Histogram;
+
+const CALLS: [IoCall; 4] = [IoCall::Read, IoCall::Write, IoCall::Sync, IoCall::Truncate];
+
+/// The kinds of file system call whose latency is sampled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum IoCall {
+    /// A read of a frame, header or payload.
+    Read,
+    /// A write of elements, a header commit or a header sidecar slot.
+    Write,
+    /// An fsync of the queue file, a sidecar or a parent directory.
+    Sync,
+    /// Shortening the queue file to its committed length.
+    Truncate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct IoLatencySummary {
+    pub call: IoCall,
+    pub count: u64,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+/// One histogram per call, shared by every queue in the process since a device is.
+static HISTOGRAMS: Mutex<[Histogram; CALLS.len()]> =
+    Mutex::new([const { Histogram::new() }; CALLS.len()]);
+
+/// Runs `op`, recording how long it took as one `call`.
+pub(crate) fn measure<T>(call: IoCall, op: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = op();
+    let micros = u64::try_from(started.elapsed().as_micros()).unwrap_or(u64::MAX);
+    let index = CALLS.iter().position(|&c| c == call).unwrap();
+    HISTOGRAMS.lock().unwrap_or_else(PoisonError::into_inner)[index].record(micros);
+    result
+}
+
+/// Latency percentiles of each kind of file system call made on behalf of any queue in this
+/// process since it started, apart from calls never sampled: the writes of a queue with
+/// `sync_writes` on, which sync inside the same call, and those made while opening and
+/// rewriting files. Slow `Read`/`Sync` with ordinary queue-level latencies point at the device.
+#[uniffi::export]
+pub fn io_latencies() -> Vec<IoLatencySummary> {
+    let histograms = HISTOGRAMS.lock().unwrap_or_else(PoisonError::into_inner);
+    CALLS
+        .iter()
+        .zip(histograms.iter())
+        .filter(|(_, histogram)| histogram.count > 0)
+        .map(|(&call, histogram)| IoLatencySummary {
+            call,
+            count: histogram.count,
+            p50: Duration::from_micros(histogram.percentile(0.50)),
+            p95: Duration::from_micros(histogram.percentile(0.95)),
+            p99: Duration::from_micros(histogram.percentile(0.99)),
+            max: Duration::from_micros(histogram.max),
+        })
+        .collect()
+}
@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::envelope::{self, ElementMeta};
+use crate::{QueueFile, QueueFileError, QueueSnapshot};
+
+/// Elements an iterator reads from the file per lock.
+const BATCH: usize = 64;
+
+/// How a `QueueFileIterator` deals with the queue changing underneath it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, uniffi::Enum)]
+pub enum IterationMode {
+    /// Reads the queue under its lock and fails with `ConcurrentModification` once it changes.
+    #[default]
+    FailOnChange,
+    /// Pins the elements queued at creation in a snapshot and returns exactly those, however
+    /// the queue changes. Elements the queue removes, or moves by clearing, growing or swapping,
+    /// before the iterator reaches them are first copied into memory.
+    Pinned,
+}
+
+/// The elements a queue held when `iterator` was called, in order from the eldest. Under
+/// `FailOnChange` they are read under the queue's lock, a batch at a time, and any add,
+/// removal, clear, rewrite or swap after that makes the next call fail with
+/// `ConcurrentModification` instead of returning elements from a queue that no longer matches.
+/// Under `Pinned` they come from a `QueueSnapshot`.
+#[derive(uniffi::Object)]
+pub struct QueueFileIterator {
+    generation: u64,
+    size: u32,
+    /// Sequence id of the element the next call returns.
+    seq: Mutex<u64>,
+    source: Source,
+}
+
+enum Source {
+    Live {
+        queue: Arc<QueueFile>,
+        cursor: Mutex<Cursor>,
+    },
+    Pinned(QueueSnapshot),
+}
+
+#[derive(Default)]
+struct Cursor {
+    /// Elements read from the file so far, including those still in `batch`.
+    read: usize,
+    batch: VecDeque<Vec<u8>>,
+}
+
+impl QueueFileIterator {
+    pub(crate) fn live(queue: Arc<QueueFile>, generation: u64, size: u32, seq: u64) -> Self {
+        QueueFileIterator {
+            generation,
+            size,
+            seq: Mutex::new(seq),
+            source: Source::Live {
+                queue,
+                cursor: Mutex::new(Cursor::default()),
+            },
+        }
+    }
+
+    /// Iterates `snapshot` past its first `skip` elements, whose removal is still deferred.
+    /// `seq` is the sequence id of the first element after them.
+    pub(crate) fn pinned(
+        snapshot: QueueSnapshot,
+        skip: u32,
+        seq: u64,
+    ) -> Result<Self, QueueFileError> {
+        for _ in 0..skip {
+            snapshot.next()?;
+        }
+        Ok(QueueFileIterator {
+            generation: snapshot.generation(),
+            size: snapshot.size() - skip,
+            seq: Mutex::new(seq),
+            source: Source::Pinned(snapshot),
+        })
+    }
+}
+
+#[uniffi::export]
+impl QueueFileIterator {
+    /// The next element, or `None` after the last one. Under `FailOnChange`, fails with
+    /// `ConcurrentModification` once the queue has changed, even if the element was already
+    /// read.
+    #[uniffi::method]
+    pub fn next(&self) -> Result<Option<Vec<u8>>, QueueFileError> {
+        Ok(self.next_with_seq()?.map(|(element, _)| element))
+    }
+
+    /// Like `next`, returning the element's `ElementMeta` instead of its bytes. The element is
+    /// read and decoded in Rust, so only its metadata and digest cross the FFI boundary.
+    #[uniffi::method]
+    pub fn next_metadata(&self) -> Result<Option<ElementMeta>, QueueFileError> {
+        Ok(self
+            .next_with_seq()?
+            .map(|(element, seq)| envelope::meta(element, seq)))
+    }
+
+    /// The `generation()` of the queue when the iterator was created.
+    #[uniffi::method]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Elements the queue held when the iterator was created.
+    #[uniffi::method]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
+
+impl QueueFileIterator {
+    /// The next element with its sequence id.
+    fn next_with_seq(&self) -> Result<Option<(Vec<u8>, u64)>, QueueFileError> {
+        let mut seq = self.seq.lock().map_err(|_| QueueFileError::LockError)?;
+        let element = match &self.source {
+            Source::Live { queue, cursor } => {
+                let mut cursor = cursor.lock().map_err(|_| QueueFileError::LockError)?;
+                let count = match cursor.batch.is_empty() {
+                    true => BATCH,
+                    false => 0,
+                };
+                let elements = queue.read_unchanged(self.generation, cursor.read, count)?;
+                cursor.read += elements.len();
+                cursor.batch.extend(elements);
+                cursor.batch.pop_front()
+            }
+            Source::Pinned(snapshot) => snapshot.next()?,
+        };
+        let next = *seq;
+        if element.is_some() {
+            *seq += 1;
+        }
+        Ok(element.map(|element| (element, next)))
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use crate::QueueFileError;
+
+/// What a journaled operation did to the elements.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Enum)]
+pub enum Change {
+    /// Elements added at the back, whether written or held in the write buffer.
+    Add {
+        count: u32,
+        bytes: u64,
+    },
+    /// Elements removed from the front.
+    Remove {
+        count: u32,
+    },
+    /// Every element removed; `count` includes buffered adds that were discarded.
+    Clear {
+        count: u32,
+    },
+    RemoveAt {
+        index: u32,
+    },
+    UpdateAt {
+        index: u32,
+    },
+    /// The elements were exchanged with another queue's by `swap_with`.
+    Swap,
+    /// Every element was copied, unchanged, into a file that stores them as `rewrite` asked.
+    Rewrite,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct ChangeRecord {
+    /// The queue's `generation()` once the change was made.
+    pub seq: u64,
+    /// When the change was made.
+    pub timestamp: SystemTime,
+    pub change: Change,
+}
+
+/// The most recent changes, oldest first, up to `capacity` of them.
+pub(crate) struct Journal {
+    capacity: usize,
+    records: VecDeque<ChangeRecord>,
+}
+
+impl Journal {
+    pub(crate) fn new(capacity: u32) -> Self {
+        Journal {
+            capacity: capacity as usize,
+            records: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn record(&mut self, seq: u64, change: Change) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(ChangeRecord {
+            seq,
+            timestamp: SystemTime::now(),
+            change,
+        });
+    }
+
+    /// The changes after `since` in a queue now at `generation`. Fails with `JournalGap` when
+    /// some of them have been dropped, or when `since` is from a later generation than the
+    /// queue has reached.
+    pub(crate) fn since(
+        &self,
+        generation: u64,
+        since: u64,
+    ) -> Result<Vec<ChangeRecord>, QueueFileError> {
+        let earliest = self
+            .records
+            .front()
+            .map_or(generation, |record| record.seq - 1);
+        if since < earliest || since > generation {
+            return Err(QueueFileError::JournalGap { since, earliest });
+        }
+        let start = self.records.partition_point(|record| record.seq <= since);
+        Ok(self.records.range(start..).cloned().collect())
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use crate::slow_ops::QueueOperation;
+
+/// Buckets per power of two, so a reported percentile is at most 25% above the true value.
+const SUB_BUCKETS: u64 = 4;
+const BUCKETS: usize = 252;
+const OPERATIONS: [QueueOperation; 6] = [
+    QueueOperation::Add,
+    QueueOperation::Peek,
+    QueueOperation::Remove,
+    QueueOperation::Clear,
+    QueueOperation::Sync,
+    QueueOperation::Rewrite,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct LatencySummary {
+    pub operation: QueueOperation,
+    pub count: u64,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+/// Log-linear histogram of durations in microseconds: exact below 8 µs, then four buckets per
+/// power of two.
+#[derive(Default)]
+pub(crate) struct Histogram {
+    /// Empty until the first sample.
+    counts: Vec<u64>,
+    pub(crate) count: u64,
+    pub(crate) max: u64,
+}
+
+impl Histogram {
+    pub(crate) const fn new() -> Self {
+        Histogram {
+            counts: Vec::new(),
+            count: 0,
+            max: 0,
+        }
+    }
+
+    pub(crate) fn record(&mut self, micros: u64) {
+        if self.counts.is_empty() {
+            self.counts = vec![0; BUCKETS];
+        }
+        self.counts[bucket(micros)] += 1;
+        self.count += 1;
+        self.max = self.max.max(micros);
+    }
+
+    /// Upper bound of the bucket holding the sample at `quantile`, capped at the largest sample.
+    pub(crate) fn percentile(&self, quantile: f64) -> u64 {
+        let rank = ((self.count as f64 * quantile).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return upper_bound(index).min(self.max);
+            }
+        }
+        self.max
+    }
+}
+
+fn bucket(micros: u64) -> usize {
+    if micros < SUB_BUCKETS {
+        return micros as usize;
+    }
+    let exp = u64::from(63 - micros.leading_zeros());
+    let mantissa = (micros >> (exp - 2)) & (SUB_BUCKETS - 1);
+    ((exp - 1) * SUB_BUCKETS + mantissa) as usize
+}
+
+fn upper_bound(index: usize) -> u64 {
+    let index = index as u64;
+    if index < SUB_BUCKETS {
+        return index;
+    }
+    let exp = index / SUB_BUCKETS + 1;
+    let lower = (SUB_BUCKETS + index % SUB_BUCKETS) << (exp - 2);
+    lower + ((1 << (exp - 2)) - 1)
+}
+
+/// One histogram per operation, kept since the queue was opened.
+#[derive(Default)]
+pub(crate) struct Latencies {
+    histograms: [Histogram; OPERATIONS.len()],
+}
+
+impl Latencies {
+    pub(crate) fn record(&mut self, operation: QueueOperation, duration: Duration) {
+        let index = OPERATIONS.iter().position(|&op| op == operation).unwrap();
+        let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        self.histograms[index].record(micros);
+    }
+
+    /// Percentiles of every operation that ran at least once.
+    pub(crate) fn summaries(&self) -> Vec<LatencySummary> {
+        OPERATIONS
+            .iter()
+            .zip(&self.histograms)
+            .filter(|(_, histogram)| histogram.count > 0)
+            .map(|(&operation, histogram)| LatencySummary {
+                operation,
+                count: histogram.count,
+                p50: Duration::from_micros(histogram.percentile(0.50)),
+                p95: Duration::from_micros(histogram.percentile(0.95)),
+                p99: Duration::from_micros(histogram.percentile(0.99)),
+                max: Duration::from_micros(histogram.max),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::verify::VERSIONED_HEADER;
+
+/// Largest file the legacy header's 31-bit length can describe.
+const MAX_FILE_LEN: u64 = i32::MAX as u64;
+
+/// Creates an empty queue file of `capacity` bytes with the 16-byte header of the original Tape
+/// format, which queue-file keeps for any file it finds that way. Written through a rename, as
+/// queue-file creates its own files.
+pub(crate) fn create(path: &Path, capacity: u64) -> io::Result<()> {
+    if capacity > MAX_FILE_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("capacity {capacity} is over the legacy format's {MAX_FILE_LEN}-byte limit"),
+        ));
+    }
+    let mut staging = path.to_path_buf().into_os_string();
+    staging.push(".tmp");
+    let staging = PathBuf::from(staging);
+    let mut file = File::create(&staging)?;
+    file.set_len(capacity)?;
+    // File length first; the element count and both positions stay zero.
+    file.write_all(&(capacity as u32).to_be_bytes())?;
+    file.sync_all()?;
+    fs::rename(&staging, path)
+}
+
+/// Whether the file at `path` has a legacy header. False if there is no file.
+pub(crate) fn is_legacy(path: &Path) -> io::Result<bool> {
+    let mut marker = [0; 4];
+    match File::open(path).and_then(|mut file| file.read_exact(&mut marker)) {
+        Ok(()) => Ok(u32::from_be_bytes(marker) != VERSIONED_HEADER),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
@@ -1,34 +1,352 @@
-use std::path::Path;
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock, TryLockError, Weak};
+use std::time::{Duration, Instant, SystemTime};
 
-use queue_file::QueueFile as RustQueueFile;
+use sha2::{Digest, Sha256};
 
-#[derive(Debug, thiserror::Error, uniffi::Error)]
-#[uniffi(flat_error)]
+mod archive;
+mod backup;
+mod bloom;
+#[cfg(feature = "c-api")]
+mod c_api;
+mod capacity;
+mod chunking;
+mod consumer;
+mod crash_sim;
+mod deadline;
+mod dictionary;
+mod dir_sync;
+mod drain;
+mod dump;
+mod envelope;
+mod exchange;
+mod export;
+mod flush_timer;
+mod fragmentation;
+mod group_commit;
+mod header_slots;
+mod health;
+mod idle;
+mod index;
+mod info;
+mod io_latency;
+mod iter;
+mod journal;
+mod latency;
+mod legacy;
+mod lock_file;
+mod manager;
+mod mirror;
+mod mmap;
+mod msgpack;
+mod options;
+mod pause;
+mod protection;
+mod quarantine;
+mod quota;
+mod rate_limit;
+mod read_limit;
+mod reconcile;
+mod registry;
+mod retention;
+mod retry;
+mod rewrite;
+mod runtime;
+mod sampling;
+mod segmented;
+mod signature;
+mod slow_ops;
+mod snapshot;
+mod space;
+mod sqlite;
+mod state;
+mod stats;
+mod subscribe;
+mod sync_trailer;
+#[cfg(test)]
+mod tests;
+mod verify;
+mod watermark;
+mod write_buffer;
+
+pub use archive::ArchivedQueue;
+pub use capacity::{CapacityRecommendation, CapacityReport, GrowthEvent};
+pub use consumer::{ConsumerStats, ProcessPolicy, QueueConsumer};
+pub use crash_sim::{CrashMode, CrashOutcome, CrashSimulator};
+pub use dictionary::TrainedDictionary;
+pub use drain::DrainHandler;
+pub use envelope::{ElementEnvelope, ElementMeta, decode_envelope, encode_envelope};
+pub use export::{ExportEncoding, Exporter};
+use flush_timer::Task;
+pub use fragmentation::{FragmentationReport, FreeRange};
+use group_commit::GroupCommit;
+pub use health::{HealthReport, HealthStatus};
+use idle::IdleClose;
+pub use info::{
+    FileInfo, LEGACY_FORMAT_VERSION, VERSIONED_FORMAT_VERSION, supported_format_versions,
+};
+pub use io_latency::{IoCall, IoLatencySummary, io_latencies};
+pub use iter::{IterationMode, QueueFileIterator};
+pub use journal::{Change, ChangeRecord};
+pub use latency::LatencySummary;
+use manager::Pool;
+pub use manager::QueueManager;
+pub use mirror::MirrorStatus;
+pub use mmap::MappedQueue;
+pub use msgpack::{MsgpackValue, decode_msgpack_map, encode_msgpack_map};
+pub use options::{
+    AlreadyOpenPolicy, CorruptionPolicy, FileFormat, GrowthStrategy, OpenMode, QueueFileOptions,
+};
+pub use pause::PauseMode;
+pub use protection::notify_unlocked;
+pub use quarantine::{CorruptionRecovery, QuarantineReport, QuarantinedRange};
+pub use quota::CapacityInfo;
+pub use rate_limit::RateLimit;
+use read_limit::FrameReader;
+pub use reconcile::{SyncReport, SyncStrategy};
+use registry::Registration;
+pub use registry::flush_and_close_all;
+pub use retention::{DroppedSegment, RetentionListener, RetentionReason};
+pub use retry::RetryPolicy;
+pub use rewrite::{RewriteOptions, RewriteReport};
+pub use runtime::{RuntimeConfig, ThreadQos, configure_runtime};
+pub use segmented::{SegmentInfo, SegmentReader, SegmentedQueue, SegmentedQueueConfig};
+pub use signature::{KeyProvider, set_key_provider};
+pub use slow_ops::{QueueOperation, SlowOpListener};
+use slow_ops::{SlowOpEvent, SlowOps};
+pub use snapshot::QueueSnapshot;
+pub use space::{SpaceReport, estimate_file_len};
+use state::QueueState;
+pub use stats::{QueueStats, StatsWindow};
+pub use subscribe::{ElementListener, Subscription};
+pub use sync_trailer::{SyncCheck, SyncStatus};
+use watermark::Watermarks;
+pub use watermark::{WatermarkConfig, WatermarkListener, WatermarkMetric};
+pub use write_buffer::WriteBufferConfig;
+
+#[derive(Debug, Clone, thiserror::Error, uniffi::Error)]
 pub enum QueueFileError {
-    #[error("IO error: {0}")]
-    IoError(String),
+    #[error("IO error: {message}")]
+    IoError {
+        /// The OS error code (`errno`), when the failure came from a system call.
+        errno: Option<i32>,
+        kind: IoErrorKind,
+        /// The file the failing operation was working on, when known.
+        path: Option<String>,
+        message: String,
+    },
     #[error("Queue is empty")]
     EmptyQueue,
     #[error("Element too big")]
     ElementTooBig,
     #[error("Too many elements")]
     TooManyElements,
-    #[error("Corrupted file: {0}")]
-    CorruptedFile(String),
+    #[error("Corrupted file: {message}")]
+    CorruptedFile {
+        message: String,
+        /// Byte offset in the file of the damaged header or frame, when known.
+        offset: Option<u64>,
+        /// Position in the queue of the damaged element, when one was involved.
+        element_index: Option<u64>,
+        /// What a consistent file would hold there, and what this one holds.
+        expected: Option<String>,
+        found: Option<String>,
+        /// Whether opening with `quarantine_corrupt` keeps the elements before the damage,
+        /// rather than failing the same way.
+        salvageable: bool,
+    },
     #[error("Unsupported version: detected {detected}, supported {supported}")]
     UnsupportedVersion { detected: u32, supported: u32 },
     #[error("Lock acquisition error")]
     LockError,
+    #[error("Queue quota exceeded")]
+    QuotaExceeded,
+    /// The file's data-protection class makes it unreadable while the device is locked.
+    #[error("File is unavailable while the device is locked")]
+    DataProtectionLocked { path: Option<String> },
+    /// The file, or the mirror, is already open through another handle in this process.
+    #[error("Queue file is already open in this process: {path}")]
+    AlreadyOpen { path: String },
+    /// The queue's `.lock` file names another process that is still running.
+    #[error("Queue file is locked by process {pid}")]
+    LockedByOtherProcess { pid: u32 },
+    /// The handle was frozen with `freeze()`; adds, removals and clears are refused.
+    #[error("Queue is frozen")]
+    Frozen,
+    /// An add waited out its timeout while writes were paused with `PauseMode::Block`.
+    #[error("Writes are paused")]
+    WritesPaused,
+    /// An element position was not below the number of elements in the file.
+    #[error("Index {index} is out of range for {size} elements")]
+    IndexOutOfRange { index: u32, size: u32 },
+    /// `configure_runtime` was called after the worker threads had started.
+    #[error("The async runtime has already started")]
+    RuntimeAlreadyStarted,
+    /// `changes_since` was asked for changes the journal no longer holds, or for a sequence
+    /// number the queue has not reached. `earliest` is the oldest one it can answer from.
+    #[error("Changes since {since} are not in the journal; the earliest available is {earliest}")]
+    JournalGap { since: u64, earliest: u64 },
+    /// An add went over the queue's `rate_limit`. Nothing was added; the same add can succeed
+    /// after `retry_after`.
+    #[error("Add rate limit reached; retry after {} ms", .retry_after.as_millis())]
+    Backpressure { retry_after: Duration },
+    /// The queue changed after an iterator was created. `expected` is the `generation()` the
+    /// iterator started from and `found` the one the queue has reached.
+    #[error("Queue changed during iteration: generation {found}, expected {expected}")]
+    ConcurrentModification { expected: u64, found: u64 },
+    /// An element is longer than the queue's `max_read_bytes`, so it was not read. Use
+    /// `peek_to_file` or `pop_to_file`, which stream it to a file instead. From
+    /// `add_from_file`, the file is longer than it reads into memory; use
+    /// `add_chunked_from_file`.
+    #[error("Element of {len} bytes is over the {limit}-byte read limit; use peek_to_file")]
+    TooLargeForRead { len: u64, limit: u64 },
+    /// A call outlasted its `_with_timeout` deadline or the queue's `operation_timeout`.
+    /// `started` is false when the operation had not begun and never will; when true it was
+    /// under way, may still complete, and the queue should be checked before it is retried.
+    #[error("{operation:?} timed out after {} ms", .after.as_millis())]
+    Timeout {
+        operation: QueueOperation,
+        after: Duration,
+        started: bool,
+    },
+    /// The queue file does not match the signature of `signature_key`: it was changed outside
+    /// the queue, its signature is missing, or it was not synced or closed after its last
+    /// change.
+    #[error("Queue file does not match its signature: {path}")]
+    TamperDetected { path: String },
+    /// The `KeyProvider` had no key for `signature_key`, or none was set.
+    #[error("No key available for {key_id}")]
+    KeyUnavailable { key_id: String },
+    /// An element read as an `ElementEnvelope` is not a well-formed envelope message.
+    #[error("Invalid element envelope: {message}")]
+    InvalidEnvelope { message: String },
+    /// An element read as a MessagePack map is not one.
+    #[error("Invalid MessagePack map: {message}")]
+    InvalidMessagePack { message: String },
+    /// The elements at the head of the queue do not form a whole chunked element, for example
+    /// after a plain `remove` took only its first chunk.
+    #[error("Invalid chunked element: {message}")]
+    InvalidChunk { message: String },
+    /// The queue's file was used while `idle_close_after` or a `QueueManager` had it closed,
+    /// without reopening it first.
+    #[error("Queue file is closed for inactivity")]
+    ClosedForInactivity,
+    /// SQLite failed to open, read or write the database of an SQLite import or export.
+    #[error("SQLite error: {message}")]
+    SqliteError {
+        /// The database file.
+        path: Option<String>,
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum IoErrorKind {
+    NotFound,
+    PermissionDenied,
+    AlreadyExists,
+    /// No space left on the device (`ENOSPC`).
+    StorageFull,
+    ReadOnlyFilesystem,
+    ResourceBusy,
+    WouldBlock,
+    Interrupted,
+    TimedOut,
+    UnexpectedEof,
+    InvalidInput,
+    Other,
+}
+
+impl From<std::io::ErrorKind> for IoErrorKind {
+    fn from(kind: std::io::ErrorKind) -> Self {
+        use std::io::ErrorKind;
+
+        match kind {
+            ErrorKind::NotFound => IoErrorKind::NotFound,
+            ErrorKind::PermissionDenied => IoErrorKind::PermissionDenied,
+            ErrorKind::AlreadyExists => IoErrorKind::AlreadyExists,
+            ErrorKind::StorageFull => IoErrorKind::StorageFull,
+            ErrorKind::ReadOnlyFilesystem => IoErrorKind::ReadOnlyFilesystem,
+            ErrorKind::ResourceBusy => IoErrorKind::ResourceBusy,
+            ErrorKind::WouldBlock => IoErrorKind::WouldBlock,
+            ErrorKind::Interrupted => IoErrorKind::Interrupted,
+            ErrorKind::TimedOut => IoErrorKind::TimedOut,
+            ErrorKind::UnexpectedEof => IoErrorKind::UnexpectedEof,
+            ErrorKind::InvalidInput | ErrorKind::InvalidData => IoErrorKind::InvalidInput,
+            _ => IoErrorKind::Other,
+        }
+    }
+}
+
+impl QueueFileError {
+    /// Records `path` on an I/O or data-protection error that does not name a file yet.
+    pub(crate) fn with_path(self, path: &Path) -> Self {
+        match self {
+            QueueFileError::IoError {
+                errno,
+                kind,
+                path: None,
+                message,
+            } => QueueFileError::IoError {
+                errno,
+                kind,
+                path: Some(path.display().to_string()),
+                message,
+            },
+            QueueFileError::DataProtectionLocked { path: None } => {
+                QueueFileError::DataProtectionLocked {
+                    path: Some(path.display().to_string()),
+                }
+            }
+            QueueFileError::SqliteError {
+                path: None,
+                message,
+            } => QueueFileError::SqliteError {
+                path: Some(path.display().to_string()),
+                message,
+            },
+            other => other,
+        }
+    }
+
+    /// True for I/O errors that may succeed if the operation is simply tried again.
+    pub(crate) fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            QueueFileError::IoError {
+                kind: IoErrorKind::Interrupted
+                    | IoErrorKind::WouldBlock
+                    | IoErrorKind::ResourceBusy
+                    | IoErrorKind::TimedOut,
+                ..
+            }
+        )
+    }
 }
 
 impl From<queue_file::Error> for QueueFileError {
     fn from(e: queue_file::Error) -> Self {
         match e {
-            queue_file::Error::Io { source } => QueueFileError::IoError(source.to_string()),
+            queue_file::Error::Io { source } => source.into(),
             queue_file::Error::ElementTooBig {} => QueueFileError::ElementTooBig,
             queue_file::Error::TooManyElements {} => QueueFileError::TooManyElements,
-            queue_file::Error::CorruptedFile { msg } => QueueFileError::CorruptedFile(msg),
+            queue_file::Error::CorruptedFile { msg } => {
+                // queue-file only checks the header; it reports a truncated file as "... expected
+                // length was N but actual length is M".
+                let lengths = msg
+                    .split_once("expected length was ")
+                    .and_then(|(_, rest)| rest.split_once(" but actual length is "));
+                QueueFileError::CorruptedFile {
+                    offset: Some(0),
+                    element_index: None,
+                    expected: lengths.map(|(expected, _)| format!("file length {expected}")),
+                    found: lengths.map(|(_, found)| format!("file length {found}")),
+                    salvageable: false,
+                    message: msg,
+                }
+            }
             queue_file::Error::UnsupportedVersion { detected, supported } => {
                 QueueFileError::UnsupportedVersion { detected, supported }
             }
@@ -36,6 +354,29 @@ impl From<queue_file::Error> for QueueFileError {
     }
 }
 
+impl From<rusqlite::Error> for QueueFileError {
+    fn from(e: rusqlite::Error) -> Self {
+        QueueFileError::SqliteError {
+            path: None,
+            message: e.to_string(),
+        }
+    }
+}
+
+impl From<std::io::Error> for QueueFileError {
+    fn from(e: std::io::Error) -> Self {
+        if protection::is_locked_error(&e) {
+            return QueueFileError::DataProtectionLocked { path: None };
+        }
+        QueueFileError::IoError {
+            errno: e.raw_os_error(),
+            kind: e.kind().into(),
+            path: None,
+            message: e.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
 pub enum OffsetCachePolicy {
     None,
@@ -43,153 +384,1678 @@ pub enum OffsetCachePolicy {
     Quadratic,
 }
 
+/// What an add left behind, read under the same lock as the add.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct AddReceipt {
+    /// Sequence id of the first element added; the rest of a batch follow on from it. Ids
+    /// survive a reopen only with `element_index`, and otherwise restart from the queue's size.
+    pub seq: u64,
+    /// Elements in the queue afterwards, as `size` counts them.
+    pub size: u32,
+    /// Bytes the queue uses once buffered adds are written.
+    pub used_bytes: u64,
+    /// Whether the add grew the file.
+    pub grew: bool,
+}
+
 #[derive(uniffi::Object)]
 pub struct QueueFile {
-    inner: Mutex<RustQueueFile>,
+    inner: Mutex<QueueState>,
+    group_commit: GroupCommit,
+    /// Signalled when `resume_writes` ends a pause, waking adds blocked by it.
+    resumed: Condvar,
+    /// Handed to the flush timer, which must not keep the queue open.
+    me: Weak<QueueFile>,
+    /// Copied from the options so plain calls can honour it without taking the lock.
+    operation_timeout: Option<Duration>,
+    /// The handles of the `QueueManager` that opened this queue, which every use is counted in.
+    pool: OnceLock<Arc<Pool>>,
+    /// Last, since fields drop in order: the paths stay registered until `inner` is closed.
+    registration: Registration,
+}
+
+impl QueueFile {
+    fn from_state(state: QueueState, registration: Registration, me: Weak<QueueFile>) -> Self {
+        QueueFile {
+            operation_timeout: state.options.operation_timeout,
+            inner: Mutex::new(state),
+            group_commit: GroupCommit::default(),
+            resumed: Condvar::new(),
+            me,
+            pool: OnceLock::new(),
+            registration,
+        }
+    }
+
+    /// Writes out buffered adds, even while paused, and deferred removals, syncs the file and
+    /// freezes the handle. Adds blocked by a pause wake up and fail with `Frozen`.
+    pub(crate) fn flush_and_close(&self) -> Result<(), QueueFileError> {
+        let result = self.with_state(|state| {
+            state.paused = None;
+            state.freeze()?;
+            state.sync_all()
+        });
+        self.resumed.notify_all();
+        result
+    }
+
+    /// The eldest element and its sequence id, which `remove_if_front` goes by.
+    pub(crate) fn peek_front(&self) -> Result<Option<(u64, Vec<u8>)>, QueueFileError> {
+        self.with_state(|state| {
+            let element = state.peek_front()?;
+            Ok(element.map(|element| (state.head_seq(), element)))
+        })
+    }
+
+    /// Removes the eldest element if its sequence id is still `seq`. Another caller may have
+    /// removed it while the lock was not held, even if an equal payload is now eldest.
+    pub(crate) fn remove_if_front(&self, seq: u64) -> Result<bool, QueueFileError> {
+        self.mutate(|state| match state.is_front(seq) {
+            true => state.remove_n(1).map(|()| true),
+            false => Ok(false),
+        })
+    }
+
+    /// Called by a subscription reading the elements after `after`; see
+    /// `QueueState::catch_up`.
+    pub(crate) fn catch_up(
+        &self,
+        after: Option<u64>,
+        feed: &Arc<subscribe::Feed>,
+    ) -> Result<Option<subscribe::Numbered>, QueueFileError> {
+        self.with_state(|state| state.catch_up(after, feed))
+    }
+
+    /// The next `count` elements an iterator over `generation` has not read yet, after the
+    /// `read` it has, or none when `count` is 0. Fails with `ConcurrentModification` once the
+    /// queue has changed.
+    pub(crate) fn read_unchanged(
+        &self,
+        generation: u64,
+        read: usize,
+        count: usize,
+    ) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        self.with_state(|state| {
+            state.finish_open()?;
+            state.check_generation(generation)?;
+            let skip = state.pending_removes + read;
+            state.read_elements(skip, count)
+        })
+    }
+
+    /// Called by the flush timer once the oldest buffered add may have reached its delay. A
+    /// failed flush keeps the adds buffered for the next call that flushes.
+    pub(crate) fn flush_if_due(&self) {
+        let _ = self.mutate(|state| match state.write_buffer.should_flush() {
+            true => state.flush_adds(),
+            false => Ok(()),
+        });
+    }
+
+    /// Called by the timer once the queue may have gone `idle_close_after` without a use. A
+    /// failed close leaves the file open until a later use schedules another check.
+    pub(crate) fn close_if_idle(&self) {
+        let Ok(mut state) = self.inner.lock() else {
+            return;
+        };
+        match state.idle.as_mut().map(IdleClose::recheck) {
+            Some(Some(at)) => flush_timer::schedule(at, self.me.clone(), Task::CloseIfIdle),
+            Some(None) => {
+                if state.close_idle().is_ok()
+                    && state.queue.is_closed()
+                    && let Some(pool) = self.pool.get()
+                {
+                    pool.closed(&self.me);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Closes the file for a `QueueManager` making room for another queue, returning whether it
+    /// did. Skipped while another thread holds the lock, which is never waited for so managed
+    /// queues cannot deadlock on each other.
+    fn evict(&self) -> bool {
+        let Ok(mut state) = self.inner.try_lock() else {
+            return false;
+        };
+        state.close_idle().is_ok() && state.queue.is_closed()
+    }
+
+    /// Counts this queue against `pool`'s cap from now on, unless another manager has it.
+    fn join_pool(&self, pool: Arc<Pool>) -> Result<(), QueueFileError> {
+        let _ = self.pool.set(pool);
+        drop(self.lock()?);
+        Ok(())
+    }
+
+    /// Takes the lock, reopening a file `idle_close_after` closed, and records the use.
+    fn lock(&self) -> Result<MutexGuard<'_, QueueState>, QueueFileError> {
+        let state = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
+        self.woken(state)
+    }
+
+    fn woken<'a>(
+        &'a self,
+        mut state: MutexGuard<'a, QueueState>,
+    ) -> Result<MutexGuard<'a, QueueState>, QueueFileError> {
+        state.wake().map_err(|e| e.with_path(&state.path))?;
+        if let Some(at) = state.idle.as_mut().and_then(IdleClose::touch) {
+            flush_timer::schedule(at, self.me.clone(), Task::CloseIfIdle);
+        }
+        if let Some(pool) = self.pool.get() {
+            let (mut excess, others) = pool.used(&self.me);
+            for other in others {
+                if excess == 0 {
+                    break;
+                }
+                if other.upgrade().is_some_and(|queue| queue.evict()) {
+                    pool.closed(&other);
+                    excess -= 1;
+                }
+            }
+        }
+        Ok(state)
+    }
+
+    /// Runs `job` within `timeout`, or inline when there is none or the caller is already a
+    /// runtime worker, which has no thread to keep responsive.
+    fn within<T: Send + 'static>(
+        &self,
+        timeout: Option<Duration>,
+        operation: QueueOperation,
+        job: impl FnOnce(&QueueFile) -> Result<T, QueueFileError> + Send + 'static,
+    ) -> Result<T, QueueFileError> {
+        match (timeout, self.me.upgrade()) {
+            (Some(timeout), Some(queue)) if !runtime::on_worker() => {
+                deadline::run(queue, operation, timeout, job)
+            }
+            _ => job(self),
+        }
+    }
+
+    /// Runs `op` under the lock and tags any I/O error it returns with the queue's path, then
+    /// reports any slow operation once the lock has been released.
+    fn with_state<T>(
+        &self,
+        op: impl FnOnce(&mut QueueState) -> Result<T, QueueFileError>,
+    ) -> Result<T, QueueFileError> {
+        let mut state = self.lock()?;
+        let result = op(&mut state).map_err(|e| e.with_path(&state.path));
+        let slow_ops = std::mem::take(&mut state.slow_op_events);
+        drop(state);
+        slow_ops.into_iter().for_each(SlowOpEvent::dispatch);
+        result
+    }
+
+    /// Runs a mutation under the lock, then reports any watermark it crossed and any slow
+    /// operation once the lock has been released.
+    fn mutate<T>(
+        &self,
+        op: impl FnOnce(&mut QueueState) -> Result<T, QueueFileError>,
+    ) -> Result<T, QueueFileError> {
+        let state = self.lock()?;
+        self.mutate_locked(state, op)
+    }
+
+    fn mutate_locked<T>(
+        &self,
+        mut state: MutexGuard<'_, QueueState>,
+        op: impl FnOnce(&mut QueueState) -> Result<T, QueueFileError>,
+    ) -> Result<T, QueueFileError> {
+        let result = op(&mut state).map_err(|e| e.with_path(&state.path));
+        let event = state.check_watermarks();
+        let slow_ops = std::mem::take(&mut state.slow_op_events);
+        drop(state);
+        if let Some(event) = event {
+            event.dispatch();
+        }
+        slow_ops.into_iter().for_each(SlowOpEvent::dispatch);
+        result
+    }
+
+    /// Locks this queue and `other`, returned in that order, always taking the lower address
+    /// first so two calls on the same pair in opposite directions cannot deadlock.
+    fn lock_pair<'a>(
+        &'a self,
+        other: &'a QueueFile,
+    ) -> Result<(MutexGuard<'a, QueueState>, MutexGuard<'a, QueueState>), QueueFileError> {
+        let lock = |queue: &'a QueueFile| queue.lock();
+        match std::ptr::from_ref(self) < std::ptr::from_ref(other) {
+            true => {
+                let ours = lock(self)?;
+                Ok((ours, lock(other)?))
+            }
+            false => {
+                let theirs = lock(other)?;
+                Ok((lock(self)?, theirs))
+            }
+        }
+    }
+
+    fn add_with_deadline(
+        &self,
+        timeout: Option<Duration>,
+        data: Vec<u8>,
+    ) -> Result<AddReceipt, QueueFileError> {
+        self.add_multiple_with_deadline(timeout, vec![data])
+    }
+
+    fn add_multiple_with_deadline(
+        &self,
+        timeout: Option<Duration>,
+        items: Vec<Vec<u8>>,
+    ) -> Result<AddReceipt, QueueFileError> {
+        self.within(timeout, QueueOperation::Add, move |queue| {
+            queue.add_items(items)
+        })
+    }
+
+    fn peek_with_deadline(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Vec<u8>>, QueueFileError> {
+        self.within(timeout, QueueOperation::Peek, |queue| {
+            queue.with_state(|state| state.peek_front())
+        })
+    }
+
+    fn remove_n_with_deadline(
+        &self,
+        timeout: Option<Duration>,
+        n: u32,
+    ) -> Result<(), QueueFileError> {
+        self.within(timeout, QueueOperation::Remove, move |queue| {
+            queue.mutate(|state| state.remove_n(n as usize))
+        })
+    }
+
+    fn clear_with_deadline(&self, timeout: Option<Duration>) -> Result<(), QueueFileError> {
+        self.within(timeout, QueueOperation::Clear, |queue| {
+            queue.mutate(|state| state.clear())
+        })
+    }
+
+    fn sync_all_with_deadline(&self, timeout: Option<Duration>) -> Result<(), QueueFileError> {
+        self.within(timeout, QueueOperation::Sync, |queue| {
+            queue.with_state(|state| {
+                state.flush()?;
+                state.sync_all()
+            })
+        })
+    }
+
+    /// Takes the lock for an add, first waiting out a blocking write pause.
+    fn lock_for_add(&self) -> Result<MutexGuard<'_, QueueState>, QueueFileError> {
+        let mut state = self.lock()?;
+        let Some(PauseMode::Block { timeout }) = state.paused else {
+            return Ok(state);
+        };
+        let deadline = Instant::now() + timeout;
+        while let Some(PauseMode::Block { .. }) = state.paused {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(QueueFileError::WritesPaused);
+            }
+            state = self
+                .resumed
+                .wait_timeout(state, deadline - now)
+                .map_err(|_| QueueFileError::LockError)?
+                .0;
+        }
+        Ok(state)
+    }
+
+    fn add_items(&self, items: Vec<Vec<u8>>) -> Result<AddReceipt, QueueFileError> {
+        self.add_framed(items.into_iter().map(chunking::escape).collect())
+    }
+
+    /// Adds elements already framed by `chunking`.
+    fn add_framed(&self, items: Vec<Vec<u8>>) -> Result<AddReceipt, QueueFileError> {
+        let state = self.lock_for_add()?;
+        if state.paused == Some(PauseMode::Buffer) {
+            return self.mutate_locked(state, |state| {
+                let started = state.receipt_start();
+                state.hold(items)?;
+                Ok(state.receipt(started))
+            });
+        }
+        if !state.queue.sync_writes() || self.group_commit.max_delay()?.is_none() {
+            let (receipt, deadline) = self.mutate_locked(state, |state| {
+                let started = state.receipt_start();
+                let was_empty = state.write_buffer.is_empty();
+                state.add_all(items)?;
+                let deadline = state.write_buffer.deadline().filter(|_| was_empty);
+                Ok((state.receipt(started), deadline))
+            })?;
+            // The first add into an empty buffer starts its delay.
+            if let Some(deadline) = deadline {
+                flush_timer::schedule(deadline, self.me.clone(), Task::Flush);
+            }
+            return Ok(receipt);
+        }
+
+        // Write without syncing and let the group commit sync on behalf of every adder.
+        let (receipt, seq, deadline) = self.mutate_locked(state, |state| {
+            let started = state.receipt_start();
+            let was_empty = state.write_buffer.is_empty();
+            state.set_sync_writes(false);
+            let written = state.add_all(items);
+            state.set_sync_writes(true);
+            let receipt = state.receipt(started);
+            match written? {
+                true => Ok((receipt, Some(self.group_commit.record_write()?), None)),
+                false => {
+                    let deadline = state.write_buffer.deadline().filter(|_| was_empty);
+                    Ok((receipt, None, deadline))
+                }
+            }
+        })?;
+        if let Some(deadline) = deadline {
+            flush_timer::schedule(deadline, self.me.clone(), Task::Flush);
+        }
+        let Some(seq) = seq else {
+            return Ok(receipt);
+        };
+
+        self.group_commit.wait_durable(seq, || {
+            let mut state = match self.lock() {
+                Ok(state) => state,
+                Err(e) => return (0, Err(e)),
+            };
+            let target = match self.group_commit.written() {
+                Ok(target) => target,
+                Err(e) => return (0, Err(e)),
+            };
+            let synced = state.sync_all().map_err(|e| e.with_path(&state.path));
+            let slow_ops = std::mem::take(&mut state.slow_op_events);
+            drop(state);
+            slow_ops.into_iter().for_each(SlowOpEvent::dispatch);
+            (target, synced)
+        })?;
+        Ok(receipt)
+    }
 }
 
 #[uniffi::export]
 impl QueueFile {
     #[uniffi::constructor]
-    pub fn open(path: String) -> Result<Self, QueueFileError> {
-        let queue = RustQueueFile::open(Path::new(&path))?;
-        Ok(QueueFile {
-            inner: Mutex::new(queue),
-        })
+    pub fn open(path: String) -> Result<Arc<Self>, QueueFileError> {
+        Self::open_with_options(path, QueueFileOptions::default())
     }
 
     #[uniffi::constructor]
-    pub fn with_capacity(path: String, capacity: u64) -> Result<Self, QueueFileError> {
-        let queue = RustQueueFile::with_capacity(Path::new(&path), capacity)?;
-        Ok(QueueFile {
-            inner: Mutex::new(queue),
-        })
+    pub fn with_capacity(path: String, capacity: u64) -> Result<Arc<Self>, QueueFileError> {
+        let options = QueueFileOptions {
+            capacity,
+            ..QueueFileOptions::default()
+        };
+        Self::open_with_options(path, options)
+    }
+
+    #[uniffi::constructor]
+    pub fn open_with_options(
+        path: String,
+        options: QueueFileOptions,
+    ) -> Result<Arc<Self>, QueueFileError> {
+        registry::open(&PathBuf::from(path), options)
     }
 
     #[uniffi::method]
-    pub fn add(&self, data: Vec<u8>) -> Result<(), QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        queue.add(&data)?;
-        Ok(())
+    pub fn add(&self, data: Vec<u8>) -> Result<AddReceipt, QueueFileError> {
+        self.add_with_deadline(self.operation_timeout, data)
     }
 
     #[uniffi::method]
-    pub fn add_multiple(&self, items: Vec<Vec<u8>>) -> Result<(), QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        queue.add_n(items)?;
-        Ok(())
+    pub fn add_multiple(&self, items: Vec<Vec<u8>>) -> Result<AddReceipt, QueueFileError> {
+        self.add_multiple_with_deadline(self.operation_timeout, items)
+    }
+
+    /// Adds the contents of the file at `src_path` as one element, read inside Rust so the
+    /// payload never crosses the FFI boundary. queue-file writes an element in a single call,
+    /// so the payload is still held in memory once while it is added; a file over 64 MiB, or
+    /// over `max_read_bytes` when that is lower, fails with `TooLargeForRead` before any of it
+    /// is read. `add_chunked_from_file` takes longer ones. Returns its length.
+    #[uniffi::method]
+    pub fn add_from_file(&self, src_path: String) -> Result<u64, QueueFileError> {
+        let src = Path::new(&src_path);
+        let max_read_bytes = self.lock()?.options.max_read_bytes;
+        let limit = max_read_bytes.map_or(read_limit::MAX_FILE_LEN, |max| {
+            max.min(read_limit::MAX_FILE_LEN)
+        });
+        let data = (|| {
+            let file = std::fs::File::open(src)?;
+            read_limit::check(file.metadata()?.len(), Some(limit))?;
+            let mut data = Vec::new();
+            // The file may have grown since its length was read.
+            file.take(limit + 1).read_to_end(&mut data)?;
+            read_limit::check(data.len() as u64, Some(limit))?;
+            Ok(data)
+        })()
+        .map_err(|e: QueueFileError| e.with_path(src))?;
+        let len = data.len() as u64;
+        self.add_items(vec![data])?;
+        Ok(len)
     }
 
     #[uniffi::method]
     pub fn peek(&self) -> Result<Option<Vec<u8>>, QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        match queue.peek()? {
-            Some(boxed) => Ok(Some(boxed.to_vec())),
-            None => Ok(None),
-        }
+        self.peek_with_deadline(self.operation_timeout)
+    }
+
+    /// Adds `envelope` encoded with `encode_envelope`, stamped with the current time if its
+    /// `created_at` is `None`.
+    #[uniffi::method]
+    pub fn add_envelope(
+        &self,
+        mut envelope: ElementEnvelope,
+    ) -> Result<AddReceipt, QueueFileError> {
+        envelope.created_at.get_or_insert_with(SystemTime::now);
+        self.add(encode_envelope(envelope))
+    }
+
+    /// The eldest element decoded with `decode_envelope`, or `None` if the queue is empty.
+    #[uniffi::method]
+    pub fn peek_envelope(&self) -> Result<Option<ElementEnvelope>, QueueFileError> {
+        self.peek()?.map(decode_envelope).transpose()
+    }
+
+    /// The eldest element's size, sequence id and envelope fields, or `None` if the queue is
+    /// empty. The element is read and decoded in Rust and only its metadata is returned.
+    #[uniffi::method]
+    pub fn peek_metadata(&self) -> Result<Option<ElementMeta>, QueueFileError> {
+        self.with_state(|state| {
+            let element = state.peek_front()?;
+            Ok(element.map(|element| envelope::meta(element, state.head_seq())))
+        })
+    }
+
+    /// Adds `map` encoded with `encode_msgpack_map`.
+    #[uniffi::method]
+    pub fn add_msgpack_map(
+        &self,
+        map: HashMap<String, MsgpackValue>,
+    ) -> Result<AddReceipt, QueueFileError> {
+        self.add(encode_msgpack_map(map))
+    }
+
+    /// The eldest element decoded with `decode_msgpack_map`, or `None` if the queue is empty.
+    #[uniffi::method]
+    pub fn peek_msgpack_map(
+        &self,
+    ) -> Result<Option<HashMap<String, MsgpackValue>>, QueueFileError> {
+        self.peek()?.map(decode_msgpack_map).transpose()
+    }
+
+    /// Adds `data` split into elements of at most `chunk_len` bytes each (64 MiB by default),
+    /// in one write, so payloads longer than the 2 GiB element limit can be queued. Read it back
+    /// with `peek_chunked`, `pop_chunked_to_file` or `remove_chunked`; to the other methods each
+    /// chunk is an element of its own, read back as its part of `data`. The chunks are told
+    /// apart from other elements by how they are stored, so no payload passes for one.
+    #[uniffi::method]
+    pub fn add_chunked(
+        &self,
+        data: Vec<u8>,
+        chunk_len: Option<u32>,
+    ) -> Result<AddReceipt, QueueFileError> {
+        self.add_framed(chunking::split(&data, chunk_len)?)
+    }
+
+    /// `add_chunked` with the contents of the file at `src_path`, which may be longer than the
+    /// 2 GiB element limit. The file is read and added one chunk at a time under the lock, so
+    /// only one chunk is held in memory (all of them while writes are paused with `Buffer`);
+    /// if a chunk fails to be added, the ones before it stay queued and `remove_chunked` drops
+    /// them. Returns its length.
+    #[uniffi::method]
+    pub fn add_chunked_from_file(
+        &self,
+        src_path: String,
+        chunk_len: Option<u32>,
+    ) -> Result<u64, QueueFileError> {
+        let src = Path::new(&src_path);
+        let from_src = |e: std::io::Error| QueueFileError::from(e).with_path(src);
+        let mut file = std::fs::File::open(src).map_err(from_src)?;
+        let (chunk_len, count) =
+            chunking::layout(file.metadata().map_err(from_src)?.len(), chunk_len)?;
+        let state = self.lock_for_add()?;
+        self.mutate_locked(state, |state| {
+            let mut len = 0;
+            for index in 0..count {
+                let chunk =
+                    chunking::read_chunk(&mut file, index, count, chunk_len).map_err(from_src)?;
+                len += (chunk.len() - chunking::HEADER_LEN) as u64;
+                match state.paused {
+                    Some(PauseMode::Buffer) => state.hold(vec![chunk])?,
+                    _ => {
+                        state.add_all(vec![chunk])?;
+                        state.flush_adds()?;
+                    }
+                }
+            }
+            Ok(len)
+        })
+    }
+
+    /// The eldest chunked element reassembled, or `None` if the queue is empty. An element not
+    /// added with `add_chunked` is returned as it is. Fails with `InvalidChunk` if the head of
+    /// the queue holds only part of a chunked element.
+    #[uniffi::method]
+    pub fn peek_chunked(&self) -> Result<Option<Vec<u8>>, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            let Some(count) = head_chunks(state)? else {
+                return Ok(None);
+            };
+            let chunks = state.retrying(|state| state.read_framed(0, count as usize))?;
+            let mut data = Vec::new();
+            for index in 0..count {
+                let chunk = chunks.get(index as usize).map_or(&[][..], Vec::as_slice);
+                data.extend_from_slice(chunking::payload(chunk, index, count)?);
+            }
+            Ok(Some(data))
+        })
+    }
+
+    /// Writes the eldest chunked element to a new file at `dest_path` one chunk at a time, syncs
+    /// it and removes the element's chunks. Returns its length, or `None` (creating no file) if
+    /// the queue is empty.
+    #[uniffi::method]
+    pub fn pop_chunked_to_file(&self, dest_path: String) -> Result<Option<u64>, QueueFileError> {
+        self.mutate(|state| {
+            state.check_not_frozen()?;
+            state.flush()?;
+            let Some(count) = head_chunks(state)? else {
+                return Ok(None);
+            };
+            let dest = Path::new(&dest_path);
+            let mut file =
+                std::fs::File::create(dest).map_err(|e| QueueFileError::from(e).with_path(dest))?;
+            let mut len = 0;
+            for index in 0..count {
+                let chunk = state.retrying(|state| state.read_framed(index as usize, 1))?;
+                let chunk = chunk.first().map_or(&[][..], Vec::as_slice);
+                let payload = chunking::payload(chunk, index, count)?;
+                file.write_all(payload)
+                    .map_err(|e| QueueFileError::from(e).with_path(dest))?;
+                len += payload.len() as u64;
+            }
+            file.sync_all()
+                .map_err(|e| QueueFileError::from(e).with_path(dest))?;
+            state.remove_n(count as usize)?;
+            Ok(Some(len))
+        })
+    }
+
+    /// Removes the eldest chunked element, every chunk of it. The rest of one that a plain
+    /// `remove` left behind, or one whose last chunks are missing, is removed up to the first
+    /// element that does not continue it, so `InvalidChunk` never blocks the queue. Does
+    /// nothing if the queue is empty.
+    #[uniffi::method]
+    pub fn remove_chunked(&self) -> Result<(), QueueFileError> {
+        self.mutate(|state| {
+            state.flush()?;
+            let span = match chunk_headers(state, 1)?.first() {
+                Some(Some((index, count))) => (count - index) as usize,
+                Some(None) => 1,
+                None => return Ok(()),
+            };
+            let headers = chunk_headers(state, span)?;
+            state.remove_n(chunking::remaining(&headers) as usize)
+        })
+    }
+
+    /// SHA-256 of the eldest element's payload, computed in Rust so only the 32-byte digest
+    /// crosses the FFI boundary. `None` if the queue is empty.
+    #[uniffi::method]
+    pub fn peek_digest(&self) -> Result<Option<Vec<u8>>, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            let element = state.retrying(|state| Ok(state.read_elements(0, 1)?.pop()))?;
+            Ok(element.map(|element| Sha256::digest(&element).to_vec()))
+        })
+    }
+
+    /// Writes the eldest element to a new file at `dest_path` and syncs it, so the payload never
+    /// crosses the FFI boundary. It is copied a chunk at a time, however long it is. Returns its
+    /// length, or `None` (creating no file) if the queue is empty.
+    #[uniffi::method]
+    pub fn peek_to_file(&self, dest_path: String) -> Result<Option<u64>, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            write_head(state, Path::new(&dest_path))
+        })
+    }
+
+    /// Like `peek_to_file`, then removes the element once the file has been synced. The queue
+    /// stays locked in between, so no other consumer can take the same element.
+    #[uniffi::method]
+    pub fn pop_to_file(&self, dest_path: String) -> Result<Option<u64>, QueueFileError> {
+        self.mutate(|state| {
+            state.check_not_frozen()?;
+            state.flush()?;
+            let written = write_head(state, Path::new(&dest_path))?;
+            if written.is_some() {
+                state.remove_n(1)?;
+            }
+            Ok(written)
+        })
+    }
+
+    /// Returns a uniformly chosen element without removing it, reading only the elements up
+    /// to it, or only that element with `element_index`. The same `seed` picks the same element
+    /// from the same queue.
+    #[uniffi::method]
+    pub fn peek_random(&self, seed: Option<u64>) -> Result<Option<Vec<u8>>, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            let len = state.queue.size() as u64;
+            if len == 0 {
+                return Ok(None);
+            }
+            let index = sampling::Rng::new(seed).below(len) as u32;
+            state.element_at(index).map(Some)
+        })
+    }
+
+    /// Returns the element at `index`, counting from the eldest, without removing it. With
+    /// `element_index` it is read from its recorded position; otherwise the elements before it
+    /// are walked. Fails with `IndexOutOfRange` past the last element.
+    #[uniffi::method]
+    pub fn element_at(&self, index: u32) -> Result<Vec<u8>, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            state.element_at(index)
+        })
+    }
+
+    /// Returns up to `n` distinct elements chosen uniformly, in queue order, copying only the
+    /// chosen ones in a single pass.
+    #[uniffi::method]
+    pub fn sample(&self, n: u32, seed: Option<u64>) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            let len = state.queue.size() as u64;
+            let picked = sampling::Rng::new(seed).distinct(u64::from(n), len);
+            let Some(&last) = picked.last() else {
+                return Ok(Vec::new());
+            };
+            if state.options.max_read_bytes.is_some() {
+                let end = last as usize + 1;
+                return state.read_frames(end, |index| picked.contains(&(index as u64)));
+            }
+            let mut sample = Vec::with_capacity(picked.len());
+            let mut iter = state.queue.get_mut()?.iter();
+            for index in 0..=last {
+                let Some(element) = iter.borrowed_next() else {
+                    break;
+                };
+                if picked.contains(&index) {
+                    sample.push(state.codec.view(element, None)?.into_owned());
+                }
+            }
+            Ok(sample)
+        })
+    }
+
+    /// Returns false if no element, written or buffered, has exactly this payload. True may be
+    /// a false positive when the `bloom_filter_elements` option is set (about 1% up to the
+    /// configured element count); without it the queue is scanned and the answer is exact.
+    #[uniffi::method]
+    pub fn probably_contains(&self, data: Vec<u8>) -> Result<bool, QueueFileError> {
+        self.with_state(|state| state.probably_contains(&data))
+    }
+
+    /// Returns the positions, eldest first, of up to `limit` elements whose payload starts with
+    /// `prefix`. Payloads are compared in place; none are copied unless they have to be
+    /// decompressed.
+    #[uniffi::method]
+    pub fn find_indices(&self, prefix: Vec<u8>, limit: u32) -> Result<Vec<u32>, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            let mut found = Vec::new();
+            let mut iter = state.queue.get_mut()?.iter();
+            let mut index = 0;
+            while found.len() < limit as usize
+                && let Some(element) = iter.borrowed_next()
+            {
+                if state.codec.view(element, None)?.starts_with(&prefix) {
+                    found.push(index);
+                }
+                index += 1;
+            }
+            Ok(found)
+        })
+    }
+
+    /// Streams every element to a new newline-delimited JSON file at `path`, one
+    /// `{"index":N,"data":"..."}` object per line. `encoding` defaults to base64. Returns the
+    /// number of elements exported.
+    #[uniffi::method]
+    pub fn export_ndjson(
+        &self,
+        path: String,
+        encoding: Option<ExportEncoding>,
+    ) -> Result<u64, QueueFileError> {
+        let format = export::Format::Ndjson(encoding.unwrap_or(ExportEncoding::Base64));
+        self.with_state(|state| {
+            state.flush()?;
+            export::export(
+                state.queue.get_mut()?,
+                &state.codec,
+                Path::new(&path),
+                format,
+            )
+        })
+    }
+
+    /// Streams every element to a new CSV file at `path` with `index,size,data` columns, the
+    /// payload as quoted UTF-8 text. Returns the number of elements exported.
+    #[uniffi::method]
+    pub fn export_csv(&self, path: String) -> Result<u64, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            export::export(
+                state.queue.get_mut()?,
+                &state.codec,
+                Path::new(&path),
+                export::Format::Csv,
+            )
+        })
+    }
+
+    /// Adds the values of `column` of every row of `table` in the SQLite database at `db_path`,
+    /// in `rowid` order, for moving pending events out of an app's old SQLite store. Blobs and
+    /// text are added as their bytes, numbers as their decimal text, and `NULL`s are skipped.
+    /// Values are added in batches of about 1 MiB, so a failure part-way leaves the batches
+    /// before it in the queue; the database is only read. Returns the number of elements added.
+    #[uniffi::method]
+    pub fn import_from_sqlite(
+        &self,
+        db_path: String,
+        table: String,
+        column: String,
+    ) -> Result<u64, QueueFileError> {
+        sqlite::import(Path::new(&db_path), &table, &column, |batch| {
+            self.add_multiple(batch).map(|_| ())
+        })
+    }
+
+    /// Inserts every element, eldest first, into `table` of the SQLite database at `db_path`
+    /// in one transaction, creating the database and the table as needed. A new table has an
+    /// `id INTEGER PRIMARY KEY` column, which orders the rows as the queue does, and a
+    /// `data BLOB` column; an existing one needs a `data` column. The queue is left as it is.
+    /// Returns the number of elements exported.
+    #[uniffi::method]
+    pub fn export_to_sqlite(&self, db_path: String, table: String) -> Result<u64, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            sqlite::export(
+                state.queue.get_mut()?,
+                &state.codec,
+                Path::new(&db_path),
+                &table,
+            )
+        })
+    }
+
+    /// Streams every element to `exporter` as length-prefixed frames in chunks of about 64 KiB,
+    /// so the queue never has to fit in memory at once. Writers wait until it returns, and the
+    /// exporter must not call back into this queue. An exporter that returns `false` stops the
+    /// export with an `Interrupted` I/O error, without `finish`. Returns the number of elements
+    /// exported.
+    #[uniffi::method]
+    pub fn export_with(&self, exporter: Arc<dyn Exporter>) -> Result<u64, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            export::export_with(state.queue.get_mut()?, &state.codec, exporter)
+        })
+    }
+
+    /// Passes elements, eldest first, to `handler` and removes each one it accepts before
+    /// passing the next, stopping at the first it rejects or after `max`. A crash while an
+    /// element is being handled leaves it in the queue, so the handler must tolerate seeing an
+    /// element again. Other callers wait until it returns, and the handler must not call back
+    /// into this queue. Returns the number of elements removed.
+    #[uniffi::method]
+    pub fn drain_with(
+        &self,
+        handler: Arc<dyn DrainHandler>,
+        max: u32,
+    ) -> Result<u32, QueueFileError> {
+        self.mutate(|state| drain::drain(state, &*handler, max))
+    }
+
+    /// Keeps delivering elements to `handler` from the runtime's workers, eldest first, until
+    /// the returned consumer is stopped. Unlike `drain_with`, the queue is not locked while an
+    /// element is handled. An accepted element is removed; a rejected one is delivered again
+    /// after the policy's backoff, and after `max_attempts` deliveries it is added to
+    /// `dead_letter` and removed, or just removed without one.
+    #[uniffi::method]
+    pub fn process(
+        self: Arc<Self>,
+        handler: Arc<dyn DrainHandler>,
+        policy: ProcessPolicy,
+        dead_letter: Option<Arc<QueueFile>>,
+    ) -> Result<Arc<QueueConsumer>, QueueFileError> {
+        QueueConsumer::start(self, handler, policy, dead_letter)
+    }
+
+    /// Starts a thread passing `listener` every element whose sequence id is after `seq`, or
+    /// every element when `seq` is `None`. It first reads the ones already committed from the
+    /// file, then receives each later commit's elements as they are written.
+    #[uniffi::method]
+    pub fn subscribe_from(
+        self: Arc<Self>,
+        seq: Option<u64>,
+        listener: Arc<dyn ElementListener>,
+    ) -> Result<Arc<Subscription>, QueueFileError> {
+        Subscription::start(self, seq, listener)
     }
 
     #[uniffi::method]
     pub fn remove(&self) -> Result<(), QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        queue.remove()?;
-        Ok(())
+        self.remove_n_with_deadline(self.operation_timeout, 1)
     }
 
     #[uniffi::method]
     pub fn remove_n(&self, n: u32) -> Result<(), QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        queue.remove_n(n as usize)?;
-        Ok(())
+        self.remove_n_with_deadline(self.operation_timeout, n)
+    }
+
+    /// Removes the element at `index`, counting from the eldest, leaving the order of the
+    /// others unchanged. The file is rewritten through a temporary copy, so this costs a pass
+    /// over the whole queue; use it for the occasional poisoned element.
+    #[uniffi::method]
+    pub fn remove_at(&self, index: u32) -> Result<(), QueueFileError> {
+        self.mutate(|state| state.remove_at(index))
+    }
+
+    /// Replaces the payload of the element at `index`, keeping its position. Like `remove_at`
+    /// it rewrites the file, since a frame cannot change length in place.
+    #[uniffi::method]
+    pub fn update_at(&self, index: u32, data: Vec<u8>) -> Result<(), QueueFileError> {
+        self.mutate(|state| state.update_at(index, data))
+    }
+
+    /// Copies every element into a new file that stores it as `options` asks, for example to
+    /// compress a queue created before compression was turned on, then renames the copy into
+    /// place. Reads return the same payloads as before, in the same order. Like `remove_at` it
+    /// costs a pass over the whole queue, and a crash leaves either the old file or the new one.
+    #[uniffi::method]
+    pub fn rewrite(&self, options: RewriteOptions) -> Result<RewriteReport, QueueFileError> {
+        self.mutate(|state| state.recode(options))
     }
 
     #[uniffi::method]
     pub fn clear(&self) -> Result<(), QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        queue.clear()?;
+        self.clear_with_deadline(self.operation_timeout)
+    }
+
+    /// Exchanges the contents of this queue and `other` with one atomic rename, so a crash
+    /// leaves both or neither swapped. Both files must be on the same volume. Each handle keeps
+    /// its own path, options and stats.
+    #[uniffi::method]
+    pub fn swap_with(&self, other: Arc<QueueFile>) -> Result<(), QueueFileError> {
+        if std::ptr::eq(self, &*other) {
+            return Ok(());
+        }
+        let (mut a, mut b) = self.lock_pair(&other)?;
+        let result = a.swap_with(&mut b).map_err(|e| e.with_path(&a.path));
+        let events = [a.check_watermarks(), b.check_watermarks()];
+        drop(b);
+        drop(a);
+        for event in events.into_iter().flatten() {
+            event.dispatch();
+        }
+        result
+    }
+
+    /// Brings this queue up to date with `source`, which is only read, for example a backup
+    /// that fell behind its primary. Elements are matched by the SHA-256 of their payloads, so
+    /// where the eldest elements of `source` are the newest ones here, only the difference is
+    /// removed and added. Both queues are locked for the duration.
+    #[uniffi::method]
+    pub fn sync_from(
+        &self,
+        source: Arc<QueueFile>,
+        strategy: SyncStrategy,
+    ) -> Result<SyncReport, QueueFileError> {
+        if std::ptr::eq(self, &*source) {
+            let kept =
+                self.with_state(|state| Ok(state.logical_size() - state.pending_removes as u64))?;
+            return Ok(SyncReport {
+                removed: 0,
+                added: 0,
+                kept,
+            });
+        }
+        let (mut target, mut source) = self.lock_pair(&source)?;
+        let result = reconcile::sync(&mut target, &mut source, strategy)
+            .map_err(|e| e.with_path(&target.path));
+        drop(source);
+        self.mutate_locked(target, |_| result)
+    }
+
+    #[uniffi::method]
+    pub fn freeze(&self) -> Result<(), QueueFileError> {
+        self.with_state(|state| state.freeze())
+    }
+
+    #[uniffi::method]
+    pub fn unfreeze(&self) -> Result<(), QueueFileError> {
+        let mut state = self.lock()?;
+        state.frozen = false;
         Ok(())
     }
 
+    #[uniffi::method]
+    pub fn is_frozen(&self) -> Result<bool, QueueFileError> {
+        let state = self.lock()?;
+        Ok(state.frozen)
+    }
+
+    #[uniffi::method]
+    pub fn pause_writes(&self, mode: PauseMode) -> Result<(), QueueFileError> {
+        self.with_state(|state| state.pause_writes(mode))
+    }
+
+    #[uniffi::method]
+    pub fn resume_writes(&self) -> Result<(), QueueFileError> {
+        let result = self.mutate(|state| state.resume_writes());
+        self.resumed.notify_all();
+        result
+    }
+
     #[uniffi::method]
     pub fn is_empty(&self) -> Result<bool, QueueFileError> {
-        let queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        Ok(queue.is_empty())
+        self.with_state(|state| {
+            state.flush_adds()?;
+            Ok(state.queue.size() <= state.pending_removes)
+        })
     }
 
+    /// Leaves deferred removals pending and does not count them.
     #[uniffi::method]
     pub fn size(&self) -> Result<u32, QueueFileError> {
-        let queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        Ok(queue.size() as u32)
+        self.with_state(|state| {
+            state.flush_adds()?;
+            Ok(state.queue.size().saturating_sub(state.pending_removes) as u32)
+        })
     }
 
     #[uniffi::method]
     pub fn file_len(&self) -> Result<u64, QueueFileError> {
-        let queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        Ok(queue.file_len())
+        self.with_state(|state| {
+            state.flush()?;
+            Ok(state.queue.file_len())
+        })
     }
 
     #[uniffi::method]
     pub fn used_bytes(&self) -> Result<u64, QueueFileError> {
-        let queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        Ok(queue.used_bytes())
+        self.with_state(|state| {
+            state.flush()?;
+            Ok(state.queue.used_bytes())
+        })
+    }
+
+    #[uniffi::method]
+    pub fn disk_usage(&self) -> Result<u64, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            Ok(space::allocated_bytes(&state.path)?)
+        })
+    }
+
+    #[uniffi::method]
+    pub fn space_report(&self) -> Result<SpaceReport, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            Ok(SpaceReport {
+                file_len: state.queue.file_len(),
+                used_bytes: state.queue.used_bytes(),
+                disk_usage: space::allocated_bytes(&state.path)?,
+            })
+        })
+    }
+
+    /// The header of the file as committed after writing out buffered adds and deferred
+    /// removals, its file system times and the optional features in use.
+    #[uniffi::method]
+    pub fn file_info(&self) -> Result<FileInfo, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            info::read(state)
+        })
+    }
+
+    /// A plain-text dump for support tickets: what `file_info` reports, the ring layout of
+    /// `fragmentation_report`, and an offset, hex and ASCII preview of the first `max_elements`
+    /// elements, each cut to `max_bytes_per_element` bytes. Previews show the bytes as stored,
+    /// so a compressed element shows its zstd frame.
+    #[uniffi::method]
+    pub fn debug_dump(
+        &self,
+        max_elements: u32,
+        max_bytes_per_element: u32,
+    ) -> Result<String, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            dump::render(state, max_elements, max_bytes_per_element)
+        })
+    }
+
+    #[uniffi::method]
+    pub fn fragmentation_report(&self) -> Result<FragmentationReport, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            let used_bytes = state.queue.used_bytes();
+            Ok(fragmentation::report(
+                &state.path,
+                used_bytes,
+                state.options.capacity,
+            )?)
+        })
+    }
+
+    /// The fragmentation, growth since open and element sizes of the queue, with the action
+    /// the library recommends: `CompactNow` when a `rewrite` would give back at least half the
+    /// file and 1 MiB, `RaiseCapacity` after three or more growths from a smaller `capacity`,
+    /// and `EnableCompression` for 1 MiB or more in 1000 or more uncompressed elements.
+    #[uniffi::method]
+    pub fn capacity_report(&self) -> Result<CapacityReport, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            let used_bytes = state.queue.used_bytes();
+            let fragmentation =
+                fragmentation::report(&state.path, used_bytes, state.options.capacity)?;
+            let header_len = match legacy::is_legacy(&state.path)? {
+                true => 16,
+                false => 32,
+            };
+            Ok(capacity::report(
+                &fragmentation,
+                header_len,
+                state.queue.size() as u64,
+                state.options.capacity,
+                state.codec.is_active(),
+                &state.growths,
+            ))
+        })
+    }
+
+    /// Counts elements by payload size in one pass over the queue. `bucket_bounds` must be
+    /// ascending; bucket `i` holds sizes in `bucket_bounds[i - 1]..bucket_bounds[i]`, so the
+    /// result has one more entry than there are bounds.
+    #[uniffi::method]
+    pub fn size_histogram(&self, bucket_bounds: Vec<u64>) -> Result<Vec<u64>, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            let mut counts = vec![0; bucket_bounds.len() + 1];
+            for element in state.queue.get_mut()?.iter() {
+                let len = state.codec.payload_len(&element);
+                counts[bucket_bounds.partition_point(|&bound| bound <= len)] += 1;
+            }
+            Ok(counts)
+        })
+    }
+
+    /// Trains a zstd dictionary on up to `sample_limit` elements from the front of the queue
+    /// and compresses every element added after it against the dictionary. Elements already
+    /// queued keep their stored form, and reads return payloads as they were added either way.
+    /// Retraining keeps the earlier dictionaries, so nothing compressed against them becomes
+    /// unreadable. Fails with an I/O error if the elements are too few or too alike to train on.
+    #[uniffi::method]
+    pub fn train_dictionary(&self, sample_limit: u32) -> Result<TrainedDictionary, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            state.train_dictionary(sample_limit)
+        })
     }
 
     #[uniffi::method]
     pub fn get_all(&self) -> Result<Vec<Vec<u8>>, QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        let items: Vec<Vec<u8>> = queue.iter().map(|boxed| boxed.to_vec()).collect();
-        Ok(items)
+        self.with_state(|state| {
+            state.flush()?;
+            state.read_elements(0, usize::MAX)
+        })
+    }
+
+    /// An iterator over the elements queued now, which under `mode` (`FailOnChange` by
+    /// default) either fails with `ConcurrentModification` once the queue changes or keeps
+    /// returning the elements it pinned. Creating it flushes buffered adds.
+    #[uniffi::method]
+    pub fn iterator(
+        self: Arc<Self>,
+        mode: Option<IterationMode>,
+    ) -> Result<Arc<QueueFileIterator>, QueueFileError> {
+        let (generation, size, seq, pinned) = self.with_state(|state| {
+            state.flush()?;
+            state.finish_open()?;
+            let skip = state.pending_removes.min(state.queue.size());
+            let seq = state.head_seq();
+            let pinned = match mode.unwrap_or_default() {
+                IterationMode::FailOnChange => None,
+                IterationMode::Pinned => Some(QueueFileIterator::pinned(
+                    state.snapshot()?,
+                    skip as u32,
+                    seq,
+                )?),
+            };
+            let size = (state.queue.size() - skip) as u32;
+            Ok((state.generation, size, seq, pinned))
+        })?;
+        let iterator =
+            pinned.unwrap_or_else(|| QueueFileIterator::live(self, generation, size, seq));
+        Ok(Arc::new(iterator))
+    }
+
+    /// A read view of the elements queued now, for iterating while adds and removals go on.
+    /// Taking it holds the lock only to flush buffered adds and read the header.
+    #[uniffi::method]
+    pub fn snapshot(&self) -> Result<Arc<QueueSnapshot>, QueueFileError> {
+        self.with_state(|state| {
+            state.flush()?;
+            state.snapshot().map(Arc::new)
+        })
     }
 
     #[uniffi::method]
     pub fn sync_all(&self) -> Result<(), QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        queue.sync_all()?;
-        Ok(())
+        self.sync_all_with_deadline(self.operation_timeout)
+    }
+
+    /// `add` failing with `Timeout` once `timeout` has passed, whatever `operation_timeout`
+    /// says. The `_with_timeout` calls run on a runtime worker while the caller waits.
+    #[uniffi::method]
+    pub fn add_with_timeout(
+        &self,
+        data: Vec<u8>,
+        timeout: Duration,
+    ) -> Result<AddReceipt, QueueFileError> {
+        self.add_with_deadline(Some(timeout), data)
+    }
+
+    #[uniffi::method]
+    pub fn add_multiple_with_timeout(
+        &self,
+        items: Vec<Vec<u8>>,
+        timeout: Duration,
+    ) -> Result<AddReceipt, QueueFileError> {
+        self.add_multiple_with_deadline(Some(timeout), items)
+    }
+
+    #[uniffi::method]
+    pub fn peek_with_timeout(&self, timeout: Duration) -> Result<Option<Vec<u8>>, QueueFileError> {
+        self.peek_with_deadline(Some(timeout))
+    }
+
+    #[uniffi::method]
+    pub fn remove_with_timeout(&self, timeout: Duration) -> Result<(), QueueFileError> {
+        self.remove_n_with_deadline(Some(timeout), 1)
+    }
+
+    #[uniffi::method]
+    pub fn remove_n_with_timeout(&self, n: u32, timeout: Duration) -> Result<(), QueueFileError> {
+        self.remove_n_with_deadline(Some(timeout), n)
+    }
+
+    #[uniffi::method]
+    pub fn clear_with_timeout(&self, timeout: Duration) -> Result<(), QueueFileError> {
+        self.clear_with_deadline(Some(timeout))
+    }
+
+    #[uniffi::method]
+    pub fn sync_all_with_timeout(&self, timeout: Duration) -> Result<(), QueueFileError> {
+        self.sync_all_with_deadline(Some(timeout))
+    }
+
+    #[uniffi::method]
+    pub fn flush(&self) -> Result<(), QueueFileError> {
+        self.with_state(|state| state.flush())
+    }
+
+    /// Commits the removals held under `deferred_removes` with one header write, giving their
+    /// space back to the ring. `flush`, `sync_all`, closing the queue and any read other than
+    /// `peek`, `size` and `is_empty` do the same.
+    #[uniffi::method]
+    pub fn gc(&self) -> Result<(), QueueFileError> {
+        self.mutate(|state| state.commit_removes())
+    }
+
+    #[uniffi::method]
+    pub fn set_write_buffering(
+        &self,
+        config: Option<WriteBufferConfig>,
+    ) -> Result<(), QueueFileError> {
+        self.with_state(|state| {
+            if config.is_none() {
+                state.flush()?;
+            }
+            state.write_buffer.set_config(config);
+            Ok(())
+        })
+    }
+
+    #[uniffi::method]
+    pub fn write_buffering(&self) -> Result<Option<WriteBufferConfig>, QueueFileError> {
+        let state = self.lock()?;
+        Ok(state.write_buffer.config())
     }
 
     #[uniffi::method]
     pub fn set_sync_writes(&self, value: bool) -> Result<(), QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        queue.set_sync_writes(value);
+        let mut state = self.lock()?;
+        state.set_sync_writes(value);
         Ok(())
     }
 
     #[uniffi::method]
     pub fn sync_writes(&self) -> Result<bool, QueueFileError> {
-        let queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        Ok(queue.sync_writes())
+        let state = self.lock()?;
+        Ok(state.queue.sync_writes())
+    }
+
+    #[uniffi::method]
+    pub fn set_max_commit_delay(&self, value: Option<Duration>) -> Result<(), QueueFileError> {
+        self.group_commit.set_max_delay(value)
+    }
+
+    #[uniffi::method]
+    pub fn max_commit_delay(&self) -> Result<Option<Duration>, QueueFileError> {
+        self.group_commit.max_delay()
     }
 
     #[uniffi::method]
     pub fn set_overwrite_on_remove(&self, value: bool) -> Result<(), QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        queue.set_overwrite_on_remove(value);
+        let mut state = self.lock()?;
+        state.set_overwrite_on_remove(value);
         Ok(())
     }
 
     #[uniffi::method]
     pub fn overwrite_on_remove(&self) -> Result<bool, QueueFileError> {
-        let queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
-        Ok(queue.overwrite_on_remove())
+        let state = self.lock()?;
+        Ok(state.queue.overwrite_on_remove())
     }
 
     #[uniffi::method]
     pub fn set_cache_offset_policy(&self, policy: OffsetCachePolicy) -> Result<(), QueueFileError> {
-        let mut queue = self.inner.lock().map_err(|_| QueueFileError::LockError)?;
+        let mut state = self.lock()?;
         match policy {
-            OffsetCachePolicy::None => queue.set_cache_offset_policy(None),
+            OffsetCachePolicy::None => state.queue.set_cache_offset_policy(None),
             OffsetCachePolicy::Linear { offset } => {
-                queue.set_cache_offset_policy(queue_file::OffsetCacheKind::Linear {
-                    offset: offset as usize,
-                })
-            }
-            OffsetCachePolicy::Quadratic => {
-                queue.set_cache_offset_policy(queue_file::OffsetCacheKind::Quadratic)
+                state
+                    .queue
+                    .set_cache_offset_policy(queue_file::OffsetCacheKind::Linear {
+                        offset: offset as usize,
+                    })
             }
+            OffsetCachePolicy::Quadratic => state
+                .queue
+                .set_cache_offset_policy(queue_file::OffsetCacheKind::Quadratic),
         }
         Ok(())
     }
+
+    #[uniffi::method]
+    pub fn remaining_capacity(&self) -> Result<CapacityInfo, QueueFileError> {
+        let state = self.lock()?;
+        Ok(state.remaining_capacity())
+    }
+
+    #[uniffi::method]
+    pub fn can_accept(&self, len: u64) -> Result<bool, QueueFileError> {
+        let state = self.lock()?;
+        Ok(state.can_accept(len))
+    }
+
+    #[uniffi::method]
+    pub fn set_watermarks(
+        &self,
+        config: WatermarkConfig,
+        listener: Arc<dyn WatermarkListener>,
+    ) -> Result<(), QueueFileError> {
+        self.mutate(|state| {
+            state.watermarks = Some(Watermarks::new(config, listener));
+            Ok(())
+        })
+    }
+
+    #[uniffi::method]
+    pub fn clear_watermarks(&self) -> Result<(), QueueFileError> {
+        let mut state = self.lock()?;
+        state.watermarks = None;
+        Ok(())
+    }
+
+    #[uniffi::method]
+    pub fn watermarks(&self) -> Result<Option<WatermarkConfig>, QueueFileError> {
+        let state = self.lock()?;
+        Ok(state.watermarks.as_ref().map(Watermarks::config))
+    }
+
+    /// Calls `listener` after every add, peek, removal, clear, sync or rewrite that took longer
+    /// than `threshold`, timed from when it got hold of the queue.
+    #[uniffi::method]
+    pub fn set_slow_op_listener(
+        &self,
+        threshold: Duration,
+        listener: Arc<dyn SlowOpListener>,
+    ) -> Result<(), QueueFileError> {
+        let mut state = self.lock()?;
+        state.slow_ops = Some(SlowOps::new(threshold, listener));
+        Ok(())
+    }
+
+    #[uniffi::method]
+    pub fn clear_slow_op_listener(&self) -> Result<(), QueueFileError> {
+        let mut state = self.lock()?;
+        state.slow_ops = None;
+        Ok(())
+    }
+
+    /// How the file compared with the trailer the last `sync_all` wrote, checked on open with
+    /// the `sync_trailer` option. `None` without the option or when no valid trailer existed.
+    #[uniffi::method]
+    pub fn last_sync_check(&self) -> Result<Option<SyncCheck>, QueueFileError> {
+        let state = self.lock()?;
+        Ok(state.sync_check)
+    }
+
+    /// What opening moved into `<path>.quarantine` under the `quarantine_corrupt` option or the
+    /// `RepairInPlace` policy. `None` without either or when every element was intact.
+    #[uniffi::method]
+    pub fn last_quarantine(&self) -> Result<Option<QuarantineReport>, QueueFileError> {
+        let state = self.lock()?;
+        Ok(state.quarantine.clone())
+    }
+
+    /// The corrupt files opening renamed aside under `QuarantineAndStartFresh` before starting
+    /// an empty queue. `None` when the file opened as it was.
+    #[uniffi::method]
+    pub fn last_corruption_recovery(&self) -> Result<Option<CorruptionRecovery>, QueueFileError> {
+        let state = self.lock()?;
+        Ok(state.corruption_recovery.clone())
+    }
+
+    #[uniffi::method]
+    pub fn stats(&self) -> Result<QueueStats, QueueFileError> {
+        self.with_state(|state| {
+            state.finish_open()?;
+            let mut stats = state.stats.snapshot();
+            stats.latencies = state.latencies.summaries();
+            stats.generation = state.generation;
+            Ok(stats)
+        })
+    }
+
+    /// The stats counted since the last `reset_stats`, or since the queue was opened. `stats`
+    /// keeps reporting the lifetime totals.
+    #[uniffi::method]
+    pub fn window_stats(&self) -> Result<StatsWindow, QueueFileError> {
+        self.with_state(|state| {
+            state.finish_open()?;
+            Ok(state.stats_window.report(&state.stats, state.generation))
+        })
+    }
+
+    /// Ends the current stats window and starts the next, returning the one that ended. Both
+    /// happen under the queue's lock, so a periodic uploader that reports what this returns
+    /// neither misses nor double-counts an operation.
+    #[uniffi::method]
+    pub fn reset_stats(&self) -> Result<StatsWindow, QueueFileError> {
+        self.with_state(|state| {
+            state.finish_open()?;
+            let ended = state.stats_window.report(&state.stats, state.generation);
+            state.stats_window = stats::Window::new(state.stats.clone());
+            Ok(ended)
+        })
+    }
+
+    /// Runs quick checks on the header, the counters, free disk space, the lock and the time
+    /// since the last sync, without walking the elements. Waits at most a second for the lock.
+    #[uniffi::method]
+    pub fn health_check(&self) -> Result<HealthReport, QueueFileError> {
+        let started = Instant::now();
+        let state = loop {
+            match self.inner.try_lock() {
+                Ok(state) => break state,
+                Err(TryLockError::Poisoned(_)) => return Err(QueueFileError::LockError),
+                Err(TryLockError::WouldBlock) if started.elapsed() >= health::LOCK_TIMEOUT => {
+                    return Ok(health::unresponsive(started.elapsed()));
+                }
+                Err(TryLockError::WouldBlock) => std::thread::sleep(Duration::from_millis(1)),
+            }
+        };
+        let state = self.woken(state)?;
+        Ok(health::check(&state, started.elapsed()))
+    }
+
+    /// A counter bumped by every add, removal, clear, rewrite and swap that changes the
+    /// elements, starting at 0 when the queue is opened, or where it stood at the last sync or
+    /// close when `element_index` restores it. Two equal readings mean nothing changed in
+    /// between.
+    #[uniffi::method]
+    pub fn generation(&self) -> Result<u64, QueueFileError> {
+        self.with_state(|state| {
+            state.finish_open()?;
+            Ok(state.generation)
+        })
+    }
+
+    /// The changes made after the one with sequence number `since`, oldest first, from the
+    /// journal kept under `journal_capacity`. Sequence numbers are `generation()` values, so
+    /// `changes_since(generation())` is empty. Fails with `JournalGap` once the journal has
+    /// dropped some of the changes asked for.
+    #[uniffi::method]
+    pub fn changes_since(&self, since: u64) -> Result<Vec<ChangeRecord>, QueueFileError> {
+        self.with_state(|state| {
+            state.finish_open()?;
+            state.changes_since(since)
+        })
+    }
+
+    /// Runs the scans `lazy_open` deferred, which the first call that needs them would
+    /// otherwise run. Does nothing once they have run or when the queue was opened eagerly.
+    #[uniffi::method]
+    pub fn finish_open(&self) -> Result<(), QueueFileError> {
+        self.with_state(|state| state.finish_open())
+    }
+
+    #[uniffi::method]
+    pub fn set_excluded_from_backup(&self, excluded: bool) -> Result<(), QueueFileError> {
+        self.with_state(|state| state.set_excluded_from_backup(excluded))
+    }
+
+    #[uniffi::method]
+    pub fn mirror_status(&self) -> Result<MirrorStatus, QueueFileError> {
+        let state = self.lock()?;
+        Ok(state.mirror_status)
+    }
+
+    #[uniffi::method]
+    pub fn set_read_buffer_size(&self, size: u32) -> Result<(), QueueFileError> {
+        let mut state = self.lock()?;
+        state.queue.set_read_buffer_size(size as usize);
+        state.read_buffer_size = Some(size as usize);
+        Ok(())
+    }
+}
+
+/// Number of elements the eldest chunked element spans, or `None` if the queue is empty.
+fn head_chunks(state: &mut QueueState) -> Result<Option<u32>, QueueFileError> {
+    let head = chunk_headers(state, 1)?;
+    let available = state.queue.size();
+    head.first()
+        .map(|&head| chunking::count(head, available))
+        .transpose()
+}
+
+/// The `chunking` headers of up to `count` of the eldest committed elements, read without their
+/// payloads unless the elements are compressed.
+fn chunk_headers(
+    state: &mut QueueState,
+    count: usize,
+) -> Result<Vec<Option<(u32, u32)>>, QueueFileError> {
+    if state.codec.is_active() {
+        let elements = state.retrying(|state| state.read_framed(0, count))?;
+        return Ok(elements.iter().map(|e| chunking::header(e)).collect());
+    }
+    state.retrying(|state| {
+        let mut reader = FrameReader::open(&state.path)?;
+        let mut headers = Vec::new();
+        while headers.len() < count {
+            let Some(frame) = reader.next_frame()? else {
+                break;
+            };
+            let prefix = reader.read_prefix(frame, chunking::HEADER_LEN)?;
+            headers.push(chunking::header(&prefix));
+        }
+        Ok(headers)
+    })
+}
+
+/// Copies the eldest element into a new file at `dest`, for `peek_to_file` and `pop_to_file`.
+fn write_head(state: &mut QueueState, dest: &Path) -> Result<Option<u64>, QueueFileError> {
+    let (mut reader, frame) = state.retrying(|state| {
+        let chunk_len = state.options.copy_buffer_size_or(read_limit::CHUNK);
+        let mut reader = FrameReader::open(&state.path)?.chunk_len(chunk_len);
+        let frame = reader.next_frame()?;
+        Ok((reader, frame))
+    })?;
+    let Some(frame) = frame else {
+        return Ok(None);
+    };
+    // A compressed element is decompressed in memory; the copy only streams ones stored as
+    // given, from after their `chunking` header.
+    let (payload, skip) = match state.codec.is_active() {
+        true => (Some(state.codec.decode(reader.read(frame)?, None)?), 0),
+        false => {
+            let prefix = reader.read_prefix(frame, chunking::HEADER_LEN)?;
+            let header_len = prefix.len() - chunking::unframe(&prefix).len();
+            (None, header_len as u64)
+        }
+    };
+    (|| {
+        let mut file = std::fs::File::create(dest)?;
+        match &payload {
+            Some(payload) => file.write_all(payload)?,
+            None => reader.copy_from(frame, skip, &mut file)?,
+        }
+        file.sync_all()
+    })()
+    .map_err(|e| QueueFileError::from(e).with_path(dest))?;
+    Ok(Some(
+        payload.map_or(frame.len - skip, |payload| payload.len() as u64),
+    ))
+}
+
+/// The same operations for callers that should not block: each runs on the runtime's worker
+/// threads (see `configure_runtime`) and resolves once it has finished there.
+#[uniffi::export]
+impl QueueFile {
+    /// `open` on a runtime worker thread, which also runs the scans `lazy_open` would defer,
+    /// so the caller waits on neither the file nor the first call that needs them.
+    #[uniffi::constructor]
+    pub async fn open_async(path: String) -> Result<Arc<Self>, QueueFileError> {
+        Self::open_with_options_async(path, QueueFileOptions::default()).await
+    }
+
+    /// `open_with_options` on a runtime worker thread, followed by `finish_open`.
+    #[uniffi::constructor]
+    pub async fn open_with_options_async(
+        path: String,
+        options: QueueFileOptions,
+    ) -> Result<Arc<Self>, QueueFileError> {
+        runtime::run(move || {
+            let queue = Self::open_with_options(path, options)?;
+            queue.finish_open()?;
+            Ok(queue)
+        })
+        .await
+    }
+
+    #[uniffi::method]
+    pub async fn add_async(self: Arc<Self>, data: Vec<u8>) -> Result<AddReceipt, QueueFileError> {
+        runtime::run(move || self.add(data)).await
+    }
+
+    #[uniffi::method]
+    pub async fn add_from_file_async(
+        self: Arc<Self>,
+        src_path: String,
+    ) -> Result<u64, QueueFileError> {
+        runtime::run(move || self.add_from_file(src_path)).await
+    }
+
+    #[uniffi::method]
+    pub async fn pop_to_file_async(
+        self: Arc<Self>,
+        dest_path: String,
+    ) -> Result<Option<u64>, QueueFileError> {
+        runtime::run(move || self.pop_to_file(dest_path)).await
+    }
+
+    #[uniffi::method]
+    pub async fn peek_to_file_async(
+        self: Arc<Self>,
+        dest_path: String,
+    ) -> Result<Option<u64>, QueueFileError> {
+        runtime::run(move || self.peek_to_file(dest_path)).await
+    }
+
+    #[uniffi::method]
+    pub async fn add_multiple_async(
+        self: Arc<Self>,
+        items: Vec<Vec<u8>>,
+    ) -> Result<AddReceipt, QueueFileError> {
+        runtime::run(move || self.add_multiple(items)).await
+    }
+
+    #[uniffi::method]
+    pub async fn peek_async(self: Arc<Self>) -> Result<Option<Vec<u8>>, QueueFileError> {
+        runtime::run(move || self.peek()).await
+    }
+
+    #[uniffi::method]
+    pub async fn remove_async(self: Arc<Self>) -> Result<(), QueueFileError> {
+        runtime::run(move || self.remove()).await
+    }
+
+    #[uniffi::method]
+    pub async fn remove_n_async(self: Arc<Self>, n: u32) -> Result<(), QueueFileError> {
+        runtime::run(move || self.remove_n(n)).await
+    }
+
+    #[uniffi::method]
+    pub async fn clear_async(self: Arc<Self>) -> Result<(), QueueFileError> {
+        runtime::run(move || self.clear()).await
+    }
+
+    #[uniffi::method]
+    pub async fn drain_with_async(
+        self: Arc<Self>,
+        handler: Arc<dyn DrainHandler>,
+        max: u32,
+    ) -> Result<u32, QueueFileError> {
+        runtime::run(move || self.drain_with(handler, max)).await
+    }
+
+    #[uniffi::method]
+    pub async fn get_all_async(self: Arc<Self>) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        runtime::run(move || self.get_all()).await
+    }
+
+    #[uniffi::method]
+    pub async fn sync_all_async(self: Arc<Self>) -> Result<(), QueueFileError> {
+        runtime::run(move || self.sync_all()).await
+    }
+
+    #[uniffi::method]
+    pub async fn finish_open_async(self: Arc<Self>) -> Result<(), QueueFileError> {
+        runtime::run(move || self.finish_open()).await
+    }
 }
 
 uniffi::setup_scaffolding!();
@@ -0,0 +1,143 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::QueueFileError;
+
+/// A `<queue>.lock` file naming the process that has the queue open. The file only records
+/// the owner; no OS lock is held on it, since iOS terminates suspended apps that hold file
+/// locks in a shared container.
+pub(crate) struct LockFile {
+    path: PathBuf,
+}
+
+/// The contents of a lock file: the owner's PID and when it took the lock.
+struct Owner {
+    pid: u32,
+    acquired_ms: u128,
+}
+
+impl Owner {
+    fn current() -> Self {
+        Owner {
+            pid: std::process::id(),
+            acquired_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis()),
+        }
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let mut fields = text.lines().filter_map(|line| line.split_once(' '));
+        let (_, pid) = fields.next().filter(|(key, _)| *key == "pid")?;
+        let (_, acquired) = fields.next().filter(|(key, _)| *key == "acquired_ms")?;
+        Some(Owner {
+            pid: pid.parse().ok()?,
+            acquired_ms: acquired.parse().ok()?,
+        })
+    }
+
+    fn render(&self) -> String {
+        format!("pid {}\nacquired_ms {}\n", self.pid, self.acquired_ms)
+    }
+}
+
+impl LockFile {
+    fn path_for(path: &Path) -> PathBuf {
+        let mut lock = path.to_path_buf().into_os_string();
+        lock.push(".lock");
+        PathBuf::from(lock)
+    }
+
+    /// Takes the lock for the queue at `path`. A lock left by a process that is no longer
+    /// running, or that cannot be parsed, is broken and taken over.
+    pub(crate) fn acquire(path: &Path) -> Result<Self, QueueFileError> {
+        let lock_path = Self::path_for(path);
+        let lock_err = |e: io::Error| QueueFileError::from(e).with_path(&lock_path);
+        // A second attempt follows breaking a stale lock; losing that race to another process
+        // means the lock is held.
+        for _ in 0..2 {
+            match create(&lock_path) {
+                Ok(()) => return Ok(LockFile { path: lock_path }),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(lock_err(e)),
+            }
+            let owner = match fs::read_to_string(&lock_path) {
+                Ok(text) => Owner::parse(&text),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(lock_err(e)),
+            };
+            // The in-process registry rules out a second handle, so a lock naming this process
+            // is left over from an earlier process that had the same PID.
+            if let Some(owner) = owner
+                && owner.pid != std::process::id()
+                && is_alive(owner.pid)
+            {
+                return Err(QueueFileError::LockedByOtherProcess { pid: owner.pid });
+            }
+            match fs::remove_file(&lock_path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(lock_err(e)),
+            }
+        }
+        let pid = fs::read_to_string(&lock_path)
+            .ok()
+            .and_then(|text| Owner::parse(&text))
+            .map_or(0, |owner| owner.pid);
+        Err(QueueFileError::LockedByOtherProcess { pid })
+    }
+}
+
+/// Creates the lock file with its contents in one step: the contents are written to a private
+/// file that is then hard-linked into place, which fails if the lock exists.
+fn create(path: &Path) -> io::Result<()> {
+    let mut staging = path.to_path_buf().into_os_string();
+    staging.push(format!(".{}", std::process::id()));
+    let staging = PathBuf::from(staging);
+    let mut file = fs::File::create(&staging)?;
+    let linked = file
+        .write_all(Owner::current().render().as_bytes())
+        .and_then(|()| file.sync_all())
+        .and_then(|()| fs::hard_link(&staging, path));
+    let _ = fs::remove_file(&staging);
+    linked
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        let ours = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|text| Owner::parse(&text))
+            .is_some_and(|owner| owner.pid == std::process::id());
+        if ours {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Whether a process with this PID exists. `EPERM` means it exists but belongs to someone
+/// else, which still counts.
+#[cfg(unix)]
+fn is_alive(pid: u32) -> bool {
+    unsafe extern "C" {
+        fn kill(pid: i32, sig: i32) -> i32;
+    }
+    const ESRCH: i32 = 3;
+
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: signal 0 only checks that the process exists; nothing is delivered.
+    if unsafe { kill(pid, 0) } == 0 {
+        return true;
+    }
+    io::Error::last_os_error().raw_os_error() != Some(ESRCH)
+}
+
+/// Without a way to probe the owner, a lock is only ever broken by hand.
+#[cfg(not(unix))]
+fn is_alive(_pid: u32) -> bool {
+    true
+}
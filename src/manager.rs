@@ -0,0 +1,132 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+
+use crate::{QueueFile, QueueFileError, QueueFileOptions};
+
+/// Opens queues under a cap on how many keep their files open at once. Once a call would take
+/// it past the cap, the least recently used queues are flushed, synced and closed as
+/// `idle_close_after` closes them, and reopen on their next call. A queue busy at that moment,
+/// or holding adds back for a pause, stays open and counted, and the next one is tried.
+#[derive(uniffi::Object)]
+pub struct QueueManager {
+    pool: Arc<Pool>,
+}
+
+/// The queues one manager opened, each with its last use.
+pub(crate) struct Pool {
+    max_open: usize,
+    lru: Mutex<Lru>,
+}
+
+#[derive(Default)]
+struct Lru {
+    /// Bumped by every use, ordering the entries.
+    tick: u64,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    queue: Weak<QueueFile>,
+    used: u64,
+    /// Cleared when the queue is closed, set again by its next use.
+    open: bool,
+}
+
+impl Pool {
+    fn lock(&self) -> MutexGuard<'_, Lru> {
+        let mut lru = self.lru.lock().unwrap_or_else(PoisonError::into_inner);
+        lru.entries.retain(|entry| entry.queue.strong_count() > 0);
+        lru
+    }
+
+    /// Records a use of `queue`, which is open. Returns how many others must close so no more
+    /// than `max_open` stay open, and the open others to try, least recently used first. Each
+    /// counts as open until `closed` is called for it.
+    pub(crate) fn used(&self, queue: &Weak<QueueFile>) -> (usize, Vec<Weak<QueueFile>>) {
+        let mut lru = self.lock();
+        lru.tick += 1;
+        let used = lru.tick;
+        match lru
+            .entries
+            .iter_mut()
+            .find(|entry| Weak::ptr_eq(&entry.queue, queue))
+        {
+            Some(entry) => {
+                entry.used = used;
+                entry.open = true;
+            }
+            None => lru.entries.push(Entry {
+                queue: queue.clone(),
+                used,
+                open: true,
+            }),
+        }
+        let open = lru.entries.iter().filter(|entry| entry.open).count();
+        if open <= self.max_open {
+            return (0, Vec::new());
+        }
+        let mut others: Vec<_> = lru
+            .entries
+            .iter()
+            .filter(|entry| entry.open && !Weak::ptr_eq(&entry.queue, queue))
+            .collect();
+        others.sort_by_key(|entry| entry.used);
+        let others = others
+            .into_iter()
+            .map(|entry| entry.queue.clone())
+            .collect();
+        (open - self.max_open, others)
+    }
+
+    /// Records that `queue` closed its file, after `idle_close_after` or to make room.
+    pub(crate) fn closed(&self, queue: &Weak<QueueFile>) {
+        let mut lru = self.lock();
+        if let Some(entry) = lru
+            .entries
+            .iter_mut()
+            .find(|entry| Weak::ptr_eq(&entry.queue, queue))
+        {
+            entry.open = false;
+        }
+    }
+}
+
+#[uniffi::export]
+impl QueueManager {
+    /// A manager keeping at most `max_open_files` of its queues open, at least one.
+    #[uniffi::constructor]
+    pub fn new(max_open_files: u32) -> Arc<Self> {
+        Arc::new(QueueManager {
+            pool: Arc::new(Pool {
+                max_open: (max_open_files as usize).max(1),
+                lru: Mutex::new(Lru::default()),
+            }),
+        })
+    }
+
+    /// Opens the queue at `path` as `QueueFile::open_with_options` does and counts it against
+    /// the cap, possibly closing others. A handle shared with `AlreadyOpenPolicy::Share` stays
+    /// with the manager that opened it first.
+    #[uniffi::method]
+    pub fn open(
+        &self,
+        path: String,
+        options: QueueFileOptions,
+    ) -> Result<Arc<QueueFile>, QueueFileError> {
+        let queue = QueueFile::open_with_options(path, options)?;
+        queue.join_pool(Arc::clone(&self.pool))?;
+        Ok(queue)
+    }
+
+    /// Queues opened through this manager that are still referenced.
+    #[uniffi::method]
+    pub fn queue_count(&self) -> u32 {
+        self.pool.lock().entries.len() as u32
+    }
+
+    /// Those of them with their files open.
+    #[uniffi::method]
+    pub fn open_file_count(&self) -> u32 {
+        let lru = self.pool.lock();
+        lru.entries.iter().filter(|entry| entry.open).count() as u32
+    }
+}
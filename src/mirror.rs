@@ -0,0 +1,154 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use queue_file::QueueFile as RustQueueFile;
+
+use crate::QueueFileError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum MirrorStatus {
+    /// No mirror path was configured.
+    Disabled,
+    /// Both files are open and every mutation is applied to both.
+    InSync,
+    /// The primary was missing or unreadable and has been rebuilt from the mirror.
+    PrimaryRestored,
+    /// The primary could not be rebuilt; the mirror is the only copy in use.
+    RunningOnMirror,
+    /// The mirror could not be opened or a write to it failed; only the primary is in use.
+    MirrorFailed,
+}
+
+pub(crate) struct Mirror {
+    pub(crate) queue: RustQueueFile,
+}
+
+pub(crate) struct OpenedPair {
+    pub(crate) queue: RustQueueFile,
+    /// The path `queue` was opened from; the mirror path when running on the mirror.
+    pub(crate) path: PathBuf,
+    pub(crate) mirror: Option<Mirror>,
+    pub(crate) status: MirrorStatus,
+}
+
+/// Opens `primary` together with its mirror, bringing whichever copy is stale or damaged back in
+/// line with the other one.
+pub(crate) fn open_pair(
+    primary: &Path,
+    mirror: &Path,
+    capacity: u64,
+) -> Result<OpenedPair, QueueFileError> {
+    let mut restored = false;
+    if !primary.exists() && mirror.exists() {
+        restored = copy_file(mirror, primary).is_ok();
+    }
+
+    let queue = match RustQueueFile::with_capacity(primary, capacity) {
+        Ok(queue) => queue,
+        Err(primary_err) => {
+            if restored || !mirror.exists() {
+                return Err(primary_err.into());
+            }
+            let Ok(mirror_queue) = RustQueueFile::with_capacity(mirror, capacity) else {
+                return Err(primary_err.into());
+            };
+            drop(mirror_queue);
+            return match copy_file(mirror, primary)
+                .map_err(QueueFileError::from)
+                .and_then(|()| Ok(RustQueueFile::with_capacity(primary, capacity)?))
+            {
+                Ok(queue) => Ok(open_mirror(queue, primary, mirror, capacity, true)),
+                Err(_) => Ok(OpenedPair {
+                    queue: RustQueueFile::with_capacity(mirror, capacity)
+                        .map_err(|e| QueueFileError::from(e).with_path(mirror))?,
+                    path: mirror.to_path_buf(),
+                    mirror: None,
+                    status: MirrorStatus::RunningOnMirror,
+                }),
+            };
+        }
+    };
+
+    Ok(open_mirror(queue, primary, mirror, capacity, restored))
+}
+
+fn open_mirror(
+    queue: RustQueueFile,
+    primary: &Path,
+    mirror: &Path,
+    capacity: u64,
+    restored: bool,
+) -> OpenedPair {
+    let in_sync = same_header(primary, mirror).unwrap_or(false);
+    let mirror_queue = if in_sync {
+        RustQueueFile::with_capacity(mirror, capacity).ok()
+    } else {
+        None
+    };
+    let mirror_queue = match mirror_queue {
+        Some(mirror_queue) => Ok(mirror_queue),
+        None => copy_file(primary, mirror)
+            .map_err(QueueFileError::from)
+            .and_then(|()| Ok(RustQueueFile::with_capacity(mirror, capacity)?)),
+    };
+
+    let (mirror, status) = match mirror_queue {
+        Ok(mirror_queue) => (
+            Some(Mirror {
+                queue: mirror_queue,
+            }),
+            if restored {
+                MirrorStatus::PrimaryRestored
+            } else {
+                MirrorStatus::InSync
+            },
+        ),
+        Err(_) => (None, MirrorStatus::MirrorFailed),
+    };
+
+    OpenedPair {
+        queue,
+        path: primary.to_path_buf(),
+        mirror,
+        status,
+    }
+}
+
+/// Replaces the mirror with a fresh copy of `primary`, for changes that rewrite the primary
+/// instead of mutating it in place.
+pub(crate) fn recopy(
+    primary: &Path,
+    mirror: &Path,
+    capacity: u64,
+) -> Result<Mirror, QueueFileError> {
+    copy_file(primary, mirror)?;
+    Ok(Mirror {
+        queue: RustQueueFile::with_capacity(mirror, capacity)?,
+    })
+}
+
+/// Two copies are considered in sync when their headers (file length, element count and the
+/// head/tail positions) and physical lengths match.
+fn same_header(a: &Path, b: &Path) -> std::io::Result<bool> {
+    fn header(path: &Path) -> std::io::Result<(Vec<u8>, u64)> {
+        let mut file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let mut buf = vec![0; 32];
+        file.read_exact(&mut buf)?;
+        Ok((buf, len))
+    }
+
+    Ok(header(a)? == header(b)?)
+}
+
+/// Replaces `dst` with a copy of `src` through a temporary file so `dst` is never half-written.
+fn copy_file(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let mut tmp = dst.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    std::fs::copy(src, &tmp)?;
+    File::open(&tmp)?.sync_all()?;
+    std::fs::rename(&tmp, dst)
+}
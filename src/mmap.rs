@@ -0,0 +1,351 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::dictionary::{self, Codec};
+use crate::export::{self, ChunkSink, FileExporter};
+use crate::verify::{Corruption, Frame, Header};
+use crate::{ExportEncoding, Exporter, QueueFileError};
+
+/// A queue file mapped read-only into memory, for tools that walk large dumps. Elements are
+/// borrowed straight from the mapping; only one that wraps around the end of the ring, or that
+/// was compressed against the queue's dictionary, is copied.
+///
+/// The file must not be truncated while it is mapped, so map a copy rather than a file a queue
+/// still has open.
+#[derive(uniffi::Object)]
+pub struct MappedQueue {
+    mapping: Mapping,
+    /// The dictionaries saved next to the mapped file, if any.
+    codec: Codec,
+    header_len: u64,
+    file_len: u64,
+    /// Every element frame, eldest first.
+    frames: Vec<Frame>,
+    /// Index of the element `next` returns.
+    cursor: Mutex<usize>,
+}
+
+impl MappedQueue {
+    /// The payload of the element at `index`, or `None` past the last one.
+    pub fn element(&self, index: usize) -> Result<Option<Cow<'_, [u8]>>, QueueFileError> {
+        Ok(match self.stored(index) {
+            Some(Cow::Borrowed(stored)) => Some(self.codec.view(stored, None)?),
+            Some(Cow::Owned(stored)) => Some(Cow::Owned(self.codec.decode(stored, None)?)),
+            None => None,
+        })
+    }
+
+    /// Every element in order, eldest first.
+    pub fn elements(&self) -> impl Iterator<Item = Result<Cow<'_, [u8]>, QueueFileError>> {
+        (0..self.frames.len()).filter_map(|index| self.element(index).transpose())
+    }
+
+    /// The element at `index` as the file stores it.
+    fn stored(&self, index: usize) -> Option<Cow<'_, [u8]>> {
+        let frame = self.frames.get(index)?;
+        let start = self.wrap(frame.pos + 4) as usize;
+        let len = frame.len as usize;
+        let bytes = self.mapping.bytes();
+        let before_end = (self.file_len as usize - start).min(len);
+        match before_end == len {
+            true => Some(Cow::Borrowed(&bytes[start..start + len])),
+            false => {
+                let header_len = self.header_len as usize;
+                let mut element = bytes[start..self.file_len as usize].to_vec();
+                element.extend_from_slice(&bytes[header_len..header_len + len - before_end]);
+                Some(Cow::Owned(element))
+            }
+        }
+    }
+
+    fn wrap(&self, pos: u64) -> u64 {
+        match pos >= self.file_len {
+            true => self.header_len + pos - self.file_len,
+            false => pos,
+        }
+    }
+
+    fn export(&self, dest: &str, format: export::Format) -> Result<u64, QueueFileError> {
+        let mut exporter = FileExporter::create(Path::new(dest), format)?;
+        for element in self.elements() {
+            exporter.write(&element?)?;
+        }
+        exporter.finish()
+    }
+}
+
+#[uniffi::export]
+impl MappedQueue {
+    /// Maps the queue file at `path` and follows its element frames, failing with
+    /// `CorruptedFile` if any of them is damaged. No payload is read here. The dictionaries of
+    /// a queue that compresses its elements are read from their `.dict` file next to `path`,
+    /// so copy it along with the queue file.
+    #[uniffi::constructor]
+    pub fn open_snapshot_mmap(path: String) -> Result<Arc<Self>, QueueFileError> {
+        let path = PathBuf::from(path);
+        let tag = |e: io::Error| QueueFileError::from(e).with_path(&path);
+        let file = File::open(&path).map_err(tag)?;
+        let mapping = Mapping::map(&file).map_err(tag)?;
+        let (header, frames) =
+            walk(mapping.bytes()).map_err(|corruption| corruption.into_error(&path))?;
+        let codec = Codec::load(&path)
+            .map_err(|e| QueueFileError::from(e).with_path(&dictionary::dictionary_path(&path)))?;
+        Ok(Arc::new(MappedQueue {
+            codec,
+            header_len: header.header_len,
+            file_len: header.file_len,
+            frames,
+            cursor: Mutex::new(0),
+            mapping,
+        }))
+    }
+
+    #[uniffi::method]
+    pub fn size(&self) -> u32 {
+        self.frames.len() as u32
+    }
+
+    #[uniffi::method]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Bytes of the file that are mapped.
+    #[uniffi::method]
+    pub fn mapped_len(&self) -> u64 {
+        self.mapping.bytes().len() as u64
+    }
+
+    /// The next element in order, or `None` after the last one.
+    #[uniffi::method]
+    pub fn next(&self) -> Result<Option<Vec<u8>>, QueueFileError> {
+        let mut cursor = self.cursor.lock().map_err(|_| QueueFileError::LockError)?;
+        let element = self.element(*cursor)?.map(Cow::into_owned);
+        *cursor += usize::from(element.is_some());
+        Ok(element)
+    }
+
+    /// Starts `next` over from the eldest element.
+    #[uniffi::method]
+    pub fn rewind(&self) -> Result<(), QueueFileError> {
+        *self.cursor.lock().map_err(|_| QueueFileError::LockError)? = 0;
+        Ok(())
+    }
+
+    /// The element at `index`, counting from the eldest. Fails with `IndexOutOfRange` past the
+    /// last element.
+    #[uniffi::method]
+    pub fn element_at(&self, index: u32) -> Result<Vec<u8>, QueueFileError> {
+        self.element(index as usize)?
+            .map(Cow::into_owned)
+            .ok_or(QueueFileError::IndexOutOfRange {
+                index,
+                size: self.size(),
+            })
+    }
+
+    #[uniffi::method]
+    pub fn get_all(&self) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        self.elements()
+            .map(|element| Ok(element?.into_owned()))
+            .collect()
+    }
+
+    #[uniffi::method]
+    pub fn export_ndjson(
+        &self,
+        path: String,
+        encoding: Option<ExportEncoding>,
+    ) -> Result<u64, QueueFileError> {
+        let format = export::Format::Ndjson(encoding.unwrap_or(ExportEncoding::Base64));
+        self.export(&path, format)
+    }
+
+    #[uniffi::method]
+    pub fn export_csv(&self, path: String) -> Result<u64, QueueFileError> {
+        self.export(&path, export::Format::Csv)
+    }
+
+    /// Streams every element to `exporter`, framed as `QueueFile::export_with` does.
+    #[uniffi::method]
+    pub fn export_with(&self, exporter: Arc<dyn Exporter>) -> Result<u64, QueueFileError> {
+        let mut sink = ChunkSink::new(exporter);
+        for element in self.elements() {
+            sink.write(&element?)?;
+        }
+        sink.finish()
+    }
+}
+
+/// Checks the header in `bytes` and follows its chain of element frames.
+fn walk(bytes: &[u8]) -> Result<(Header, Vec<Frame>), Corruption> {
+    let physical_len = bytes.len() as u64;
+    let mut prefix = [0; 32];
+    let available = bytes.len().min(prefix.len());
+    prefix[..available].copy_from_slice(&bytes[..available]);
+    let header = Header::parse(&prefix);
+    let (header_len, file_len) = (header.header_len, header.file_len);
+    if physical_len < header_len {
+        return Err(Corruption::header(
+            format!("file is {physical_len} bytes, shorter than its header"),
+            format!("file length >= {header_len}"),
+            format!("file length {physical_len}"),
+        ));
+    }
+    if file_len < header_len + 4 || file_len > physical_len {
+        return Err(Corruption::header(
+            format!("header file length {file_len} does not fit the {physical_len}-byte file"),
+            format!("header file length in {}..={physical_len}", header_len + 4),
+            format!("header file length {file_len}"),
+        ));
+    }
+
+    let wrap = |pos: u64| match pos >= file_len {
+        true => header_len + pos - file_len,
+        false => pos,
+    };
+    let max_len = file_len - header_len - 4;
+    let mut frames = Vec::with_capacity(header.count.min(max_len / 4) as usize);
+    let mut pos = header.first;
+    for index in 0..header.count {
+        if !(header_len..file_len).contains(&pos) {
+            return Err(Corruption::element(
+                index,
+                pos,
+                &format!("starts outside the ring at offset {pos}"),
+                format!("offset in {header_len}..{file_len}"),
+                format!("offset {pos}"),
+            ));
+        }
+        let mut len = [0; 4];
+        for (i, byte) in len.iter_mut().enumerate() {
+            *byte = bytes[wrap(pos + i as u64) as usize];
+        }
+        let len = u64::from(u32::from_be_bytes(len));
+        if len > max_len {
+            return Err(Corruption::element(
+                index,
+                pos,
+                &format!("at offset {pos} has impossible length {len}"),
+                format!("length <= {max_len}"),
+                format!("length {len}"),
+            ));
+        }
+        let last = header.last;
+        if index + 1 == header.count && pos != last {
+            return Err(Corruption::element(
+                index,
+                pos,
+                &format!("found at offset {pos}, but the header puts the last element at {last}"),
+                format!("last element at offset {last}"),
+                format!("last element at offset {pos}"),
+            ));
+        }
+        frames.push(Frame { pos, len });
+        pos = wrap(pos + 4 + len);
+    }
+    Ok((header, frames))
+}
+
+/// A private read-only mapping of a whole file.
+#[cfg(unix)]
+struct Mapping {
+    ptr: *const u8,
+    len: usize,
+}
+
+// SAFETY: the mapping is read-only and owned by this value until it is dropped.
+#[cfg(unix)]
+unsafe impl Send for Mapping {}
+#[cfg(unix)]
+unsafe impl Sync for Mapping {}
+
+#[cfg(unix)]
+impl Mapping {
+    fn map(file: &File) -> io::Result<Mapping> {
+        use std::ffi::{c_int, c_void};
+        use std::os::fd::AsRawFd;
+
+        unsafe extern "C" {
+            fn mmap(
+                addr: *mut c_void,
+                len: usize,
+                prot: c_int,
+                flags: c_int,
+                fd: c_int,
+                offset: i64,
+            ) -> *mut c_void;
+        }
+        const PROT_READ: c_int = 0x1;
+        const MAP_PRIVATE: c_int = 0x2;
+        let map_failed = !0 as *mut c_void;
+
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| io::Error::from(io::ErrorKind::FileTooLarge))?;
+        // An empty mapping is rejected by `mmap`; the header check reports the empty file.
+        if len == 0 {
+            return Ok(Mapping {
+                ptr: std::ptr::NonNull::dangling().as_ptr(),
+                len,
+            });
+        }
+        // SAFETY: a fresh read-only mapping of an open descriptor; the kernel picks the address.
+        let ptr = unsafe {
+            mmap(
+                std::ptr::null_mut(),
+                len,
+                PROT_READ,
+                MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        match ptr == map_failed {
+            true => Err(io::Error::last_os_error()),
+            false => Ok(Mapping {
+                ptr: ptr.cast(),
+                len,
+            }),
+        }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: `ptr` is valid for `len` readable bytes until `drop` unmaps it.
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe extern "C" {
+            fn munmap(addr: *mut std::ffi::c_void, len: usize) -> std::ffi::c_int;
+        }
+        if self.len > 0 {
+            // SAFETY: unmaps exactly the region `map` created, which nothing borrows any more.
+            unsafe { munmap(self.ptr as *mut _, self.len) };
+        }
+    }
+}
+
+/// Without `mmap`, the whole file is read into memory instead.
+#[cfg(not(unix))]
+struct Mapping(Vec<u8>);
+
+#[cfg(not(unix))]
+impl Mapping {
+    fn map(mut file: &File) -> io::Result<Mapping> {
+        use std::io::Read;
+
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        Ok(Mapping(bytes))
+    }
+
+    fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+
+use crate::QueueFileError;
+
+/// Containers nested deeper than this are refused when decoding, so a hostile element cannot
+/// exhaust the stack.
+const MAX_DEPTH: usize = 64;
+
+/// A value of a MessagePack map element.
+#[derive(Debug, Clone, PartialEq, uniffi::Enum)]
+pub enum MsgpackValue {
+    Nil,
+    Bool {
+        value: bool,
+    },
+    /// Any MessagePack integer that fits in an `i64`.
+    Int {
+        value: i64,
+    },
+    /// A 32-bit float widens to this when decoded.
+    Float {
+        value: f64,
+    },
+    String {
+        value: String,
+    },
+    Binary {
+        value: Vec<u8>,
+    },
+    Array {
+        values: Vec<MsgpackValue>,
+    },
+    /// A nested map. Keys must be strings.
+    Map {
+        entries: HashMap<String, MsgpackValue>,
+    },
+}
+
+/// Encodes `map` as a MessagePack map with its keys sorted, so equal maps encode to equal
+/// bytes. Integers take the shortest encoding.
+#[uniffi::export]
+pub fn encode_msgpack_map(map: HashMap<String, MsgpackValue>) -> Vec<u8> {
+    let mut out = Vec::new();
+    put_map(&mut out, &map);
+    out
+}
+
+/// Decodes a MessagePack map with string keys, the format `encode_msgpack_map` writes. Fails
+/// with `InvalidMessagePack` for anything else, including extension types, integers above
+/// `i64::MAX` and trailing bytes.
+#[uniffi::export]
+pub fn decode_msgpack_map(bytes: Vec<u8>) -> Result<HashMap<String, MsgpackValue>, QueueFileError> {
+    let mut reader = Reader(&bytes);
+    let value = reader.value(0)?;
+    if !reader.0.is_empty() {
+        return Err(invalid("trailing bytes after the map"));
+    }
+    match value {
+        MsgpackValue::Map { entries } => Ok(entries),
+        _ => Err(invalid("not a map")),
+    }
+}
+
+fn invalid(message: &str) -> QueueFileError {
+    QueueFileError::InvalidMessagePack {
+        message: message.to_string(),
+    }
+}
+
+fn put_map(out: &mut Vec<u8>, map: &HashMap<String, MsgpackValue>) {
+    put_len(out, map.len(), 0x80, 16, [0xde, 0xdf]);
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    for (key, value) in entries {
+        put_str(out, key);
+        put_value(out, value);
+    }
+}
+
+fn put_value(out: &mut Vec<u8>, value: &MsgpackValue) {
+    match value {
+        MsgpackValue::Nil => out.push(0xc0),
+        MsgpackValue::Bool { value } => out.push(if *value { 0xc3 } else { 0xc2 }),
+        MsgpackValue::Int { value } => put_int(out, *value),
+        MsgpackValue::Float { value } => {
+            out.push(0xcb);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+        MsgpackValue::String { value } => put_str(out, value),
+        MsgpackValue::Binary { value } => {
+            match u8::try_from(value.len()) {
+                Ok(len) => out.extend_from_slice(&[0xc4, len]),
+                Err(_) => put_len(out, value.len(), 0, 0, [0xc5, 0xc6]),
+            }
+            out.extend_from_slice(value);
+        }
+        MsgpackValue::Array { values } => {
+            put_len(out, values.len(), 0x90, 16, [0xdc, 0xdd]);
+            for value in values {
+                put_value(out, value);
+            }
+        }
+        MsgpackValue::Map { entries } => put_map(out, entries),
+    }
+}
+
+fn put_int(out: &mut Vec<u8>, value: i64) {
+    match value {
+        0..=0x7f => out.push(value as u8),
+        -32..=-1 => out.push(value as i8 as u8),
+        0x80..=0xff => out.extend_from_slice(&[0xcc, value as u8]),
+        0x100..=0xffff => {
+            out.push(0xcd);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(0xce);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        0x1_0000_0000.. => {
+            out.push(0xcf);
+            out.extend_from_slice(&(value as u64).to_be_bytes());
+        }
+        -0x80..=-33 => out.extend_from_slice(&[0xd0, value as i8 as u8]),
+        -0x8000..=-0x81 => {
+            out.push(0xd1);
+            out.extend_from_slice(&(value as i16).to_be_bytes());
+        }
+        -0x8000_0000..=-0x8001 => {
+            out.push(0xd2);
+            out.extend_from_slice(&(value as i32).to_be_bytes());
+        }
+        _ => {
+            out.push(0xd3);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+fn put_str(out: &mut Vec<u8>, value: &str) {
+    match u8::try_from(value.len()) {
+        Ok(len) if len < 32 => out.push(0xa0 | len),
+        Ok(len) => out.extend_from_slice(&[0xd9, len]),
+        Err(_) => put_len(out, value.len(), 0, 0, [0xda, 0xdb]),
+    }
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// Writes the length of a container or byte string: in the `fixed` marker itself when below
+/// `fixed_max`, otherwise after the 16-bit or 32-bit marker of `wide`.
+fn put_len(out: &mut Vec<u8>, len: usize, fixed: u8, fixed_max: usize, wide: [u8; 2]) {
+    if len < fixed_max {
+        out.push(fixed | len as u8);
+    } else if let Ok(len) = u16::try_from(len) {
+        out.push(wide[0]);
+        out.extend_from_slice(&len.to_be_bytes());
+    } else {
+        out.push(wide[1]);
+        out.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn value(&mut self, depth: usize) -> Result<MsgpackValue, QueueFileError> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("containers nested too deeply"));
+        }
+        let marker = self.take(1)?[0];
+        let value = match marker {
+            0x00..=0x7f => MsgpackValue::Int {
+                value: i64::from(marker),
+            },
+            0x80..=0x8f => self.map(usize::from(marker & 0x0f), depth)?,
+            0x90..=0x9f => self.array(usize::from(marker & 0x0f), depth)?,
+            0xa0..=0xbf => self.string(usize::from(marker & 0x1f))?,
+            0xc0 => MsgpackValue::Nil,
+            0xc2 => MsgpackValue::Bool { value: false },
+            0xc3 => MsgpackValue::Bool { value: true },
+            0xc4..=0xc6 => {
+                let len = self.len(marker - 0xc4)?;
+                MsgpackValue::Binary {
+                    value: self.take(len)?.to_vec(),
+                }
+            }
+            0xca => MsgpackValue::Float {
+                value: f64::from(f32::from_be_bytes(self.array_of()?)),
+            },
+            0xcb => MsgpackValue::Float {
+                value: f64::from_be_bytes(self.array_of()?),
+            },
+            0xcc => int(u8::from_be_bytes(self.array_of()?)),
+            0xcd => int(u16::from_be_bytes(self.array_of()?)),
+            0xce => int(u32::from_be_bytes(self.array_of()?)),
+            0xcf => MsgpackValue::Int {
+                value: i64::try_from(u64::from_be_bytes(self.array_of()?))
+                    .map_err(|_| invalid("integer above i64::MAX"))?,
+            },
+            0xd0 => int(i8::from_be_bytes(self.array_of()?)),
+            0xd1 => int(i16::from_be_bytes(self.array_of()?)),
+            0xd2 => int(i32::from_be_bytes(self.array_of()?)),
+            0xd3 => int(i64::from_be_bytes(self.array_of()?)),
+            0xd9..=0xdb => {
+                let len = self.len(marker - 0xd9)?;
+                self.string(len)?
+            }
+            0xdc | 0xdd => {
+                let len = self.len(marker - 0xdc + 1)?;
+                self.array(len, depth)?
+            }
+            0xde | 0xdf => {
+                let len = self.len(marker - 0xde + 1)?;
+                self.map(len, depth)?
+            }
+            0xe0..=0xff => int(marker as i8),
+            0xc7..=0xc9 | 0xd4..=0xd8 => return Err(invalid("extension types are not supported")),
+            0xc1 => return Err(invalid("reserved marker 0xc1")),
+        };
+        Ok(value)
+    }
+
+    /// A length of 1, 2 or 4 bytes for `width` 0, 1 or 2.
+    fn len(&mut self, width: u8) -> Result<usize, QueueFileError> {
+        let len = match width {
+            0 => u32::from(self.take(1)?[0]),
+            1 => u32::from(u16::from_be_bytes(self.array_of()?)),
+            _ => u32::from_be_bytes(self.array_of()?),
+        };
+        Ok(len as usize)
+    }
+
+    fn string(&mut self, len: usize) -> Result<MsgpackValue, QueueFileError> {
+        let value = String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| invalid("string is not valid UTF-8"))?;
+        Ok(MsgpackValue::String { value })
+    }
+
+    fn array(&mut self, len: usize, depth: usize) -> Result<MsgpackValue, QueueFileError> {
+        // Every value takes at least a byte, which bounds the allocation by the input.
+        let mut values = Vec::with_capacity(len.min(self.0.len()));
+        for _ in 0..len {
+            values.push(self.value(depth + 1)?);
+        }
+        Ok(MsgpackValue::Array { values })
+    }
+
+    fn map(&mut self, len: usize, depth: usize) -> Result<MsgpackValue, QueueFileError> {
+        let mut entries = HashMap::with_capacity(len.min(self.0.len() / 2));
+        for _ in 0..len {
+            let MsgpackValue::String { value: key } = self.value(depth + 1)? else {
+                return Err(invalid("map key is not a string"));
+            };
+            entries.insert(key, self.value(depth + 1)?);
+        }
+        Ok(MsgpackValue::Map { entries })
+    }
+
+    fn array_of<const N: usize>(&mut self) -> Result<[u8; N], QueueFileError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], QueueFileError> {
+        if len > self.0.len() {
+            return Err(invalid("value runs past the end of the element"));
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+}
+
+fn int(value: impl Into<i64>) -> MsgpackValue {
+    MsgpackValue::Int {
+        value: value.into(),
+    }
+}
@@ -0,0 +1,251 @@
+use std::time::Duration;
+
+use crate::{RateLimit, RetryPolicy};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum OpenMode {
+    /// Opens the queue, creating an empty one if neither the file nor its mirror exists.
+    CreateIfMissing,
+    /// Creates a new queue; fails with an `AlreadyExists` I/O error if the file or its mirror
+    /// exists.
+    CreateNew,
+    /// Opens an existing queue; fails with a `NotFound` I/O error if neither the file nor its
+    /// mirror exists.
+    MustExist,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum AlreadyOpenPolicy {
+    /// Fails with `AlreadyOpen` if the file or its mirror is open through another handle.
+    Fail,
+    /// Returns the existing handle for the same file; the options of the later open are
+    /// ignored. Still fails if the path is open as another queue's mirror.
+    Share,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum GrowthStrategy {
+    /// Doubles the file until the new elements fit, as queue-file does by itself.
+    Double,
+    /// Grows the file in steps of `bytes`.
+    FixedIncrement { bytes: u64 },
+    /// Grows the file by exactly the space the new elements need.
+    ExactFit,
+}
+
+/// The header layout of a new queue file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FileFormat {
+    /// The 32-byte header with 64-bit lengths and positions that queue-file writes.
+    Versioned,
+    /// The 16-byte header with 31-bit lengths and positions of the original Java Tape format,
+    /// readable by every Tape version. Files are limited to 2 GiB.
+    Legacy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum CorruptionPolicy {
+    /// Fails the open with `CorruptedFile`.
+    Fail,
+    /// Opens with the elements that can still be read, as `quarantine_corrupt` does. A header
+    /// that describes no ring still fails the open.
+    RepairInPlace,
+    /// Renames the file and its sidecars to `<name>.corrupt-<timestamp>` and opens a new,
+    /// empty queue (see `last_corruption_recovery`).
+    QuarantineAndStartFresh,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct QueueFileOptions {
+    /// How to treat a missing or existing file; `None` behaves as `CreateIfMissing`.
+    #[uniffi(default = None)]
+    pub mode: Option<OpenMode>,
+    /// Minimum file size in bytes; the file never shrinks below it.
+    #[uniffi(default = 4096)]
+    pub capacity: u64,
+    /// `clear` keeps the file at least this long (or at its current length, if shorter) instead
+    /// of truncating it to `capacity`, so the next burst does not have to grow it again.
+    #[uniffi(default = None)]
+    pub min_retained_len: Option<u64>,
+    /// How the file grows when new elements do not fit; `None` behaves as `Double`. Only
+    /// applies while the elements do not wrap around the end of the ring; a wrapped queue
+    /// always doubles.
+    #[uniffi(default = None)]
+    pub growth: Option<GrowthStrategy>,
+    /// Caps each growth step at this many bytes (several steps are taken if needed), with the
+    /// same restriction to unwrapped queues as `growth`.
+    #[uniffi(default = None)]
+    pub max_growth_step: Option<u64>,
+    /// Keeps a hot mirror of the queue at this path. Every mutation is applied to both files, and
+    /// the mirror takes over if the primary is missing, fails to open or is corrupt.
+    #[uniffi(default = None)]
+    pub mirror_path: Option<String>,
+    /// Adds that would take the queue above this many elements fail with `QuotaExceeded`.
+    #[uniffi(default = None)]
+    pub max_elements: Option<u64>,
+    /// Adds that would take `used_bytes` (payloads, 4-byte frame headers and the file header)
+    /// above this value fail with `QuotaExceeded`.
+    #[uniffi(default = None)]
+    pub max_bytes: Option<u64>,
+    /// Adds beyond these rates fail with `Backpressure` instead of being written.
+    #[uniffi(default = None)]
+    pub rate_limit: Option<RateLimit>,
+    /// Retries transient I/O errors on open, read, write and fsync. `None` surfaces them
+    /// immediately.
+    #[uniffi(default = None)]
+    pub retry_policy: Option<RetryPolicy>,
+    /// Operations that fail with `DataProtectionLocked` wait up to this long for
+    /// `notify_unlocked()` and are then tried again. `None` fails them immediately.
+    #[uniffi(default = None)]
+    pub wait_for_unlock: Option<Duration>,
+    /// Keeps two checksummed copies of the header in a `<path>.hdr` sidecar and restores the
+    /// newest valid one on open if the file's own header was torn.
+    #[uniffi(default = false)]
+    pub redundant_header: bool,
+    /// Fsyncs the containing directory after open creates the queue file, its sidecars or
+    /// mirror, or renames a restored copy into place, so the new directory entries survive
+    /// power loss.
+    #[uniffi(default = true)]
+    pub sync_directory: bool,
+    /// Marks the queue file, its sidecar and mirror as excluded from iCloud and device backups
+    /// on open. Has no effect on non-Apple platforms.
+    #[uniffi(default = false)]
+    pub exclude_from_backup: bool,
+    /// What to do when this process already has the file open; `None` behaves as `Fail`.
+    #[uniffi(default = None)]
+    pub already_open: Option<AlreadyOpenPolicy>,
+    /// Records the owning process in a `<path>.lock` file while the queue is open, failing with
+    /// `LockedByOtherProcess` if a running process holds it and breaking it if its owner died.
+    #[uniffi(default = false)]
+    pub lock_file: bool,
+    /// Keeps an in-memory counting bloom filter over payloads, sized for this many elements,
+    /// for `probably_contains`. It is rebuilt from the file on open.
+    #[uniffi(default = None)]
+    pub bloom_filter_elements: Option<u64>,
+    /// Records the header and element directory in a `<path>.sync` trailer on every
+    /// `sync_all`, and compares the file with it on open (see `last_sync_check`).
+    #[uniffi(default = false)]
+    pub sync_trailer: bool,
+    /// Walks every element frame and reads every payload before opening returns, failing with
+    /// `CorruptedFile` and a description of the first broken frame. Costs a full read of the file.
+    #[uniffi(default = false)]
+    pub verify_on_open: bool,
+    /// Moves elements whose frames are broken or whose payloads cannot be read into a
+    /// `<path>.quarantine` queue file and opens with the rest (see `last_quarantine`).
+    #[uniffi(default = false)]
+    pub quarantine_corrupt: bool,
+    /// Keeps the most recent this many changes in memory for `changes_since`. Off when unset.
+    #[uniffi(default = None)]
+    pub journal_capacity: Option<u32>,
+    /// Keeps the position of every element in memory for `element_at`, saved to `<path>.idx`
+    /// by `sync_all` and on close. A saved index that still matches the file's header restores
+    /// `generation()` and the sequence ids on open instead of a frame walk.
+    #[uniffi(default = false)]
+    pub element_index: bool,
+    /// Returns from opening without the frame walks that fill the bloom filter and build the
+    /// element index. They run on the first call that needs them, or on `finish_open`.
+    #[uniffi(default = false)]
+    pub lazy_open: bool,
+    /// Holds up to this many removals in memory, hidden from `peek` and `size`, before
+    /// committing them with one header write. Uncommitted removals count against quotas and
+    /// watermarks, and are lost on a crash. Off when unset.
+    #[uniffi(default = None)]
+    pub deferred_removes: Option<u32>,
+    /// Free space on the volume below which `health_check` reports `Degraded`.
+    #[uniffi(default = None)]
+    pub free_space_reserve: Option<u64>,
+    /// How long written bytes may go without a sync before `health_check` reports `Degraded`.
+    #[uniffi(default = None)]
+    pub max_unsynced_age: Option<Duration>,
+    /// What to do when the file is corrupt on open; `None` behaves as `Fail`, unless
+    /// `quarantine_corrupt` is set.
+    #[uniffi(default = None)]
+    pub on_corruption: Option<CorruptionPolicy>,
+    /// The longest element a single peek or get may copy into memory. Longer ones fail with
+    /// `TooLargeForRead` before anything is read; `peek_to_file` and `pop_to_file` still read
+    /// them, a chunk at a time.
+    #[uniffi(default = None)]
+    pub max_read_bytes: Option<u64>,
+    /// How long `add`, `add_multiple`, `peek`, `remove`, `remove_n`, `clear` and `sync_all`
+    /// may take before failing with `Timeout`. When set, they run on a runtime worker while the
+    /// caller waits; the `_async` calls are not limited. Unlimited when unset.
+    #[uniffi(default = None)]
+    pub operation_timeout: Option<Duration>,
+    /// Id of the key, asked of the `KeyProvider` set with `set_key_provider`, that signs the
+    /// header and contents in `<path>.sig` on every sync and close. Opening a file that does
+    /// not match its signature fails with `TamperDetected`. Unsigned when unset.
+    #[uniffi(default = None)]
+    pub signature_key: Option<String>,
+    /// Header layout of a file this open creates. An existing file, including one restored
+    /// from the mirror, keeps its own, and rewrites keep it too. `None` is `Versioned`.
+    #[uniffi(default = None)]
+    pub format: Option<FileFormat>,
+    /// Bytes queue-file reads ahead for sequential peeks, removals and iteration, as
+    /// `set_read_buffer_size` sets later. `None` is 4 KiB.
+    #[uniffi(default = None)]
+    pub read_buffer_size: Option<u32>,
+    /// Bytes copied at a time by `peek_to_file` and `pop_to_file`, and payload bytes written to
+    /// the new file per batch when `remove_at`, `update_at`, `rewrite` and `quarantine_corrupt`
+    /// copy the queue. `None` is 64 KiB for the former and 1 MiB for the latter.
+    #[uniffi(default = None)]
+    pub copy_buffer_size: Option<u32>,
+    /// How long the queue may go unused before its file, and the mirror, header sidecar and
+    /// element index, are synced and closed. The next call reopens them first. Open until
+    /// closed when unset.
+    #[uniffi(default = None)]
+    pub idle_close_after: Option<Duration>,
+}
+
+impl QueueFileOptions {
+    /// Whether opening moves damaged elements into `<path>.quarantine`.
+    pub(crate) fn quarantines(&self) -> bool {
+        self.quarantine_corrupt || self.on_corruption == Some(CorruptionPolicy::RepairInPlace)
+    }
+
+    /// `copy_buffer_size`, or `default` without one.
+    pub(crate) fn copy_buffer_size_or(&self, default: usize) -> usize {
+        self.copy_buffer_size
+            .map_or(default, |size| (size as usize).max(1))
+    }
+}
+
+impl Default for QueueFileOptions {
+    fn default() -> Self {
+        QueueFileOptions {
+            mode: None,
+            capacity: 4096,
+            min_retained_len: None,
+            growth: None,
+            max_growth_step: None,
+            mirror_path: None,
+            max_elements: None,
+            max_bytes: None,
+            rate_limit: None,
+            retry_policy: None,
+            wait_for_unlock: None,
+            redundant_header: false,
+            sync_directory: true,
+            exclude_from_backup: false,
+            already_open: None,
+            lock_file: false,
+            bloom_filter_elements: None,
+            sync_trailer: false,
+            verify_on_open: false,
+            quarantine_corrupt: false,
+            journal_capacity: None,
+            element_index: false,
+            lazy_open: false,
+            deferred_removes: None,
+            free_space_reserve: None,
+            max_unsynced_age: None,
+            on_corruption: None,
+            max_read_bytes: None,
+            operation_timeout: None,
+            signature_key: None,
+            format: None,
+            read_buffer_size: None,
+            copy_buffer_size: None,
+            idle_close_after: None,
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum PauseMode {
+    /// Adds wait for `resume_writes`, failing with `WritesPaused` after `timeout`.
+    Block { timeout: Duration },
+    /// Adds are accepted into memory and written as one batch by `resume_writes`. They count
+    /// towards quotas but are not visible to reads until then.
+    Buffer,
+}
@@ -0,0 +1,52 @@
+use std::io;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::time::Instant;
+
+/// Bumped by every `notify_unlocked` call, so waiters can tell an unlock that happened after
+/// their failed attempt from one that happened before it.
+static UNLOCKS: Mutex<u64> = Mutex::new(0);
+static UNLOCKED: Condvar = Condvar::new();
+
+/// Tells queues waiting on a data-protection-locked file that protected data is available
+/// again, typically from `UIApplication.protectedDataDidBecomeAvailableNotification`.
+#[uniffi::export]
+pub fn notify_unlocked() {
+    let mut unlocks = UNLOCKS.lock().unwrap_or_else(PoisonError::into_inner);
+    *unlocks += 1;
+    UNLOCKED.notify_all();
+}
+
+pub(crate) fn unlock_count() -> u64 {
+    *UNLOCKS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Blocks until `notify_unlocked` has been called since `seen` was read, or until `deadline`.
+/// Returns false on timeout.
+pub(crate) fn wait_for_unlock(seen: u64, deadline: Instant) -> bool {
+    let mut unlocks = UNLOCKS.lock().unwrap_or_else(PoisonError::into_inner);
+    while *unlocks == seen {
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        unlocks = UNLOCKED
+            .wait_timeout(unlocks, deadline - now)
+            .unwrap_or_else(PoisonError::into_inner)
+            .0;
+    }
+    true
+}
+
+/// iOS reports I/O on a file whose protection class is unavailable while the device is locked
+/// as `EPERM`, which nothing else in the queue's file access produces.
+#[cfg(target_os = "ios")]
+pub(crate) fn is_locked_error(e: &io::Error) -> bool {
+    const EPERM: i32 = 1;
+
+    e.raw_os_error() == Some(EPERM)
+}
+
+#[cfg(not(target_os = "ios"))]
+pub(crate) fn is_locked_error(_e: &io::Error) -> bool {
+    false
+}
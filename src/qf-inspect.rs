@@ -0,0 +1,371 @@
+//! Inspects queue files pulled off devices: prints the header and sizes, dumps elements,
+//! verifies the element chain and the redundant header sidecar, and repairs damaged files.
+//!
+//! This reads the file format directly rather than through the queue, so it works on files the
+//! queue refuses to open.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::UNIX_EPOCH;
+
+const USAGE: &str = "\
+usage: qf-inspect <file>                                  header, sizes and timestamps
+       qf-inspect <file> dump [--hex|--base64|--utf8] [--limit N]
+       qf-inspect <file> verify                           check elements and the .hdr sidecar
+       qf-inspect <file> repair                           restore the header or salvage elements";
+
+const VERSIONED_HEADER: u32 = 0x8000_0001;
+/// Generation, header copy and CRC-32 of both, as written by the `redundant_header` option.
+const SLOT_LEN: usize = 8 + 32 + 4;
+
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    versioned: bool,
+    header_len: u64,
+    file_len: u64,
+    count: u64,
+    first: u64,
+    last: u64,
+}
+
+impl Header {
+    fn parse(bytes: &[u8]) -> Result<Header, String> {
+        let be = |at: usize, len: usize| -> Result<u64, String> {
+            let field = bytes
+                .get(at..at + len)
+                .ok_or("file is shorter than its header")?;
+            Ok(field.iter().fold(0, |acc, &b| (acc << 8) | u64::from(b)))
+        };
+        if be(0, 4)? == u64::from(VERSIONED_HEADER) {
+            Ok(Header {
+                versioned: true,
+                header_len: 32,
+                file_len: be(4, 8)?,
+                count: be(12, 4)?,
+                first: be(16, 8)?,
+                last: be(24, 8)?,
+            })
+        } else {
+            Ok(Header {
+                versioned: false,
+                header_len: 16,
+                file_len: be(0, 4)?,
+                count: be(4, 4)?,
+                first: be(8, 4)?,
+                last: be(12, 4)?,
+            })
+        }
+    }
+}
+
+/// The result of following the element chain from the header's first position.
+struct Walk {
+    elements: Vec<Vec<u8>>,
+    /// Why the walk stopped before reaching `count` elements, if it did.
+    problem: Option<String>,
+}
+
+fn walk(bytes: &[u8], header: &Header) -> Walk {
+    let mut walk = Walk {
+        elements: Vec::new(),
+        problem: None,
+    };
+    let file_len = header.file_len;
+    if file_len < header.header_len || file_len > bytes.len() as u64 {
+        walk.problem = Some(format!(
+            "header file length {file_len} does not fit the {}-byte file",
+            bytes.len()
+        ));
+        return walk;
+    }
+    let wrap = |pos: u64| {
+        if pos < file_len {
+            pos
+        } else {
+            header.header_len + pos - file_len
+        }
+    };
+    let read = |pos: u64, len: u64| -> Vec<u8> {
+        (0..len).map(|i| bytes[wrap(pos + i) as usize]).collect()
+    };
+
+    let mut pos = header.first;
+    for index in 0..header.count {
+        if pos < header.header_len || pos >= file_len {
+            walk.problem = Some(format!("element {index} starts outside the ring at {pos}"));
+            return walk;
+        }
+        let len = read(pos, 4)
+            .iter()
+            .fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+        if len > file_len - header.header_len {
+            walk.problem = Some(format!(
+                "element {index} at {pos} has impossible length {len}"
+            ));
+            return walk;
+        }
+        if index + 1 == header.count && pos != header.last {
+            walk.problem = Some(format!(
+                "last element found at {pos}, header says {}",
+                header.last
+            ));
+            return walk;
+        }
+        walk.elements.push(read(pos + 4, len));
+        pos = wrap(pos + 4 + len);
+    }
+    walk
+}
+
+/// A header copy from the `.hdr` sidecar whose CRC checked out.
+struct HeaderCopy {
+    generation: u64,
+    header: Vec<u8>,
+}
+
+/// Valid header copies from the `.hdr` sidecar, newest first, and how many slots were damaged.
+fn sidecar_headers(path: &Path) -> Option<(Vec<HeaderCopy>, usize)> {
+    let bytes = fs::read(sidecar_path(path)).ok()?;
+    let mut valid = Vec::new();
+    let mut damaged = 0;
+    for slot in bytes.as_chunks::<SLOT_LEN>().0 {
+        let (body, crc) = slot.split_at(SLOT_LEN - 4);
+        if crc32fast::hash(body).to_be_bytes() != crc {
+            damaged += 1;
+            continue;
+        }
+        valid.push(HeaderCopy {
+            generation: u64::from_be_bytes(body[..8].try_into().unwrap()),
+            header: body[8..].to_vec(),
+        });
+    }
+    valid.sort_by_key(|copy| std::cmp::Reverse(copy.generation));
+    Some((valid, damaged))
+}
+
+fn sidecar_path(path: &Path) -> PathBuf {
+    let mut sidecar = path.to_path_buf().into_os_string();
+    sidecar.push(".hdr");
+    PathBuf::from(sidecar)
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut sibling = path.to_path_buf().into_os_string();
+    sibling.push(suffix);
+    PathBuf::from(sibling)
+}
+
+fn info(path: &Path, bytes: &[u8]) -> Result<bool, String> {
+    let header = Header::parse(bytes)?;
+    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+    let seconds = |time: std::io::Result<std::time::SystemTime>| {
+        time.ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or("unknown".to_string(), |d| {
+                format!("{} (unix seconds)", d.as_secs())
+            })
+    };
+
+    println!("file:         {}", path.display());
+    println!(
+        "format:       {}",
+        if header.versioned {
+            "versioned (32-byte header)"
+        } else {
+            "legacy (16-byte header)"
+        }
+    );
+    println!("physical len: {}", bytes.len());
+    println!("header len:   {}", header.file_len);
+    println!("elements:     {}", header.count);
+    println!("first pos:    {}", header.first);
+    println!("last pos:     {}", header.last);
+    println!("modified:     {}", seconds(metadata.modified()));
+    println!("created:      {}", seconds(metadata.created()));
+
+    let walk = walk(bytes, &header);
+    let payload: u64 = walk.elements.iter().map(|e| e.len() as u64).sum();
+    let used = header.header_len + payload + 4 * walk.elements.len() as u64;
+    println!(
+        "payload:      {payload} bytes in {} readable elements",
+        walk.elements.len()
+    );
+    println!("used bytes:   {used}");
+    if let Some(problem) = &walk.problem {
+        println!("problem:      {problem}");
+    }
+    Ok(walk.problem.is_none())
+}
+
+fn dump(bytes: &[u8], format: &str, limit: Option<usize>) -> Result<bool, String> {
+    let header = Header::parse(bytes)?;
+    let walk = walk(bytes, &header);
+    for (index, element) in walk
+        .elements
+        .iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .enumerate()
+    {
+        let rendered = match format {
+            "--hex" => element.iter().map(|b| format!("{b:02x}")).collect(),
+            "--base64" => base64(element),
+            _ => String::from_utf8_lossy(element).into_owned(),
+        };
+        println!("[{index}] {} bytes: {rendered}", element.len());
+    }
+    if let Some(problem) = &walk.problem {
+        eprintln!("stopped: {problem}");
+    }
+    Ok(walk.problem.is_none())
+}
+
+fn verify(path: &Path, bytes: &[u8]) -> Result<bool, String> {
+    let header = Header::parse(bytes)?;
+    let walk = walk(bytes, &header);
+    let mut ok = true;
+    match &walk.problem {
+        None => println!("elements: ok ({} elements)", walk.elements.len()),
+        Some(problem) => {
+            ok = false;
+            println!(
+                "elements: {problem} (after {} readable elements)",
+                walk.elements.len()
+            );
+        }
+    }
+    match sidecar_headers(path) {
+        None => println!("sidecar:  none"),
+        Some((valid, damaged)) => {
+            let current = bytes.get(..32).unwrap_or_default();
+            let matches = valid.first().is_some_and(|copy| copy.header == current);
+            println!(
+                "sidecar:  {} valid slots, {} damaged, newest generation {}, {}",
+                valid.len(),
+                damaged,
+                valid.first().map_or(0, |copy| copy.generation),
+                if matches {
+                    "matches the file header"
+                } else {
+                    "differs from the file header"
+                }
+            );
+            ok &= damaged == 0;
+        }
+    }
+    Ok(ok)
+}
+
+/// Restores the newest sidecar header that yields a consistent element chain; failing that,
+/// rewrites the file with the elements that can still be read. The original is kept as
+/// `<file>.bak`.
+fn repair(path: &Path, bytes: &[u8]) -> Result<bool, String> {
+    let header = Header::parse(bytes);
+    if let Ok(header) = &header
+        && walk(bytes, header).problem.is_none()
+    {
+        println!("nothing to repair");
+        return Ok(true);
+    }
+    let backup = sibling(path, ".bak");
+    fs::copy(path, &backup).map_err(|e| format!("backup failed: {e}"))?;
+    println!("original saved as {}", backup.display());
+
+    for copy in sidecar_headers(path)
+        .map(|(valid, _)| valid)
+        .unwrap_or_default()
+    {
+        let mut restored = bytes.to_vec();
+        let Some(target) = restored.get_mut(..copy.header.len()) else {
+            continue;
+        };
+        target.copy_from_slice(&copy.header);
+        let Ok(parsed) = Header::parse(&restored) else {
+            continue;
+        };
+        if walk(&restored, &parsed).problem.is_none() {
+            fs::write(path, &restored).map_err(|e| e.to_string())?;
+            println!(
+                "restored header generation {} from the sidecar",
+                copy.generation
+            );
+            return Ok(true);
+        }
+    }
+
+    let elements = header.map(|h| walk(bytes, &h).elements).unwrap_or_default();
+    let salvage = sibling(path, ".repair");
+    let _ = fs::remove_file(&salvage);
+    {
+        let mut queue = queue_file::QueueFile::open(&salvage).map_err(|e| e.to_string())?;
+        queue.add_n(elements.iter()).map_err(|e| e.to_string())?;
+        queue.sync_all().map_err(|e| e.to_string())?;
+    }
+    fs::rename(&salvage, path).map_err(|e| e.to_string())?;
+    println!("rewrote the file with {} salvaged elements", elements.len());
+    Ok(true)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(path) = args.first().map(PathBuf::from) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let bytes = match fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("{}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match args.get(1).map(String::as_str) {
+        None => info(&path, &bytes),
+        Some("dump") => {
+            let format = args
+                .iter()
+                .find(|a| ["--hex", "--base64", "--utf8"].contains(&a.as_str()))
+                .map_or("--utf8", String::as_str);
+            let limit = args
+                .iter()
+                .position(|a| a == "--limit")
+                .and_then(|i| args.get(i + 1))
+                .and_then(|n| n.parse().ok());
+            dump(&bytes, format, limit)
+        }
+        Some("verify") => verify(&path, &bytes),
+        Some("repair") => repair(&path, &bytes),
+        Some(_) => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{}: {e}", path.display());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use queue_file::QueueFile as RustQueueFile;
+
+use crate::header_slots::HeaderSlots;
+use crate::verify::{self, Scan};
+use crate::{QueueFileError, dictionary, index, rewrite, signature, sync_trailer};
+
+/// A stretch of the queue file that opening moved aside.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct QuarantinedRange {
+    /// Position in the queue of the first element the range held.
+    pub index: u64,
+    /// Byte offset of the range in the queue file.
+    pub offset: u64,
+    /// Raw bytes copied to the quarantine file; 0 when they could not be read at all.
+    pub bytes: u64,
+    /// Elements lost with the range: 1 for an unreadable payload, every element from `index`
+    /// on when the chain of frames breaks.
+    pub elements: u64,
+    pub reason: String,
+}
+
+/// What the `quarantine_corrupt` option moved out of the queue file when it was opened.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct QuarantineReport {
+    /// Elements the queue opened with.
+    pub kept_elements: u64,
+    pub ranges: Vec<QuarantinedRange>,
+    /// The queue file the raw bytes were appended to.
+    pub quarantine_path: String,
+}
+
+pub(crate) fn quarantine_path(path: &Path) -> PathBuf {
+    let mut quarantine = path.to_path_buf().into_os_string();
+    quarantine.push(".quarantine");
+    PathBuf::from(quarantine)
+}
+
+/// Moves corrupt elements of the file at `path` into `<path>.quarantine` and rewrites the file
+/// with the readable ones. An unreadable payload loses only its own element; a broken frame
+/// loses it and everything after it, since the frames that follow cannot be located. `None`
+/// if nothing was wrong, and also if the header itself is unusable, which is left for the
+/// normal open to report.
+///
+/// Each quarantine element is the range's 8-byte big-endian offset followed by its raw bytes.
+pub(crate) fn recover(
+    path: &Path,
+    capacity: u64,
+    batch_bytes: usize,
+) -> Result<Option<QuarantineReport>, QueueFileError> {
+    let with_path = |e| QueueFileError::from(e).with_path(path);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let Ok(mut scan) = Scan::open(path).map_err(with_path)? else {
+        return Ok(None);
+    };
+
+    let mut kept = Vec::new();
+    let mut ranges = Vec::new();
+    let mut records = Vec::new();
+    let mut payload = Vec::new();
+    for (index, frame) in scan.frames.clone().into_iter().enumerate() {
+        match scan.read_payload(frame, &mut payload) {
+            Ok(()) => kept.push(frame),
+            Err(e) => {
+                ranges.push(QuarantinedRange {
+                    index: index as u64,
+                    offset: frame.pos,
+                    bytes: 0,
+                    elements: 1,
+                    reason: verify::unreadable(index as u64, frame, &e).message,
+                });
+                records.push(frame.pos.to_be_bytes().to_vec());
+            }
+        }
+    }
+    if let Some(broken) = scan.broken.take() {
+        let rest = scan.read_rest(broken.pos).unwrap_or_default();
+        ranges.push(QuarantinedRange {
+            index: broken.index,
+            offset: broken.pos,
+            bytes: rest.len() as u64,
+            elements: scan.element_count() - broken.index,
+            reason: broken.problem.message,
+        });
+        let mut record = broken.pos.to_be_bytes().to_vec();
+        record.extend_from_slice(&rest);
+        records.push(record);
+    }
+    if ranges.is_empty() {
+        return Ok(None);
+    }
+
+    // Saved before the file holding the damaged bytes is replaced.
+    let quarantine = quarantine_path(path);
+    let mut quarantined = RustQueueFile::open(&quarantine)
+        .map_err(|e| QueueFileError::from(e).with_path(&quarantine))?;
+    quarantined.add_n(&records)?;
+    quarantined.sync_all()?;
+    drop(quarantined);
+
+    let mut kept_frames = kept.iter();
+    rewrite::replace(path, capacity, batch_bytes, || {
+        let Some(&frame) = kept_frames.next() else {
+            return Ok(None);
+        };
+        let mut payload = Vec::new();
+        scan.read_payload(frame, &mut payload).map_err(with_path)?;
+        Ok(Some(payload))
+    })?;
+    Ok(Some(QuarantineReport {
+        kept_elements: kept.len() as u64,
+        ranges,
+        quarantine_path: quarantine.display().to_string(),
+    }))
+}
+
+/// What the `QuarantineAndStartFresh` policy did with a queue file that failed to open.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct CorruptionRecovery {
+    /// The `CorruptedFile` message the open failed with.
+    pub reason: String,
+    /// Where the queue file, its mirror and its sidecars were moved, each renamed to
+    /// `<name>.corrupt-<unix time in ms>`. The queue file comes first.
+    pub moved_files: Vec<String>,
+}
+
+/// Fails with `CorruptedFile` if the chain of element frames of the file at `path` is broken,
+/// without reading any payload.
+pub(crate) fn check_chain(path: &Path) -> Result<(), QueueFileError> {
+    let scan = Scan::open(path)
+        .map_err(|e| QueueFileError::from(e).with_path(path))?
+        .map_err(|corruption| corruption.into_error(path))?;
+    match scan.broken {
+        Some(broken) => Err(broken.problem.into_error(path)),
+        None => Ok(()),
+    }
+}
+
+/// Renames the queue file at `path`, its mirror and its sidecars out of the way so the queue
+/// can be created again from scratch.
+pub(crate) fn set_aside(
+    path: &Path,
+    mirror_path: Option<&Path>,
+    reason: String,
+) -> Result<CorruptionRecovery, QueueFileError> {
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    let moved = |file: &Path| {
+        let mut aside = file.to_path_buf().into_os_string();
+        aside.push(format!(".corrupt-{timestamp_ms}"));
+        let aside = PathBuf::from(aside);
+        fs::rename(file, &aside).map_err(|e| QueueFileError::from(e).with_path(file))?;
+        Ok::<_, QueueFileError>(aside.display().to_string())
+    };
+    let mut moved_files = Vec::new();
+    let files = [
+        Some(path.to_path_buf()),
+        mirror_path.map(Path::to_path_buf),
+        Some(HeaderSlots::sidecar_path(path)),
+        Some(sync_trailer::trailer_path(path)),
+        Some(index::index_path(path)),
+        Some(signature::signature_path(path)),
+        Some(dictionary::dictionary_path(path)),
+    ];
+    for file in files.into_iter().flatten().filter(|file| file.exists()) {
+        moved_files.push(moved(&file)?);
+    }
+    Ok(CorruptionRecovery {
+        reason,
+        moved_files,
+    })
+}
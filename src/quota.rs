@@ -0,0 +1,8 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct CapacityInfo {
+    /// Elements that can still be added before `max_elements` is reached; `None` when uncapped.
+    pub elements_remaining: Option<u64>,
+    /// Payload bytes that can still be added (in a single element) before `max_bytes` is
+    /// reached; `None` when uncapped.
+    pub bytes_remaining: Option<u64>,
+}
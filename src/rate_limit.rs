@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+use crate::QueueFileError;
+
+/// Sustained add rates above which adds fail with `Backpressure`. Each limit allows bursts of
+/// up to one second's worth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct RateLimit {
+    #[uniffi(default = None)]
+    pub max_adds_per_sec: Option<u32>,
+    /// Payload bytes, not counting frame headers.
+    #[uniffi(default = None)]
+    pub max_bytes_per_sec: Option<u64>,
+}
+
+/// A token bucket holding at most one second of `rate`, refilled continuously.
+#[derive(Debug)]
+struct Bucket {
+    rate: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        Bucket {
+            rate,
+            tokens: rate,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.refilled_at = now;
+    }
+
+    /// Milliseconds until `cost` can be spent, or `None` if it can be now. A cost above one
+    /// second's worth waits for a full bucket and then overdraws it.
+    fn wait(&self, cost: f64) -> Option<Duration> {
+        let needed = cost.min(self.rate);
+        match self.tokens >= needed {
+            true => None,
+            false => {
+                let ms = ((needed - self.tokens) / self.rate * 1000.0).ceil();
+                Some(Duration::from_millis(ms as u64))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    adds: Option<Bucket>,
+    bytes: Option<Bucket>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        let now = Instant::now();
+        RateLimiter {
+            adds: limit
+                .max_adds_per_sec
+                .map(|rate| Bucket::new(f64::from(rate.max(1)), now)),
+            bytes: limit
+                .max_bytes_per_sec
+                .map(|rate| Bucket::new(rate.max(1) as f64, now)),
+        }
+    }
+
+    /// Charges an add of `count` elements holding `bytes` of payload, or fails with
+    /// `Backpressure` without charging anything.
+    pub(crate) fn admit(&mut self, count: usize, bytes: u64) -> Result<(), QueueFileError> {
+        let now = Instant::now();
+        let mut costs = [
+            (self.adds.as_mut(), count as f64),
+            (self.bytes.as_mut(), bytes as f64),
+        ];
+        let mut retry_after = None;
+        for (bucket, cost) in &mut costs {
+            if let Some(bucket) = bucket {
+                bucket.refill(now);
+                retry_after = retry_after.max(bucket.wait(*cost));
+            }
+        }
+        if let Some(retry_after) = retry_after {
+            return Err(QueueFileError::Backpressure { retry_after });
+        }
+        for (bucket, cost) in costs {
+            if let Some(bucket) = bucket {
+                bucket.tokens -= cost;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::QueueFileError;
+use crate::fragmentation;
+use crate::verify::{Frame, Header};
+
+/// Bytes `FrameReader::copy` reads at a time, unless `chunk_len` says otherwise.
+pub(crate) const CHUNK: usize = 64 * 1024;
+/// Longest file `add_from_file` reads into memory, unless `max_read_bytes` is lower.
+pub(crate) const MAX_FILE_LEN: u64 = 64 << 20;
+
+/// Fails with `TooLargeForRead` if an element of `len` bytes is over `limit`.
+pub(crate) fn check(len: u64, limit: Option<u64>) -> Result<(), QueueFileError> {
+    match limit {
+        Some(limit) if len > limit => Err(QueueFileError::TooLargeForRead { len, limit }),
+        _ => Ok(()),
+    }
+}
+
+/// Reads a queue file's elements by following the length prefixes of its frames, so the only
+/// payloads read are the ones asked for, and each one only after its length is known.
+pub(crate) struct FrameReader {
+    file: File,
+    header_len: u64,
+    file_len: u64,
+    /// Frames left from `pos` on.
+    remaining: u64,
+    pos: u64,
+    chunk_len: usize,
+}
+
+impl FrameReader {
+    /// Starts at the eldest element of the committed header of the file at `path`.
+    pub(crate) fn open(path: &Path) -> io::Result<FrameReader> {
+        let mut file = File::open(path)?;
+        let mut bytes = [0; 32];
+        file.read_exact(&mut bytes)?;
+        let header = Header::parse(&bytes);
+        Ok(FrameReader {
+            file,
+            header_len: header.header_len,
+            file_len: header.file_len,
+            remaining: header.count,
+            pos: header.first,
+            chunk_len: CHUNK,
+        })
+    }
+
+    /// This reader, copying `len` bytes at a time.
+    pub(crate) fn chunk_len(self, len: usize) -> FrameReader {
+        FrameReader {
+            chunk_len: len,
+            ..self
+        }
+    }
+
+    fn wrap(&self, pos: u64) -> u64 {
+        match pos >= self.file_len {
+            true => self.header_len + pos - self.file_len,
+            false => pos,
+        }
+    }
+
+    /// The next frame, read from its length prefix, or `None` after the last one.
+    pub(crate) fn next_frame(&mut self) -> io::Result<Option<Frame>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        let (pos, header_len, file_len) = (self.pos, self.header_len, self.file_len);
+        let mut len = [0; 4];
+        fragmentation::read_ring(&mut self.file, pos, &mut len, header_len, file_len)?;
+        let len = u64::from(u32::from_be_bytes(len));
+        if len > file_len - header_len - 4 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("element at offset {pos} has impossible length {len}"),
+            ));
+        }
+        self.pos = self.wrap(pos + 4 + len);
+        self.remaining -= 1;
+        Ok(Some(Frame { pos, len }))
+    }
+
+    /// The payload of `frame`, which `next_frame` returned.
+    pub(crate) fn read(&mut self, frame: Frame) -> io::Result<Vec<u8>> {
+        self.read_prefix(frame, frame.len as usize)
+    }
+
+    /// The first `len` bytes of the payload of `frame`, or all of it if it is shorter.
+    pub(crate) fn read_prefix(&mut self, frame: Frame, len: usize) -> io::Result<Vec<u8>> {
+        let mut payload = vec![0; len.min(frame.len as usize)];
+        let start = self.wrap(frame.pos + 4);
+        fragmentation::read_ring(
+            &mut self.file,
+            start,
+            &mut payload,
+            self.header_len,
+            self.file_len,
+        )?;
+        Ok(payload)
+    }
+
+    /// Writes the payload of `frame` to `dest` a chunk at a time.
+    pub(crate) fn copy(&mut self, frame: Frame, dest: &mut impl Write) -> io::Result<()> {
+        self.copy_from(frame, 0, dest)
+    }
+
+    /// Like `copy`, leaving out the first `skip` bytes of the payload.
+    pub(crate) fn copy_from(
+        &mut self,
+        frame: Frame,
+        skip: u64,
+        dest: &mut impl Write,
+    ) -> io::Result<()> {
+        let mut chunk = vec![0; self.chunk_len.min(frame.len as usize)];
+        let mut done = skip.min(frame.len);
+        while done < frame.len {
+            let n = (frame.len - done).min(self.chunk_len as u64) as usize;
+            let start = self.wrap(frame.pos + 4 + done);
+            fragmentation::read_ring(
+                &mut self.file,
+                start,
+                &mut chunk[..n],
+                self.header_len,
+                self.file_len,
+            )?;
+            dest.write_all(&chunk[..n])?;
+            done += n as u64;
+        }
+        Ok(())
+    }
+}
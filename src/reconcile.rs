@@ -0,0 +1,113 @@
+use sha2::{Digest, Sha256};
+
+use crate::QueueFileError;
+use crate::state::QueueState;
+
+/// Payload bytes added to the target per `add_all`.
+const BATCH_BYTES: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum SyncStrategy {
+    /// Adds the source's elements that come after the newest ones both queues hold, keeping
+    /// every element already in the target.
+    AppendMissing,
+    /// Leaves the target holding exactly the source's elements, in the same order.
+    Mirror,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct SyncReport {
+    /// Elements removed from the front of the target.
+    pub removed: u64,
+    /// Elements copied from the source to the back of the target.
+    pub added: u64,
+    /// Elements of the target left in place because the source holds them too.
+    pub kept: u64,
+}
+
+/// Brings `target` up to date with `source`, comparing the two by the SHA-256 of each payload.
+/// The source is only read.
+pub(crate) fn sync(
+    target: &mut QueueState,
+    source: &mut QueueState,
+    strategy: SyncStrategy,
+) -> Result<SyncReport, QueueFileError> {
+    target.check_not_frozen()?;
+    target.flush()?;
+    source.flush()?;
+    let ours = digests(target)?;
+    let theirs = digests(source)?;
+    let (overlap, contained) = overlap(&ours, &theirs);
+    let (removed, from) = match strategy {
+        SyncStrategy::Mirror => (ours.len() - overlap, overlap),
+        SyncStrategy::AppendMissing if contained => (0, theirs.len()),
+        SyncStrategy::AppendMissing => (0, overlap),
+    };
+    if removed > 0 {
+        target.remove_n(removed)?;
+    }
+
+    let mut batch = Vec::new();
+    let mut batch_bytes = 0;
+    for element in source.queue.get_mut()?.iter().skip(from) {
+        // Kept framed, so chunks stay chunks in the target.
+        let element = source.codec.decode_framed(element.into_vec(), None)?;
+        batch_bytes += element.len();
+        batch.push(element);
+        if batch_bytes >= BATCH_BYTES {
+            target.add_all(std::mem::take(&mut batch))?;
+            batch_bytes = 0;
+        }
+    }
+    if !batch.is_empty() {
+        target.add_all(batch)?;
+    }
+    Ok(SyncReport {
+        removed: removed as u64,
+        added: (theirs.len() - from) as u64,
+        kept: (ours.len() - removed) as u64,
+    })
+}
+
+fn digests(state: &mut QueueState) -> Result<Vec<[u8; 32]>, QueueFileError> {
+    let mut digests = Vec::with_capacity(state.queue.size());
+    let mut iter = state.queue.get_mut()?.iter();
+    while let Some(element) = iter.borrowed_next() {
+        digests.push(Sha256::digest(state.codec.view(element, None)?).into());
+    }
+    Ok(digests)
+}
+
+/// The length of the longest run at the back of `ours` that starts `theirs`, and whether all
+/// of `theirs` appears somewhere in `ours` as a contiguous run. Knuth-Morris-Pratt, with
+/// `theirs` as the pattern.
+fn overlap(ours: &[[u8; 32]], theirs: &[[u8; 32]]) -> (usize, bool) {
+    if theirs.is_empty() {
+        return (0, true);
+    }
+    let mut fallback = vec![0; theirs.len()];
+    let mut matched = 0;
+    for i in 1..theirs.len() {
+        while matched > 0 && theirs[i] != theirs[matched] {
+            matched = fallback[matched - 1];
+        }
+        if theirs[i] == theirs[matched] {
+            matched += 1;
+        }
+        fallback[i] = matched;
+    }
+    let (mut matched, mut contained) = (0, false);
+    for digest in ours {
+        if matched == theirs.len() {
+            matched = fallback[matched - 1];
+        }
+        while matched > 0 && *digest != theirs[matched] {
+            matched = fallback[matched - 1];
+        }
+        if *digest == theirs[matched] {
+            matched += 1;
+        }
+        contained |= matched == theirs.len();
+    }
+    (matched, contained)
+}
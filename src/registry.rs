@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, Weak};
+
+use crate::state::QueueState;
+use crate::{AlreadyOpenPolicy, QueueFile, QueueFileError, QueueFileOptions};
+
+/// Every queue file and mirror open in this process, by canonical path. An entry lives exactly
+/// as long as the handle's `Registration`, so its presence means the file has a writer.
+static OPEN: Mutex<BTreeMap<PathBuf, Entry>> = Mutex::new(BTreeMap::new());
+/// Signalled whenever an open finishes, for opens of the same file waiting on it.
+static OPENED: Condvar = Condvar::new();
+
+enum Entry {
+    /// Being opened by a thread that does not hold the registry lock meanwhile, since the open
+    /// may retry, wait for the lock file or scan the whole file.
+    Opening,
+    Open(Weak<QueueFile>),
+}
+
+fn lock() -> MutexGuard<'static, BTreeMap<PathBuf, Entry>> {
+    OPEN.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Keeps the keys of an open in progress as `Opening`, and removes them if it fails.
+struct Reservation<'a> {
+    keys: &'a [PathBuf],
+    done: bool,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            let mut open = lock();
+            for key in self.keys {
+                open.remove(key);
+            }
+        }
+        OPENED.notify_all();
+    }
+}
+
+/// Keeps a handle's paths registered. It is the handle's last field, so the entries are only
+/// removed once the queue state has been flushed and closed.
+pub(crate) struct Registration {
+    keys: Vec<PathBuf>,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut open = lock();
+        for key in &self.keys {
+            open.remove(key);
+        }
+    }
+}
+
+/// Opens the queue at `path` unless this process already has it, or its mirror, open. Its keys
+/// are reserved while the file is opened so that two concurrent opens cannot both succeed; a
+/// second open of the same file waits for the first to finish.
+pub(crate) fn open(
+    path: &Path,
+    options: QueueFileOptions,
+) -> Result<Arc<QueueFile>, QueueFileError> {
+    let primary = key(path);
+    let mut keys = vec![primary.clone()];
+    if let Some(mirror_path) = &options.mirror_path {
+        keys.push(key(Path::new(mirror_path)));
+    }
+
+    let mut open = lock();
+    while let Some(found) = keys.iter().find(|key| open.contains_key(*key)) {
+        let existing = match &open[found] {
+            Entry::Opening => {
+                open = OPENED.wait(open).unwrap_or_else(PoisonError::into_inner);
+                continue;
+            }
+            Entry::Open(existing) => existing,
+        };
+        // A handle whose last reference is being dropped cannot be shared any more.
+        if options.already_open == Some(AlreadyOpenPolicy::Share)
+            && *found == primary
+            && let Some(queue) = existing.upgrade()
+        {
+            return Ok(queue);
+        }
+        return Err(QueueFileError::AlreadyOpen {
+            path: found.display().to_string(),
+        });
+    }
+    for key in &keys {
+        open.insert(key.clone(), Entry::Opening);
+    }
+    drop(open);
+
+    let mut reservation = Reservation {
+        keys: &keys,
+        done: false,
+    };
+    let state = QueueState::open(path.to_path_buf(), options).map_err(|e| e.with_path(path))?;
+    let mut open = lock();
+    reservation.done = true;
+    let registration = Registration { keys: keys.clone() };
+    let queue = Arc::new_cyclic(|me| QueueFile::from_state(state, registration, me.clone()));
+    for key in &queue.registration.keys {
+        open.insert(key.clone(), Entry::Open(Arc::downgrade(&queue)));
+    }
+    drop(open);
+    drop(reservation);
+    Ok(queue)
+}
+
+/// Writes out and fsyncs every queue open in this process, then freezes it, for
+/// `applicationWillTerminate` or an expiring background task. A later operation on a frozen
+/// handle fails with `Frozen` rather than writing after the app has been told it is done. If
+/// a queue fails, the rest are still closed and the first error is returned.
+#[uniffi::export]
+pub fn flush_and_close_all() -> Result<u32, QueueFileError> {
+    // Upgraded outside the registry lock, so a handle released meanwhile can unregister.
+    let queues: Vec<Weak<QueueFile>> = lock()
+        .values()
+        .filter_map(|entry| match entry {
+            Entry::Opening => None,
+            Entry::Open(queue) => Some(queue.clone()),
+        })
+        .collect();
+    let mut closed: Vec<Arc<QueueFile>> = Vec::new();
+    let mut first_error = None;
+    for queue in queues.iter().filter_map(Weak::upgrade) {
+        // A queue with a mirror is registered under both paths.
+        if closed.iter().any(|done| Arc::ptr_eq(done, &queue)) {
+            continue;
+        }
+        if let Err(e) = queue.flush_and_close() {
+            first_error.get_or_insert(e);
+        }
+        closed.push(queue);
+    }
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(closed.len() as u32),
+    }
+}
+
+/// The canonical form of `path`, which need not exist yet: links and relative components are
+/// resolved in its directory.
+fn key(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    match (parent.canonicalize(), path.file_name()) {
+        (Ok(parent), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
+}
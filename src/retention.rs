@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum RetentionReason {
+    /// The segments together held more than `max_total_bytes`.
+    TotalBytes,
+    /// The segment was last written longer than `max_segment_age` ago.
+    SegmentAge,
+    /// There were more than `max_segments` segments.
+    SegmentCount,
+}
+
+/// A segment deleted by a retention limit, with elements that were never removed.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct DroppedSegment {
+    pub id: u64,
+    pub elements: u64,
+    /// Bytes of the dropped elements, with their 4-byte frames.
+    pub bytes: u64,
+    pub reason: RetentionReason,
+}
+
+#[uniffi::export(with_foreign)]
+pub trait RetentionListener: Send + Sync {
+    /// Called with the segments one add or `enforce_retention` dropped, oldest first.
+    fn on_segments_dropped(&self, dropped: Vec<DroppedSegment>);
+}
+
+/// Drops decided under the queue lock, delivered once the lock has been released so the
+/// listener may call back into the queue.
+pub(crate) struct RetentionEvent {
+    pub(crate) listener: Arc<dyn RetentionListener>,
+    pub(crate) dropped: Vec<DroppedSegment>,
+}
+
+impl RetentionEvent {
+    pub(crate) fn dispatch(self) {
+        self.listener.on_segments_dropped(self.dropped);
+    }
+}
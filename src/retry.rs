@@ -0,0 +1,101 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::protection;
+use crate::stats::QueueStats;
+use crate::{QueueFileError, QueueFileOptions};
+
+/// Retries for I/O errors that are expected to clear on their own (`EINTR`, `EAGAIN`, `EBUSY`,
+/// timeouts), as seen with iOS data protection and network-backed volumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct RetryPolicy {
+    /// Total attempts per operation, including the first one.
+    #[uniffi(default = 3)]
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each further retry. `None` is 10 ms.
+    #[uniffi(default = None)]
+    pub initial_backoff: Option<Duration>,
+    /// `None` is 1 s.
+    #[uniffi(default = None)]
+    pub max_backoff: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: None,
+            max_backoff: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay to wait after failed attempt number `attempt`, or `None` once attempts run out.
+    fn backoff(&self, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let initial = self.initial_backoff.unwrap_or(Duration::from_millis(10));
+        let max = self.max_backoff.unwrap_or(Duration::from_secs(1));
+        let factor = 1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX);
+        Some(initial.saturating_mul(factor).min(max))
+    }
+}
+
+/// The retry settings of a `QueueFileOptions`, copied out so they can be used while the queue
+/// state is borrowed.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryConfig {
+    policy: Option<RetryPolicy>,
+    unlock_wait: Option<Duration>,
+}
+
+impl RetryConfig {
+    pub(crate) fn new(options: &QueueFileOptions) -> Self {
+        RetryConfig {
+            policy: options.retry_policy,
+            unlock_wait: options.wait_for_unlock,
+        }
+    }
+}
+
+/// Runs `op`, retrying it under `config` while it fails with a transient I/O error, or once
+/// `notify_unlocked` is called after it failed with `DataProtectionLocked`. `op` must leave the
+/// queue as it found it when it fails.
+pub(crate) fn run<T>(
+    config: RetryConfig,
+    stats: &mut QueueStats,
+    mut op: impl FnMut() -> Result<T, QueueFileError>,
+) -> Result<T, QueueFileError> {
+    let mut attempt = 1;
+    let mut unlock_deadline = None;
+    loop {
+        let unlocks = protection::unlock_count();
+        let err = match op() {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        if let QueueFileError::DataProtectionLocked { .. } = err {
+            let Some(wait) = config.unlock_wait else {
+                return Err(err);
+            };
+            let deadline = *unlock_deadline.get_or_insert_with(|| Instant::now() + wait);
+            if !protection::wait_for_unlock(unlocks, deadline) {
+                return Err(err);
+            }
+            stats.unlock_waits += 1;
+            continue;
+        }
+        let Some(policy) = config.policy.filter(|_| err.is_transient()) else {
+            return Err(err);
+        };
+        let Some(delay) = policy.backoff(attempt) else {
+            stats.io_retries_exhausted += 1;
+            return Err(err);
+        };
+        stats.io_retries += 1;
+        thread::sleep(delay);
+        attempt += 1;
+    }
+}
@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use queue_file::QueueFile as RustQueueFile;
+
+use crate::{QueueFileError, TrainedDictionary, legacy};
+
+/// Copies are added to the new file in batches of about this many payload bytes, unless the
+/// queue's `copy_buffer_size` says otherwise.
+pub(crate) const BATCH_BYTES: usize = 1 << 20;
+
+/// How `rewrite` stores the elements it copies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct RewriteOptions {
+    /// Compresses every element against the queue's dictionary, training one first if there is
+    /// none. `false` stores every element as it was added and stops compressing adds.
+    #[uniffi(default = true)]
+    pub compress: bool,
+    /// Trains a new dictionary on the current elements even if the queue has one.
+    #[uniffi(default = false)]
+    pub retrain: bool,
+    /// Elements a dictionary is trained on. `None` is 1000.
+    #[uniffi(default = None)]
+    pub sample_limit: Option<u32>,
+}
+
+impl Default for RewriteOptions {
+    fn default() -> Self {
+        RewriteOptions {
+            compress: true,
+            retrain: false,
+            sample_limit: None,
+        }
+    }
+}
+
+/// What `rewrite` did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct RewriteReport {
+    pub elements: u64,
+    /// `used_bytes()` before and after.
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    /// The dictionary trained for the rewrite, if one was.
+    pub trained: Option<TrainedDictionary>,
+}
+
+/// Writes the elements of `queue`, passed through `edit`, to a new queue file that replaces the
+/// one at `path` (see `replace`). `edit` gets each element's position and payload and returns
+/// what to store in its place, or `None` to drop it; an error from `edit` leaves the file as it
+/// was. Returns the bytes written to the new file.
+pub(crate) fn rewrite(
+    queue: &mut RustQueueFile,
+    path: &Path,
+    capacity: u64,
+    batch_bytes: usize,
+    mut edit: impl FnMut(usize, &[u8]) -> Result<Option<Vec<u8>>, QueueFileError>,
+) -> Result<u64, QueueFileError> {
+    let mut iter = queue.iter();
+    let mut index = 0;
+    replace(path, capacity, batch_bytes, || {
+        while let Some(element) = iter.borrowed_next() {
+            index += 1;
+            if let Some(element) = edit(index - 1, element)? {
+                return Ok(Some(element));
+            }
+        }
+        Ok(None)
+    })
+}
+
+/// Writes the elements returned by `next`, until it returns `None`, to a new queue file that
+/// then replaces the one at `path` with a rename, so a crash leaves either the old contents or
+/// the new ones. Elements are added `batch_bytes` of payload at a time. Returns the bytes
+/// written to the new file.
+///
+/// The caller must reopen its handles on `path` afterwards; the ones it has still point at the
+/// replaced file.
+pub(crate) fn replace(
+    path: &Path,
+    capacity: u64,
+    batch_bytes: usize,
+    mut next: impl FnMut() -> Result<Option<Vec<u8>>, QueueFileError>,
+) -> Result<u64, QueueFileError> {
+    let staging = staging_path(path);
+    let _ = fs::remove_file(&staging);
+    let result = (|| {
+        // A copy of a legacy file has to be created legacy; queue-file would create it versioned.
+        if legacy::is_legacy(path)? {
+            legacy::create(&staging, capacity)?;
+        }
+        let mut copy = RustQueueFile::with_capacity(&staging, capacity)?;
+        copy.set_sync_writes(false);
+        let mut batch = Vec::new();
+        let mut batch_len = 0;
+        while let Some(element) = next()? {
+            batch_len += element.len();
+            batch.push(element);
+            if batch_len >= batch_bytes {
+                copy.add_n(&batch)?;
+                batch.clear();
+                batch_len = 0;
+            }
+        }
+        copy.add_n(&batch)?;
+        copy.sync_all()?;
+        let written = copy.used_bytes();
+        drop(copy);
+        fs::rename(&staging, path)?;
+        Ok(written)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&staging);
+    }
+    result
+}
+
+fn staging_path(path: &Path) -> PathBuf {
+    let mut staging = path.to_path_buf().into_os_string();
+    staging.push(".rewrite");
+    PathBuf::from(staging)
+}
@@ -0,0 +1,172 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, mpsc};
+use std::task::{Context, Poll, Waker};
+
+use crate::QueueFileError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum ThreadQos {
+    UserInitiated,
+    Default,
+    Utility,
+    Background,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct RuntimeConfig {
+    /// Worker threads running the `_async` methods; at least one is started.
+    #[uniffi(default = 2)]
+    pub threads: u32,
+    /// Workers are named `<prefix>-<n>`.
+    #[uniffi(default = "queuefile")]
+    pub thread_name_prefix: String,
+    /// Quality-of-service class of the workers on Apple platforms; `None` behaves as `Utility`.
+    /// Ignored elsewhere.
+    #[uniffi(default = None)]
+    pub qos: Option<ThreadQos>,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        RuntimeConfig {
+            threads: 2,
+            thread_name_prefix: "queuefile".to_string(),
+            qos: None,
+        }
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The worker threads, started by `configure_runtime` or by the first `_async` call.
+static POOL: OnceLock<Pool> = OnceLock::new();
+
+thread_local! {
+    static ON_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+struct Pool {
+    jobs: Mutex<mpsc::Sender<Job>>,
+}
+
+impl Pool {
+    fn start(config: RuntimeConfig) -> Pool {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let queue = Arc::new(Mutex::new(queue));
+        for n in 0..config.threads.max(1) {
+            let queue = Arc::clone(&queue);
+            let qos = config.qos.unwrap_or(ThreadQos::Utility);
+            // A worker that fails to start leaves the others; with none, jobs run inline.
+            let _ = std::thread::Builder::new()
+                .name(format!("{}-{n}", config.thread_name_prefix))
+                .spawn(move || {
+                    set_qos(qos);
+                    ON_WORKER.set(true);
+                    loop {
+                        let job = queue.lock().unwrap_or_else(PoisonError::into_inner).recv();
+                        let Ok(job) = job else {
+                            return;
+                        };
+                        let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                    }
+                });
+        }
+        Pool {
+            jobs: Mutex::new(jobs),
+        }
+    }
+}
+
+/// Starts the worker threads behind the `_async` methods. Must be called before the first of
+/// them; fails with `RuntimeAlreadyStarted` afterwards.
+#[uniffi::export]
+pub fn configure_runtime(config: RuntimeConfig) -> Result<(), QueueFileError> {
+    let mut config = Some(config);
+    POOL.get_or_init(|| Pool::start(config.take().unwrap_or_default()));
+    match config {
+        None => Ok(()),
+        Some(_) => Err(QueueFileError::RuntimeAlreadyStarted),
+    }
+}
+
+/// Runs `job` on a worker thread and resolves to its result, or to `LockError` if it panics.
+pub(crate) fn run<T: Send + 'static>(
+    job: impl FnOnce() -> Result<T, QueueFileError> + Send + 'static,
+) -> Completion<Result<T, QueueFileError>> {
+    let shared = Arc::new(Mutex::new(Shared {
+        value: None,
+        waker: None,
+    }));
+    let completion = Completion(Arc::clone(&shared));
+    spawn(move || {
+        let value =
+            panic::catch_unwind(AssertUnwindSafe(job)).unwrap_or(Err(QueueFileError::LockError));
+        let mut shared = shared.lock().unwrap_or_else(PoisonError::into_inner);
+        shared.value = Some(value);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    });
+    completion
+}
+
+/// Hands `job` to a worker thread without waiting for it.
+pub(crate) fn spawn(job: impl FnOnce() + Send + 'static) {
+    let pool = POOL.get_or_init(|| Pool::start(RuntimeConfig::default()));
+    let sent = pool
+        .jobs
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .send(Box::new(job));
+    if let Err(mpsc::SendError(job)) = sent {
+        job();
+    }
+}
+
+/// Whether the calling thread is one of the workers.
+pub(crate) fn on_worker() -> bool {
+    ON_WORKER.get()
+}
+
+struct Shared<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+pub(crate) struct Completion<T>(Arc<Mutex<Shared<T>>>);
+
+impl<T> Future for Completion<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match shared.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(target_vendor = "apple")]
+fn set_qos(qos: ThreadQos) {
+    unsafe extern "C" {
+        fn pthread_set_qos_class_self_np(qos_class: u32, relative_priority: i32) -> i32;
+    }
+    let class = match qos {
+        ThreadQos::UserInitiated => 0x19,
+        ThreadQos::Default => 0x15,
+        ThreadQos::Utility => 0x11,
+        ThreadQos::Background => 0x09,
+    };
+    // SAFETY: only changes the scheduling class of the calling thread.
+    unsafe { pthread_set_qos_class_self_np(class, 0) };
+}
+
+#[cfg(not(target_vendor = "apple"))]
+fn set_qos(_qos: ThreadQos) {}
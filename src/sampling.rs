@@ -0,0 +1,42 @@
+use std::collections::BTreeSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// SplitMix64: small, fast and good enough for picking diagnostic samples.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    /// Seeds from `seed`, or from the clock when none is given.
+    pub(crate) fn new(seed: Option<u64>) -> Self {
+        Rng(seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+        }))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed value in `0..bound`; `bound` must not be 0.
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        ((u128::from(self.next()) * u128::from(bound)) >> 64) as u64
+    }
+
+    /// `n` distinct indices drawn uniformly from `0..len` (all of them if `n >= len`), in
+    /// ascending order. Uses Floyd's algorithm, so it costs `O(n)` whatever `len` is.
+    pub(crate) fn distinct(&mut self, n: u64, len: u64) -> BTreeSet<u64> {
+        let mut picked = BTreeSet::new();
+        for j in len - n.min(len)..len {
+            let candidate = self.below(j + 1);
+            if !picked.insert(candidate) {
+                picked.insert(j);
+            }
+        }
+        picked
+    }
+}
@@ -0,0 +1,564 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use queue_file::QueueFile as RustQueueFile;
+
+use crate::QueueFileError;
+use crate::retention::{DroppedSegment, RetentionEvent, RetentionListener, RetentionReason};
+
+const PREFIX: &str = "segment-";
+const SUFFIX: &str = ".qf";
+/// Initial length of a new segment file; it grows by doubling up to about `segment_bytes`.
+const SEGMENT_CAPACITY: u64 = 4096;
+/// Counted in `used_bytes`, but not towards `segment_bytes`.
+const HEADER_LEN: u64 = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct SegmentedQueueConfig {
+    /// Bytes of elements, with their 4-byte frames, after which adds go to a new segment. One
+    /// add is never split, so a segment holding a single large add can be bigger.
+    #[uniffi(default = 1048576)]
+    pub segment_bytes: u64,
+    /// Oldest segments are dropped while all segments together hold more than this many bytes.
+    #[uniffi(default = None)]
+    pub max_total_bytes: Option<u64>,
+    /// Segments last written longer ago than this are dropped.
+    #[uniffi(default = None)]
+    pub max_segment_age: Option<Duration>,
+    /// Oldest segments are dropped while there are more than this many.
+    #[uniffi(default = None)]
+    pub max_segments: Option<u32>,
+}
+
+impl Default for SegmentedQueueConfig {
+    fn default() -> Self {
+        SegmentedQueueConfig {
+            segment_bytes: 1 << 20,
+            max_total_bytes: None,
+            max_segment_age: None,
+            max_segments: None,
+        }
+    }
+}
+
+/// One segment file. Without per-element timestamps, the time range a segment covers is
+/// bounded by when its file was created and last written.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct SegmentInfo {
+    pub id: u64,
+    /// Elements not yet removed.
+    pub elements: u64,
+    /// Bytes of those elements, with their 4-byte frames.
+    pub bytes: u64,
+    /// `None` where the file system does not record it.
+    pub created: Option<SystemTime>,
+    /// When the last add to the segment was made.
+    pub modified: SystemTime,
+}
+
+struct Segment {
+    id: u64,
+    path: PathBuf,
+    len: usize,
+    /// Bytes of the elements, with their frames.
+    bytes: u64,
+    modified: SystemTime,
+    /// Only the head and the tail are kept open; the segments between them are waiting to be
+    /// read and are opened when they become the head.
+    queue: Option<RustQueueFile>,
+}
+
+impl Segment {
+    fn queue(&mut self) -> Result<&mut RustQueueFile, QueueFileError> {
+        match &mut self.queue {
+            Some(queue) => Ok(queue),
+            queue => Ok(queue.insert(
+                RustQueueFile::with_capacity(&self.path, SEGMENT_CAPACITY)
+                    .map_err(|e| QueueFileError::from(e).with_path(&self.path))?,
+            )),
+        }
+    }
+
+    /// Runs `op` on the segment's handle, or on a temporary one if it is waiting, so reads leave
+    /// waiting segments closed.
+    fn read<T>(&mut self, op: impl FnOnce(&mut RustQueueFile) -> T) -> Result<T, QueueFileError> {
+        match &mut self.queue {
+            Some(queue) => Ok(op(queue)),
+            None => {
+                let mut queue = RustQueueFile::with_capacity(&self.path, SEGMENT_CAPACITY)
+                    .map_err(|e| QueueFileError::from(e).with_path(&self.path))?;
+                Ok(op(&mut queue))
+            }
+        }
+    }
+
+    fn info(&self) -> SegmentInfo {
+        let metadata = fs::metadata(&self.path).ok();
+        SegmentInfo {
+            id: self.id,
+            elements: self.len as u64,
+            bytes: self.bytes,
+            created: metadata.and_then(|metadata| metadata.created().ok()),
+            modified: self.modified,
+        }
+    }
+
+    fn sync_counts(&mut self) -> Result<(), QueueFileError> {
+        let queue = self.queue()?;
+        (self.len, self.bytes) = (queue.size(), queue.used_bytes() - HEADER_LEN);
+        Ok(())
+    }
+}
+
+struct Segments {
+    dir: PathBuf,
+    config: SegmentedQueueConfig,
+    /// Oldest first; never empty. Adds go to the last segment and reads come from the first.
+    segments: VecDeque<Segment>,
+    listener: Option<Arc<dyn RetentionListener>>,
+}
+
+impl Segments {
+    fn open(dir: PathBuf, config: SegmentedQueueConfig) -> Result<Self, QueueFileError> {
+        fs::create_dir_all(&dir).map_err(|e| QueueFileError::from(e).with_path(&dir))?;
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(&dir).map_err(|e| QueueFileError::from(e).with_path(&dir))? {
+            let name = entry?.file_name();
+            let id = name.to_str().and_then(|name| {
+                name.strip_prefix(PREFIX)?
+                    .strip_suffix(SUFFIX)?
+                    .parse::<u64>()
+                    .ok()
+            });
+            ids.extend(id);
+        }
+        ids.sort_unstable();
+
+        let mut segments = VecDeque::new();
+        for id in ids {
+            let path = segment_path(&dir, id);
+            let modified = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .map_err(|e| QueueFileError::from(e).with_path(&path))?;
+            let mut segment = Segment {
+                id,
+                path,
+                len: 0,
+                bytes: 0,
+                modified,
+                queue: None,
+            };
+            segment.sync_counts()?;
+            segments.push_back(segment);
+        }
+        let mut state = Segments {
+            dir,
+            config,
+            segments,
+            listener: None,
+        };
+        if state.segments.is_empty() {
+            state.roll()?;
+        }
+        // Segments emptied just before a crash were not deleted yet.
+        state.drop_consumed()?;
+        for segment in state.segments.range_mut(1..).rev().skip(1) {
+            segment.queue = None;
+        }
+        Ok(state)
+    }
+
+    fn head(&mut self) -> &mut Segment {
+        self.segments
+            .front_mut()
+            .expect("there is always a segment")
+    }
+
+    fn tail(&mut self) -> &mut Segment {
+        self.segments.back_mut().expect("there is always a segment")
+    }
+
+    /// Starts a new, empty tail segment.
+    fn roll(&mut self) -> Result<(), QueueFileError> {
+        let id = self.segments.back().map_or(0, |segment| segment.id + 1);
+        let mut segment = Segment {
+            id,
+            path: segment_path(&self.dir, id),
+            len: 0,
+            bytes: 0,
+            modified: SystemTime::now(),
+            queue: None,
+        };
+        segment.queue()?;
+        if self.segments.len() > 1 {
+            // The old tail now waits between the head and the new tail.
+            self.tail().queue = None;
+        }
+        self.segments.push_back(segment);
+        Ok(())
+    }
+
+    /// Deletes fully read segments from the front, always keeping the tail.
+    fn drop_consumed(&mut self) -> Result<(), QueueFileError> {
+        while self.segments.len() > 1 && self.head().len == 0 {
+            let segment = self.segments.pop_front().expect("checked above");
+            drop(segment.queue);
+            fs::remove_file(&segment.path)
+                .map_err(|e| QueueFileError::from(e).with_path(&segment.path))?;
+        }
+        Ok(())
+    }
+
+    fn add_all(&mut self, items: &[Vec<u8>]) -> Result<(), QueueFileError> {
+        let needed: u64 = items.iter().map(|item| item.len() as u64 + 4).sum();
+        let tail = self.tail();
+        if tail.len > 0 && tail.bytes + needed > self.config.segment_bytes {
+            self.roll()?;
+        }
+        let tail = self.tail();
+        let path = tail.path.clone();
+        tail.queue()?
+            .add_n(items)
+            .map_err(|e| QueueFileError::from(e).with_path(&path))?;
+        tail.modified = SystemTime::now();
+        tail.sync_counts()
+    }
+
+    /// Drops the oldest segments that break a retention limit. The tail, which adds go to, is
+    /// always kept.
+    fn enforce_retention(&mut self) -> Result<Vec<DroppedSegment>, QueueFileError> {
+        let now = SystemTime::now();
+        let max_age = self.config.max_segment_age;
+        let mut total: u64 = self.segments.iter().map(|segment| segment.bytes).sum();
+        let mut dropped = Vec::new();
+        while self.segments.len() > 1 {
+            let head = &self.segments[0];
+            let reason = if self
+                .config
+                .max_segments
+                .is_some_and(|max| self.segments.len() > max.max(1) as usize)
+            {
+                RetentionReason::SegmentCount
+            } else if self.config.max_total_bytes.is_some_and(|max| total > max) {
+                RetentionReason::TotalBytes
+            } else if max_age.is_some_and(|age| {
+                now.duration_since(head.modified)
+                    .is_ok_and(|elapsed| elapsed > age)
+            }) {
+                RetentionReason::SegmentAge
+            } else {
+                break;
+            };
+            let segment = self.segments.pop_front().expect("checked above");
+            total -= segment.bytes;
+            drop(segment.queue);
+            fs::remove_file(&segment.path)
+                .map_err(|e| QueueFileError::from(e).with_path(&segment.path))?;
+            if segment.len > 0 {
+                dropped.push(DroppedSegment {
+                    id: segment.id,
+                    elements: segment.len as u64,
+                    bytes: segment.bytes,
+                    reason,
+                });
+            }
+        }
+        Ok(dropped)
+    }
+
+    /// Pairs drops with the listener, for delivery once the lock is released.
+    fn event(&self, dropped: Vec<DroppedSegment>) -> Option<RetentionEvent> {
+        match dropped.is_empty() {
+            true => None,
+            false => Some(RetentionEvent {
+                listener: self.listener.clone()?,
+                dropped,
+            }),
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<Vec<u8>>, QueueFileError> {
+        let head = self.head();
+        let path = head.path.clone();
+        let element = head
+            .queue()?
+            .peek()
+            .map_err(|e| QueueFileError::from(e).with_path(&path))?;
+        Ok(element.map(|element| element.to_vec()))
+    }
+
+    fn remove_n(&mut self, mut n: usize) -> Result<(), QueueFileError> {
+        while n > 0 && self.size() > 0 {
+            let head = self.head();
+            let count = n.min(head.len);
+            let path = head.path.clone();
+            let queue = head.queue()?;
+            queue
+                .remove_n(count)
+                .map_err(|e| QueueFileError::from(e).with_path(&path))?;
+            head.sync_counts()?;
+            n -= count;
+            self.drop_consumed()?;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<(), QueueFileError> {
+        let tail = self.segments.len() - 1;
+        for segment in self.segments.range_mut(..tail) {
+            segment.len = 0;
+        }
+        self.drop_consumed()?;
+        let head = self.head();
+        let path = head.path.clone();
+        head.queue()?
+            .clear()
+            .map_err(|e| QueueFileError::from(e).with_path(&path))?;
+        head.sync_counts()
+    }
+
+    fn get_all(&mut self) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        let mut elements = Vec::with_capacity(self.size());
+        for segment in &mut self.segments {
+            segment.read(|queue| elements.extend(queue.iter().map(|element| element.to_vec())))?;
+        }
+        Ok(elements)
+    }
+
+    fn segment(&mut self, id: u64) -> Result<&mut Segment, QueueFileError> {
+        let dir = &self.dir;
+        self.segments
+            .iter_mut()
+            .find(|segment| segment.id == id)
+            .ok_or_else(|| {
+                let path = segment_path(dir, id);
+                QueueFileError::from(io::Error::from(io::ErrorKind::NotFound)).with_path(&path)
+            })
+    }
+
+    fn sync_all(&mut self) -> Result<(), QueueFileError> {
+        for segment in &mut self.segments {
+            if let Some(queue) = &mut segment.queue {
+                queue
+                    .sync_all()
+                    .map_err(|e| QueueFileError::from(e).with_path(&segment.path))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> usize {
+        self.segments.iter().map(|segment| segment.len).sum()
+    }
+}
+
+fn segment_path(dir: &Path, id: u64) -> PathBuf {
+    dir.join(format!("{PREFIX}{id:010}{SUFFIX}"))
+}
+
+/// A queue spread over a directory of fixed-size segment files, like a commit log. Adds append
+/// to the newest segment and start a new one once it is full; a segment is deleted as soon as
+/// its last element is removed, so space is given back without compacting anything.
+///
+/// Every segment is an ordinary queue file, readable with `qf-inspect`.
+#[derive(uniffi::Object)]
+pub struct SegmentedQueue {
+    inner: Arc<Mutex<Segments>>,
+}
+
+impl SegmentedQueue {
+    fn lock(&self) -> Result<MutexGuard<'_, Segments>, QueueFileError> {
+        self.inner.lock().map_err(|_| QueueFileError::LockError)
+    }
+
+    fn add_all(&self, items: &[Vec<u8>]) -> Result<(), QueueFileError> {
+        let mut segments = self.lock()?;
+        segments.add_all(items)?;
+        Self::retain(segments).map(drop)
+    }
+
+    /// Applies the retention limits, then reports the drops once the lock has been released.
+    fn retain(
+        mut segments: MutexGuard<'_, Segments>,
+    ) -> Result<Vec<DroppedSegment>, QueueFileError> {
+        let dropped = segments.enforce_retention()?;
+        let event = segments.event(dropped.clone());
+        drop(segments);
+        if let Some(event) = event {
+            event.dispatch();
+        }
+        Ok(dropped)
+    }
+}
+
+#[uniffi::export]
+impl SegmentedQueue {
+    /// Opens the segments in `dir`, creating the directory and a first segment if needed.
+    #[uniffi::constructor]
+    pub fn open(dir: String) -> Result<Self, QueueFileError> {
+        Self::open_with_config(dir, SegmentedQueueConfig::default())
+    }
+
+    #[uniffi::constructor]
+    pub fn open_with_config(
+        dir: String,
+        config: SegmentedQueueConfig,
+    ) -> Result<Self, QueueFileError> {
+        Ok(SegmentedQueue {
+            inner: Arc::new(Mutex::new(Segments::open(PathBuf::from(dir), config)?)),
+        })
+    }
+
+    #[uniffi::method]
+    pub fn add(&self, data: Vec<u8>) -> Result<(), QueueFileError> {
+        self.add_all(&[data])
+    }
+
+    /// Adds all items to the same segment, so they become visible together.
+    #[uniffi::method]
+    pub fn add_multiple(&self, items: Vec<Vec<u8>>) -> Result<(), QueueFileError> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        self.add_all(&items)
+    }
+
+    /// Called with the segments each retention pass drops; replaces any previous listener.
+    #[uniffi::method]
+    pub fn set_retention_listener(
+        &self,
+        listener: Arc<dyn RetentionListener>,
+    ) -> Result<(), QueueFileError> {
+        self.lock()?.listener = Some(listener);
+        Ok(())
+    }
+
+    #[uniffi::method]
+    pub fn clear_retention_listener(&self) -> Result<(), QueueFileError> {
+        self.lock()?.listener = None;
+        Ok(())
+    }
+
+    /// Applies the retention limits now. They are also applied after every add, but a queue
+    /// that receives no adds only ages out segments when this is called.
+    #[uniffi::method]
+    pub fn enforce_retention(&self) -> Result<Vec<DroppedSegment>, QueueFileError> {
+        Self::retain(self.lock()?)
+    }
+
+    #[uniffi::method]
+    pub fn peek(&self) -> Result<Option<Vec<u8>>, QueueFileError> {
+        self.lock()?.peek()
+    }
+
+    #[uniffi::method]
+    pub fn remove(&self) -> Result<(), QueueFileError> {
+        self.lock()?.remove_n(1)
+    }
+
+    #[uniffi::method]
+    pub fn remove_n(&self, n: u32) -> Result<(), QueueFileError> {
+        self.lock()?.remove_n(n as usize)
+    }
+
+    /// Deletes every segment but one, which is emptied.
+    #[uniffi::method]
+    pub fn clear(&self) -> Result<(), QueueFileError> {
+        self.lock()?.clear()
+    }
+
+    #[uniffi::method]
+    pub fn size(&self) -> Result<u32, QueueFileError> {
+        Ok(self.lock()?.size() as u32)
+    }
+
+    #[uniffi::method]
+    pub fn is_empty(&self) -> Result<bool, QueueFileError> {
+        Ok(self.lock()?.size() == 0)
+    }
+
+    #[uniffi::method]
+    pub fn get_all(&self) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        self.lock()?.get_all()
+    }
+
+    #[uniffi::method]
+    pub fn segment_count(&self) -> Result<u32, QueueFileError> {
+        Ok(self.lock()?.segments.len() as u32)
+    }
+
+    /// The segments, oldest first.
+    #[uniffi::method]
+    pub fn segments(&self) -> Result<Vec<SegmentInfo>, QueueFileError> {
+        Ok(self.lock()?.segments.iter().map(Segment::info).collect())
+    }
+
+    /// A reader over one segment, for inspecting or exporting it without removing anything.
+    #[uniffi::method]
+    pub fn open_segment_reader(&self, id: u64) -> Result<Arc<SegmentReader>, QueueFileError> {
+        self.lock()?.segment(id)?;
+        Ok(Arc::new(SegmentReader {
+            segments: self.inner.clone(),
+            id,
+        }))
+    }
+
+    #[uniffi::method]
+    pub fn sync_all(&self) -> Result<(), QueueFileError> {
+        self.lock()?.sync_all()
+    }
+}
+
+/// Reads the elements of one segment of a `SegmentedQueue`. Each call sees the segment as it is
+/// at that moment: removals from the head segment shift the positions of what is left, and a
+/// segment that has been consumed or dropped fails with a `NotFound` I/O error.
+#[derive(uniffi::Object)]
+pub struct SegmentReader {
+    segments: Arc<Mutex<Segments>>,
+    id: u64,
+}
+
+impl SegmentReader {
+    fn with_segment<T>(
+        &self,
+        op: impl FnOnce(&mut Segment) -> Result<T, QueueFileError>,
+    ) -> Result<T, QueueFileError> {
+        let mut segments = self
+            .segments
+            .lock()
+            .map_err(|_| QueueFileError::LockError)?;
+        op(segments.segment(self.id)?)
+    }
+}
+
+#[uniffi::export]
+impl SegmentReader {
+    #[uniffi::method]
+    pub fn info(&self) -> Result<SegmentInfo, QueueFileError> {
+        self.with_segment(|segment| Ok(segment.info()))
+    }
+
+    /// Up to `limit` elements starting at position `offset` within the segment.
+    #[uniffi::method]
+    pub fn read(&self, offset: u32, limit: u32) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        self.with_segment(|segment| {
+            segment.read(|queue| {
+                queue
+                    .iter()
+                    .skip(offset as usize)
+                    .take(limit as usize)
+                    .map(|element| element.to_vec())
+                    .collect()
+            })
+        })
+    }
+
+    #[uniffi::method]
+    pub fn read_all(&self) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        self.read(0, u32::MAX)
+    }
+}
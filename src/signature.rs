@@ -0,0 +1,134 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use sha2::{Digest, Sha256};
+
+use crate::QueueFileError;
+use crate::read_limit::FrameReader;
+
+const HEADER_LEN: usize = 32;
+const BLOCK_LEN: usize = 64;
+const MAGIC: &[u8; 4] = b"QFSG";
+/// Magic and the HMAC-SHA256 of the header and content digest.
+const SIGNATURE_LEN: usize = 4 + 32;
+
+/// Supplies the keys that `signature_key` names, typically from the keychain.
+#[uniffi::export(with_foreign)]
+pub trait KeyProvider: Send + Sync {
+    /// The secret for `key_id`, or `None` if there is none.
+    fn key(&self, key_id: String) -> Option<Vec<u8>>;
+}
+
+static PROVIDER: Mutex<Option<Arc<dyn KeyProvider>>> = Mutex::new(None);
+
+/// Sets the provider asked for the key of every queue opened with `signature_key` from now on.
+#[uniffi::export]
+pub fn set_key_provider(provider: Arc<dyn KeyProvider>) {
+    *PROVIDER.lock().unwrap_or_else(PoisonError::into_inner) = Some(provider);
+}
+
+pub(crate) fn signature_path(path: &Path) -> PathBuf {
+    let mut signature = path.to_path_buf().into_os_string();
+    signature.push(".sig");
+    PathBuf::from(signature)
+}
+
+/// Asks the provider for `key_id`, failing with `KeyUnavailable` without a provider or key.
+pub(crate) fn key(key_id: &str) -> Result<Vec<u8>, QueueFileError> {
+    let provider = PROVIDER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    provider
+        .and_then(|provider| provider.key(key_id.to_string()))
+        .ok_or_else(|| QueueFileError::KeyUnavailable {
+            key_id: key_id.to_string(),
+        })
+}
+
+/// Signs the file as it is now, replacing the signature through a rename so a crash leaves the
+/// previous one intact.
+pub(crate) fn record(path: &Path, key: &[u8]) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(SIGNATURE_LEN);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&mac(path, key)?);
+
+    let signature = signature_path(path);
+    let mut staging = signature.clone().into_os_string();
+    staging.push(".tmp");
+    let staging = PathBuf::from(staging);
+    let mut file = File::create(&staging)?;
+    file.write_all(&bytes)?;
+    file.sync_all()?;
+    fs::rename(&staging, &signature)
+}
+
+/// Whether the file is the one last signed with `key`. A missing or malformed signature does
+/// not match.
+pub(crate) fn matches(path: &Path, key: &[u8]) -> io::Result<bool> {
+    let bytes = match fs::read(signature_path(path)) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    if bytes.len() != SIGNATURE_LEN || &bytes[..4] != MAGIC {
+        return Ok(false);
+    }
+    let expected = mac(path, key)?;
+    // Compares every byte, so the time taken does not reveal where they differ.
+    let diff = expected
+        .iter()
+        .zip(&bytes[4..])
+        .fold(0, |diff, (a, b)| diff | (a ^ b));
+    Ok(diff == 0)
+}
+
+/// HMAC-SHA256 over the header and the SHA-256 of every element's length and payload, in
+/// order.
+fn mac(path: &Path, key: &[u8]) -> io::Result<[u8; 32]> {
+    let mut header = [0; HEADER_LEN];
+    File::open(path)?.read_exact(&mut header)?;
+    let mut content = Sha256::new();
+    let mut reader = FrameReader::open(path)?;
+    while let Some(frame) = reader.next_frame()? {
+        content.update((frame.len as u32).to_be_bytes());
+        reader.copy(frame, &mut DigestWriter(&mut content))?;
+    }
+    let mut message = header.to_vec();
+    message.extend_from_slice(&content.finalize());
+    Ok(hmac(key, &message))
+}
+
+/// HMAC as in RFC 2104, with SHA-256.
+fn hmac(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0; BLOCK_LEN];
+    match key.len() > BLOCK_LEN {
+        true => block[..32].copy_from_slice(&Sha256::digest(key)),
+        false => block[..key.len()].copy_from_slice(key),
+    }
+    let inner = Sha256::new()
+        .chain_update(block.map(|b| b ^ 0x36))
+        .chain_update(message)
+        .finalize();
+    Sha256::new()
+        .chain_update(block.map(|b| b ^ 0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// Feeds what `FrameReader::copy` writes into a digest.
+struct DigestWriter<'a>(&'a mut Sha256);
+
+impl Write for DigestWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
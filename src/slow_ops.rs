@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The queue operations whose duration is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum QueueOperation {
+    /// An add or `add_multiple`; bytes are the payload added.
+    Add,
+    /// A peek; bytes are the payload read.
+    Peek,
+    /// `remove` or `remove_n`; bytes are the space freed, frames included.
+    Remove,
+    /// `clear`; bytes are the space freed, frames included.
+    Clear,
+    /// `sync_all` or a group commit; bytes are those written since the previous sync.
+    Sync,
+    /// `remove_at` or `update_at` rewriting the file; bytes are the size of the new copy.
+    Rewrite,
+}
+
+#[uniffi::export(with_foreign)]
+pub trait SlowOpListener: Send + Sync {
+    fn on_slow_operation(&self, operation: QueueOperation, duration: Duration, bytes: u64);
+}
+
+pub(crate) struct SlowOps {
+    threshold: Duration,
+    listener: Arc<dyn SlowOpListener>,
+}
+
+/// An operation that outlasted the threshold, delivered once the queue lock has been released
+/// so the listener may call back into the queue.
+pub(crate) struct SlowOpEvent {
+    listener: Arc<dyn SlowOpListener>,
+    operation: QueueOperation,
+    duration: Duration,
+    bytes: u64,
+}
+
+impl SlowOpEvent {
+    pub(crate) fn dispatch(self) {
+        self.listener
+            .on_slow_operation(self.operation, self.duration, self.bytes);
+    }
+}
+
+impl SlowOps {
+    pub(crate) fn new(threshold: Duration, listener: Arc<dyn SlowOpListener>) -> Self {
+        SlowOps {
+            threshold,
+            listener,
+        }
+    }
+
+    pub(crate) fn check(
+        &self,
+        operation: QueueOperation,
+        duration: Duration,
+        bytes: u64,
+    ) -> Option<SlowOpEvent> {
+        (duration > self.threshold).then(|| SlowOpEvent {
+            listener: self.listener.clone(),
+            operation,
+            duration,
+            bytes,
+        })
+    }
+}
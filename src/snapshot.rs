@@ -0,0 +1,229 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::dictionary::Codec;
+use crate::verify::Header;
+use crate::{QueueFileError, fragmentation};
+
+/// The elements a queue held when `snapshot` was called, read in order from the eldest while
+/// the queue keeps changing. Elements stay in the file until the queue is about to overwrite or
+/// move them; only then are the ones not yet read copied into the snapshot.
+#[derive(uniffi::Object)]
+pub struct QueueSnapshot {
+    path: PathBuf,
+    size: u32,
+    generation: u64,
+    view: Arc<Mutex<View>>,
+    codec: Codec,
+}
+
+/// What a snapshot has left to return: elements copied out of the file, then the rest of the
+/// ones still in it.
+struct View {
+    copied: VecDeque<Vec<u8>>,
+    /// Dropped once no element is left in the file.
+    file: Option<File>,
+    header_len: u64,
+    file_len: u64,
+    /// Position of the next frame in the file.
+    pos: u64,
+    in_file: u64,
+    /// Number of elements removed from the queue before the next one in the file.
+    seq: u64,
+    /// A failed copy, reported in place of the elements it lost.
+    error: Option<io::Error>,
+}
+
+impl View {
+    fn wrap(&self, pos: u64) -> u64 {
+        match pos >= self.file_len {
+            true => self.header_len + pos - self.file_len,
+            false => pos,
+        }
+    }
+
+    fn read_file(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let (header_len, file_len, pos) = (self.header_len, self.file_len, self.pos);
+        let start = self.wrap(pos + 4);
+        let Some(file) = &mut self.file else {
+            return Ok(None);
+        };
+        let mut len = [0; 4];
+        fragmentation::read_ring(file, pos, &mut len, header_len, file_len)?;
+        let len = u64::from(u32::from_be_bytes(len));
+        if len > file_len - header_len - 4 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("element at offset {pos} has impossible length {len}"),
+            ));
+        }
+        let mut element = vec![0; len as usize];
+        fragmentation::read_ring(file, start, &mut element, header_len, file_len)?;
+        self.pos = self.wrap(pos + 4 + len);
+        self.seq += 1;
+        self.in_file -= 1;
+        if self.in_file == 0 {
+            self.file = None;
+        }
+        Ok(Some(element))
+    }
+
+    /// Copies out the elements still in the file that come before `seq`.
+    fn copy_before(&mut self, seq: u64) {
+        while self.seq < seq && self.file.is_some() {
+            match self.read_file() {
+                Ok(Some(element)) => self.copied.push_back(element),
+                Ok(None) => break,
+                Err(e) => {
+                    self.error = Some(e);
+                    self.file = None;
+                }
+            }
+        }
+    }
+
+    fn next(&mut self) -> io::Result<Option<Vec<u8>>> {
+        if let Some(element) = self.copied.pop_front() {
+            return Ok(Some(element));
+        }
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.read_file()
+    }
+}
+
+/// The snapshots of a queue that still read from its file.
+#[derive(Default)]
+pub(crate) struct Snapshots {
+    views: Vec<Weak<Mutex<View>>>,
+    /// Elements removed from the front of the queue since it was opened.
+    removed: u64,
+}
+
+impl Snapshots {
+    /// Snapshots the committed contents of the file at `path`, a queue at `generation`.
+    pub(crate) fn take(
+        &mut self,
+        path: &Path,
+        generation: u64,
+        codec: Codec,
+    ) -> io::Result<QueueSnapshot> {
+        let mut file = File::open(path)?;
+        let mut bytes = [0; 32];
+        file.read_exact(&mut bytes)?;
+        let header = Header::parse(&bytes);
+        let view = Arc::new(Mutex::new(View {
+            copied: VecDeque::new(),
+            file: (header.count > 0).then_some(file),
+            header_len: header.header_len,
+            file_len: header.file_len,
+            pos: header.first,
+            in_file: header.count,
+            seq: self.removed,
+            error: None,
+        }));
+        self.views.push(Arc::downgrade(&view));
+        Ok(QueueSnapshot {
+            path: path.to_path_buf(),
+            size: header.count as u32,
+            generation,
+            view,
+            codec,
+        })
+    }
+
+    /// Copies what the snapshots have not read of the `n` eldest elements, which are about to
+    /// be removed. Call `removed` once they are.
+    pub(crate) fn removing(&mut self, n: u64) {
+        let seq = self.removed + n;
+        self.each(|view| view.copy_before(seq));
+    }
+
+    pub(crate) fn removed(&mut self, n: u64) {
+        self.removed += n;
+    }
+
+    /// Copies everything the snapshots have not read, before the file is cleared, grown or
+    /// handed to another queue.
+    pub(crate) fn detach(&mut self) {
+        self.each(|view| view.copy_before(u64::MAX));
+    }
+
+    /// Stops tracking the snapshots once a new file has been renamed over the queue's: they
+    /// keep reading the replaced file, which nothing writes to any more.
+    pub(crate) fn release(&mut self) {
+        self.views.clear();
+    }
+
+    fn each(&mut self, mut op: impl FnMut(&mut View)) {
+        self.views.retain(|view| {
+            let Some(view) = view.upgrade() else {
+                return false;
+            };
+            let Ok(mut view) = view.lock() else {
+                return false;
+            };
+            op(&mut view);
+            view.file.is_some()
+        });
+    }
+}
+
+impl QueueSnapshot {
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, View>, QueueFileError> {
+        self.view.lock().map_err(|_| QueueFileError::LockError)
+    }
+
+    /// Tags an I/O error with the file, and decodes what was read.
+    fn deliver(
+        &self,
+        result: io::Result<Option<Vec<u8>>>,
+    ) -> Result<Option<Vec<u8>>, QueueFileError> {
+        let element = result.map_err(|e| QueueFileError::from(e).with_path(&self.path))?;
+        element
+            .map(|element| self.codec.decode(element, None))
+            .transpose()
+    }
+}
+
+#[uniffi::export]
+impl QueueSnapshot {
+    /// Elements in the queue when the snapshot was taken.
+    #[uniffi::method]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    #[uniffi::method]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// The queue's `generation()` when the snapshot was taken.
+    #[uniffi::method]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The next element in order, or `None` after the last one.
+    #[uniffi::method]
+    pub fn next(&self) -> Result<Option<Vec<u8>>, QueueFileError> {
+        let result = self.lock()?.next();
+        self.deliver(result)
+    }
+
+    /// Every element `next` has not returned yet.
+    #[uniffi::method]
+    pub fn read_remaining(&self) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        let mut view = self.lock()?;
+        let mut items = Vec::new();
+        while let Some(element) = self.deliver(view.next())? {
+            items.push(element);
+        }
+        Ok(items)
+    }
+}
@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::Path;
+
+use crate::{FileFormat, GrowthStrategy, QueueFileOptions};
+
+/// Bytes in front of every element: its 32-bit length.
+const FRAME_HEADER_LEN: u64 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct SpaceReport {
+    /// Logical length of the queue file, as returned by `file_len`.
+    pub file_len: u64,
+    /// Bytes holding the header and live elements, as returned by `used_bytes`.
+    pub used_bytes: u64,
+    /// Bytes actually allocated on disk. Smaller than `file_len` for sparse regions and for
+    /// blocks shared with an APFS clone.
+    pub disk_usage: u64,
+}
+
+/// The length a queue file opened with `options` reaches once it holds `element_count` elements
+/// of `avg_element_size` bytes each: their payloads, the length in front of each one and the
+/// file header, rounded up the way the queue grows from `options.capacity`. Passing it as the
+/// capacity creates the file at that length, so none of those adds has to grow it. Payloads a
+/// dictionary compresses take less than estimated.
+#[uniffi::export]
+pub fn estimate_file_len(
+    element_count: u64,
+    avg_element_size: u64,
+    options: QueueFileOptions,
+) -> u64 {
+    let header_len = match options.format {
+        Some(FileFormat::Legacy) => 16,
+        _ => 32,
+    };
+    let needed = avg_element_size
+        .saturating_add(FRAME_HEADER_LEN)
+        .saturating_mul(element_count)
+        .saturating_add(header_len);
+    let strategy = options.growth.unwrap_or(GrowthStrategy::Double);
+    let mut len = options.capacity.max(header_len);
+    while len < needed {
+        let step = match strategy {
+            GrowthStrategy::Double => len,
+            GrowthStrategy::FixedIncrement { bytes } => bytes.max(1),
+            GrowthStrategy::ExactFit => needed - len,
+        };
+        let step = options
+            .max_growth_step
+            .map_or(step, |max| step.min(max.max(1)));
+        if step < len || strategy != GrowthStrategy::Double {
+            // Every step from here on is the same size, so take them all at once.
+            let steps = (needed - len).div_ceil(step);
+            return len.saturating_add(steps.saturating_mul(step));
+        }
+        len = len.saturating_mul(2);
+    }
+    len
+}
+
+/// Bytes allocated for the file on disk, from `st_blocks`.
+#[cfg(unix)]
+pub(crate) fn allocated_bytes(path: &Path) -> std::io::Result<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    Ok(fs::metadata(path)?.blocks() * 512)
+}
+
+/// Falls back to the logical length where the allocated size is not available.
+#[cfg(not(unix))]
+pub(crate) fn allocated_bytes(path: &Path) -> std::io::Result<u64> {
+    Ok(fs::metadata(path)?.len())
+}
+
+/// Bytes available to this process on the volume holding `path`, from `statvfs`.
+#[cfg(all(target_os = "linux", target_pointer_width = "64"))]
+pub(crate) fn available_bytes(path: &Path) -> std::io::Result<u64> {
+    use std::ffi::{CString, c_char, c_int};
+    use std::os::unix::ffi::OsStrExt;
+
+    unsafe extern "C" {
+        fn statvfs(path: *const c_char, buf: *mut u64) -> c_int;
+    }
+
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    // Larger than `struct statvfs`, which starts with `f_bsize`, `f_frsize`, `f_blocks`,
+    // `f_bfree` and `f_bavail`, each 64 bits wide here.
+    let mut buf = [0u64; 32];
+    // SAFETY: `path` is NUL-terminated and `buf` has room for the whole struct.
+    if unsafe { statvfs(path.as_ptr(), buf.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(buf[4].saturating_mul(buf[1]))
+}
+
+/// Bytes available to this process on the volume holding `path`, from `statfs`. Purgeable
+/// space the system would free on demand is not included.
+#[cfg(target_vendor = "apple")]
+pub(crate) fn available_bytes(path: &Path) -> std::io::Result<u64> {
+    use std::ffi::{CString, c_char, c_int};
+    use std::os::unix::ffi::OsStrExt;
+
+    unsafe extern "C" {
+        #[cfg_attr(target_arch = "x86_64", link_name = "statfs$INODE64")]
+        fn statfs(path: *const c_char, buf: *mut u64) -> c_int;
+    }
+
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    // Larger than `struct statfs`, which starts with a 32-bit `f_bsize` and `f_iosize`, then
+    // 64-bit `f_blocks`, `f_bfree` and `f_bavail`.
+    let mut buf = [0u64; 512];
+    // SAFETY: `path` is NUL-terminated and `buf` has room for the whole struct.
+    if unsafe { statfs(path.as_ptr(), buf.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // Apple platforms are little-endian, so `f_bsize` is the low half of the first word.
+    let block_size = buf[0] & u64::from(u32::MAX);
+    Ok(buf[3].saturating_mul(block_size))
+}
+
+#[cfg(not(any(
+    all(target_os = "linux", target_pointer_width = "64"),
+    target_vendor = "apple"
+)))]
+pub(crate) fn available_bytes(_path: &Path) -> std::io::Result<u64> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use queue_file::QueueFile as RustQueueFile;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+
+use crate::QueueFileError;
+use crate::dictionary::Codec;
+
+/// Rows passed to the queue per add, by payload bytes.
+const BATCH_BYTES: usize = 1 << 20;
+
+/// Reads `column` of every row of `table` in the database at `db_path`, in `rowid` order, and
+/// passes the values to `add` in batches. Returns the number of values passed.
+pub(crate) fn import(
+    db_path: &Path,
+    table: &str,
+    column: &str,
+    mut add: impl FnMut(Vec<Vec<u8>>) -> Result<(), QueueFileError>,
+) -> Result<u64, QueueFileError> {
+    let tag = |e: rusqlite::Error| QueueFileError::from(e).with_path(db_path);
+    let db = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(tag)?;
+    let query = format!(
+        "SELECT {} FROM {} ORDER BY rowid",
+        quoted(column),
+        quoted(table)
+    );
+    let mut statement = db.prepare(&query).map_err(tag)?;
+    let mut rows = statement.query([]).map_err(tag)?;
+    let mut batch = Vec::new();
+    let mut batch_bytes = 0;
+    let mut count = 0;
+    while let Some(row) = rows.next().map_err(tag)? {
+        let value = match row.get_ref(0).map_err(tag)? {
+            ValueRef::Null => continue,
+            ValueRef::Blob(bytes) | ValueRef::Text(bytes) => bytes.to_vec(),
+            ValueRef::Integer(value) => value.to_string().into_bytes(),
+            ValueRef::Real(value) => value.to_string().into_bytes(),
+        };
+        batch_bytes += value.len();
+        batch.push(value);
+        count += 1;
+        if batch_bytes >= BATCH_BYTES {
+            add(std::mem::take(&mut batch))?;
+            batch_bytes = 0;
+        }
+    }
+    if !batch.is_empty() {
+        add(batch)?;
+    }
+    Ok(count)
+}
+
+/// Inserts every element of `queue` into `table` of the database at `db_path`, creating both
+/// if needed, in one transaction. Returns the number of elements inserted.
+pub(crate) fn export(
+    queue: &mut RustQueueFile,
+    codec: &Codec,
+    db_path: &Path,
+    table: &str,
+) -> Result<u64, QueueFileError> {
+    let tag = |e: rusqlite::Error| QueueFileError::from(e).with_path(db_path);
+    let mut db = Connection::open(db_path).map_err(tag)?;
+    let transaction = db.transaction().map_err(tag)?;
+    let table = quoted(table);
+    transaction
+        .execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {table} (id INTEGER PRIMARY KEY, data BLOB NOT NULL)"
+            ),
+            [],
+        )
+        .map_err(tag)?;
+    let mut count = 0;
+    {
+        let mut insert = transaction
+            .prepare(&format!("INSERT INTO {table} (data) VALUES (?1)"))
+            .map_err(tag)?;
+        let mut iter = queue.iter();
+        while let Some(element) = iter.borrowed_next() {
+            insert
+                .execute([&*codec.view(element, None)?])
+                .map_err(tag)?;
+            count += 1;
+        }
+    }
+    transaction.commit().map_err(tag)?;
+    Ok(count)
+}
+
+/// `name` as an SQL identifier.
+fn quoted(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
@@ -0,0 +1,1571 @@
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::Instant;
+
+use queue_file::QueueFile as RustQueueFile;
+
+use crate::bloom::{Bloom, Probe};
+use crate::capacity::Growths;
+use crate::dictionary::{self, Codec, TrainedDictionary};
+use crate::fragmentation::Layout;
+use crate::header_slots::{self, HeaderSlots};
+use crate::idle::{IdleClose, QueueHandle, Settings};
+use crate::index::{self, ElementIndex};
+use crate::io_latency::{self, IoCall};
+use crate::journal::{Change, ChangeRecord, Journal};
+use crate::latency::Latencies;
+use crate::lock_file::LockFile;
+use crate::mirror::{self, Mirror, MirrorStatus};
+use crate::quarantine::{self, CorruptionRecovery, QuarantineReport};
+use crate::quota::CapacityInfo;
+use crate::rate_limit::RateLimiter;
+use crate::read_limit::{self, FrameReader};
+use crate::retry::{self, RetryConfig};
+use crate::rewrite::{RewriteOptions, RewriteReport};
+use crate::signature;
+use crate::slow_ops::{QueueOperation, SlowOpEvent, SlowOps};
+use crate::snapshot::{QueueSnapshot, Snapshots};
+use crate::stats::{QueueStats, Window};
+use crate::subscribe::{self, Feed};
+use crate::sync_trailer::{self, SyncCheck, SyncStatus};
+use crate::watermark::{WatermarkEvent, Watermarks};
+use crate::write_buffer::WriteBuffer;
+use crate::{
+    AddReceipt, CorruptionPolicy, FileFormat, GrowthStrategy, OpenMode, PauseMode, QueueFileError,
+    QueueFileOptions,
+};
+use crate::{backup, chunking, dir_sync, exchange, legacy, rewrite, verify};
+
+/// Length of the versioned header queue-file rewrites on every commit.
+const HEADER_LEN: u64 = 32;
+
+/// How `read_decoded` turns an element read from the file into the one it returns.
+type Decode = fn(&Codec, Vec<u8>, Option<u64>) -> Result<Vec<u8>, QueueFileError>;
+
+pub(crate) struct QueueState {
+    pub(crate) queue: QueueHandle,
+    pub(crate) path: PathBuf,
+    pub(crate) options: QueueFileOptions,
+    pub(crate) read_buffer_size: Option<usize>,
+    pub(crate) write_buffer: WriteBuffer,
+    pub(crate) mirror: Option<Mirror>,
+    pub(crate) mirror_status: MirrorStatus,
+    pub(crate) watermarks: Option<Watermarks>,
+    rate_limiter: Option<RateLimiter>,
+    pub(crate) stats: QueueStats,
+    /// Bumped by every operation that changes the elements, including buffered adds.
+    pub(crate) generation: u64,
+    journal: Journal,
+    /// While set, adds, removals and clears fail with `Frozen`.
+    pub(crate) frozen: bool,
+    /// While set, adds wait or are held in the write buffer, which is not flushed.
+    pub(crate) paused: Option<PauseMode>,
+    /// The file compared with its `.sync` trailer when it was opened.
+    pub(crate) sync_check: Option<SyncCheck>,
+    pub(crate) latencies: Latencies,
+    /// The counters `reset_stats` last started from.
+    pub(crate) stats_window: Window,
+    /// Every time an add lengthened the file since it was opened.
+    pub(crate) growths: Growths,
+    pub(crate) slow_ops: Option<SlowOps>,
+    /// Slow operations waiting to be reported once the lock is released.
+    pub(crate) slow_op_events: Vec<SlowOpEvent>,
+    /// `stats.bytes_written` at the last sync.
+    synced_bytes_written: u64,
+    /// When the file was last synced, or opened.
+    synced_at: Instant,
+    /// What `quarantine_corrupt` moved aside when the queue was opened.
+    pub(crate) quarantine: Option<QuarantineReport>,
+    /// What `QuarantineAndStartFresh` moved aside when the queue was opened.
+    pub(crate) corruption_recovery: Option<CorruptionRecovery>,
+    snapshots: Snapshots,
+    /// Every element in the file and the write buffer, when `bloom_filter_elements` is set.
+    bloom: Option<Bloom>,
+    header_slots: Option<HeaderSlots>,
+    /// Where each committed element starts, when `element_index` is set.
+    index: Option<ElementIndex>,
+    /// The sequence id the next committed element gets.
+    next_seq: u64,
+    /// Removals from the front accepted under `deferred_removes` but not yet committed.
+    pub(crate) pending_removes: usize,
+    /// Set by `lazy_open` until `finish_open` has filled the bloom filter and element index.
+    open_pending: bool,
+    /// Subscriptions that have caught up and receive each commit's elements.
+    feeds: Vec<Weak<Feed>>,
+    /// Compresses adds once `train_dictionary` has run, and decodes what is read.
+    pub(crate) codec: Codec,
+    /// The key of `signature_key`, fetched once on open.
+    signing_key: Option<Vec<u8>>,
+    /// When the queue was last used, when `idle_close_after` is set.
+    pub(crate) idle: Option<IdleClose>,
+    /// Declared last so the lock is released only after the queue has been flushed and closed.
+    _lock_file: Option<LockFile>,
+}
+
+impl QueueState {
+    pub(crate) fn open(path: PathBuf, options: QueueFileOptions) -> Result<Self, QueueFileError> {
+        if options.on_corruption != Some(CorruptionPolicy::QuarantineAndStartFresh) {
+            return QueueState::open_as_found(path, options);
+        }
+        // A version from the future is set aside too, since it would fail every open as well.
+        let reason = match QueueState::open_as_found(path.clone(), options.clone()) {
+            Err(e @ QueueFileError::UnsupportedVersion { .. }) => e.to_string(),
+            Err(QueueFileError::CorruptedFile { message, .. }) => message,
+            opened => return opened,
+        };
+        let mirror_path = options.mirror_path.as_deref().map(Path::new);
+        let recovery = quarantine::set_aside(&path, mirror_path, reason)?;
+        // The fresh queue is created even if the mode asked for an existing one.
+        let mode = options.mode;
+        let mut state = QueueState::open_as_found(
+            path,
+            QueueFileOptions {
+                mode: None,
+                ..options
+            },
+        )?;
+        state.options.mode = mode;
+        state.corruption_recovery = Some(recovery);
+        Ok(state)
+    }
+
+    fn open_as_found(path: PathBuf, options: QueueFileOptions) -> Result<Self, QueueFileError> {
+        check_open_mode(&path, &options)?;
+        let lock_file = match options.lock_file {
+            true => Some(LockFile::acquire(&path)?),
+            false => None,
+        };
+        let created = !path.exists();
+        let signing_key = options
+            .signature_key
+            .as_deref()
+            .map(signature::key)
+            .transpose()?;
+        // Before anything below repairs the file, so the checks see it as it was found.
+        if let Some(key) = &signing_key
+            && !created
+            && !signature::matches(&path, key)
+                .map_err(|e| QueueFileError::from(e).with_path(&path))?
+        {
+            return Err(QueueFileError::TamperDetected {
+                path: path.display().to_string(),
+            });
+        }
+        let mirror_exists = options
+            .mirror_path
+            .as_ref()
+            .is_some_and(|mirror_path| Path::new(mirror_path).exists());
+        if created && !mirror_exists && options.format == Some(FileFormat::Legacy) {
+            legacy::create(&path, options.capacity)
+                .map_err(|e| QueueFileError::from(e).with_path(&path))?;
+        }
+        let sync_check = match options.sync_trailer {
+            true => {
+                sync_trailer::check(&path).map_err(|e| QueueFileError::from(e).with_path(&path))?
+            }
+            false => None,
+        };
+        let capacity = options.capacity;
+        let mut stats = QueueStats::default();
+        if options.redundant_header && HeaderSlots::recover(&path)? {
+            stats.headers_restored += 1;
+        }
+        let quarantine = match options.quarantines() {
+            true => {
+                let batch_bytes = options.copy_buffer_size_or(rewrite::BATCH_BYTES);
+                quarantine::recover(&path, capacity, batch_bytes)?
+            }
+            false => None,
+        };
+        let (mut queue, path, mirror, mirror_status) = match &options.mirror_path {
+            Some(mirror_path) => {
+                let pair = retry::run(RetryConfig::new(&options), &mut stats, || {
+                    mirror::open_pair(&path, Path::new(mirror_path), capacity)
+                })?;
+                (pair.queue, pair.path, pair.mirror, pair.status)
+            }
+            None => {
+                let queue = retry::run(RetryConfig::new(&options), &mut stats, || {
+                    Ok(RustQueueFile::with_capacity(&path, capacity)?)
+                })?;
+                (queue, path, None, MirrorStatus::Disabled)
+            }
+        };
+        if options.verify_on_open {
+            verify_file(&path, sync_check)?;
+        } else if options.on_corruption == Some(CorruptionPolicy::QuarantineAndStartFresh) {
+            quarantine::check_chain(&path)?;
+        }
+        let header_slots = match options.redundant_header {
+            true => Some(HeaderSlots::open(&path)?),
+            false => None,
+        };
+        if options.sync_directory {
+            // Sidecars and mirror copies may have been created or renamed into place.
+            if created
+                || options.redundant_header
+                || options.mirror_path.is_some()
+                || quarantine.is_some()
+            {
+                dir_sync::sync_parent(&path)?;
+            }
+            if let Some(mirror_path) = &options.mirror_path {
+                dir_sync::sync_parent(Path::new(mirror_path))?;
+            }
+        }
+
+        let (index, restored) = match options.element_index && !options.lazy_open {
+            true => {
+                let (index, restored) = ElementIndex::open(&path)?;
+                (Some(index), restored)
+            }
+            false => (None, None),
+        };
+        let (generation, next_seq) = restored.map_or((0, queue.size() as u64), |restored| {
+            (restored.generation, restored.next_seq)
+        });
+        let codec = Codec::load(&path)
+            .map_err(|e| QueueFileError::from(e).with_path(&dictionary::dictionary_path(&path)))?;
+        let journal_capacity = options.journal_capacity.unwrap_or(0);
+        let rate_limiter = options.rate_limit.map(RateLimiter::new);
+        let open_pending = options.lazy_open;
+        let read_buffer_size = options.read_buffer_size.map(|size| size as usize);
+        if let Some(size) = read_buffer_size {
+            queue.set_read_buffer_size(size);
+        }
+        let idle = options.idle_close_after.map(IdleClose::new);
+        let mut state = QueueState {
+            queue: queue.into(),
+            path,
+            options,
+            read_buffer_size,
+            write_buffer: WriteBuffer::default(),
+            mirror,
+            mirror_status,
+            watermarks: None,
+            rate_limiter,
+            stats,
+            generation,
+            journal: Journal::new(journal_capacity),
+            frozen: false,
+            paused: None,
+            sync_check,
+            quarantine,
+            corruption_recovery: None,
+            snapshots: Snapshots::default(),
+            latencies: Latencies::default(),
+            stats_window: Window::new(QueueStats::default()),
+            growths: Growths::default(),
+            slow_ops: None,
+            slow_op_events: Vec::new(),
+            synced_bytes_written: 0,
+            synced_at: Instant::now(),
+            bloom: None,
+            header_slots,
+            index,
+            next_seq,
+            pending_removes: 0,
+            open_pending,
+            feeds: Vec::new(),
+            codec,
+            signing_key,
+            idle,
+            _lock_file: lock_file,
+        };
+        if created {
+            state.record_signature()?;
+        }
+        if state.options.exclude_from_backup {
+            state.set_excluded_from_backup(true)?;
+        }
+        if !state.open_pending {
+            state.rebuild_bloom();
+        }
+        Ok(state)
+    }
+
+    /// Runs the frame walks `lazy_open` skipped. Until then adds, removals and clears leave the
+    /// bloom filter and element index alone, since both are built from what is queued by then.
+    pub(crate) fn finish_open(&mut self) -> Result<(), QueueFileError> {
+        if !self.open_pending {
+            return Ok(());
+        }
+        if self.options.element_index {
+            let (index, restored) = ElementIndex::open(&self.path)?;
+            // Once anything has changed, the counters carry on from the open instead.
+            self.index = match restored {
+                Some(restored) if self.generation == 0 => {
+                    self.generation = restored.generation;
+                    self.next_seq = restored.next_seq;
+                    Some(index)
+                }
+                Some(_) => Some(ElementIndex::build(&self.path, Some(self.next_seq))?),
+                None => Some(index),
+            };
+        }
+        self.rebuild_bloom();
+        self.open_pending = false;
+        Ok(())
+    }
+
+    /// The queue file and every file kept alongside it.
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = vec![self.path.clone()];
+        if self.header_slots.is_some() {
+            files.push(HeaderSlots::sidecar_path(&self.path));
+        }
+        if let Some(mirror_path) = &self.options.mirror_path
+            && self.mirror.is_some()
+        {
+            files.push(PathBuf::from(mirror_path));
+        }
+        let trailer = sync_trailer::trailer_path(&self.path);
+        if self.options.sync_trailer && trailer.exists() {
+            files.push(trailer);
+        }
+        let signature = signature::signature_path(&self.path);
+        if self.signing_key.is_some() && signature.exists() {
+            files.push(signature);
+        }
+        if self.codec.is_active() {
+            files.push(dictionary::dictionary_path(&self.path));
+        }
+        let index = index::index_path(&self.path);
+        if self.options.element_index && index.exists() {
+            files.push(index);
+        }
+        let quarantine = quarantine::quarantine_path(&self.path);
+        if self.options.quarantines() && quarantine.exists() {
+            files.push(quarantine);
+        }
+        files
+    }
+
+    pub(crate) fn set_excluded_from_backup(
+        &mut self,
+        excluded: bool,
+    ) -> Result<(), QueueFileError> {
+        for file in self.files() {
+            backup::set_excluded(&file, excluded)
+                .map_err(|e| QueueFileError::from(e).with_path(&file))?;
+        }
+        self.options.exclude_from_backup = excluded;
+        Ok(())
+    }
+
+    /// Fills the bloom filter from the elements in the file and the write buffer.
+    fn rebuild_bloom(&mut self) {
+        let Some(expected) = self.options.bloom_filter_elements else {
+            return;
+        };
+        let Ok(queue) = self.queue.get_mut() else {
+            return;
+        };
+        let mut bloom = Bloom::new(expected);
+        let mut iter = queue.iter();
+        while let Some(element) = iter.borrowed_next() {
+            match self.codec.view(element, None) {
+                Ok(payload) => bloom.insert(Probe::of(&payload)),
+                // Left out, so the filter may answer false for it, as for an unreadable file.
+                Err(_) => continue,
+            }
+        }
+        for item in self.write_buffer.items() {
+            bloom.insert(Probe::of(chunking::unframe(item)));
+        }
+        self.bloom = Some(bloom);
+    }
+
+    fn bloom_insert(&mut self, items: &[Vec<u8>]) {
+        if let Some(bloom) = &mut self.bloom {
+            let payloads = items.iter().map(|item| chunking::unframe(item));
+            payloads.for_each(|payload| bloom.insert(Probe::of(payload)));
+        }
+    }
+
+    /// False means no element, written or buffered, has this payload. Without a bloom filter
+    /// the queue is scanned for an exact answer.
+    pub(crate) fn probably_contains(&mut self, data: &[u8]) -> Result<bool, QueueFileError> {
+        self.finish_open()?;
+        if let Some(bloom) = &self.bloom {
+            return Ok(bloom.contains(Probe::of(data)));
+        }
+        if self.write_buffer.contains(data) {
+            return Ok(true);
+        }
+        let mut iter = self.queue.get_mut()?.iter();
+        let mut skipped = 0;
+        while let Some(element) = iter.borrowed_next() {
+            if skipped < self.pending_removes {
+                skipped += 1;
+            } else if *self.codec.view(element, None)? == *data {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// A snapshot of the elements committed to the file, which the caller flushes first.
+    pub(crate) fn snapshot(&mut self) -> Result<QueueSnapshot, QueueFileError> {
+        Ok(self
+            .snapshots
+            .take(&self.path, self.generation, self.codec.clone())?)
+    }
+
+    /// Counts a change to the elements and journals it.
+    fn changed(&mut self, change: Change) {
+        self.generation += 1;
+        self.journal.record(self.generation, change);
+    }
+
+    pub(crate) fn changes_since(&self, since: u64) -> Result<Vec<ChangeRecord>, QueueFileError> {
+        self.journal.since(self.generation, since)
+    }
+
+    /// Runs `op`, which returns its result and the bytes it moved, records its duration and
+    /// queues an event for the slow-operation listener if it took longer than the threshold.
+    /// Failures are reported too, with 0 bytes.
+    pub(crate) fn timed<T>(
+        &mut self,
+        operation: QueueOperation,
+        op: impl FnOnce(&mut Self) -> Result<(T, u64), QueueFileError>,
+    ) -> Result<T, QueueFileError> {
+        let start = Instant::now();
+        let result = op(self);
+        let duration = start.elapsed();
+        self.latencies.record(operation, duration);
+        self.stats_window.latencies.record(operation, duration);
+        if let Some(slow_ops) = &self.slow_ops {
+            let bytes = result.as_ref().map_or(0, |(_, bytes)| *bytes);
+            let event = slow_ops.check(operation, duration, bytes);
+            self.slow_op_events.extend(event);
+        }
+        result.map(|(value, _)| value)
+    }
+
+    /// Runs `op` under the configured retry policy, counting retries in the stats.
+    pub(crate) fn retrying<T>(
+        &mut self,
+        mut op: impl FnMut(&mut Self) -> Result<T, QueueFileError>,
+    ) -> Result<T, QueueFileError> {
+        let mut stats = std::mem::take(&mut self.stats);
+        let result = retry::run(RetryConfig::new(&self.options), &mut stats, || op(self));
+        self.stats = stats;
+        result
+    }
+
+    /// Discards the in-memory view and reloads it from the last committed header, carrying the
+    /// settings over. Space added by an expansion that was never committed is given back.
+    pub(crate) fn reload(&mut self) -> Result<(), QueueFileError> {
+        self.reopen(self.options.capacity)
+    }
+
+    /// Like `reload`, with `capacity` as the length `clear` truncates the file to. A capacity
+    /// above the current file length grows the file, which is only safe for an unwrapped ring.
+    fn reopen(&mut self, capacity: u64) -> Result<(), QueueFileError> {
+        let mut queue = RustQueueFile::with_capacity(&self.path, capacity)?;
+        queue.set_sync_writes(self.queue.sync_writes());
+        queue.set_overwrite_on_remove(self.queue.overwrite_on_remove());
+        queue.set_cache_offset_policy(self.queue.cache_offset_policy());
+        if let Some(size) = self.read_buffer_size {
+            queue.set_read_buffer_size(size);
+        }
+
+        let file = OpenOptions::new().write(true).open(&self.path)?;
+        if file.metadata()?.len() > queue.file_len() {
+            io_latency::measure(IoCall::Truncate, || file.set_len(queue.file_len()))?;
+            io_latency::measure(IoCall::Sync, || file.sync_all())?;
+        }
+
+        self.queue = queue.into();
+        Ok(())
+    }
+
+    /// Syncs the file and closes it, with the mirror, header sidecar and element index, until
+    /// `wake` reopens them. A queue holding adds back for a pause stays open.
+    pub(crate) fn close_idle(&mut self) -> Result<(), QueueFileError> {
+        if self.queue.is_closed() {
+            return Ok(());
+        }
+        self.flush()?;
+        if !self.write_buffer.is_empty() {
+            return Ok(());
+        }
+        self.sync_all()?;
+        let settings = Settings {
+            size: self.queue.size(),
+            used_bytes: self.queue.used_bytes(),
+            file_len: self.queue.file_len(),
+            sync_writes: self.queue.sync_writes(),
+            overwrite_on_remove: self.queue.overwrite_on_remove(),
+            cache_offset_policy: self.queue.cache_offset_policy(),
+            mirror: self.mirror.take().is_some(),
+            header_slots: self.header_slots.take().is_some(),
+            index: self.index.take().is_some(),
+        };
+        self.queue = QueueHandle::Closed(settings);
+        Ok(())
+    }
+
+    /// Reopens what `close_idle` closed. A file removed in the meantime fails with `NotFound`
+    /// instead of being created again empty.
+    pub(crate) fn wake(&mut self) -> Result<(), QueueFileError> {
+        let QueueHandle::Closed(settings) = self.queue else {
+            return Ok(());
+        };
+        if !self.path.exists() {
+            return Err(io::Error::from(io::ErrorKind::NotFound).into());
+        }
+        let capacity = self.options.capacity;
+        let mut queue = RustQueueFile::with_capacity(&self.path, capacity)?;
+        queue.set_sync_writes(settings.sync_writes);
+        queue.set_overwrite_on_remove(settings.overwrite_on_remove);
+        queue.set_cache_offset_policy(settings.cache_offset_policy);
+        if let Some(size) = self.read_buffer_size {
+            queue.set_read_buffer_size(size);
+        }
+        let header_slots = match settings.header_slots {
+            true => Some(HeaderSlots::open(&self.path)?),
+            false => None,
+        };
+        let index = match settings.index {
+            true => Some(match ElementIndex::open(&self.path)? {
+                (index, Some(restored)) if restored.next_seq == self.next_seq => index,
+                _ => ElementIndex::build(&self.path, Some(self.next_seq))?,
+            }),
+            false => None,
+        };
+        if let (true, Some(mirror_path)) = (settings.mirror, &self.options.mirror_path) {
+            match RustQueueFile::with_capacity(mirror_path, capacity) {
+                Ok(mut mirror) => {
+                    mirror.set_sync_writes(settings.sync_writes);
+                    mirror.set_overwrite_on_remove(settings.overwrite_on_remove);
+                    self.mirror = Some(Mirror { queue: mirror });
+                }
+                Err(_) => self.mirror_status = MirrorStatus::MirrorFailed,
+            }
+        }
+        self.header_slots = header_slots;
+        self.index = index;
+        self.queue = queue.into();
+        Ok(())
+    }
+
+    /// Runs a write through queue-file, timed as a `Write` unless `sync_writes` makes queue-file
+    /// sync within the same call.
+    fn queue_write<T>(
+        &mut self,
+        op: impl FnOnce(&mut RustQueueFile) -> Result<T, queue_file::Error>,
+    ) -> Result<T, QueueFileError> {
+        let sync_writes = self.queue.sync_writes();
+        let queue = self.queue.get_mut()?;
+        Ok(match sync_writes {
+            true => op(queue),
+            false => io_latency::measure(IoCall::Write, || op(queue)),
+        }?)
+    }
+
+    /// Applies a mutation that already succeeded on the primary to the mirror. A mirror that
+    /// fails is dropped rather than failing the operation, since the primary is authoritative.
+    fn apply_to_mirror(
+        &mut self,
+        op: impl FnOnce(&mut RustQueueFile) -> Result<(), queue_file::Error>,
+    ) {
+        let Some(mirror) = &mut self.mirror else {
+            return;
+        };
+        if op(&mut mirror.queue).is_err() {
+            self.mirror = None;
+            self.mirror_status = MirrorStatus::MirrorFailed;
+        }
+    }
+
+    /// Copies a header the primary just committed to the redundant header sidecar. The commit
+    /// has already happened, so a failure here is counted rather than returned.
+    fn record_header(&mut self) {
+        let sync = self.queue.sync_writes();
+        let Some(slots) = &mut self.header_slots else {
+            return;
+        };
+        match slots.record(sync) {
+            Ok(()) => self.stats.bytes_written += header_slots::SLOT_LEN as u64,
+            Err(_) => self.stats.header_copy_failures += 1,
+        }
+    }
+
+    /// Writes a batch, rolling the queue back to its previous committed state if any part of
+    /// the write fails.
+    fn commit_batch(&mut self, items: &[Vec<u8>]) -> Result<(), QueueFileError> {
+        let payload_len: u64 = items.iter().map(|item| item.len() as u64).sum();
+        let codec = self.codec.clone();
+        let (feed_items, items) = (items, codec.encode_all(items)?);
+        let items = &*items;
+        let needed: u64 = items.iter().map(|item| item.len() as u64 + 4).sum();
+        let old_file_len = self.queue.file_len();
+        // Growing a wrapped ring moves the part after the header to the new end of the file.
+        let moved = match self.queue.file_len() - self.queue.used_bytes() < needed {
+            true => Layout::read(&self.path).map_or(0, |layout| layout.wrapped_len()),
+            false => 0,
+        };
+        if self.queue.file_len() - self.queue.used_bytes() < needed {
+            self.snapshots.detach();
+        }
+        let grown = self.retrying(|state| {
+            let grown = state.grow_for(needed)?;
+            if let Err(e) = state.queue_write(|queue| queue.add_n(items)) {
+                state.reload()?;
+                return Err(e);
+            }
+            Ok(grown)
+        })?;
+        // Growing set the length `clear` truncates to; put the configured one back. The add is
+        // committed, so if this fails the handle simply keeps the larger value.
+        if grown {
+            let _ = self.reload();
+        }
+        if self.queue.file_len() > old_file_len {
+            self.growths.record(old_file_len, self.queue.file_len());
+        }
+        if let Some(index) = &mut self.index {
+            let lens = items.iter().map(Vec::len);
+            index.added(lens, self.next_seq, old_file_len, self.queue.file_len());
+        }
+        let first_seq = self.next_seq;
+        self.next_seq += items.len() as u64;
+        self.feeds.retain(|feed| {
+            feed.upgrade()
+                .is_some_and(|feed| feed.push(first_seq, feed_items))
+        });
+        let erase_factor = 1 + u64::from(self.queue.overwrite_on_remove());
+        self.stats.payload_bytes_written += payload_len;
+        self.stats.bytes_written += needed + HEADER_LEN + moved * erase_factor;
+        self.record_header();
+        self.apply_to_mirror(|queue| queue.add_n(items));
+        Ok(())
+    }
+
+    /// Makes room for `needed` more bytes according to the configured growth strategy, ahead
+    /// of queue-file's own doubling. Returns true if the file was grown. The new length is only
+    /// committed by the header of the add that follows.
+    fn grow_for(&mut self, needed: u64) -> Result<bool, QueueFileError> {
+        let strategy = self.options.growth.unwrap_or(GrowthStrategy::Double);
+        let file_len = self.queue.file_len();
+        let mut remaining = file_len - self.queue.used_bytes();
+        if remaining >= needed
+            || (strategy == GrowthStrategy::Double && self.options.max_growth_step.is_none())
+        {
+            return Ok(false);
+        }
+        // Lengthening a wrapped ring without moving its wrapped part would corrupt it; only
+        // queue-file's expansion does that move.
+        if Layout::read(&self.path)?.wrapped() {
+            return Ok(false);
+        }
+        let mut len = file_len;
+        while remaining < needed {
+            let step = match strategy {
+                GrowthStrategy::Double => len,
+                GrowthStrategy::FixedIncrement { bytes } => bytes.max(1),
+                GrowthStrategy::ExactFit => needed - remaining,
+            };
+            let step = self
+                .options
+                .max_growth_step
+                .map_or(step, |max| step.min(max.max(1)));
+            len += step;
+            remaining += step;
+        }
+        self.reopen(len)?;
+        Ok(true)
+    }
+
+    /// Writes out buffered adds, then commits deferred removals.
+    pub(crate) fn flush(&mut self) -> Result<(), QueueFileError> {
+        self.flush_adds()?;
+        self.commit_removes()
+    }
+
+    pub(crate) fn flush_adds(&mut self) -> Result<(), QueueFileError> {
+        if self.write_buffer.is_empty() || self.paused.is_some() {
+            return Ok(());
+        }
+        let items = self.write_buffer.take();
+        if let Err(e) = self.commit_batch(&items) {
+            self.write_buffer.push(items);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Rejects a batch that queue-file would refuse, before anything is written or buffered,
+    /// so a failed `add_multiple` never adds a prefix of its items.
+    fn validate_batch(&self, items: &[Vec<u8>]) -> Result<(), QueueFileError> {
+        if items.iter().any(|item| i32::try_from(item.len()).is_err()) {
+            return Err(QueueFileError::ElementTooBig);
+        }
+        let count = self.queue.size() + self.write_buffer.len() + items.len();
+        if count >= i32::MAX as usize {
+            return Err(QueueFileError::TooManyElements);
+        }
+        if self
+            .options
+            .max_elements
+            .is_some_and(|max| count as u64 > max)
+        {
+            return Err(QueueFileError::QuotaExceeded);
+        }
+        let added: u64 = items.iter().map(|item| item.len() as u64 + 4).sum();
+        if self
+            .options
+            .max_bytes
+            .is_some_and(|max| self.logical_used_bytes() + added > max)
+        {
+            return Err(QueueFileError::QuotaExceeded);
+        }
+        Ok(())
+    }
+
+    /// Charges a valid batch to the rate limit, the last check before it is accepted.
+    fn admit(&mut self, items: &[Vec<u8>]) -> Result<(), QueueFileError> {
+        let Some(limiter) = &mut self.rate_limiter else {
+            return Ok(());
+        };
+        if items.is_empty() {
+            return Ok(());
+        }
+        let bytes = items.iter().map(|item| item.len() as u64).sum();
+        limiter.admit(items.len(), bytes)
+    }
+
+    pub(crate) fn remaining_capacity(&self) -> CapacityInfo {
+        CapacityInfo {
+            elements_remaining: self
+                .options
+                .max_elements
+                .map(|max| max.saturating_sub(self.logical_size())),
+            bytes_remaining: self
+                .options
+                .max_bytes
+                .map(|max| max.saturating_sub(self.logical_used_bytes() + 4)),
+        }
+    }
+
+    /// Returns true if an element with a `len`-byte payload would pass the quota checks.
+    pub(crate) fn can_accept(&self, len: u64) -> bool {
+        let fits_elements = self
+            .options
+            .max_elements
+            .is_none_or(|max| self.logical_size() < max);
+        let fits_bytes = self
+            .options
+            .max_bytes
+            .is_none_or(|max| self.logical_used_bytes() + len + 4 <= max);
+        fits_elements && fits_bytes && i32::try_from(len).is_ok()
+    }
+
+    /// Returns true if the items (or a flushed batch) were written to the file.
+    pub(crate) fn add_all(&mut self, items: Vec<Vec<u8>>) -> Result<bool, QueueFileError> {
+        let bytes = items.iter().map(|item| item.len() as u64).sum();
+        self.timed(QueueOperation::Add, |state| {
+            state.check_not_frozen()?;
+            state.validate_batch(&items)?;
+            state.admit(&items)?;
+            let change = (!items.is_empty()).then_some(Change::Add {
+                count: items.len() as u32,
+                bytes,
+            });
+            if !state.write_buffer.is_enabled() {
+                state.commit_batch(&items)?;
+                state.bloom_insert(&items);
+                if let Some(change) = change {
+                    state.changed(change);
+                }
+                return Ok((true, bytes));
+            }
+            state.bloom_insert(&items);
+            state.write_buffer.push(items);
+            if let Some(change) = change {
+                state.changed(change);
+            }
+            if state.write_buffer.should_flush() {
+                state.flush()?;
+                return Ok((true, bytes));
+            }
+            Ok((false, bytes))
+        })
+    }
+
+    /// The sequence id the next accepted element gets and the file length before it is added,
+    /// taken ahead of an add for its `receipt`.
+    pub(crate) fn receipt_start(&self) -> (u64, u64) {
+        let seq = self.next_seq + self.write_buffer.len() as u64;
+        (seq, self.queue.file_len())
+    }
+
+    pub(crate) fn receipt(&self, (seq, file_len): (u64, u64)) -> AddReceipt {
+        AddReceipt {
+            seq,
+            size: (self.logical_size() - self.pending_removes as u64) as u32,
+            used_bytes: self.logical_used_bytes(),
+            grew: self.queue.file_len() > file_len,
+        }
+    }
+
+    /// Accepts an add made while writes are paused, leaving it in the write buffer until
+    /// `resume_writes`.
+    pub(crate) fn hold(&mut self, items: Vec<Vec<u8>>) -> Result<(), QueueFileError> {
+        self.check_not_frozen()?;
+        self.validate_batch(&items)?;
+        self.admit(&items)?;
+        self.bloom_insert(&items);
+        if !items.is_empty() {
+            let bytes = items.iter().map(|item| item.len() as u64).sum();
+            let count = items.len() as u32;
+            self.changed(Change::Add { count, bytes });
+        }
+        self.write_buffer.push(items);
+        Ok(())
+    }
+
+    /// Writes out buffered adds, then stops adds from reaching the file until `resume_writes`.
+    pub(crate) fn pause_writes(&mut self, mode: PauseMode) -> Result<(), QueueFileError> {
+        self.flush()?;
+        self.paused = Some(mode);
+        Ok(())
+    }
+
+    /// Ends a pause and writes the adds it held. If that write fails they stay buffered.
+    pub(crate) fn resume_writes(&mut self) -> Result<(), QueueFileError> {
+        self.paused = None;
+        self.flush()
+    }
+
+    pub(crate) fn remove_n(&mut self, n: usize) -> Result<(), QueueFileError> {
+        self.timed(QueueOperation::Remove, |state| {
+            state.check_not_frozen()?;
+            if let Some(limit) = state.options.deferred_removes {
+                state.defer_removes(n, limit as usize)?;
+                return Ok(((), 0));
+            }
+            state.flush()?;
+            let used_before = state.queue.used_bytes();
+            let count = state.remove_front(n)?;
+            if count > 0 {
+                state.changed(Change::Remove {
+                    count: count as u32,
+                });
+            }
+            Ok(((), used_before - state.queue.used_bytes()))
+        })
+    }
+
+    /// Sequence id of the eldest element, after the removals not yet committed.
+    pub(crate) fn head_seq(&self) -> u64 {
+        self.next_seq.saturating_sub(self.queue.size() as u64) + self.pending_removes as u64
+    }
+
+    /// Whether an element is left and `seq` is the eldest one's.
+    pub(crate) fn is_front(&self, seq: u64) -> bool {
+        self.logical_size() > self.pending_removes as u64 && self.head_seq() == seq
+    }
+
+    /// The eldest element, after writing out buffered adds. Elements whose removal is deferred
+    /// are skipped rather than committed.
+    pub(crate) fn peek_front(&mut self) -> Result<Option<Vec<u8>>, QueueFileError> {
+        self.flush_adds()?;
+        self.timed(QueueOperation::Peek, |state| {
+            let skip = state.pending_removes;
+            let element = state.retrying(|state| match (skip, state.options.max_read_bytes) {
+                (0, None) => {
+                    let queue = state.queue.get_mut()?;
+                    let element = io_latency::measure(IoCall::Read, || queue.peek())?;
+                    element
+                        .map(|boxed| state.codec.decode(boxed.into_vec(), None))
+                        .transpose()
+                }
+                (_, None) => {
+                    let queue = state.queue.get_mut()?;
+                    let element = io_latency::measure(IoCall::Read, || queue.iter().nth(skip));
+                    element
+                        .map(|boxed| state.codec.decode(boxed.into_vec(), None))
+                        .transpose()
+                }
+                (_, Some(_)) => Ok(state.read_elements(skip, 1)?.pop()),
+            })?;
+            let bytes = element.as_ref().map_or(0, |element| element.len() as u64);
+            Ok((element, bytes))
+        })
+    }
+
+    /// Counts up to `n` of the eldest elements not yet removed as removed, committing them all
+    /// once `limit` are pending.
+    fn defer_removes(&mut self, n: usize, limit: usize) -> Result<(), QueueFileError> {
+        let count = n.min(self.logical_size() as usize - self.pending_removes);
+        if count == 0 {
+            return Ok(());
+        }
+        self.pending_removes += count;
+        self.changed(Change::Remove {
+            count: count as u32,
+        });
+        if self.pending_removes >= limit {
+            self.commit_removes()?;
+        }
+        Ok(())
+    }
+
+    /// Commits the removals `deferred_removes` is holding with a single header write. Those of
+    /// adds still held by a paused write buffer stay pending until the adds are written.
+    pub(crate) fn commit_removes(&mut self) -> Result<(), QueueFileError> {
+        let n = self.pending_removes.min(self.queue.size());
+        if n > 0 {
+            self.remove_front(n)?;
+            self.pending_removes -= n;
+        }
+        Ok(())
+    }
+
+    /// Removes the `n` eldest elements from the file, returning how many there were.
+    fn remove_front(&mut self, n: usize) -> Result<u64, QueueFileError> {
+        let used_before = self.queue.used_bytes();
+        let removed: Vec<Probe> = match self.bloom {
+            Some(_) => {
+                let codec = self.codec.clone();
+                let mut probes = Vec::new();
+                for element in self.queue.get_mut()?.iter().take(n) {
+                    probes.push(Probe::of(&codec.view(&element, None)?));
+                }
+                probes
+            }
+            None => Vec::new(),
+        };
+        let count = n.min(self.queue.size()) as u64;
+        self.snapshots.removing(count);
+        self.retrying(|state| {
+            if let Err(e) = state.queue_write(|queue| queue.remove_n(n)) {
+                state.reload()?;
+                return Err(e);
+            }
+            Ok(())
+        })?;
+        self.snapshots.removed(count);
+        if let Some(index) = &mut self.index {
+            index.removed(count as usize);
+        }
+        if let Some(bloom) = &mut self.bloom {
+            removed.into_iter().for_each(|probe| bloom.remove(probe));
+        }
+        self.stats.bytes_written += HEADER_LEN;
+        if self.queue.overwrite_on_remove() {
+            self.stats.bytes_written += used_before - self.queue.used_bytes();
+        }
+        self.record_header();
+        self.apply_to_mirror(|queue| queue.remove_n(n));
+        Ok(count)
+    }
+
+    pub(crate) fn clear(&mut self) -> Result<(), QueueFileError> {
+        self.timed(QueueOperation::Clear, |state| {
+            state.check_not_frozen()?;
+            let count = state.logical_size() - state.pending_removes as u64;
+            // Receipts already handed out ids for the buffered adds; they are not reused.
+            state.next_seq += state.write_buffer.len() as u64;
+            state.write_buffer.clear();
+            let used_before = state.queue.used_bytes();
+            if let Some(bloom) = &mut state.bloom {
+                bloom.clear();
+            }
+            let retained = state
+                .options
+                .min_retained_len
+                .map_or(0, |floor| floor.min(state.queue.file_len()));
+            state.snapshots.detach();
+            state.retrying(|state| {
+                if retained > state.options.capacity {
+                    state.reopen(retained)?;
+                }
+                if let Err(e) = state.queue.get_mut()?.clear() {
+                    state.reload()?;
+                    return Err(e.into());
+                }
+                Ok(())
+            })?;
+            state.pending_removes = 0;
+            if let Some(index) = &mut state.index {
+                index.clear();
+            }
+            if count > 0 {
+                state.changed(Change::Clear {
+                    count: count as u32,
+                });
+            }
+            state.stats.bytes_written += HEADER_LEN;
+            if state.queue.overwrite_on_remove() {
+                state.stats.bytes_written += retained.max(state.options.capacity) - HEADER_LEN;
+            }
+            state.record_header();
+            state.apply_to_mirror(|queue| queue.clear());
+            Ok(((), used_before.saturating_sub(state.queue.used_bytes())))
+        })
+    }
+
+    /// Removes the element at `index`, counting from the eldest, by rewriting the file.
+    pub(crate) fn remove_at(&mut self, index: u32) -> Result<(), QueueFileError> {
+        self.check_not_frozen()?;
+        self.flush()?;
+        self.check_index(index)?;
+        self.rewrite(Change::RemoveAt { index }, |i, element| {
+            Ok((i != index as usize).then(|| element.to_vec()))
+        })
+    }
+
+    /// Replaces the payload of the element at `index` by rewriting the file, so the element
+    /// keeps its position whether the new payload is larger or smaller.
+    pub(crate) fn update_at(&mut self, index: u32, data: Vec<u8>) -> Result<(), QueueFileError> {
+        self.check_not_frozen()?;
+        self.flush()?;
+        self.check_index(index)?;
+        let payload_len = data.len() as u64;
+        // The quota counts bytes as stored, so the new payload is measured once encoded too.
+        let stored = self.codec.encode(&chunking::escape(data))?;
+        if i32::try_from(stored.len()).is_err() {
+            return Err(QueueFileError::ElementTooBig);
+        }
+        let old_len = self
+            .queue
+            .get_mut()?
+            .iter()
+            .nth(index as usize)
+            .map_or(0, |element| element.len() as u64);
+        if self
+            .options
+            .max_bytes
+            .is_some_and(|max| self.logical_used_bytes() - old_len + stored.len() as u64 > max)
+        {
+            return Err(QueueFileError::QuotaExceeded);
+        }
+        self.rewrite(Change::UpdateAt { index }, |i, element| {
+            match i == index as usize {
+                true => Ok(Some(stored.clone())),
+                false => Ok(Some(element.to_vec())),
+            }
+        })?;
+        self.stats.payload_bytes_written += payload_len;
+        Ok(())
+    }
+
+    fn check_index(&self, index: u32) -> Result<(), QueueFileError> {
+        let size = self.queue.size() as u32;
+        match index < size {
+            true => Ok(()),
+            false => Err(QueueFileError::IndexOutOfRange { index, size }),
+        }
+    }
+
+    /// Copies every element into a new file stored as `options` asks, decompressing the ones
+    /// it should not compress and compressing the rest against a single dictionary.
+    pub(crate) fn recode(
+        &mut self,
+        options: RewriteOptions,
+    ) -> Result<RewriteReport, QueueFileError> {
+        self.check_not_frozen()?;
+        self.flush()?;
+        let bytes_before = self.queue.used_bytes();
+        let trained = match options.compress && (options.retrain || !self.codec.is_active()) {
+            true => Some(self.train_dictionary(options.sample_limit.unwrap_or(1000))?),
+            false => None,
+        };
+        // Once every element is compressed against the current dictionary, the earlier ones
+        // are no longer needed; they are dropped only after the new file is in place.
+        let target = match options.compress {
+            true => self.codec.current_only()?,
+            false => Codec::default(),
+        };
+        let source = self.codec.clone();
+        self.rewrite(Change::Rewrite, |_, element| {
+            let framed = source.view_framed(element, None)?;
+            Ok(Some(target.encode(&framed)?))
+        })?;
+        self.save_codec(&target)?;
+        self.codec = target;
+        Ok(RewriteReport {
+            elements: self.queue.size() as u64,
+            bytes_before,
+            bytes_after: self.queue.used_bytes(),
+            trained,
+        })
+    }
+
+    /// Replaces the file with a copy whose elements went through `edit`, journaled as `change`.
+    fn rewrite(
+        &mut self,
+        change: Change,
+        mut edit: impl FnMut(usize, &[u8]) -> Result<Option<Vec<u8>>, QueueFileError>,
+    ) -> Result<(), QueueFileError> {
+        let capacity = self.options.capacity;
+        self.timed(QueueOperation::Rewrite, |state| {
+            let written = state.retrying(|state| {
+                let batch_bytes = state.options.copy_buffer_size_or(rewrite::BATCH_BYTES);
+                rewrite::rewrite(
+                    state.queue.get_mut()?,
+                    &state.path,
+                    capacity,
+                    batch_bytes,
+                    &mut edit,
+                )
+            })?;
+            state.snapshots.release();
+            if let (Some(index), Change::RemoveAt { index: at }) = (&mut state.index, &change) {
+                index.remove_at(*at as usize);
+            }
+            state.changed(change);
+            state.stats.bytes_written += written;
+            state.replaced()?;
+            Ok(((), written))
+        })
+    }
+
+    /// Exchanges the files of two queues, then reopens both. Buffered adds are written first,
+    /// so each handle's contents move with its file.
+    pub(crate) fn swap_with(&mut self, other: &mut QueueState) -> Result<(), QueueFileError> {
+        self.check_not_frozen()?;
+        other.check_not_frozen()?;
+        self.flush()?;
+        other.flush()?;
+        self.snapshots.detach();
+        other.snapshots.detach();
+        // Both files may hold elements compressed against either queue's dictionaries, so
+        // each keeps all of them, saved before the exchange so a crash leaves both readable.
+        if self.codec.is_active() || other.codec.is_active() {
+            let ours = self.codec.merged(&other.codec)?;
+            let theirs = other.codec.merged(&self.codec)?;
+            self.save_codec(&ours)?;
+            other.save_codec(&theirs)?;
+            self.codec = ours;
+            other.codec = theirs;
+        }
+        exchange::exchange(&self.path, &other.path)?;
+        std::mem::swap(&mut self.index, &mut other.index);
+        std::mem::swap(&mut self.next_seq, &mut other.next_seq);
+        self.changed(Change::Swap);
+        other.changed(Change::Swap);
+        self.replaced()?;
+        other.replaced()
+    }
+
+    /// Reopens everything that referred to the file at `path` after another file was moved
+    /// into its place: the queue, the header sidecar, the element index and the mirror.
+    fn replaced(&mut self) -> Result<(), QueueFileError> {
+        let capacity = self.options.capacity;
+        self.reload()?;
+        self.rebuild_bloom();
+        self.open_pending = false;
+        // After a swap the index came with the file, but either queue may have had none.
+        self.index = match (self.options.element_index, self.index.take()) {
+            (false, _) => None,
+            (true, Some(mut index)) => {
+                index.relocate(&self.path, self.next_seq)?;
+                Some(index)
+            }
+            (true, None) => Some(ElementIndex::build(&self.path, Some(self.next_seq))?),
+        };
+        if self.header_slots.is_some() {
+            // Its copies describe the replaced file.
+            self.header_slots = None;
+            let _ = fs::remove_file(HeaderSlots::sidecar_path(&self.path));
+            self.header_slots = Some(HeaderSlots::open(&self.path)?);
+        }
+        if let (Some(mirror_path), Some(_)) = (&self.options.mirror_path, &self.mirror) {
+            match mirror::recopy(&self.path, Path::new(mirror_path), capacity) {
+                Ok(mirror) => self.mirror = Some(mirror),
+                Err(_) => {
+                    self.mirror = None;
+                    self.mirror_status = MirrorStatus::MirrorFailed;
+                }
+            }
+        }
+        // The trailer and signature describe the replaced file; the new one is synced already.
+        self.record_sync_trailer()?;
+        self.record_signature()?;
+        if self.options.sync_directory {
+            dir_sync::sync_parent(&self.path)?;
+        }
+        // Extended attributes belong to the file now in place, which may carry another queue's.
+        self.set_excluded_from_backup(self.options.exclude_from_backup)
+    }
+
+    /// Writes out buffered adds and makes the handle read-only, so the file stays unchanged
+    /// until `unfreeze`.
+    pub(crate) fn freeze(&mut self) -> Result<(), QueueFileError> {
+        self.flush()?;
+        self.frozen = true;
+        Ok(())
+    }
+
+    pub(crate) fn check_not_frozen(&self) -> Result<(), QueueFileError> {
+        match self.frozen {
+            true => Err(QueueFileError::Frozen),
+            false => Ok(()),
+        }
+    }
+
+    pub(crate) fn sync_all(&mut self) -> Result<(), QueueFileError> {
+        self.timed(QueueOperation::Sync, |state| {
+            let bytes = state.stats.bytes_written - state.synced_bytes_written;
+            state.retrying(|state| {
+                let queue = state.queue.get_mut()?;
+                Ok(io_latency::measure(IoCall::Sync, || queue.sync_all())?)
+            })?;
+            if let Some(slots) = &state.header_slots {
+                slots.sync()?;
+            }
+            state.record_sync_trailer()?;
+            state.record_signature()?;
+            state.save_index()?;
+            state.apply_to_mirror(|queue| queue.sync_all());
+            state.synced_bytes_written = state.stats.bytes_written;
+            state.synced_at = Instant::now();
+            Ok(((), bytes))
+        })
+    }
+
+    /// Bytes written since the last sync, none with `sync_writes`, and when that sync was.
+    pub(crate) fn unsynced(&self) -> (u64, Instant) {
+        let bytes = match self.queue.sync_writes() {
+            true => 0,
+            false => self.stats.bytes_written - self.synced_bytes_written,
+        };
+        (bytes, self.synced_at)
+    }
+
+    /// Where the element counts kept in memory disagree with each other.
+    pub(crate) fn counter_problems(&self) -> Vec<String> {
+        let size = self.queue.size();
+        let mut problems = Vec::new();
+        if self.pending_removes > size {
+            problems.push(format!(
+                "{} deferred removals but only {size} elements",
+                self.pending_removes
+            ));
+        }
+        if let Some(index) = &self.index
+            && index.len() != size
+        {
+            problems.push(format!(
+                "element index holds {} entries for {size} elements",
+                index.len()
+            ));
+        }
+        let (used, file_len) = (self.queue.used_bytes(), self.queue.file_len());
+        if used > file_len {
+            problems.push(format!("{used} bytes used in a {file_len}-byte file"));
+        }
+        problems
+    }
+
+    fn record_sync_trailer(&mut self) -> Result<(), QueueFileError> {
+        if !self.options.sync_trailer {
+            return Ok(());
+        }
+        let trailer = sync_trailer::trailer_path(&self.path);
+        sync_trailer::record(&self.path)
+            .map_err(|e| QueueFileError::from(e).with_path(&trailer))?;
+        // Each record renames a new file into place.
+        if self.options.exclude_from_backup {
+            backup::set_excluded(&trailer, true)
+                .map_err(|e| QueueFileError::from(e).with_path(&trailer))?;
+        }
+        Ok(())
+    }
+
+    /// Trains a dictionary on up to `sample_limit` elements, from the eldest, that later adds
+    /// compress against.
+    pub(crate) fn train_dictionary(
+        &mut self,
+        sample_limit: u32,
+    ) -> Result<TrainedDictionary, QueueFileError> {
+        let samples = self.read_elements(self.pending_removes, sample_limit as usize)?;
+        let (codec, trained) = self.codec.train(&samples)?;
+        self.save_codec(&codec)?;
+        self.codec = codec;
+        Ok(trained)
+    }
+
+    /// Saves `codec` for the queue file and the mirror, or removes their dictionaries if it
+    /// has none.
+    fn save_codec(&self, codec: &Codec) -> Result<(), QueueFileError> {
+        let mut paths = vec![self.path.clone()];
+        paths.extend(self.options.mirror_path.as_ref().map(PathBuf::from));
+        for path in paths {
+            let dictionary = dictionary::dictionary_path(&path);
+            codec
+                .save(&path)
+                .map_err(|e| QueueFileError::from(e).with_path(&dictionary))?;
+            if self.options.exclude_from_backup && codec.is_active() {
+                backup::set_excluded(&dictionary, true)
+                    .map_err(|e| QueueFileError::from(e).with_path(&dictionary))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Signs the file as synced, under `signature_key`.
+    fn record_signature(&mut self) -> Result<(), QueueFileError> {
+        let Some(key) = &self.signing_key else {
+            return Ok(());
+        };
+        let signature = signature::signature_path(&self.path);
+        signature::record(&self.path, key)
+            .map_err(|e| QueueFileError::from(e).with_path(&signature))?;
+        if self.options.exclude_from_backup {
+            backup::set_excluded(&signature, true)
+                .map_err(|e| QueueFileError::from(e).with_path(&signature))?;
+        }
+        Ok(())
+    }
+
+    /// Saves the element index with the counters it restores on the next open.
+    fn save_index(&mut self) -> Result<(), QueueFileError> {
+        let Some(element_index) = &mut self.index else {
+            return Ok(());
+        };
+        let path = index::index_path(&self.path);
+        element_index
+            .save(&self.path, self.generation, self.next_seq)
+            .map_err(|e| QueueFileError::from(e).with_path(&path))?;
+        // Each save renames a new file into place.
+        if self.options.exclude_from_backup {
+            backup::set_excluded(&path, true)
+                .map_err(|e| QueueFileError::from(e).with_path(&path))?;
+        }
+        Ok(())
+    }
+
+    /// The element at `index`, counting from the eldest, read straight from its position when
+    /// the element index is kept. The caller flushes first.
+    pub(crate) fn element_at(&mut self, index: u32) -> Result<Vec<u8>, QueueFileError> {
+        self.finish_open()?;
+        self.check_index(index)?;
+        let element = self.read_elements(index as usize, 1)?.pop();
+        element.ok_or(QueueFileError::IndexOutOfRange {
+            index,
+            size: self.queue.size() as u32,
+        })
+    }
+
+    /// Up to `CATCH_UP_BATCH` committed elements with sequence ids after `after` (or from the
+    /// eldest), numbered. Once there are none left, registers `feed` for the elements of later
+    /// commits instead and returns `None`.
+    pub(crate) fn catch_up(
+        &mut self,
+        after: Option<u64>,
+        feed: &Arc<Feed>,
+    ) -> Result<Option<subscribe::Numbered>, QueueFileError> {
+        self.finish_open()?;
+        let size = self.queue.size();
+        let first_seq = self.next_seq.saturating_sub(size as u64);
+        let start = after.map_or(first_seq, |after| (after + 1).max(first_seq));
+        if start >= self.next_seq {
+            feed.go_live();
+            self.feeds.push(Arc::downgrade(feed));
+            return Ok(None);
+        }
+        let elements =
+            self.read_elements((start - first_seq) as usize, subscribe::CATCH_UP_BATCH)?;
+        Ok(Some((start..).zip(elements).collect()))
+    }
+
+    /// Up to `count` committed elements from position `skip` in the file, read from their
+    /// recorded positions when the element index is kept. Under `max_read_bytes`, fails with
+    /// `TooLargeForRead` before reading an element over the limit.
+    pub(crate) fn read_elements(
+        &mut self,
+        skip: usize,
+        count: usize,
+    ) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        self.read_decoded(skip, count, Codec::decode)
+    }
+
+    /// Like `read_elements`, keeping the `chunking` header of each element that has one.
+    pub(crate) fn read_framed(
+        &mut self,
+        skip: usize,
+        count: usize,
+    ) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        self.read_decoded(skip, count, Codec::decode_framed)
+    }
+
+    fn read_decoded(
+        &mut self,
+        skip: usize,
+        count: usize,
+        decode: Decode,
+    ) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        let end = self.queue.size().min(skip.saturating_add(count));
+        if skip >= end {
+            return Ok(Vec::new());
+        }
+        let (limit, file_len) = (self.options.max_read_bytes, self.queue.file_len());
+        match &mut self.index {
+            Some(index) => {
+                let mut elements = Vec::with_capacity(end - skip);
+                for i in skip..end {
+                    read_limit::check(index.payload_len(i).unwrap_or(0), limit)?;
+                    match index.read(i, file_len)? {
+                        Some(element) => elements.push(decode(&self.codec, element, limit)?),
+                        None => break,
+                    }
+                }
+                Ok(elements)
+            }
+            None if limit.is_some() => self.read_frames_as(end, |i| i >= skip, decode),
+            None => self
+                .queue
+                .get_mut()?
+                .iter()
+                .skip(skip)
+                .take(end - skip)
+                .map(|element| decode(&self.codec, element.into_vec(), None))
+                .collect(),
+        }
+    }
+
+    /// The elements before position `end` that `wanted` picks, read by following the frames of
+    /// the committed file so that no other payload is read. Fails with `TooLargeForRead` before
+    /// reading a picked element over `max_read_bytes`.
+    pub(crate) fn read_frames(
+        &self,
+        end: usize,
+        wanted: impl Fn(usize) -> bool,
+    ) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        self.read_frames_as(end, wanted, Codec::decode)
+    }
+
+    fn read_frames_as(
+        &self,
+        end: usize,
+        wanted: impl Fn(usize) -> bool,
+        decode: Decode,
+    ) -> Result<Vec<Vec<u8>>, QueueFileError> {
+        let mut reader = FrameReader::open(&self.path)?;
+        let mut elements = Vec::new();
+        for i in 0..end {
+            let Some(frame) = reader.next_frame()? else {
+                break;
+            };
+            if wanted(i) {
+                let limit = self.options.max_read_bytes;
+                read_limit::check(frame.len, limit)?;
+                elements.push(decode(&self.codec, reader.read(frame)?, limit)?);
+            }
+        }
+        Ok(elements)
+    }
+
+    /// Fails with `ConcurrentModification` if the elements changed since `generation` was read.
+    /// The caller has finished opening.
+    pub(crate) fn check_generation(&self, generation: u64) -> Result<(), QueueFileError> {
+        match self.generation == generation {
+            true => Ok(()),
+            false => Err(QueueFileError::ConcurrentModification {
+                expected: generation,
+                found: self.generation,
+            }),
+        }
+    }
+
+    /// Element count including adds still held by the write buffer and elements whose removal
+    /// is deferred, which quotas and watermarks go by.
+    pub(crate) fn logical_size(&self) -> u64 {
+        (self.queue.size() + self.write_buffer.len()) as u64
+    }
+
+    /// Bytes the queue will use once buffered adds are flushed.
+    pub(crate) fn logical_used_bytes(&self) -> u64 {
+        let frames = self.write_buffer.len() as u64 * 4;
+        self.queue.used_bytes() + self.write_buffer.bytes() + frames
+    }
+
+    pub(crate) fn check_watermarks(&mut self) -> Option<WatermarkEvent> {
+        let (elements, bytes) = (self.logical_size(), self.logical_used_bytes());
+        self.watermarks.as_mut()?.check(elements, bytes)
+    }
+
+    pub(crate) fn set_sync_writes(&mut self, value: bool) {
+        self.queue.set_sync_writes(value);
+        if let Some(mirror) = &mut self.mirror {
+            mirror.queue.set_sync_writes(value);
+        }
+    }
+
+    pub(crate) fn set_overwrite_on_remove(&mut self, value: bool) {
+        self.queue.set_overwrite_on_remove(value);
+        if let Some(mirror) = &mut self.mirror {
+            mirror.queue.set_overwrite_on_remove(value);
+        }
+    }
+}
+
+/// Applies `options.mode` before anything is created. A mirror counts as an existing copy of
+/// the queue, since opening restores the file from it.
+fn check_open_mode(path: &Path, options: &QueueFileOptions) -> Result<(), QueueFileError> {
+    let mirror = options.mirror_path.as_deref().map(Path::new);
+    let exists = path.exists() || mirror.is_some_and(Path::exists);
+    match options.mode.unwrap_or(OpenMode::CreateIfMissing) {
+        OpenMode::CreateIfMissing => Ok(()),
+        OpenMode::CreateNew if exists => {
+            Err(io::Error::new(io::ErrorKind::AlreadyExists, "queue file already exists").into())
+        }
+        // Report the primary's own lookup error, which carries the OS error code.
+        OpenMode::MustExist if !exists => Err(fs::metadata(path).err().map_or_else(
+            || QueueFileError::from(io::Error::from(io::ErrorKind::NotFound)),
+            QueueFileError::from,
+        )),
+        OpenMode::CreateNew | OpenMode::MustExist => Ok(()),
+    }
+}
+
+/// Applies `verify_on_open`: a full frame scan, plus the verdict of the sync trailer when the
+/// option recorded one.
+fn verify_file(path: &Path, sync_check: Option<SyncCheck>) -> Result<(), QueueFileError> {
+    match verify::verify(path) {
+        Ok(Ok(())) => {}
+        Ok(Err(corruption)) => return Err(corruption.into_error(path)),
+        Err(e) => return Err(QueueFileError::from(e).with_path(path)),
+    }
+    match sync_check {
+        Some(check) if check.status == SyncStatus::Corrupted => {
+            Err(QueueFileError::CorruptedFile {
+                message: format!(
+                    "{}: elements differ from the ones recorded by the last sync",
+                    path.display()
+                ),
+                offset: None,
+                element_index: None,
+                expected: Some(format!("{} synced elements", check.synced_elements)),
+                found: None,
+                salvageable: false,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+impl Drop for QueueState {
+    fn drop(&mut self) {
+        // Closed for inactivity, it was synced with nothing left to write.
+        if self.queue.is_closed() {
+            self.snapshots.detach();
+            return;
+        }
+        self.paused = None;
+        let _ = self.flush();
+        let _ = self.save_index();
+        // Signs what was committed since the last sync, so a clean close reopens cleanly.
+        let _ = self.record_signature();
+        // Another handle may change the file once this one is closed.
+        self.snapshots.detach();
+    }
+}
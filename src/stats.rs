@@ -0,0 +1,95 @@
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::latency::{Latencies, LatencySummary};
+
+#[derive(Debug, Clone, Default, PartialEq, uniffi::Record)]
+pub struct QueueStats {
+    /// Operations re-attempted after a transient I/O error.
+    pub io_retries: u64,
+    /// Operations that still failed with a transient I/O error after the last attempt.
+    pub io_retries_exhausted: u64,
+    /// Operations resumed by `notify_unlocked` after failing with `DataProtectionLocked`.
+    pub unlock_waits: u64,
+    /// Times a torn header was replaced from the redundant copy on open.
+    pub headers_restored: u64,
+    /// Commits whose header could not be copied to the redundant header sidecar.
+    pub header_copy_failures: u64,
+    /// Payload bytes of the elements added.
+    pub payload_bytes_written: u64,
+    /// Bytes written to the queue file and its header sidecar for them and for removals:
+    /// frame headers, header commits, the move of the wrapped part of the ring on growth and,
+    /// with overwrite on remove, zero-fill. Derived from what each operation writes; a mirror
+    /// writes about as much again.
+    pub bytes_written: u64,
+    /// `bytes_written / payload_bytes_written`, or 0 before anything was added.
+    pub write_amplification: f64,
+    /// The queue's `generation()` when the stats were taken.
+    pub generation: u64,
+    /// Duration percentiles of each kind of operation that has run since the queue was opened.
+    pub latencies: Vec<LatencySummary>,
+}
+
+impl QueueStats {
+    /// The stats as reported, with the derived ratio filled in.
+    pub(crate) fn snapshot(&self) -> QueueStats {
+        let mut stats = self.clone();
+        if stats.payload_bytes_written > 0 {
+            stats.write_amplification =
+                stats.bytes_written as f64 / stats.payload_bytes_written as f64;
+        }
+        stats
+    }
+}
+
+/// What the queue counted between the last `reset_stats` (or its open) and the moment these
+/// were taken.
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct StatsWindow {
+    pub started_at: SystemTime,
+    pub duration: Duration,
+    /// The counters and latencies of the window alone. `generation` is the queue's at its end.
+    pub stats: QueueStats,
+}
+
+/// The start of the current stats window.
+pub(crate) struct Window {
+    started_at: SystemTime,
+    started: Instant,
+    /// The lifetime counters when the window started.
+    base: QueueStats,
+    /// Durations of the operations run in the window.
+    pub(crate) latencies: Latencies,
+}
+
+impl Window {
+    pub(crate) fn new(base: QueueStats) -> Window {
+        Window {
+            started_at: SystemTime::now(),
+            started: Instant::now(),
+            base,
+            latencies: Latencies::default(),
+        }
+    }
+
+    /// The window up to now, given the lifetime counters `total`.
+    pub(crate) fn report(&self, total: &QueueStats, generation: u64) -> StatsWindow {
+        let base = &self.base;
+        let counted = QueueStats {
+            io_retries: total.io_retries - base.io_retries,
+            io_retries_exhausted: total.io_retries_exhausted - base.io_retries_exhausted,
+            unlock_waits: total.unlock_waits - base.unlock_waits,
+            headers_restored: total.headers_restored - base.headers_restored,
+            header_copy_failures: total.header_copy_failures - base.header_copy_failures,
+            payload_bytes_written: total.payload_bytes_written - base.payload_bytes_written,
+            bytes_written: total.bytes_written - base.bytes_written,
+            write_amplification: 0.0,
+            generation,
+            latencies: self.latencies.summaries(),
+        };
+        StatsWindow {
+            started_at: self.started_at,
+            duration: self.started.elapsed(),
+            stats: counted.snapshot(),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::{QueueFile, QueueFileError, chunking};
+
+/// Elements read from the file per lock while a subscription catches up.
+pub(crate) const CATCH_UP_BATCH: usize = 256;
+/// Live elements a subscription may fall behind by before it goes back to reading the file.
+const MAX_LIVE: usize = 1024;
+/// How often a subscription whose read failed tries again.
+const RETRY_MS: u64 = 500;
+
+/// Elements paired with their sequence ids, in order.
+pub(crate) type Numbered = Vec<(u64, Vec<u8>)>;
+
+/// Receives the elements of a queue from `subscribe_from`, one at a time and in order, on the
+/// subscription's own thread.
+#[uniffi::export(with_foreign)]
+pub trait ElementListener: Send + Sync {
+    fn on_element(&self, seq: u64, element: Vec<u8>);
+}
+
+/// A thread passing every element added to a queue to a listener until `cancel` is called.
+#[derive(uniffi::Object)]
+pub struct Subscription {
+    feed: Arc<Feed>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+/// Where commits hand their elements to a subscription that has caught up.
+#[derive(Default)]
+pub(crate) struct Feed {
+    inner: Mutex<FeedState>,
+    wake: Condvar,
+}
+
+#[derive(Default)]
+struct FeedState {
+    live: VecDeque<(u64, Vec<u8>)>,
+    /// Set when `live` overflowed; the queue has dropped the feed and the subscription reads
+    /// the file again.
+    lagging: bool,
+    cancelled: bool,
+    /// The last sequence id delivered.
+    delivered: Option<u64>,
+}
+
+impl Feed {
+    fn lock(&self) -> std::sync::MutexGuard<'_, FeedState> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Called under the queue's lock by a commit of `items` numbered from `first_seq`. Returns
+    /// false once the feed no longer wants elements and can be dropped.
+    pub(crate) fn push(&self, first_seq: u64, items: &[Vec<u8>]) -> bool {
+        let mut feed = self.lock();
+        if feed.cancelled || feed.lagging {
+            return false;
+        }
+        if feed.live.len() + items.len() > MAX_LIVE {
+            feed.live.clear();
+            feed.lagging = true;
+        } else {
+            let payloads = items.iter().map(|item| chunking::unframe(item).to_vec());
+            feed.live.extend((first_seq..).zip(payloads));
+        }
+        self.wake.notify_all();
+        !feed.lagging
+    }
+
+    /// Called under the queue's lock when the subscription has read everything committed and
+    /// is about to be registered for live elements.
+    pub(crate) fn go_live(&self) {
+        self.lock().lagging = false;
+    }
+
+    fn cancel(&self) {
+        self.lock().cancelled = true;
+        self.wake.notify_all();
+    }
+
+    /// Waits for `ms` or until cancelled.
+    fn sleep(&self, ms: u64) {
+        let feed = self.lock();
+        let _ = self
+            .wake
+            .wait_timeout_while(feed, Duration::from_millis(ms), |feed| !feed.cancelled);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.lock().cancelled
+    }
+
+    /// Waits up to `ms` for live elements, returning those that arrived. `None` once the feed
+    /// is cancelled or has fallen behind.
+    fn wait(&self, ms: u64) -> Option<Numbered> {
+        let feed = self.lock();
+        let (mut feed, _) = self
+            .wake
+            .wait_timeout_while(feed, Duration::from_millis(ms), |feed| {
+                feed.live.is_empty() && !feed.cancelled && !feed.lagging
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+        match feed.cancelled || feed.lagging {
+            true => None,
+            false => Some(feed.live.drain(..).collect()),
+        }
+    }
+}
+
+impl Subscription {
+    pub(crate) fn start(
+        queue: Arc<QueueFile>,
+        after: Option<u64>,
+        listener: Arc<dyn ElementListener>,
+    ) -> Result<Arc<Self>, QueueFileError> {
+        let feed = Arc::new(Feed::default());
+        feed.lock().delivered = after;
+        let thread = {
+            let feed = Arc::clone(&feed);
+            std::thread::Builder::new()
+                .name("queuefile-subscriber".to_string())
+                .spawn(move || run(&queue, &*listener, &feed))?
+        };
+        Ok(Arc::new(Subscription {
+            feed,
+            thread: Mutex::new(Some(thread)),
+        }))
+    }
+}
+
+#[uniffi::export]
+impl Subscription {
+    /// Stops delivering and waits for the element being delivered, if any.
+    #[uniffi::method]
+    pub fn cancel(&self) {
+        self.feed.cancel();
+        let thread = self
+            .thread
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        // A listener cancelling from its callback cannot wait for its own thread.
+        if let Some(thread) = thread
+            && thread.thread().id() != std::thread::current().id()
+        {
+            let _ = thread.join();
+        }
+    }
+
+    #[uniffi::method]
+    pub fn is_active(&self) -> bool {
+        !self.feed.is_cancelled()
+    }
+
+    /// The sequence id of the last element delivered, or the one subscribed from.
+    #[uniffi::method]
+    pub fn last_seq(&self) -> Option<u64> {
+        self.feed.lock().delivered
+    }
+}
+
+impl Drop for Subscription {
+    /// Dropping the last reference stops the thread without waiting for it.
+    fn drop(&mut self) {
+        self.feed.cancel();
+    }
+}
+
+fn run(queue: &Arc<QueueFile>, listener: &dyn ElementListener, feed: &Arc<Feed>) {
+    let mut live = false;
+    while !feed.is_cancelled() {
+        if live {
+            match feed.wait(RETRY_MS) {
+                Some(elements) => deliver(listener, feed, elements),
+                // Fallen behind: read what was missed from the file.
+                None => live = false,
+            }
+            continue;
+        }
+        let after = feed.lock().delivered;
+        match queue.catch_up(after, feed) {
+            Ok(Some(elements)) => deliver(listener, feed, elements),
+            Ok(None) => live = true,
+            Err(_) => feed.sleep(RETRY_MS),
+        }
+    }
+}
+
+fn deliver(listener: &dyn ElementListener, feed: &Feed, elements: Numbered) {
+    for (seq, element) in elements {
+        if feed.is_cancelled() {
+            return;
+        }
+        // Elements read from the file may overlap ones already delivered live.
+        if feed
+            .lock()
+            .delivered
+            .is_some_and(|delivered| seq <= delivered)
+        {
+            continue;
+        }
+        listener.on_element(seq, element);
+        feed.lock().delivered = Some(seq);
+    }
+}
@@ -0,0 +1,156 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::fragmentation;
+
+const HEADER_LEN: usize = 32;
+const VERSIONED_HEADER: u32 = 0x8000_0001;
+const MAGIC: &[u8; 4] = b"QFST";
+/// Magic, sync time, element count, header copy, directory CRC and the record's own CRC.
+const TRAILER_LEN: usize = 4 + 8 + 8 + HEADER_LEN + 4 + 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum SyncStatus {
+    /// The file is exactly as the last `sync_all` left it.
+    Unchanged,
+    /// Operations were committed after the last sync and the file is consistent.
+    ModifiedSinceSync,
+    /// The header changed after the last sync and the element chain it describes is broken:
+    /// the file was torn by a crash during a write that followed the last sync.
+    TornSinceSync,
+    /// The header is the one the last sync recorded, but the elements it describes are not:
+    /// the file changed without going through the queue.
+    Corrupted,
+}
+
+/// What opening found, compared with the `.sync` trailer written by the last `sync_all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct SyncCheck {
+    pub status: SyncStatus,
+    /// Number of elements in the queue at the last sync.
+    pub synced_elements: u64,
+    /// When the last sync was made, to the millisecond.
+    pub synced_at: SystemTime,
+}
+
+/// The header and element directory of the file as it is now.
+struct Snapshot {
+    header: [u8; HEADER_LEN],
+    count: u64,
+    /// CRC-32 over the position and length of every element, or `None` if the chain breaks.
+    directory_crc: Option<u32>,
+}
+
+pub(crate) fn trailer_path(path: &Path) -> PathBuf {
+    let mut trailer = path.to_path_buf().into_os_string();
+    trailer.push(".sync");
+    PathBuf::from(trailer)
+}
+
+/// Records the file's header and element directory after a sync, replacing the trailer
+/// through a rename so a crash leaves the previous one intact.
+pub(crate) fn record(path: &Path) -> io::Result<()> {
+    let snapshot = snapshot(path)?;
+    let synced_at_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    let mut bytes = Vec::with_capacity(TRAILER_LEN);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&synced_at_ms.to_be_bytes());
+    bytes.extend_from_slice(&snapshot.count.to_be_bytes());
+    bytes.extend_from_slice(&snapshot.header);
+    bytes.extend_from_slice(&snapshot.directory_crc.unwrap_or(0).to_be_bytes());
+    bytes.extend_from_slice(&crc32fast::hash(&bytes).to_be_bytes());
+
+    let trailer = trailer_path(path);
+    let mut staging = trailer.clone().into_os_string();
+    staging.push(".tmp");
+    let staging = PathBuf::from(staging);
+    let mut file = File::create(&staging)?;
+    file.write_all(&bytes)?;
+    file.sync_all()?;
+    fs::rename(&staging, &trailer)
+}
+
+/// Compares the file with its trailer. `None` if there is no trailer or it is damaged.
+pub(crate) fn check(path: &Path) -> io::Result<Option<SyncCheck>> {
+    let bytes = match fs::read(trailer_path(path)) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let Ok(bytes) = <[u8; TRAILER_LEN]>::try_from(bytes) else {
+        return Ok(None);
+    };
+    let (body, crc) = bytes.split_at(TRAILER_LEN - 4);
+    if &body[..4] != MAGIC || crc32fast::hash(body).to_be_bytes() != crc {
+        return Ok(None);
+    }
+    let u64_at = |at: usize| u64::from_be_bytes(body[at..at + 8].try_into().unwrap());
+    let synced_header = &body[20..20 + HEADER_LEN];
+    let synced_crc = u32::from_be_bytes(body[20 + HEADER_LEN..].try_into().unwrap());
+
+    let current = snapshot(path)?;
+    let status = match (current.header == synced_header, current.directory_crc) {
+        (true, Some(crc)) if crc == synced_crc => SyncStatus::Unchanged,
+        (true, _) => SyncStatus::Corrupted,
+        (false, Some(_)) => SyncStatus::ModifiedSinceSync,
+        (false, None) => SyncStatus::TornSinceSync,
+    };
+    Ok(Some(SyncCheck {
+        status,
+        synced_elements: u64_at(12),
+        synced_at: UNIX_EPOCH + Duration::from_millis(u64_at(4)),
+    }))
+}
+
+/// Reads the header and follows the chain of element frames it describes.
+fn snapshot(path: &Path) -> io::Result<Snapshot> {
+    let mut file = File::open(path)?;
+    let physical_len = file.metadata()?.len();
+    let mut header = [0; HEADER_LEN];
+    if file.read_exact(&mut header).is_err() {
+        return Ok(Snapshot {
+            header,
+            count: 0,
+            directory_crc: None,
+        });
+    }
+    let u64_at = |at: usize| u64::from_be_bytes(header[at..at + 8].try_into().unwrap());
+    let marker = u32::from_be_bytes(header[..4].try_into().unwrap());
+    let count = u64::from(u32::from_be_bytes(header[12..16].try_into().unwrap()));
+    let (file_len, first) = (u64_at(4), u64_at(16));
+    let header_len = HEADER_LEN as u64;
+    let mut snapshot = Snapshot {
+        header,
+        count,
+        directory_crc: None,
+    };
+    if marker != VERSIONED_HEADER || file_len < header_len || file_len > physical_len {
+        return Ok(snapshot);
+    }
+
+    let mut crc = crc32fast::Hasher::new();
+    let mut pos = first;
+    for _ in 0..count {
+        if !(header_len..file_len).contains(&pos) {
+            return Ok(snapshot);
+        }
+        let mut len = [0; 4];
+        fragmentation::read_ring(&mut file, pos, &mut len, header_len, file_len)?;
+        let len = u64::from(u32::from_be_bytes(len));
+        if len > file_len - header_len {
+            return Ok(snapshot);
+        }
+        crc.update(&pos.to_be_bytes());
+        crc.update(&len.to_be_bytes());
+        pos += 4 + len;
+        if pos >= file_len {
+            pos = header_len + pos - file_len;
+        }
+    }
+    snapshot.directory_crc = Some(crc.finalize());
+    Ok(snapshot)
+}
@@ -0,0 +1,56 @@
+use std::fs::File;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileError, QueueFileOptions};
+
+#[test]
+fn file_is_added_as_one_element() {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("photo");
+    std::fs::write(&src, vec![7; 5000]).unwrap();
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+
+    assert_eq!(
+        queue.add_from_file(src.display().to_string()).unwrap(),
+        5000
+    );
+    assert_eq!(queue.get_all().unwrap(), vec![vec![7; 5000]]);
+}
+
+#[test]
+fn file_over_64_mib_is_refused_unread() {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("video");
+    // Sparse, so the test writes nothing.
+    File::create(&src).unwrap().set_len((64 << 20) + 1).unwrap();
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+
+    assert!(matches!(
+        queue.add_from_file(src.display().to_string()),
+        Err(QueueFileError::TooLargeForRead { len, limit }) if len == (64 << 20) + 1 && limit == 64 << 20
+    ));
+    assert!(queue.is_empty().unwrap());
+}
+
+#[test]
+fn lower_read_limit_caps_the_file_too() {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("photo");
+    std::fs::write(&src, vec![7; 5000]).unwrap();
+    let options = QueueFileOptions {
+        max_read_bytes: Some(4096),
+        ..Default::default()
+    };
+    let path = dir.path().join("q").display().to_string();
+    let queue = QueueFile::open_with_options(path, options).unwrap();
+
+    assert!(matches!(
+        queue.add_from_file(src.display().to_string()),
+        Err(QueueFileError::TooLargeForRead {
+            len: 5000,
+            limit: 4096
+        })
+    ));
+    assert!(queue.is_empty().unwrap());
+}
@@ -0,0 +1,97 @@
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileError, QueueFileOptions, WriteBufferConfig};
+
+fn items(range: std::ops::Range<u8>) -> Vec<Vec<u8>> {
+    range.map(|n| vec![n; 8]).collect()
+}
+
+fn holding_at_most(dir: &TempDir, max_elements: u64) -> Arc<QueueFile> {
+    let options = QueueFileOptions {
+        max_elements: Some(max_elements),
+        ..Default::default()
+    };
+    let path = dir.path().join("q").display().to_string();
+    QueueFile::open_with_options(path, options).unwrap()
+}
+
+#[test]
+fn batch_over_the_quota_adds_none_of_its_items() {
+    let dir = TempDir::new().unwrap();
+    let queue = holding_at_most(&dir, 5);
+    queue.add_multiple(items(0..3)).unwrap();
+
+    assert!(matches!(
+        queue.add_multiple(items(3..6)),
+        Err(QueueFileError::QuotaExceeded)
+    ));
+    assert_eq!(queue.get_all().unwrap(), items(0..3));
+    queue.add_multiple(items(3..5)).unwrap();
+    assert_eq!(queue.get_all().unwrap(), items(0..5));
+}
+
+#[test]
+fn refused_batch_leaves_the_write_buffer_alone() {
+    let dir = TempDir::new().unwrap();
+    let queue = holding_at_most(&dir, 4);
+    queue
+        .set_write_buffering(Some(WriteBufferConfig {
+            max_bytes: 1 << 20,
+            max_delay: Duration::from_secs(3600),
+            max_items: None,
+        }))
+        .unwrap();
+    queue.add_multiple(items(0..2)).unwrap();
+
+    assert!(queue.add_multiple(items(2..5)).is_err());
+    assert_eq!(queue.size().unwrap(), 2);
+    queue.flush().unwrap();
+    assert_eq!(queue.get_all().unwrap(), items(0..2));
+}
+
+/// Set for the copy of the test binary that `failed_write_rolls_back_to_the_committed_queue`
+/// runs under a file size limit, naming the queue it works on.
+const FAILING_QUEUE: &str = "QUEUEFILE_TEST_FAILING_QUEUE";
+
+#[test]
+fn failed_write_rolls_back_to_the_committed_queue() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    // Growing the file past 8 KiB fails with EFBIG instead of raising SIGXFSZ.
+    let status = Command::new("sh")
+        .args(["-c", "trap '' XFSZ; ulimit -f 16 && exec \"$@\"", "sh"])
+        .arg(std::env::current_exe().unwrap())
+        .args([
+            "--ignored",
+            "--exact",
+            "tests::add_multiple::write_past_the_file_size_limit",
+        ])
+        .env(FAILING_QUEUE, &path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let queue = QueueFile::open(path.display().to_string()).unwrap();
+    let mut expected = items(0..30);
+    expected.push(vec![99]);
+    assert_eq!(queue.get_all().unwrap(), expected);
+}
+
+#[test]
+#[ignore = "run by failed_write_rolls_back_to_the_committed_queue"]
+fn write_past_the_file_size_limit() {
+    let path = std::env::var(FAILING_QUEUE).unwrap();
+    let queue = QueueFile::open(path.clone()).unwrap();
+    queue.add_multiple(items(0..30)).unwrap();
+    let file_len = std::fs::metadata(&path).unwrap().len();
+
+    let err = queue.add_multiple(vec![vec![0; 4096]; 16]).unwrap_err();
+    assert!(matches!(err, QueueFileError::IoError { .. }), "{err:?}");
+    assert_eq!(queue.get_all().unwrap(), items(0..30));
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), file_len);
+    queue.add(vec![99]).unwrap();
+}
@@ -0,0 +1,44 @@
+use std::fs::File;
+use std::path::Path;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use tempfile::TempDir;
+
+use crate::{ArchivedQueue, QueueFile};
+
+/// Fills the queue at `path`, compressing all but the first elements against a dictionary, and
+/// returns the payloads it holds.
+fn compressed_queue(path: &Path) -> Vec<Vec<u8>> {
+    let queue = QueueFile::open(path.display().to_string()).unwrap();
+    let event = |n: u32| format!(r#"{{"id":{n},"event":"page_view","path":"/p/{}"}}"#, n % 7);
+    let mut elements: Vec<_> = (0..300).map(|n| event(n).into_bytes()).collect();
+    queue.add_multiple(elements.clone()).unwrap();
+    queue.train_dictionary(300).unwrap();
+    let later: Vec<_> = (300..350).map(|n| event(n).into_bytes()).collect();
+    queue.add_multiple(later.clone()).unwrap();
+    elements.extend(later);
+    elements
+}
+
+fn gzip(src: &Path, dest: &Path) {
+    let mut encoder = GzEncoder::new(File::create(dest).unwrap(), Compression::default());
+    std::io::copy(&mut File::open(src).unwrap(), &mut encoder).unwrap();
+    encoder.finish().unwrap();
+}
+
+#[test]
+fn compressed_elements_are_decoded_with_the_queues_dictionary() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("events.qf");
+    let elements = compressed_queue(&path);
+    let archive_path = dir.path().join("events.qf.gz");
+    gzip(&path, &archive_path);
+    std::fs::remove_file(&path).unwrap();
+
+    let archive =
+        ArchivedQueue::open_compressed_read_only(archive_path.display().to_string()).unwrap();
+    archive.verify().unwrap();
+    assert_eq!(archive.get_all().unwrap(), elements);
+    assert_eq!(archive.next().unwrap(), Some(elements[0].clone()));
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileError};
+
+/// A payload that a reader of the old in-band header would have taken for chunk 0 of 3.
+fn lookalike() -> Vec<u8> {
+    let mut data = b"QFCK".to_vec();
+    data.extend_from_slice(&0u32.to_be_bytes());
+    data.extend_from_slice(&3u32.to_be_bytes());
+    data.extend_from_slice(b"user data");
+    data
+}
+
+fn open(dir: &TempDir) -> Arc<QueueFile> {
+    QueueFile::open(dir.path().join("q").display().to_string()).unwrap()
+}
+
+#[test]
+fn payload_with_chunk_magic_is_not_a_chunk() {
+    let dir = TempDir::new().unwrap();
+    let queue = open(&dir);
+    queue.add(lookalike()).unwrap();
+    queue.add(b"after".to_vec()).unwrap();
+
+    assert_eq!(queue.peek().unwrap(), Some(lookalike()));
+    assert_eq!(queue.peek_chunked().unwrap(), Some(lookalike()));
+    let copy = dir.path().join("copy").display().to_string();
+    assert_eq!(
+        queue.peek_to_file(copy.clone()).unwrap(),
+        Some(lookalike().len() as u64)
+    );
+    assert_eq!(std::fs::read(copy).unwrap(), lookalike());
+
+    queue.remove_chunked().unwrap();
+    assert_eq!(queue.get_all().unwrap(), vec![b"after".to_vec()]);
+}
+
+#[test]
+fn plain_reads_see_each_chunk_as_its_slice() {
+    let dir = TempDir::new().unwrap();
+    let queue = open(&dir);
+    let data = b"0123456789".to_vec();
+    queue.add_chunked(data.clone(), Some(4)).unwrap();
+
+    assert_eq!(queue.size().unwrap(), 3);
+    assert_eq!(queue.peek().unwrap(), Some(b"0123".to_vec()));
+    assert_eq!(queue.get_all().unwrap().concat(), data);
+    assert_eq!(queue.peek_chunked().unwrap(), Some(data));
+}
+
+#[test]
+fn file_is_added_a_chunk_at_a_time_and_popped_whole() {
+    let dir = TempDir::new().unwrap();
+    let queue = open(&dir);
+    let data: Vec<u8> = (0..10_000u32).map(|n| n as u8).collect();
+    let src = dir.path().join("src");
+    std::fs::write(&src, &data).unwrap();
+
+    let len = queue
+        .add_chunked_from_file(src.display().to_string(), Some(1024))
+        .unwrap();
+    assert_eq!(len, data.len() as u64);
+    assert_eq!(queue.size().unwrap(), 10);
+    assert_eq!(queue.peek_chunked().unwrap().as_ref(), Some(&data));
+
+    let dest = dir.path().join("dest").display().to_string();
+    assert_eq!(queue.pop_chunked_to_file(dest.clone()).unwrap(), Some(len));
+    assert_eq!(std::fs::read(dest).unwrap(), data);
+    assert!(queue.is_empty().unwrap());
+}
+
+#[test]
+fn remove_chunked_drops_only_what_is_left_of_an_element() {
+    let dir = TempDir::new().unwrap();
+    let queue = open(&dir);
+    queue.add_chunked(b"abcdefghi".to_vec(), Some(3)).unwrap();
+    queue.add(b"next".to_vec()).unwrap();
+
+    queue.remove().unwrap();
+    assert!(matches!(
+        queue.peek_chunked(),
+        Err(QueueFileError::InvalidChunk { .. })
+    ));
+    queue.remove_chunked().unwrap();
+    assert_eq!(queue.get_all().unwrap(), vec![b"next".to_vec()]);
+}
@@ -0,0 +1,114 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tempfile::TempDir;
+
+use crate::{DrainHandler, ProcessPolicy, QueueFile, runtime};
+
+/// Accepts elements once `rejections` deliveries have been rejected, recording each delivery
+/// and whether it ran on a runtime worker.
+#[derive(Default)]
+struct Recorder {
+    rejections: Mutex<u32>,
+    deliveries: Mutex<Vec<(Vec<u8>, bool)>>,
+}
+
+impl DrainHandler for Recorder {
+    fn handle(&self, element: Vec<u8>) -> bool {
+        self.deliveries
+            .lock()
+            .unwrap()
+            .push((element, runtime::on_worker()));
+        let mut rejections = self.rejections.lock().unwrap();
+        let accept = *rejections == 0;
+        *rejections = rejections.saturating_sub(1);
+        accept
+    }
+}
+
+fn open(dir: &TempDir, name: &str) -> Arc<QueueFile> {
+    QueueFile::open(dir.path().join(name).display().to_string()).unwrap()
+}
+
+fn quick() -> ProcessPolicy {
+    ProcessPolicy {
+        max_attempts: 2,
+        initial_backoff: Some(Duration::from_millis(1)),
+        max_backoff: None,
+        idle_poll: Some(Duration::from_millis(5)),
+    }
+}
+
+fn wait_until(done: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !done() {
+        assert!(Instant::now() < deadline, "timed out");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn elements_are_delivered_on_the_runtime() {
+    let dir = TempDir::new().unwrap();
+    let queue = open(&dir, "q");
+    queue
+        .add_multiple(vec![b"a".to_vec(), b"b".to_vec()])
+        .unwrap();
+    let handler = Arc::new(Recorder::default());
+
+    let consumer = Arc::clone(&queue)
+        .process(handler.clone(), quick(), None)
+        .unwrap();
+    queue.add(b"c".to_vec()).unwrap();
+    wait_until(|| consumer.stats().delivered == 3);
+    consumer.stop();
+
+    assert!(!consumer.is_running());
+    assert!(queue.is_empty().unwrap());
+    let deliveries = handler.deliveries.lock().unwrap();
+    let expected: Vec<_> = [b"a", b"b", b"c"].map(|e| (e.to_vec(), true)).into();
+    assert_eq!(*deliveries, expected);
+}
+
+#[test]
+fn rejected_element_moves_to_the_dead_letter_queue() {
+    let dir = TempDir::new().unwrap();
+    let queue = open(&dir, "q");
+    let dead_letter = open(&dir, "failed");
+    queue
+        .add_multiple(vec![b"bad".to_vec(), b"good".to_vec()])
+        .unwrap();
+    let handler = Arc::new(Recorder {
+        rejections: Mutex::new(2),
+        ..Default::default()
+    });
+
+    let consumer = Arc::clone(&queue)
+        .process(handler, quick(), Some(Arc::clone(&dead_letter)))
+        .unwrap();
+    wait_until(|| consumer.stats().delivered == 1);
+    consumer.stop();
+
+    let stats = consumer.stats();
+    assert_eq!((stats.rejected, stats.dead_lettered), (2, 1));
+    assert_eq!(dead_letter.get_all().unwrap(), vec![b"bad".to_vec()]);
+    assert!(queue.is_empty().unwrap());
+}
+
+#[test]
+fn front_removed_meanwhile_is_not_mistaken_for_an_equal_one() {
+    let dir = TempDir::new().unwrap();
+    let queue = open(&dir, "q");
+    queue
+        .add_multiple(vec![b"same".to_vec(), b"same".to_vec()])
+        .unwrap();
+
+    let (seq, _) = queue.peek_front().unwrap().unwrap();
+    queue.remove().unwrap();
+    assert!(!queue.remove_if_front(seq).unwrap());
+    assert_eq!(queue.size().unwrap(), 1);
+
+    let (seq, _) = queue.peek_front().unwrap().unwrap();
+    assert!(queue.remove_if_front(seq).unwrap());
+    assert!(queue.is_empty().unwrap());
+}
@@ -0,0 +1,76 @@
+use std::fs;
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileOptions};
+
+/// A queue at `path` holding the elements 0 to 9, holding up to `removes` removals back.
+fn ten_deferring(path: &str, removes: u32) -> Arc<QueueFile> {
+    let options = QueueFileOptions {
+        deferred_removes: Some(removes),
+        ..Default::default()
+    };
+    let queue = QueueFile::open_with_options(path.to_string(), options).unwrap();
+    queue
+        .add_multiple((0..10).map(|n| vec![n]).collect())
+        .unwrap();
+    queue
+}
+
+/// The element count in the file's header, which a crash would leave behind.
+fn count_in_file(path: &str) -> u32 {
+    let header = fs::read(path).unwrap();
+    u32::from_be_bytes(header[12..16].try_into().unwrap())
+}
+
+#[test]
+fn deferred_removes_are_hidden_but_not_written() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = ten_deferring(&path, 4);
+    queue.remove_n(3).unwrap();
+    assert_eq!(queue.peek().unwrap(), Some(vec![3]));
+    assert_eq!(queue.size().unwrap(), 7);
+    assert_eq!(count_in_file(&path), 10);
+}
+
+#[test]
+fn deferred_removes_commit_once_the_batch_is_full() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = ten_deferring(&path, 4);
+    queue.remove_n(3).unwrap();
+    assert_eq!(count_in_file(&path), 10);
+    queue.remove().unwrap();
+    assert_eq!(count_in_file(&path), 6);
+    assert_eq!(queue.peek().unwrap(), Some(vec![4]));
+}
+
+#[test]
+fn flush_and_gc_commit_deferred_removes() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = ten_deferring(&path, 100);
+    queue.remove_n(3).unwrap();
+    queue.flush().unwrap();
+    assert_eq!(count_in_file(&path), 7);
+    queue.remove_n(2).unwrap();
+    queue.gc().unwrap();
+    assert_eq!(count_in_file(&path), 5);
+    assert_eq!(queue.get_all().unwrap()[0], vec![5]);
+}
+
+#[test]
+fn close_commits_deferred_removes() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = ten_deferring(&path, 100);
+    queue.remove_n(6).unwrap();
+    drop(queue);
+    let queue = QueueFile::open(path).unwrap();
+    assert_eq!(
+        queue.get_all().unwrap(),
+        vec![vec![6], vec![7], vec![8], vec![9]]
+    );
+}
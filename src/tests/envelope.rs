@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tempfile::TempDir;
+
+use crate::{ElementEnvelope, QueueFile, QueueFileError, decode_envelope, encode_envelope};
+
+/// A time the envelope's microsecond timestamps hold exactly.
+fn at(micros: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_micros(micros)
+}
+
+fn full_envelope() -> ElementEnvelope {
+    ElementEnvelope {
+        payload: b"{\"order\":42}".to_vec(),
+        headers: HashMap::from([
+            ("tag".to_string(), "orders".to_string()),
+            ("trace".to_string(), "abc123".to_string()),
+        ]),
+        created_at: Some(at(1_700_000_000_123_456)),
+        schema_version: 2,
+        attempts: 3,
+        last_attempt_at: Some(at(1_700_000_060_000_001)),
+    }
+}
+
+#[test]
+fn envelope_round_trips_through_the_queue() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = QueueFile::open(path.clone()).unwrap();
+    queue.add_envelope(full_envelope()).unwrap();
+
+    drop(queue);
+    let queue = QueueFile::open(path).unwrap();
+    assert_eq!(queue.peek_envelope().unwrap(), Some(full_envelope()));
+}
+
+#[test]
+fn add_envelope_stamps_a_missing_creation_time() {
+    let dir = TempDir::new().unwrap();
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+    let before = SystemTime::now() - Duration::from_millis(1);
+    queue
+        .add_envelope(ElementEnvelope {
+            created_at: None,
+            ..full_envelope()
+        })
+        .unwrap();
+
+    let created_at = queue.peek_envelope().unwrap().unwrap().created_at.unwrap();
+    assert!(created_at >= before && created_at <= SystemTime::now());
+}
+
+#[test]
+fn encoding_is_protobuf_wire_format() {
+    let envelope = ElementEnvelope {
+        payload: b"hi".to_vec(),
+        headers: HashMap::from([("k".to_string(), "v".to_string())]),
+        created_at: None,
+        schema_version: 0,
+        attempts: 300,
+        last_attempt_at: None,
+    };
+    let expected = [
+        0x39, b'Q', b'F', b'E', b'N', b'V', b'L', b'P', 1, // mark = 7, fixed64
+        0x0a, 2, b'h', b'i', // payload = 1
+        0x1a, 6, 0x0a, 1, b'k', 0x12, 1, b'v', // headers = 3, key = 1, value = 2
+        0x28, 0xac, 0x02, // attempts = 5, varint 300
+    ];
+    assert_eq!(encode_envelope(envelope.clone()), expected);
+    assert_eq!(decode_envelope(expected.to_vec()).unwrap(), envelope);
+}
+
+#[test]
+fn metadata_comes_only_from_marked_envelopes() {
+    let dir = TempDir::new().unwrap();
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+    queue.add_envelope(full_envelope()).unwrap();
+    // A payload and attempts field, as any protobuf encoder would write them.
+    let lookalike = vec![0x0a, 2, b'h', b'i', 0x28, 9];
+    assert_eq!(decode_envelope(lookalike.clone()).unwrap().attempts, 9);
+    queue.add(lookalike).unwrap();
+
+    let meta = queue.peek_metadata().unwrap().unwrap();
+    assert_eq!(meta.enqueued_at, full_envelope().created_at);
+    assert_eq!((meta.attempts, meta.tag), (3, Some("orders".to_string())));
+
+    queue.remove().unwrap();
+    let meta = queue.peek_metadata().unwrap().unwrap();
+    assert_eq!((meta.enqueued_at, meta.attempts, meta.tag), (None, 0, None));
+    assert_eq!(meta.size, 6);
+}
+
+#[test]
+fn unknown_fields_are_skipped() {
+    let mut bytes = encode_envelope(full_envelope());
+    // Field 15 as a varint, field 16 as bytes and field 17 as a fixed64.
+    bytes.extend_from_slice(&[0x78, 7, 0x82, 0x01, 2, 0, 0, 0x89, 0x01]);
+    bytes.extend_from_slice(&[0; 8]);
+    assert_eq!(decode_envelope(bytes).unwrap(), full_envelope());
+}
+
+#[test]
+fn malformed_messages_are_refused() {
+    let truncated = encode_envelope(full_envelope())[..5].to_vec();
+    for bytes in [truncated, vec![0x08, 0xff], vec![0x0d, 1, 2, 3, 4]] {
+        assert!(matches!(
+            decode_envelope(bytes),
+            Err(QueueFileError::InvalidEnvelope { .. })
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::Duration;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueOperation};
+
+const ADDERS: usize = 8;
+
+/// Syncs the queue has run on behalf of its adders.
+fn syncs(queue: &QueueFile) -> u64 {
+    let stats = queue.stats().unwrap();
+    let sync = stats
+        .latencies
+        .iter()
+        .find(|summary| summary.operation == QueueOperation::Sync);
+    sync.map_or(0, |summary| summary.count)
+}
+
+/// Adds one element from each of `ADDERS` threads released at the same moment.
+fn add_together(queue: &Arc<QueueFile>) {
+    let barrier = Arc::new(Barrier::new(ADDERS));
+    let adders: Vec<_> = (0..ADDERS)
+        .map(|n| {
+            let (queue, barrier) = (queue.clone(), barrier.clone());
+            thread::spawn(move || {
+                barrier.wait();
+                queue.add(vec![n as u8]).unwrap();
+            })
+        })
+        .collect();
+    adders.into_iter().for_each(|adder| adder.join().unwrap());
+}
+
+#[test]
+fn concurrent_adds_share_a_sync() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    let queue = QueueFile::open(path.display().to_string()).unwrap();
+    queue
+        .set_max_commit_delay(Some(Duration::from_millis(100)))
+        .unwrap();
+
+    add_together(&queue);
+    let syncs = syncs(&queue);
+    assert!(syncs >= 1 && syncs < ADDERS as u64, "{syncs} syncs");
+
+    let mut all = queue.get_all().unwrap();
+    all.sort();
+    assert_eq!(all, (0..ADDERS as u8).map(|n| vec![n]).collect::<Vec<_>>());
+    let header = std::fs::read(&path).unwrap();
+    assert_eq!(&header[12..16], &(ADDERS as u32).to_be_bytes());
+}
+
+#[test]
+fn adds_sync_themselves_without_a_commit_delay() {
+    let dir = TempDir::new().unwrap();
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+    assert_eq!(queue.max_commit_delay().unwrap(), None);
+
+    add_together(&queue);
+    assert_eq!(syncs(&queue), 0);
+    assert_eq!(queue.size().unwrap(), ADDERS as u32);
+}
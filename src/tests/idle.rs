@@ -0,0 +1,117 @@
+use std::fs;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tempfile::TempDir;
+
+use crate::{MirrorStatus, PauseMode, QueueFile, QueueFileError, QueueFileOptions};
+
+fn closing_after(millis: u64) -> QueueFileOptions {
+    QueueFileOptions {
+        idle_close_after: Some(Duration::from_millis(millis)),
+        ..Default::default()
+    }
+}
+
+fn open(path: &str, options: QueueFileOptions) -> Arc<QueueFile> {
+    QueueFile::open_with_options(path.to_string(), options).unwrap()
+}
+
+/// Whether the queue's file is closed for inactivity, looked at without reopening it.
+fn is_closed(queue: &QueueFile) -> bool {
+    queue.inner.lock().unwrap().queue.is_closed()
+}
+
+/// Polls `condition` for up to five seconds, for the flush timer closing the queue.
+fn eventually(condition: impl Fn() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !condition() {
+        if Instant::now() > deadline {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    true
+}
+
+#[test]
+fn idle_queue_closes_and_reopens_on_use() {
+    let dir = TempDir::new().unwrap();
+    let mirror = dir.path().join("mirror").display().to_string();
+    let queue = open(
+        &dir.path().join("q").display().to_string(),
+        QueueFileOptions {
+            mirror_path: Some(mirror.clone()),
+            element_index: true,
+            redundant_header: true,
+            ..closing_after(50)
+        },
+    );
+    queue.add_multiple(vec![vec![1], vec![2]]).unwrap();
+    assert!(eventually(|| is_closed(&queue)));
+
+    assert_eq!(queue.peek().unwrap(), Some(vec![1]));
+    assert!(!is_closed(&queue));
+    assert_eq!(queue.mirror_status().unwrap(), MirrorStatus::InSync);
+    queue.remove().unwrap();
+    queue.add(vec![3]).unwrap();
+    assert_eq!(queue.element_at(1).unwrap(), vec![3]);
+
+    assert!(eventually(|| is_closed(&queue)));
+    drop(queue);
+    // The flush timer may hold the queue for a moment after closing it.
+    assert!(eventually(|| {
+        QueueFile::open(mirror.clone())
+            .is_ok_and(|mirror| mirror.get_all().unwrap() == vec![vec![2], vec![3]])
+    }));
+}
+
+#[test]
+fn closed_handle_answers_from_its_settings() {
+    let dir = TempDir::new().unwrap();
+    let queue = open(
+        &dir.path().join("q").display().to_string(),
+        closing_after(50),
+    );
+    queue.add_multiple(vec![vec![1], vec![2], vec![3]]).unwrap();
+    let used = queue.used_bytes().unwrap();
+    assert!(eventually(|| is_closed(&queue)));
+
+    let mut state = queue.inner.lock().unwrap();
+    assert_eq!(state.queue.size(), 3);
+    assert_eq!(state.queue.used_bytes(), used);
+    state.queue.set_sync_writes(false);
+    assert!(!state.queue.sync_writes());
+    assert!(matches!(
+        state.queue.get_mut(),
+        Err(QueueFileError::ClosedForInactivity)
+    ));
+}
+
+#[test]
+fn queue_holding_paused_adds_stays_open() {
+    let dir = TempDir::new().unwrap();
+    let queue = open(
+        &dir.path().join("q").display().to_string(),
+        closing_after(20),
+    );
+    queue.pause_writes(PauseMode::Buffer).unwrap();
+    queue.add(vec![1]).unwrap();
+    thread::sleep(Duration::from_millis(200));
+    assert!(!is_closed(&queue));
+    queue.resume_writes().unwrap();
+    assert_eq!(queue.get_all().unwrap(), vec![vec![1]]);
+}
+
+#[test]
+fn removed_file_is_not_created_again() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = open(&path, closing_after(50));
+    queue.add(vec![1]).unwrap();
+    assert!(eventually(|| is_closed(&queue)));
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(queue.size(), Err(QueueFileError::IoError { .. })));
+    assert!(fs::metadata(&path).is_err());
+}
@@ -0,0 +1,97 @@
+use std::fs;
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileOptions};
+
+fn open_indexed(path: &str) -> Arc<QueueFile> {
+    let options = QueueFileOptions {
+        element_index: true,
+        ..Default::default()
+    };
+    QueueFile::open_with_options(path.to_string(), options).unwrap()
+}
+
+/// Checks `element_at` against a full read of the queue.
+fn assert_positions(queue: &QueueFile) {
+    let all = queue.get_all().unwrap();
+    for (index, element) in all.iter().enumerate() {
+        assert_eq!(
+            &queue.element_at(index as u32).unwrap(),
+            element,
+            "at {index}"
+        );
+    }
+    assert!(queue.element_at(all.len() as u32).is_err());
+}
+
+/// Adds and removes elements of varying length until the ring has wrapped and grown.
+fn churn(queue: &QueueFile) {
+    for n in 0..200u32 {
+        queue
+            .add(vec![n as u8; (n as usize * 37) % 300 + 1])
+            .unwrap();
+        if n % 7 == 6 {
+            queue.remove_n(4).unwrap();
+        }
+    }
+}
+
+#[test]
+fn index_follows_changes() {
+    let dir = TempDir::new().unwrap();
+    let queue = open_indexed(&dir.path().join("q").display().to_string());
+    churn(&queue);
+    assert_positions(&queue);
+    queue.remove_at(2).unwrap();
+    assert_positions(&queue);
+    queue.update_at(1, vec![9; 999]).unwrap();
+    assert_positions(&queue);
+    queue.clear().unwrap();
+    queue.add(vec![5; 5000]).unwrap();
+    assert_positions(&queue);
+}
+
+#[test]
+fn saved_index_restores_generation_and_seq() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = open_indexed(&path);
+    churn(&queue);
+    let generation = queue.generation().unwrap();
+    let next_seq = queue.add(vec![1]).unwrap().seq + 1;
+    drop(queue);
+    assert!(fs::metadata(format!("{path}.idx")).is_ok());
+
+    let queue = open_indexed(&path);
+    assert_eq!(queue.generation().unwrap(), generation + 1);
+    assert_eq!(queue.add(vec![2]).unwrap().seq, next_seq);
+    assert_positions(&queue);
+}
+
+#[test]
+fn stale_or_damaged_index_is_rebuilt() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = open_indexed(&path);
+    churn(&queue);
+    drop(queue);
+
+    // Changed without the index, which no longer matches the header.
+    let queue = QueueFile::open(path.clone()).unwrap();
+    queue.remove_n(3).unwrap();
+    queue.add(vec![7; 77]).unwrap();
+    drop(queue);
+    let queue = open_indexed(&path);
+    assert_eq!(
+        queue.element_at(queue.size().unwrap() - 1).unwrap(),
+        vec![7; 77]
+    );
+    assert_positions(&queue);
+    drop(queue);
+
+    fs::write(format!("{path}.idx"), b"junk").unwrap();
+    let queue = open_indexed(&path);
+    assert_positions(&queue);
+}
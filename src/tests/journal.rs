@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::{Change, QueueFile, QueueFileError, QueueFileOptions};
+
+fn journaling(dir: &TempDir, capacity: u32) -> Arc<QueueFile> {
+    let options = QueueFileOptions {
+        journal_capacity: Some(capacity),
+        ..Default::default()
+    };
+    let path = dir.path().join("q").display().to_string();
+    QueueFile::open_with_options(path, options).unwrap()
+}
+
+/// Makes three changes: an add of one element, one of two, and a removal.
+fn three_changes(queue: &QueueFile) {
+    queue.add(b"a".to_vec()).unwrap();
+    queue
+        .add_multiple(vec![b"bb".to_vec(), b"cc".to_vec()])
+        .unwrap();
+    queue.remove().unwrap();
+}
+
+#[test]
+fn changes_come_back_in_order_by_generation() {
+    let dir = TempDir::new().unwrap();
+    let queue = journaling(&dir, 10);
+    let start = queue.generation().unwrap();
+    three_changes(&queue);
+
+    let records = queue.changes_since(start).unwrap();
+    let changes: Vec<_> = records.iter().map(|record| record.change.clone()).collect();
+    assert_eq!(
+        changes,
+        vec![
+            Change::Add { count: 1, bytes: 1 },
+            Change::Add { count: 2, bytes: 4 },
+            Change::Remove { count: 1 },
+        ]
+    );
+    let seqs: Vec<_> = records.iter().map(|record| record.seq).collect();
+    assert_eq!(seqs, vec![start + 1, start + 2, start + 3]);
+    assert_eq!(queue.generation().unwrap(), start + 3);
+
+    assert!(queue.changes_since(start + 3).unwrap().is_empty());
+    assert_eq!(queue.changes_since(start + 2).unwrap().len(), 1);
+}
+
+#[test]
+fn dropped_changes_are_a_gap() {
+    let dir = TempDir::new().unwrap();
+    let queue = journaling(&dir, 2);
+    let start = queue.generation().unwrap();
+    three_changes(&queue);
+
+    assert!(matches!(
+        queue.changes_since(start),
+        Err(QueueFileError::JournalGap { since, earliest }) if since == start && earliest == start + 1
+    ));
+    assert_eq!(queue.changes_since(start + 1).unwrap().len(), 2);
+}
+
+#[test]
+fn generation_not_yet_reached_is_a_gap() {
+    let dir = TempDir::new().unwrap();
+    let queue = journaling(&dir, 10);
+    queue.add(b"a".to_vec()).unwrap();
+    let now = queue.generation().unwrap();
+
+    assert!(matches!(
+        queue.changes_since(now + 1),
+        Err(QueueFileError::JournalGap { .. })
+    ));
+}
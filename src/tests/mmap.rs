@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use tempfile::TempDir;
+
+use crate::{MappedQueue, QueueFile};
+
+/// Fills the queue at `path`, compressing all but the first elements against a dictionary, and
+/// returns the payloads it holds.
+fn compressed_queue(path: &Path) -> Vec<Vec<u8>> {
+    let queue = QueueFile::open(path.display().to_string()).unwrap();
+    let event = |n: u32| format!(r#"{{"id":{n},"event":"page_view","path":"/p/{}"}}"#, n % 7);
+    let mut elements: Vec<_> = (0..300).map(|n| event(n).into_bytes()).collect();
+    queue.add_multiple(elements.clone()).unwrap();
+    queue.train_dictionary(300).unwrap();
+    let later: Vec<_> = (300..350).map(|n| event(n).into_bytes()).collect();
+    queue.add_multiple(later.clone()).unwrap();
+    // Framed on the way in, so the mapping must strip that too.
+    let lookalike = b"QFCK\0\0\0\0\0\0\0\x03user data".to_vec();
+    queue.add(lookalike.clone()).unwrap();
+    elements.extend(later);
+    elements.push(lookalike);
+
+    let zstd_frame = [0x28, 0xb5, 0x2f, 0xfd];
+    let stored = std::fs::read(path).unwrap();
+    assert!(stored.windows(4).any(|window| window == zstd_frame));
+    elements
+}
+
+#[test]
+fn compressed_elements_are_decoded() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    let elements = compressed_queue(&path);
+
+    let mapped = MappedQueue::open_snapshot_mmap(path.display().to_string()).unwrap();
+    assert_eq!(mapped.size(), elements.len() as u32);
+    assert_eq!(mapped.get_all().unwrap(), elements);
+    assert_eq!(mapped.element_at(320).unwrap(), elements[320]);
+    assert_eq!(mapped.next().unwrap(), Some(elements[0].clone()));
+}
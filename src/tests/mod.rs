@@ -0,0 +1,27 @@
+//! Behaviour tests of the exported API, one file per feature, run against real files.
+
+mod add_from_file;
+mod add_multiple;
+mod archive;
+mod chunking;
+mod consumer;
+mod deferred;
+mod envelope;
+mod group_commit;
+mod idle;
+mod index;
+mod journal;
+mod mmap;
+mod msgpack;
+mod on_corruption;
+mod pool;
+mod quarantine;
+mod rate_limit;
+mod remove_at;
+mod rewrite;
+mod runtime;
+mod signature;
+mod sqlite;
+mod swap;
+mod update_at;
+mod write_buffer;
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use tempfile::TempDir;
+
+use crate::{MsgpackValue, QueueFile, QueueFileError, decode_msgpack_map, encode_msgpack_map};
+
+fn int(value: i64) -> MsgpackValue {
+    MsgpackValue::Int { value }
+}
+
+fn string(value: &str) -> MsgpackValue {
+    MsgpackValue::String {
+        value: value.to_string(),
+    }
+}
+
+/// One entry of every kind of value, integers at the edges of their encodings.
+fn every_kind() -> HashMap<String, MsgpackValue> {
+    let ints = [
+        0,
+        127,
+        128,
+        255,
+        256,
+        65_536,
+        1 << 32,
+        i64::MAX,
+        -1,
+        -32,
+        -33,
+        -129,
+        i64::MIN,
+    ];
+    HashMap::from([
+        ("nil".to_string(), MsgpackValue::Nil),
+        ("bool".to_string(), MsgpackValue::Bool { value: true }),
+        (
+            "ints".to_string(),
+            MsgpackValue::Array {
+                values: ints.into_iter().map(int).collect(),
+            },
+        ),
+        ("float".to_string(), MsgpackValue::Float { value: -1.5 }),
+        ("long".to_string(), string(&"s".repeat(300))),
+        (
+            "binary".to_string(),
+            MsgpackValue::Binary {
+                value: vec![0, 1, 2],
+            },
+        ),
+        (
+            "nested".to_string(),
+            MsgpackValue::Map {
+                entries: HashMap::from([("inner".to_string(), string("value"))]),
+            },
+        ),
+    ])
+}
+
+#[test]
+fn map_round_trips_through_the_queue() {
+    let dir = TempDir::new().unwrap();
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+    queue.add_msgpack_map(every_kind()).unwrap();
+
+    assert_eq!(queue.peek_msgpack_map().unwrap(), Some(every_kind()));
+}
+
+#[test]
+fn encoding_is_sorted_and_shortest() {
+    let map = HashMap::from([
+        (
+            "b".to_string(),
+            MsgpackValue::Array {
+                values: vec![MsgpackValue::Bool { value: true }, MsgpackValue::Nil],
+            },
+        ),
+        ("a".to_string(), int(1)),
+        ("c".to_string(), int(-200)),
+    ]);
+    let expected = [
+        0x83, 0xa1, b'a', 0x01, 0xa1, b'b', 0x92, 0xc3, 0xc0, 0xa1, b'c', 0xd1, 0xff, 0x38,
+    ];
+    assert_eq!(encode_msgpack_map(map.clone()), expected);
+    assert_eq!(decode_msgpack_map(expected.to_vec()).unwrap(), map);
+}
+
+#[test]
+fn wider_encodings_from_other_libraries_decode() {
+    // A 32-bit float and a 0xcd-encoded small integer, as other libraries may write them.
+    let bytes = vec![
+        0x82, 0xa1, b'f', 0xca, 0x3f, 0xc0, 0, 0, 0xa1, b'n', 0xcd, 0, 5,
+    ];
+    let map = decode_msgpack_map(bytes).unwrap();
+    assert_eq!(map["f"], MsgpackValue::Float { value: 1.5 });
+    assert_eq!(map["n"], int(5));
+}
+
+#[test]
+fn anything_but_a_string_keyed_map_is_refused() {
+    let mut deep = vec![0x91; 100];
+    deep.push(0xc0);
+    let refused = [
+        vec![0x92, 0x01, 0x02],
+        vec![0x81, 0x01, 0x02],
+        vec![0x80, 0x00],
+        vec![0x81, 0xa1, b'x', 0xd4, 0x01, 0x00],
+        vec![0x81, 0xa1, b'x', 0xcf, 0xff, 0, 0, 0, 0, 0, 0, 0],
+        vec![0x81, 0xa1, b'x', 0xa3, b'a'],
+        [vec![0x81, 0xa1, b'x'], deep].concat(),
+    ];
+    for bytes in refused {
+        assert!(
+            matches!(
+                decode_msgpack_map(bytes.clone()),
+                Err(QueueFileError::InvalidMessagePack { .. })
+            ),
+            "{bytes:02x?}"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::Path;
+
+use tempfile::TempDir;
+
+use crate::{CorruptionPolicy, OpenMode, QueueFile, QueueFileOptions};
+
+/// A queue file of three elements whose header claims it is several times its real length.
+fn corrupt_queue(path: &Path) -> Vec<u8> {
+    let queue = QueueFile::open(path.display().to_string()).unwrap();
+    queue
+        .add_multiple(vec![vec![1; 8], vec![2; 8], vec![3; 8]])
+        .unwrap();
+    drop(queue);
+    let mut bytes = fs::read(path).unwrap();
+    // Element count at 12..16: more elements than the ring could hold.
+    bytes[12..16].copy_from_slice(&1000u32.to_be_bytes());
+    fs::write(path, &bytes).unwrap();
+    bytes
+}
+
+fn on_corruption(policy: CorruptionPolicy) -> QueueFileOptions {
+    QueueFileOptions {
+        on_corruption: Some(policy),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn start_fresh_sets_the_file_aside_and_reports_it() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    let broken = corrupt_queue(&path);
+    let path = path.display().to_string();
+    let options = QueueFileOptions {
+        mode: Some(OpenMode::MustExist),
+        ..on_corruption(CorruptionPolicy::QuarantineAndStartFresh)
+    };
+
+    let queue = QueueFile::open_with_options(path.clone(), options.clone()).unwrap();
+    assert!(queue.is_empty().unwrap());
+    let recovery = queue.last_corruption_recovery().unwrap().unwrap();
+    assert!(recovery.moved_files[0].starts_with(&format!("{path}.corrupt-")));
+    assert_eq!(fs::read(&recovery.moved_files[0]).unwrap(), broken);
+    queue.add(vec![4]).unwrap();
+    drop(queue);
+
+    let queue = QueueFile::open_with_options(path, options).unwrap();
+    assert_eq!(queue.last_corruption_recovery().unwrap(), None);
+    assert_eq!(queue.get_all().unwrap(), vec![vec![4]]);
+}
+
+#[test]
+fn fail_leaves_the_file_alone() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    let broken = corrupt_queue(&path);
+    let options = QueueFileOptions {
+        verify_on_open: true,
+        ..on_corruption(CorruptionPolicy::Fail)
+    };
+    assert!(QueueFile::open_with_options(path.display().to_string(), options).is_err());
+    assert_eq!(fs::read(&path).unwrap(), broken);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn repair_in_place_keeps_what_it_can() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    let queue = QueueFile::open(path.display().to_string()).unwrap();
+    for n in 0..4 {
+        queue.add(vec![n; 10]).unwrap();
+    }
+    drop(queue);
+    let mut bytes = fs::read(&path).unwrap();
+    // The length prefix of the last element, after the 32-byte header and three 14-byte frames.
+    bytes[32 + 3 * 14] = 0xff;
+    fs::write(&path, &bytes).unwrap();
+
+    let options = on_corruption(CorruptionPolicy::RepairInPlace);
+    let queue = QueueFile::open_with_options(path.display().to_string(), options).unwrap();
+    assert_eq!(queue.size().unwrap(), 3);
+    assert_eq!(queue.last_quarantine().unwrap().unwrap().kept_elements, 3);
+    assert_eq!(queue.last_corruption_recovery().unwrap(), None);
+}
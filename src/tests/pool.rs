@@ -0,0 +1,107 @@
+use std::sync::Arc;
+use std::thread;
+
+use tempfile::TempDir;
+
+use crate::{PauseMode, QueueFile, QueueFileOptions, QueueManager};
+
+/// Opens `q<n>` in `dir` through `manager`.
+fn open_in(manager: &QueueManager, dir: &TempDir, n: u32) -> Arc<QueueFile> {
+    let path = dir.path().join(format!("q{n}")).display().to_string();
+    manager.open(path, QueueFileOptions::default()).unwrap()
+}
+
+/// Whether the manager closed the queue's file, looked at without reopening it.
+fn is_closed(queue: &QueueFile) -> bool {
+    queue.inner.lock().unwrap().queue.is_closed()
+}
+
+#[test]
+fn manager_keeps_at_most_the_cap_open() {
+    let dir = TempDir::new().unwrap();
+    let manager = QueueManager::new(3);
+    let queues: Vec<_> = (0..10)
+        .map(|n| {
+            let queue = open_in(&manager, &dir, n);
+            queue.add(vec![n as u8]).unwrap();
+            assert!(manager.open_file_count() <= 3);
+            queue
+        })
+        .collect();
+    assert_eq!(manager.queue_count(), 10);
+    assert_eq!(manager.open_file_count(), 3);
+    for (n, queue) in queues.iter().enumerate() {
+        assert_eq!(queue.peek().unwrap(), Some(vec![n as u8]));
+        assert_eq!(manager.open_file_count(), 3);
+    }
+    drop(queues);
+    assert_eq!(manager.queue_count(), 0);
+}
+
+#[test]
+fn least_recently_used_queue_is_closed_first() {
+    let dir = TempDir::new().unwrap();
+    let manager = QueueManager::new(2);
+    let a = open_in(&manager, &dir, 0);
+    let b = open_in(&manager, &dir, 1);
+    a.size().unwrap();
+    let c = open_in(&manager, &dir, 2);
+    assert!(!is_closed(&a));
+    assert!(is_closed(&b));
+    assert!(!is_closed(&c));
+}
+
+#[test]
+fn queue_that_cannot_close_is_passed_over() {
+    let dir = TempDir::new().unwrap();
+    let manager = QueueManager::new(2);
+    let a = open_in(&manager, &dir, 0);
+    a.pause_writes(PauseMode::Buffer).unwrap();
+    a.add(vec![1]).unwrap();
+    let b = open_in(&manager, &dir, 1);
+    let c = open_in(&manager, &dir, 2);
+    assert!(!is_closed(&a));
+    assert!(is_closed(&b));
+    assert!(!is_closed(&c));
+    assert_eq!(manager.open_file_count(), 2);
+
+    a.resume_writes().unwrap();
+    assert_eq!(a.get_all().unwrap(), vec![vec![1]]);
+}
+
+#[test]
+fn queue_in_use_is_passed_over() {
+    let dir = TempDir::new().unwrap();
+    let manager = QueueManager::new(2);
+    let a = open_in(&manager, &dir, 0);
+    let b = open_in(&manager, &dir, 1);
+    // Another thread is in the middle of a call on `a`, the least recently used.
+    let busy = a.inner.lock().unwrap();
+    let c = open_in(&manager, &dir, 2);
+    drop(busy);
+    assert!(!is_closed(&a));
+    assert!(is_closed(&b));
+    assert!(!is_closed(&c));
+    assert_eq!(manager.open_file_count(), 2);
+}
+
+#[test]
+fn cap_holds_under_contention() {
+    let dir = TempDir::new().unwrap();
+    let manager = QueueManager::new(3);
+    let queues: Vec<_> = (0..8).map(|n| open_in(&manager, &dir, n)).collect();
+    thread::scope(|scope| {
+        for worker in 0..4 {
+            let queues = &queues;
+            scope.spawn(move || {
+                for round in 0..50 {
+                    let queue = &queues[(worker * 3 + round) % queues.len()];
+                    queue.add(vec![worker as u8]).unwrap();
+                }
+            });
+        }
+    });
+    let total: u32 = queues.iter().map(|queue| queue.size().unwrap()).sum();
+    assert_eq!(total, 200);
+    assert!(manager.open_file_count() <= 3);
+}
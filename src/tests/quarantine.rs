@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::Path;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileError, QueueFileOptions};
+
+/// Bytes before the first element of a file queue-file creates.
+const HEADER_LEN: usize = 32;
+/// Offset of the third of the 10-byte elements `break_third_frame` writes, each framed by a
+/// 4-byte length.
+const THIRD_FRAME: usize = HEADER_LEN + 2 * 14;
+
+/// Writes five 10-byte elements to `path`, then breaks the length prefix of the third one so
+/// the frames from there on cannot be located.
+fn break_third_frame(path: &Path) {
+    let queue = QueueFile::open(path.display().to_string()).unwrap();
+    for n in 0..5 {
+        queue.add(vec![n; 10]).unwrap();
+    }
+    drop(queue);
+    let mut bytes = fs::read(path).unwrap();
+    bytes[THIRD_FRAME] = 0xff;
+    fs::write(path, &bytes).unwrap();
+}
+
+fn quarantining() -> QueueFileOptions {
+    QueueFileOptions {
+        quarantine_corrupt: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn verify_on_open_reports_the_broken_frame() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    break_third_frame(&path);
+    let options = QueueFileOptions {
+        verify_on_open: true,
+        ..Default::default()
+    };
+    match QueueFile::open_with_options(path.display().to_string(), options) {
+        Err(QueueFileError::CorruptedFile {
+            offset,
+            element_index,
+            ..
+        }) => {
+            assert_eq!(offset, Some(THIRD_FRAME as u64));
+            assert_eq!(element_index, Some(2));
+        }
+        other => panic!("expected CorruptedFile, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn quarantine_opens_with_the_readable_elements() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    break_third_frame(&path);
+
+    let queue = QueueFile::open_with_options(path.display().to_string(), quarantining()).unwrap();
+    assert_eq!(queue.get_all().unwrap(), vec![vec![0; 10], vec![1; 10]]);
+    let report = queue.last_quarantine().unwrap().unwrap();
+    assert_eq!(report.kept_elements, 2);
+    assert_eq!(report.ranges.len(), 1);
+    assert_eq!(report.ranges[0].index, 2);
+    assert_eq!(report.ranges[0].offset, THIRD_FRAME as u64);
+    assert_eq!(report.ranges[0].elements, 3);
+    queue.add(vec![9]).unwrap();
+}
+
+#[test]
+fn quarantine_file_records_offset_and_raw_bytes() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    break_third_frame(&path);
+    let broken = fs::read(&path).unwrap();
+
+    let queue = QueueFile::open_with_options(path.display().to_string(), quarantining()).unwrap();
+    let report = queue.last_quarantine().unwrap().unwrap();
+    drop(queue);
+    let quarantined = QueueFile::open(report.quarantine_path).unwrap();
+    let record = quarantined.peek().unwrap().unwrap();
+    assert_eq!(record[..8], (THIRD_FRAME as u64).to_be_bytes());
+    let raw = &record[8..];
+    assert_eq!(raw.len() as u64, report.ranges[0].bytes);
+    assert_eq!(raw, &broken[THIRD_FRAME..THIRD_FRAME + raw.len()]);
+}
+
+#[test]
+fn repaired_file_reopens_clean() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q");
+    break_third_frame(&path);
+    let path = path.display().to_string();
+    drop(QueueFile::open_with_options(path.clone(), quarantining()).unwrap());
+
+    let queue = QueueFile::open_with_options(path, quarantining()).unwrap();
+    assert_eq!(queue.last_quarantine().unwrap(), None);
+    assert_eq!(queue.size().unwrap(), 2);
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileError, QueueFileOptions, RateLimit};
+
+fn limited(dir: &TempDir, limit: RateLimit) -> Arc<QueueFile> {
+    let options = QueueFileOptions {
+        rate_limit: Some(limit),
+        ..Default::default()
+    };
+    let path = dir.path().join("q").display().to_string();
+    QueueFile::open_with_options(path, options).unwrap()
+}
+
+fn retry_after(result: Result<impl Sized, QueueFileError>) -> Duration {
+    match result {
+        Err(QueueFileError::Backpressure { retry_after }) => retry_after,
+        Err(e) => panic!("expected Backpressure, got {e:?}"),
+        Ok(_) => panic!("expected Backpressure, the add went through"),
+    }
+}
+
+#[test]
+fn adds_past_the_burst_get_backpressure_and_add_nothing() {
+    let dir = TempDir::new().unwrap();
+    let queue = limited(
+        &dir,
+        RateLimit {
+            max_adds_per_sec: Some(5),
+            max_bytes_per_sec: None,
+        },
+    );
+    for n in 0..5u8 {
+        queue.add(vec![n]).unwrap();
+    }
+
+    let wait = retry_after(queue.add(vec![5]));
+    assert!(wait > Duration::ZERO && wait <= Duration::from_millis(200));
+    assert_eq!(queue.size().unwrap(), 5);
+}
+
+#[test]
+fn bucket_refills_at_the_configured_rate() {
+    let dir = TempDir::new().unwrap();
+    let queue = limited(
+        &dir,
+        RateLimit {
+            max_adds_per_sec: Some(10),
+            max_bytes_per_sec: None,
+        },
+    );
+    queue.add_multiple(vec![vec![0]; 10]).unwrap();
+    let wait = retry_after(queue.add(vec![1]));
+
+    thread::sleep(wait + Duration::from_millis(20));
+    queue.add(vec![1]).unwrap();
+    assert!(matches!(
+        queue.add(vec![2]),
+        Err(QueueFileError::Backpressure { .. })
+    ));
+}
+
+#[test]
+fn byte_limit_counts_payload_bytes() {
+    let dir = TempDir::new().unwrap();
+    let queue = limited(
+        &dir,
+        RateLimit {
+            max_adds_per_sec: None,
+            max_bytes_per_sec: Some(1000),
+        },
+    );
+    queue.add(vec![0; 600]).unwrap();
+
+    let wait = retry_after(queue.add(vec![0; 600]));
+    assert!(wait >= Duration::from_millis(150));
+    queue.add(vec![0; 300]).unwrap();
+    assert_eq!(queue.size().unwrap(), 2);
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileError};
+
+fn letters(queue: &QueueFile) -> String {
+    let all = queue.get_all().unwrap();
+    all.iter().map(|element| element[0] as char).collect()
+}
+
+fn queue_of(dir: &TempDir, letters: &str) -> Arc<QueueFile> {
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+    queue
+        .add_multiple(letters.bytes().map(|b| vec![b]).collect())
+        .unwrap();
+    queue
+}
+
+#[test]
+fn removes_from_the_middle_keeping_the_order() {
+    let dir = TempDir::new().unwrap();
+    let queue = queue_of(&dir, "abcde");
+
+    queue.remove_at(2).unwrap();
+    assert_eq!(letters(&queue), "abde");
+    queue.remove_at(0).unwrap();
+    queue.remove_at(2).unwrap();
+    assert_eq!(letters(&queue), "bd");
+
+    drop(queue);
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+    assert_eq!(letters(&queue), "bd");
+}
+
+#[test]
+fn index_past_the_end_is_refused() {
+    let dir = TempDir::new().unwrap();
+    let queue = queue_of(&dir, "abc");
+
+    assert!(matches!(
+        queue.remove_at(3),
+        Err(QueueFileError::IndexOutOfRange { index: 3, size: 3 })
+    ));
+    assert_eq!(letters(&queue), "abc");
+}
+
+#[test]
+fn counts_from_the_eldest_of_a_wrapped_ring() {
+    let dir = TempDir::new().unwrap();
+    let queue = queue_of(&dir, "");
+    // Big enough elements that the later ones wrap around the 4 KiB ring.
+    let element = |b: u8| vec![b; 1000];
+    queue
+        .add_multiple(vec![element(b'x'), element(b'y'), element(b'a')])
+        .unwrap();
+    queue.remove_n(2).unwrap();
+    queue
+        .add_multiple(vec![element(b'b'), element(b'c')])
+        .unwrap();
+    assert_eq!(queue.file_len().unwrap(), 4096);
+
+    queue.remove_at(1).unwrap();
+    assert_eq!(letters(&queue), "ac");
+}
@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, RewriteOptions};
+
+/// A JSON event, similar enough to the others for a dictionary to pay off.
+fn event(n: u32) -> Vec<u8> {
+    format!(
+        r#"{{"id":{n},"user":"user-{}","event":"page_view","path":"/products/{}"}}"#,
+        n % 17,
+        n % 31
+    )
+    .into_bytes()
+}
+
+/// A plain queue at `path`, as an older app version created it, holding `count` events.
+fn queue_of_events(path: &str, count: u32) -> Arc<QueueFile> {
+    let queue = QueueFile::open(path.to_string()).unwrap();
+    queue.add_multiple((0..count).map(event).collect()).unwrap();
+    queue
+}
+
+fn assert_events(queue: &QueueFile, count: u32) {
+    let all = queue.get_all().unwrap();
+    assert_eq!(all.len(), count as usize);
+    for (n, element) in all.iter().enumerate() {
+        assert_eq!(element, &event(n as u32));
+    }
+}
+
+#[test]
+fn rewrite_compresses_and_keeps_payloads() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = queue_of_events(&path, 2000);
+
+    let report = queue.rewrite(RewriteOptions::default()).unwrap();
+    assert_eq!(report.elements, 2000);
+    assert!(report.bytes_after < report.bytes_before);
+    assert!(report.trained.is_some());
+    assert_events(&queue, 2000);
+
+    queue.add(event(2000)).unwrap();
+    drop(queue);
+    let queue = QueueFile::open(path).unwrap();
+    assert_events(&queue, 2001);
+}
+
+#[test]
+fn rewrite_without_compression_stores_payloads_as_added() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = queue_of_events(&path, 500);
+    let plain = queue.used_bytes().unwrap();
+    queue.rewrite(RewriteOptions::default()).unwrap();
+
+    let report = queue
+        .rewrite(RewriteOptions {
+            compress: false,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(report.bytes_after, plain);
+    assert!(!Path::new(&format!("{path}.dict")).exists());
+    assert_events(&queue, 500);
+}
@@ -0,0 +1,46 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread::{self, Thread};
+
+use crate::QueueFileError;
+use crate::runtime;
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Polls `future` on the calling thread, parking it until the worker wakes it.
+fn block_on<T>(future: impl Future<Output = T>) -> T {
+    let waker = Arc::new(Unpark(thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(value) => return value,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn job_result_is_delivered() {
+    assert_eq!(block_on(runtime::run(|| Ok(7))).unwrap(), 7);
+}
+
+#[test]
+fn panicking_job_resolves_to_lock_error() {
+    let result = block_on(runtime::run(|| -> Result<(), QueueFileError> {
+        panic!("job")
+    }));
+    assert!(matches!(result, Err(QueueFileError::LockError)));
+    // The worker that caught the panic goes on running jobs.
+    for n in 0..4 {
+        assert_eq!(block_on(runtime::run(move || Ok(n))).unwrap(), n);
+    }
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::{KeyProvider, QueueFile, QueueFileError, QueueFileOptions, set_key_provider};
+
+struct Keys;
+
+impl KeyProvider for Keys {
+    fn key(&self, key_id: String) -> Option<Vec<u8>> {
+        (key_id == "audit").then(|| b"secret".to_vec())
+    }
+}
+
+/// Opens `path` signed with `key_id`. The provider is process-wide, so every test sets the
+/// same one.
+fn open_signed(path: &str, key_id: &str) -> Result<Arc<QueueFile>, QueueFileError> {
+    set_key_provider(Arc::new(Keys));
+    let options = QueueFileOptions {
+        signature_key: Some(key_id.to_string()),
+        ..Default::default()
+    };
+    QueueFile::open_with_options(path.to_string(), options)
+}
+
+#[test]
+fn signed_queue_reopens() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = open_signed(&path, "audit").unwrap();
+    queue.add(b"hello".to_vec()).unwrap();
+    queue.add(b"world".to_vec()).unwrap();
+    drop(queue);
+    assert!(fs::metadata(format!("{path}.sig")).is_ok());
+
+    let queue = open_signed(&path, "audit").unwrap();
+    queue.remove().unwrap();
+    queue.sync_all().unwrap();
+    drop(queue);
+    let queue = open_signed(&path, "audit").unwrap();
+    assert_eq!(queue.get_all().unwrap(), vec![b"world".to_vec()]);
+}
+
+#[test]
+fn edited_payload_fails_with_tamper_detected() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    let queue = open_signed(&path, "audit").unwrap();
+    queue.add(b"hello".to_vec()).unwrap();
+    queue.add(b"world".to_vec()).unwrap();
+    drop(queue);
+
+    let mut bytes = fs::read(&path).unwrap();
+    let at = bytes
+        .windows(5)
+        .position(|window| window == b"world")
+        .unwrap();
+    bytes[at] = b'W';
+    fs::write(&path, &bytes).unwrap();
+    let result = open_signed(&path, "audit");
+    assert!(matches!(result, Err(QueueFileError::TamperDetected { .. })));
+
+    bytes[at] = b'w';
+    fs::write(&path, &bytes).unwrap();
+    assert_eq!(open_signed(&path, "audit").unwrap().size().unwrap(), 2);
+}
+
+#[test]
+fn elements_added_without_the_key_fail_with_tamper_detected() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("q").display().to_string();
+    drop(open_signed(&path, "audit").unwrap());
+    QueueFile::open(path.clone()).unwrap().add(vec![1]).unwrap();
+    let result = open_signed(&path, "audit");
+    assert!(matches!(result, Err(QueueFileError::TamperDetected { .. })));
+}
+
+#[test]
+fn unknown_key_fails_with_key_unavailable() {
+    let dir = TempDir::new().unwrap();
+    let result = open_signed(&dir.path().join("q").display().to_string(), "missing");
+    assert!(matches!(result, Err(QueueFileError::KeyUnavailable { .. })));
+}
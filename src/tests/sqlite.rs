@@ -0,0 +1,102 @@
+use std::path::Path;
+
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileError};
+
+/// An old app store with a pending event of every SQLite type, inserted out of `rowid` order.
+fn legacy_store(path: &Path) {
+    let db = Connection::open(path).unwrap();
+    db.execute_batch(
+        r#"CREATE TABLE "pending ""events""" (rowid INTEGER PRIMARY KEY, body);
+        INSERT INTO "pending ""events""" VALUES (3, x'00ff');
+        INSERT INTO "pending ""events""" VALUES (1, 'first');
+        INSERT INTO "pending ""events""" VALUES (2, 42);
+        INSERT INTO "pending ""events""" VALUES (4, NULL);
+        INSERT INTO "pending ""events""" VALUES (5, 2.5);"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn import_adds_values_in_rowid_order() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("store.db");
+    legacy_store(&db);
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+
+    let added = queue
+        .import_from_sqlite(
+            db.display().to_string(),
+            "pending \"events\"".to_string(),
+            "body".to_string(),
+        )
+        .unwrap();
+    assert_eq!(added, 4);
+    assert_eq!(
+        queue.get_all().unwrap(),
+        vec![
+            b"first".to_vec(),
+            b"42".to_vec(),
+            vec![0, 0xff],
+            b"2.5".to_vec()
+        ]
+    );
+}
+
+#[test]
+fn export_writes_rows_that_import_reads_back() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("out.db").display().to_string();
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+    let elements = vec![b"a".to_vec(), vec![0; 3], b"{\"id\":1}".to_vec()];
+    queue.add_multiple(elements.clone()).unwrap();
+
+    assert_eq!(
+        queue
+            .export_to_sqlite(db.clone(), "events".to_string())
+            .unwrap(),
+        3
+    );
+    assert_eq!(
+        queue
+            .export_to_sqlite(db.clone(), "events".to_string())
+            .unwrap(),
+        3
+    );
+    assert_eq!(queue.size().unwrap(), 3);
+
+    let copy = QueueFile::open(dir.path().join("copy").display().to_string()).unwrap();
+    let imported = copy
+        .import_from_sqlite(db, "events".to_string(), "data".to_string())
+        .unwrap();
+    assert_eq!(imported, 6);
+    assert_eq!(
+        copy.get_all().unwrap(),
+        [elements.clone(), elements].concat()
+    );
+}
+
+#[test]
+fn missing_table_names_the_database() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("empty.db");
+    Connection::open(&db).unwrap();
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+
+    let err = queue
+        .import_from_sqlite(
+            db.display().to_string(),
+            "nowhere".to_string(),
+            "data".to_string(),
+        )
+        .unwrap_err();
+    match err {
+        QueueFileError::SqliteError { path, .. } => {
+            assert_eq!(path, Some(db.display().to_string()))
+        }
+        e => panic!("expected SqliteError, got {e:?}"),
+    }
+    assert!(queue.is_empty().unwrap());
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::thread;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileOptions};
+
+/// Queues `a` holding 1, 2 and 3 and `b` holding 7, in `dir`.
+fn pair(dir: &TempDir, b_options: QueueFileOptions) -> (Arc<QueueFile>, Arc<QueueFile>) {
+    let a = QueueFile::open(dir.path().join("a").display().to_string()).unwrap();
+    let b_path = dir.path().join("b").display().to_string();
+    let b = QueueFile::open_with_options(b_path, b_options).unwrap();
+    a.add_multiple(vec![vec![1], vec![2], vec![3]]).unwrap();
+    b.add(vec![7]).unwrap();
+    (a, b)
+}
+
+#[test]
+fn swap_exchanges_contents_and_keeps_paths() {
+    let dir = TempDir::new().unwrap();
+    let (a, b) = pair(&dir, QueueFileOptions::default());
+
+    a.swap_with(b.clone()).unwrap();
+    assert_eq!(a.get_all().unwrap(), vec![vec![7]]);
+    assert_eq!(b.get_all().unwrap(), vec![vec![1], vec![2], vec![3]]);
+
+    a.add(vec![8]).unwrap();
+    drop((a, b));
+    let a = QueueFile::open(dir.path().join("a").display().to_string()).unwrap();
+    assert_eq!(a.get_all().unwrap(), vec![vec![7], vec![8]]);
+}
+
+#[test]
+fn swap_with_itself_changes_nothing() {
+    let dir = TempDir::new().unwrap();
+    let (a, _) = pair(&dir, QueueFileOptions::default());
+    a.swap_with(a.clone()).unwrap();
+    assert_eq!(a.size().unwrap(), 3);
+}
+
+#[test]
+fn opposite_swaps_do_not_deadlock() {
+    let dir = TempDir::new().unwrap();
+    let (a, b) = pair(&dir, QueueFileOptions::default());
+
+    let (a2, b2) = (a.clone(), b.clone());
+    let other = thread::spawn(move || {
+        for _ in 0..100 {
+            b2.swap_with(a2.clone()).unwrap();
+        }
+    });
+    for _ in 0..101 {
+        a.swap_with(b.clone()).unwrap();
+    }
+    other.join().unwrap();
+    assert_eq!(a.get_all().unwrap(), vec![vec![7]]);
+    assert_eq!(b.size().unwrap(), 3);
+}
+
+#[test]
+fn swap_mirrors_the_new_contents() {
+    let dir = TempDir::new().unwrap();
+    let mirror = dir.path().join("b-mirror").display().to_string();
+    let (a, b) = pair(
+        &dir,
+        QueueFileOptions {
+            mirror_path: Some(mirror.clone()),
+            ..Default::default()
+        },
+    );
+    b.swap_with(a.clone()).unwrap();
+    drop((a, b));
+    let mirror = QueueFile::open(mirror).unwrap();
+    assert_eq!(mirror.get_all().unwrap(), vec![vec![1], vec![2], vec![3]]);
+}
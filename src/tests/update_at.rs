@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, QueueFileError, QueueFileOptions, RewriteOptions};
+
+fn queue_of(dir: &TempDir, elements: &[&[u8]]) -> Arc<QueueFile> {
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+    queue
+        .add_multiple(elements.iter().map(|e| e.to_vec()).collect())
+        .unwrap();
+    queue
+}
+
+#[test]
+fn replaces_in_place_whatever_the_new_length() {
+    let dir = TempDir::new().unwrap();
+    let queue = queue_of(&dir, &[b"first", b"second", b"third"]);
+
+    queue.update_at(1, vec![b'x'; 3000]).unwrap();
+    queue.update_at(0, b"1".to_vec()).unwrap();
+    let all = queue.get_all().unwrap();
+    assert_eq!(
+        all,
+        vec![b"1".to_vec(), vec![b'x'; 3000], b"third".to_vec()]
+    );
+
+    drop(queue);
+    let queue = QueueFile::open(dir.path().join("q").display().to_string()).unwrap();
+    assert_eq!(queue.get_all().unwrap(), all);
+}
+
+#[test]
+fn index_past_the_end_is_refused() {
+    let dir = TempDir::new().unwrap();
+    let queue = queue_of(&dir, &[b"only"]);
+
+    assert!(matches!(
+        queue.update_at(1, b"new".to_vec()),
+        Err(QueueFileError::IndexOutOfRange { index: 1, size: 1 })
+    ));
+    assert_eq!(queue.get_all().unwrap(), vec![b"only".to_vec()]);
+}
+
+#[test]
+fn quota_counts_the_new_payload_as_stored() {
+    let dir = TempDir::new().unwrap();
+    let options = QueueFileOptions {
+        max_bytes: Some(64 * 1024),
+        ..Default::default()
+    };
+    let path = dir.path().join("q").display().to_string();
+    let queue = QueueFile::open_with_options(path, options).unwrap();
+    let event = |n: u32| format!(r#"{{"id":{n},"event":"page_view","path":"/p/{}"}}"#, n % 7);
+    queue
+        .add_multiple((0..500).map(|n| event(n).into_bytes()).collect())
+        .unwrap();
+    queue.rewrite(RewriteOptions::default()).unwrap();
+
+    // Over the quota as given, far under it once compressed.
+    let repetitive = event(0).repeat(2000).into_bytes();
+    queue.update_at(0, repetitive.clone()).unwrap();
+    assert_eq!(queue.peek().unwrap(), Some(repetitive));
+
+    let mut state = 1u32;
+    let noise = (0..64 * 1024)
+        .map(|_| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 16) as u8
+        })
+        .collect();
+    assert!(matches!(
+        queue.update_at(1, noise),
+        Err(QueueFileError::QuotaExceeded)
+    ));
+    assert_eq!(queue.get_all().unwrap()[1], event(1).into_bytes());
+}
@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tempfile::TempDir;
+
+use crate::{QueueFile, WriteBufferConfig};
+
+fn buffered(dir: &TempDir, config: WriteBufferConfig) -> (Arc<QueueFile>, PathBuf) {
+    let path = dir.path().join("q");
+    let queue = QueueFile::open(path.display().to_string()).unwrap();
+    queue.set_write_buffering(Some(config)).unwrap();
+    (queue, path)
+}
+
+/// Elements the committed header of the file at `path` counts, whatever the handle holds back.
+fn committed(path: &Path) -> u32 {
+    let header = std::fs::read(path).unwrap();
+    u32::from_be_bytes(header[12..16].try_into().unwrap())
+}
+
+fn config(max_bytes: u64, max_items: Option<u32>) -> WriteBufferConfig {
+    WriteBufferConfig {
+        max_bytes,
+        max_delay: Duration::from_secs(3600),
+        max_items,
+    }
+}
+
+#[test]
+fn adds_stay_in_memory_until_flush() {
+    let dir = TempDir::new().unwrap();
+    let (queue, path) = buffered(&dir, config(1 << 20, None));
+    queue.add(b"a".to_vec()).unwrap();
+    queue.add(b"b".to_vec()).unwrap();
+
+    assert_eq!(committed(&path), 0);
+    assert_eq!(queue.size().unwrap(), 2);
+    queue.flush().unwrap();
+    assert_eq!(committed(&path), 2);
+    assert_eq!(queue.get_all().unwrap(), vec![b"a".to_vec(), b"b".to_vec()]);
+}
+
+#[test]
+fn buffer_flushes_itself_when_full() {
+    let dir = TempDir::new().unwrap();
+    let (queue, path) = buffered(&dir, config(10, Some(3)));
+    queue.add(b"1".to_vec()).unwrap();
+    queue.add(b"2".to_vec()).unwrap();
+    assert_eq!(committed(&path), 0);
+    queue.add(b"3".to_vec()).unwrap();
+    assert_eq!(committed(&path), 3);
+
+    queue.add(vec![0; 10]).unwrap();
+    assert_eq!(committed(&path), 4);
+}
+
+#[test]
+fn buffer_flushes_after_its_delay() {
+    let dir = TempDir::new().unwrap();
+    let (queue, path) = buffered(
+        &dir,
+        WriteBufferConfig {
+            max_bytes: 1 << 20,
+            max_delay: Duration::from_millis(50),
+            max_items: None,
+        },
+    );
+    queue.add(b"late".to_vec()).unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while committed(&path) == 0 {
+        assert!(Instant::now() < deadline, "buffer was never flushed");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn turning_buffering_off_flushes() {
+    let dir = TempDir::new().unwrap();
+    let (queue, path) = buffered(&dir, config(1 << 20, None));
+    queue.add(b"held".to_vec()).unwrap();
+
+    queue.set_write_buffering(None).unwrap();
+    assert_eq!(committed(&path), 1);
+    assert_eq!(queue.write_buffering().unwrap(), None);
+    queue.add(b"direct".to_vec()).unwrap();
+    assert_eq!(committed(&path), 2);
+}
@@ -0,0 +1,290 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::{QueueFileError, fragmentation};
+
+pub(crate) const VERSIONED_HEADER: u32 = 0x8000_0001;
+
+/// The header fields of a queue file, in either format.
+pub(crate) struct Header {
+    pub(crate) header_len: u64,
+    pub(crate) file_len: u64,
+    pub(crate) count: u64,
+    pub(crate) first: u64,
+    pub(crate) last: u64,
+}
+
+impl Header {
+    /// Reads a versioned header, or a legacy one from the first 16 bytes.
+    pub(crate) fn parse(bytes: &[u8; 32]) -> Header {
+        let be = |at: usize, len: usize| {
+            bytes[at..at + len]
+                .iter()
+                .fold(0u64, |acc, &b| (acc << 8) | u64::from(b))
+        };
+        match be(0, 4) == u64::from(VERSIONED_HEADER) {
+            true => Header {
+                header_len: 32,
+                file_len: be(4, 8),
+                count: be(12, 4),
+                first: be(16, 8),
+                last: be(24, 8),
+            },
+            false => Header {
+                header_len: 16,
+                file_len: be(0, 4),
+                count: be(4, 4),
+                first: be(8, 4),
+                last: be(12, 4),
+            },
+        }
+    }
+}
+
+/// An element frame: the position of its length prefix and its payload length.
+#[derive(Clone, Copy)]
+pub(crate) struct Frame {
+    pub(crate) pos: u64,
+    pub(crate) len: u64,
+}
+
+/// A problem found in a queue file, with the fields of `QueueFileError::CorruptedFile`.
+pub(crate) struct Corruption {
+    pub(crate) message: String,
+    pub(crate) offset: Option<u64>,
+    pub(crate) element_index: Option<u64>,
+    pub(crate) expected: Option<String>,
+    pub(crate) found: Option<String>,
+    pub(crate) salvageable: bool,
+}
+
+impl Corruption {
+    /// A header that describes no usable ring, so no element can be recovered.
+    pub(crate) fn header(message: String, expected: String, found: String) -> Self {
+        Corruption {
+            message,
+            offset: Some(0),
+            element_index: None,
+            expected: Some(expected),
+            found: Some(found),
+            salvageable: false,
+        }
+    }
+
+    /// A damaged element: quarantining opens the queue with the elements before it.
+    pub(crate) fn element(
+        index: u64,
+        offset: u64,
+        problem: &str,
+        expected: String,
+        found: String,
+    ) -> Self {
+        Corruption {
+            message: format!("element {index} {problem}"),
+            offset: Some(offset),
+            element_index: Some(index),
+            expected: Some(expected),
+            found: Some(found),
+            salvageable: true,
+        }
+    }
+
+    pub(crate) fn into_error(self, path: &Path) -> QueueFileError {
+        QueueFileError::CorruptedFile {
+            message: format!("{}: {}", path.display(), self.message),
+            offset: self.offset,
+            element_index: self.element_index,
+            expected: self.expected,
+            found: self.found,
+            salvageable: self.salvageable,
+        }
+    }
+}
+
+/// Where the chain of frames stops making sense.
+pub(crate) struct Break {
+    /// Number of elements before the broken one.
+    pub(crate) index: u64,
+    pub(crate) pos: u64,
+    pub(crate) problem: Corruption,
+}
+
+/// The element frames of a queue file, followed from its header.
+pub(crate) struct Scan {
+    file: File,
+    header: Header,
+    /// Every frame before the break, eldest first.
+    pub(crate) frames: Vec<Frame>,
+    pub(crate) broken: Option<Break>,
+}
+
+impl Scan {
+    /// Follows the chain of element frames of the file at `path`. The inner error is a header
+    /// that describes no ring at all; the outer one is an I/O failure that is not the file's
+    /// fault.
+    pub(crate) fn open(path: &Path) -> io::Result<Result<Scan, Corruption>> {
+        let mut file = File::open(path)?;
+        let physical_len = file.metadata()?.len();
+        let mut bytes = [0; 32];
+        let available = (physical_len as usize).min(bytes.len());
+        file.read_exact(&mut bytes[..available])?;
+        let header = Header::parse(&bytes);
+        let (header_len, file_len) = (header.header_len, header.file_len);
+        if physical_len < header_len {
+            return Ok(Err(Corruption::header(
+                format!("file is {physical_len} bytes, shorter than its header"),
+                format!("file length >= {header_len}"),
+                format!("file length {physical_len}"),
+            )));
+        }
+        if file_len < header_len + 4 || file_len > physical_len {
+            return Ok(Err(Corruption::header(
+                format!("header file length {file_len} does not fit the {physical_len}-byte file"),
+                format!("header file length in {}..={physical_len}", header_len + 4),
+                format!("header file length {file_len}"),
+            )));
+        }
+        if header.count == 0 && (header.first != 0 || header.last != 0) {
+            return Ok(Err(Corruption::header(
+                format!(
+                    "empty queue has element offsets {} and {}",
+                    header.first, header.last
+                ),
+                "element offsets 0 and 0".to_string(),
+                format!("element offsets {} and {}", header.first, header.last),
+            )));
+        }
+
+        let mut scan = Scan {
+            file,
+            header,
+            frames: Vec::new(),
+            broken: None,
+        };
+        let mut pos = scan.header.first;
+        for index in 0..scan.header.count {
+            let last = scan.header.last;
+            let problem = match scan.frame_at(index, pos)? {
+                Err(problem) => problem,
+                Ok(_) if index + 1 == scan.header.count && pos != last => Corruption::element(
+                    index,
+                    pos,
+                    &format!(
+                        "found at offset {pos}, but the header puts the last element at {last}"
+                    ),
+                    format!("last element at offset {last}"),
+                    format!("last element at offset {pos}"),
+                ),
+                Ok(frame) => {
+                    scan.frames.push(frame);
+                    pos = scan.wrap(pos + 4 + frame.len);
+                    continue;
+                }
+            };
+            scan.broken = Some(Break {
+                index,
+                pos,
+                problem,
+            });
+            break;
+        }
+        Ok(Ok(scan))
+    }
+
+    fn frame_at(&mut self, index: u64, pos: u64) -> io::Result<Result<Frame, Corruption>> {
+        let (header_len, file_len) = (self.header.header_len, self.header.file_len);
+        if !(header_len..file_len).contains(&pos) {
+            return Ok(Err(Corruption::element(
+                index,
+                pos,
+                &format!("starts outside the ring at offset {pos}"),
+                format!("offset in {header_len}..{file_len}"),
+                format!("offset {pos}"),
+            )));
+        }
+        let mut len = [0; 4];
+        fragmentation::read_ring(&mut self.file, pos, &mut len, header_len, file_len)?;
+        let len = u64::from(u32::from_be_bytes(len));
+        let max_len = file_len - header_len - 4;
+        if len > max_len {
+            return Ok(Err(Corruption::element(
+                index,
+                pos,
+                &format!("at offset {pos} has impossible length {len}"),
+                format!("length <= {max_len}"),
+                format!("length {len}"),
+            )));
+        }
+        Ok(Ok(Frame { pos, len }))
+    }
+
+    fn wrap(&self, pos: u64) -> u64 {
+        match pos >= self.header.file_len {
+            true => self.header.header_len + pos - self.header.file_len,
+            false => pos,
+        }
+    }
+
+    pub(crate) fn element_count(&self) -> u64 {
+        self.header.count
+    }
+
+    /// Reads the payload of `frame` into `buf`.
+    pub(crate) fn read_payload(&mut self, frame: Frame, buf: &mut Vec<u8>) -> io::Result<()> {
+        buf.resize(frame.len as usize, 0);
+        let start = self.wrap(frame.pos + 4);
+        let (header_len, file_len) = (self.header.header_len, self.header.file_len);
+        fragmentation::read_ring(&mut self.file, start, buf, header_len, file_len)
+    }
+
+    /// The raw bytes of the ring from `from` (or from the start of the ring, if `from` lies
+    /// outside it) to the end of the element the header names as the last one, or to the start
+    /// of the eldest element if that frame is damaged as well.
+    pub(crate) fn read_rest(&mut self, from: u64) -> io::Result<Vec<u8>> {
+        let (header_len, file_len) = (self.header.header_len, self.header.file_len);
+        let from = match (header_len..file_len).contains(&from) {
+            true => from,
+            false => header_len,
+        };
+        let last_index = self.header.count.saturating_sub(1);
+        let end = match self.frame_at(last_index, self.header.last)? {
+            Ok(frame) => self.wrap(frame.pos + 4 + frame.len),
+            Err(_) => self.header.first.clamp(header_len, file_len - 1),
+        };
+        let ring_len = file_len - header_len;
+        let mut bytes = vec![0; ((end + ring_len - from - 1) % ring_len + 1) as usize];
+        fragmentation::read_ring(&mut self.file, from, &mut bytes, header_len, file_len)?;
+        Ok(bytes)
+    }
+}
+
+/// Describes an element whose frame is intact but whose payload cannot be read.
+pub(crate) fn unreadable(index: u64, frame: Frame, e: &io::Error) -> Corruption {
+    Corruption::element(
+        index,
+        frame.pos,
+        &format!("at offset {} cannot be read: {e}", frame.pos),
+        format!("{} readable payload bytes", frame.len),
+        e.to_string(),
+    )
+}
+
+/// Checks every element frame of the file at `path` and reads every payload. The inner error
+/// describes the first problem found.
+pub(crate) fn verify(path: &Path) -> io::Result<Result<(), Corruption>> {
+    let mut scan = match Scan::open(path)? {
+        Ok(scan) => scan,
+        Err(problem) => return Ok(Err(problem)),
+    };
+    if let Some(broken) = scan.broken.take() {
+        return Ok(Err(broken.problem));
+    }
+    let mut payload = Vec::new();
+    for (index, frame) in scan.frames.clone().into_iter().enumerate() {
+        if let Err(e) = scan.read_payload(frame, &mut payload) {
+            return Ok(Err(unreadable(index as u64, frame, &e)));
+        }
+    }
+    Ok(Ok(()))
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum WatermarkMetric {
+    /// Number of elements, including adds still held by the write buffer.
+    Elements,
+    /// Bytes used by the queue, including the frames of buffered adds.
+    Bytes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct WatermarkConfig {
+    pub metric: WatermarkMetric,
+    /// `on_high_watermark` fires once the metric rises above this value.
+    pub high: u64,
+    /// `on_low_watermark` fires once the metric, having crossed `high`, drops below this value.
+    pub low: u64,
+}
+
+#[uniffi::export(with_foreign)]
+pub trait WatermarkListener: Send + Sync {
+    fn on_high_watermark(&self, value: u64);
+    fn on_low_watermark(&self, value: u64);
+}
+
+pub(crate) struct Watermarks {
+    config: WatermarkConfig,
+    listener: Arc<dyn WatermarkListener>,
+    above: bool,
+}
+
+/// A crossing detected under the queue lock, delivered once the lock has been released so the
+/// listener may call back into the queue.
+pub(crate) struct WatermarkEvent {
+    listener: Arc<dyn WatermarkListener>,
+    high: bool,
+    value: u64,
+}
+
+impl WatermarkEvent {
+    pub(crate) fn dispatch(self) {
+        if self.high {
+            self.listener.on_high_watermark(self.value);
+        } else {
+            self.listener.on_low_watermark(self.value);
+        }
+    }
+}
+
+impl Watermarks {
+    pub(crate) fn new(config: WatermarkConfig, listener: Arc<dyn WatermarkListener>) -> Self {
+        Watermarks {
+            config,
+            listener,
+            above: false,
+        }
+    }
+
+    pub(crate) fn config(&self) -> WatermarkConfig {
+        self.config
+    }
+
+    pub(crate) fn check(&mut self, elements: u64, bytes: u64) -> Option<WatermarkEvent> {
+        let value = match self.config.metric {
+            WatermarkMetric::Elements => elements,
+            WatermarkMetric::Bytes => bytes,
+        };
+        let high = if !self.above && value > self.config.high {
+            true
+        } else if self.above && value < self.config.low {
+            false
+        } else {
+            return None;
+        };
+        self.above = high;
+        Some(WatermarkEvent {
+            listener: self.listener.clone(),
+            high,
+            value,
+        })
+    }
+}
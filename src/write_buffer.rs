@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+use crate::chunking;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct WriteBufferConfig {
+    pub max_bytes: u64,
+    pub max_delay: Duration,
+    /// Flushes once this many adds are buffered, whatever their size.
+    #[uniffi(default = None)]
+    pub max_items: Option<u32>,
+}
+
+/// Adds held in memory until they are flushed to the queue file as one batch.
+#[derive(Debug, Default)]
+pub(crate) struct WriteBuffer {
+    config: Option<WriteBufferConfig>,
+    items: Vec<Vec<u8>>,
+    bytes: u64,
+    first_buffered_at: Option<Instant>,
+}
+
+impl WriteBuffer {
+    pub(crate) fn config(&self) -> Option<WriteBufferConfig> {
+        self.config
+    }
+
+    pub(crate) fn set_config(&mut self, config: Option<WriteBufferConfig>) {
+        self.config = config;
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub(crate) fn contains(&self, data: &[u8]) -> bool {
+        self.items
+            .iter()
+            .any(|item| chunking::unframe(item) == data)
+    }
+
+    pub(crate) fn items(&self) -> &[Vec<u8>] {
+        &self.items
+    }
+
+    pub(crate) fn push(&mut self, items: impl IntoIterator<Item = Vec<u8>>) {
+        for item in items {
+            self.bytes += item.len() as u64;
+            self.items.push(item);
+        }
+        if self.first_buffered_at.is_none() && !self.items.is_empty() {
+            self.first_buffered_at = Some(Instant::now());
+        }
+    }
+
+    pub(crate) fn should_flush(&self) -> bool {
+        let Some(config) = self.config else {
+            return true;
+        };
+        let expired = self
+            .first_buffered_at
+            .is_some_and(|at| at.elapsed() >= config.max_delay);
+        let full = config
+            .max_items
+            .is_some_and(|max| self.items.len() >= max as usize);
+        self.bytes >= config.max_bytes || expired || full
+    }
+
+    /// When the oldest buffered add reaches the configured delay.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        let delay = self.config?.max_delay;
+        Some(self.first_buffered_at? + delay)
+    }
+
+    /// Removes and returns every buffered item.
+    pub(crate) fn take(&mut self) -> Vec<Vec<u8>> {
+        self.bytes = 0;
+        self.first_buffered_at = None;
+        std::mem::take(&mut self.items)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.items.clear();
+        self.bytes = 0;
+        self.first_buffered_at = None;
+    }
+}